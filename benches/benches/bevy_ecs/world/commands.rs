@@ -1,10 +1,11 @@
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    system::{Command, CommandQueue, Commands},
+    system::{Command, CommandQueue, Commands, Despawn, Insert, Remove},
     world::World,
 };
-use criterion::{black_box, Criterion};
+use criterion::{black_box, BatchSize, Criterion};
+use std::marker::PhantomData;
 
 #[derive(Component)]
 struct A;
@@ -261,3 +262,231 @@ pub fn get_or_spawn(criterion: &mut Criterion) {
 
     group.finish();
 }
+
+const STRUCTURAL_ENTITY_COUNT: u32 = 100_000;
+
+/// Spawns entities holding some data, which is moved along with them on each archetype move.
+fn world_with_entities() -> (World, Vec<Entity>) {
+    let mut world = World::default();
+    let entities = (0..STRUCTURAL_ENTITY_COUNT)
+        .map(|_| world.spawn((Matrix::default(), Vec3::default())).id())
+        .collect();
+    (world, entities)
+}
+
+fn world_with_tagged_entities() -> (World, Vec<Entity>) {
+    let (mut world, entities) = world_with_entities();
+    for &entity in &entities {
+        world.entity_mut(entity).insert((A, B, C));
+    }
+    (world, entities)
+}
+
+pub fn coalesced_insert_commands(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("coalesced_insert_commands");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(10));
+    group.sample_size(10);
+
+    group.bench_function("uncoalesced", |bencher| {
+        bencher.iter_batched(
+            world_with_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                let mut commands = Commands::new(&mut command_queue, &world);
+                for &entity in &entities {
+                    // Queuing the commands directly skips coalescing.
+                    commands.add(Insert { entity, bundle: A });
+                    commands.add(Insert { entity, bundle: B });
+                    commands.add(Insert { entity, bundle: C });
+                }
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("coalesced", |bencher| {
+        bencher.iter_batched(
+            world_with_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                let mut commands = Commands::new(&mut command_queue, &world);
+                for &entity in &entities {
+                    commands.entity(entity).insert(A).insert(B).insert(C);
+                }
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("bundle", |bencher| {
+        bencher.iter_batched(
+            world_with_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                let mut commands = Commands::new(&mut command_queue, &world);
+                for &entity in &entities {
+                    commands.entity(entity).insert((A, B, C));
+                }
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+pub fn remove_commands(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("remove_commands");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(10));
+    group.sample_size(10);
+
+    group.bench_function("uncoalesced", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                let mut commands = Commands::new(&mut command_queue, &world);
+                for &entity in &entities {
+                    // Queuing the commands directly skips coalescing.
+                    commands.add(Remove::<A> {
+                        entity,
+                        phantom: PhantomData,
+                    });
+                    commands.add(Remove::<B> {
+                        entity,
+                        phantom: PhantomData,
+                    });
+                }
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("coalesced", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                let mut commands = Commands::new(&mut command_queue, &world);
+                for &entity in &entities {
+                    commands.entity(entity).remove::<A>().remove::<B>();
+                }
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("batched", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                Commands::new(&mut command_queue, &world).remove_batch::<(A, B), _>(entities);
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+pub fn despawn_commands(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("despawn_commands");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(10));
+    group.sample_size(10);
+
+    group.bench_function("individual", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                let mut commands = Commands::new(&mut command_queue, &world);
+                for &entity in &entities {
+                    commands.add(Despawn { entity });
+                }
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("batched", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                let mut command_queue = CommandQueue::default();
+                Commands::new(&mut command_queue, &world).despawn_batch(entities);
+                command_queue.apply(&mut world);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+pub fn world_batch_structural_changes(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("world_batch_structural_changes");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(10));
+    group.sample_size(10);
+
+    group.bench_function("remove_loop", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                for &entity in &entities {
+                    world.entity_mut(entity).remove_intersection::<(A, B)>();
+                }
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("remove_batch", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                world.remove_batch::<(A, B)>(entities);
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("despawn_loop", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                for &entity in &entities {
+                    world.despawn(entity);
+                }
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("despawn_batch", |bencher| {
+        bencher.iter_batched(
+            world_with_tagged_entities,
+            |(mut world, entities)| {
+                world.despawn_batch(entities).unwrap();
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
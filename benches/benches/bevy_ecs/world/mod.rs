@@ -18,6 +18,10 @@ criterion_group!(
     medium_sized_commands,
     large_sized_commands,
     get_or_spawn,
+    coalesced_insert_commands,
+    remove_commands,
+    despawn_commands,
+    world_batch_structural_changes,
     world_entity,
     world_get,
     world_query_get,
@@ -11,7 +11,12 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_utils::{tracing::debug, HashMap, HashSet};
-use std::fmt::Debug;
+use std::{
+    any::TypeId,
+    fmt::{self, Debug},
+    panic::AssertUnwindSafe,
+    sync::mpsc::{channel, Receiver, Sender},
+};
 
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -28,7 +33,56 @@ bevy_utils::define_label!(
 pub struct AppTypeRegistry(pub bevy_reflect::TypeRegistryArc);
 
 pub(crate) enum AppError {
-    DuplicatePlugin { plugin_name: String },
+    DuplicatePlugin {
+        plugin_name: String,
+    },
+    MissingDependency {
+        plugin_name: String,
+        dependency_name: String,
+    },
+    DependencyCycle {
+        plugin_names: Vec<String>,
+    },
+    PluginsFinished {
+        plugin_name: String,
+    },
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::DuplicatePlugin { plugin_name } => {
+                write!(f, "plugin {plugin_name} was already added in application")
+            }
+            AppError::MissingDependency {
+                plugin_name,
+                dependency_name,
+            } => write!(
+                f,
+                "plugin {plugin_name} depends on {dependency_name}, which was not added before it"
+            ),
+            AppError::DependencyCycle { plugin_names } => write!(
+                f,
+                "plugins depend on each other: {}",
+                plugin_names.join(" -> ")
+            ),
+            AppError::PluginsFinished { plugin_name } => write!(
+                f,
+                "plugin {plugin_name} was added after the plugins of the application were finished"
+            ),
+        }
+    }
+}
+
+/// The configuration stage of the [`Plugin`]s of an [`App`], see [`App::plugins_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginsState {
+    /// Plugins are being added and built.
+    Adding,
+    /// [`Plugin::finish`] was called for every plugin. No plugin can be added anymore.
+    Finished,
+    /// [`Plugin::cleanup`] was called for every plugin.
+    Cleaned,
 }
 
 #[allow(clippy::needless_doctest_main)]
@@ -61,7 +115,7 @@ pub struct App {
     /// The main ECS [`World`] of the [`App`].
     /// This stores and provides access to all the main data of the application.
     /// The systems of the [`App`] will run using this [`World`].
-    /// If additional separate [`World`]-[`Schedule`] pairs are needed, you can use [`sub_app`](App::add_sub_app)s.
+    /// If additional separate [`World`]-[`Schedule`] pairs are needed, you can use [`SubApp`]s.
     pub world: World,
     /// The [runner function](Self::set_runner) is primarily responsible for managing
     /// the application's event loop and advancing the [`Schedule`].
@@ -73,6 +127,10 @@ pub struct App {
     sub_apps: HashMap<AppLabelId, SubApp>,
     plugin_registry: Vec<Box<dyn Plugin>>,
     plugin_name_added: HashSet<String>,
+    plugin_types_added: HashSet<TypeId>,
+    /// The plugins whose dependencies are being added, with their names.
+    plugins_resolving: Vec<(TypeId, String)>,
+    plugins_state: PluginsState,
 }
 
 impl Debug for App {
@@ -85,10 +143,219 @@ impl Debug for App {
     }
 }
 
+/// An [`App`] run by another [`App`], identified by an [`AppLabel`].
+///
 /// Each `SubApp` has its own [`Schedule`] and [`World`], enabling a separation of concerns.
-struct SubApp {
-    app: App,
-    runner: Box<dyn Fn(&mut World, &mut App)>,
+/// Every time the sub-app is updated, its `extract` function is called with the [`World`] of the
+/// parent app to copy the data the sub-app needs, then the sub-app is [updated](App::update).
+///
+/// A sub-app can be updated only once every few updates of its parent with
+/// [`set_update_interval`](Self::set_update_interval), and run on a separate thread in parallel
+/// with the [`Schedule`] of its parent with [`set_pipelined`](Self::set_pipelined).
+///
+/// ```
+/// # use bevy_app::{App, AppLabel, SubApp};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(AppLabel)]
+/// struct PhysicsApp;
+///
+/// #[derive(Resource, Clone, Default)]
+/// struct Gravity(f32);
+///
+/// let mut app = App::new();
+/// app.insert_resource(Gravity(-9.8));
+///
+/// let mut physics = SubApp::new(App::new(), |main_world, physics_app| {
+///     let gravity = main_world.resource::<Gravity>().clone();
+///     physics_app.insert_resource(gravity);
+/// });
+/// physics.set_update_interval(2).set_pipelined(true);
+/// app.insert_sub_app(PhysicsApp, physics);
+///
+/// app.update();
+/// assert_eq!(app.sub_app(PhysicsApp).world.resource::<Gravity>().0, -9.8);
+/// ```
+pub struct SubApp {
+    /// The [`App`] of the sub-app.
+    pub app: App,
+    extract: ExtractFn,
+    /// Whether the `app` is updated after `extract`. This is `false` for sub-apps added with
+    /// [`App::add_sub_app`], whose runner is responsible for updating the sub-app.
+    update_app: bool,
+    update_interval: u32,
+    pipelined: bool,
+    /// The number of updates of the parent app to skip before the next update of the sub-app.
+    skipped_updates: u32,
+    /// The thread running the schedule of the sub-app when it is pipelined.
+    pipeline_thread: Option<PipelineThread>,
+}
+
+type ExtractFn = Box<dyn Fn(&mut World, &mut App)>;
+
+impl SubApp {
+    /// Creates a sub-app from an [`App`] and the function extracting data from the [`World`] of the
+    /// parent app into it.
+    pub fn new(app: App, extract: impl Fn(&mut World, &mut App) + 'static) -> Self {
+        Self {
+            app,
+            extract: Box::new(extract),
+            update_app: true,
+            update_interval: 1,
+            pipelined: false,
+            skipped_updates: 0,
+            pipeline_thread: None,
+        }
+    }
+
+    /// Updates the sub-app only once every `interval` updates of its parent, starting with the
+    /// first one. Defaults to `1`, updating the sub-app along with its parent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is `0`.
+    pub fn set_update_interval(&mut self, interval: u32) -> &mut Self {
+        assert!(interval > 0, "The update interval of a sub-app can't be 0");
+        self.update_interval = interval;
+        self.skipped_updates = self.skipped_updates.min(interval - 1);
+        self
+    }
+
+    /// The number of updates of the parent app between two updates of this sub-app.
+    pub fn update_interval(&self) -> u32 {
+        self.update_interval
+    }
+
+    /// Runs the [`Schedule`] of the sub-app on a separate thread, in parallel with the schedule of
+    /// its parent. The thread is kept alive between updates.
+    ///
+    /// The data is extracted before the schedule of the parent runs, so the sub-app sees the
+    /// state of the parent [`World`] at the end of its previous update. The sub-app has finished
+    /// running when [`App::update`] returns. Its own sub-apps are updated on the thread of its
+    /// parent, once its schedule has run.
+    ///
+    /// Non-send resources of the sub-app can't be accessed when it is pipelined. On platforms
+    /// without threads, pipelined sub-apps run after the schedule of their parent.
+    pub fn set_pipelined(&mut self, pipelined: bool) -> &mut Self {
+        self.pipelined = pipelined;
+        if !pipelined {
+            self.pipeline_thread = None;
+        }
+        self
+    }
+
+    /// Whether the sub-app runs in parallel with its parent.
+    pub fn is_pipelined(&self) -> bool {
+        self.pipelined
+    }
+
+    /// Extracts data from the [`World`] of the parent app into the sub-app.
+    pub fn extract(&mut self, parent_world: &mut World) {
+        (self.extract)(parent_world, &mut self.app);
+    }
+
+    /// Updates the [`App`] of the sub-app, without extracting data.
+    pub fn run(&mut self) {
+        if self.update_app {
+            self.app.update();
+        }
+    }
+
+    /// Whether the schedule of the sub-app runs on its pipeline thread during the next update.
+    fn runs_pipelined(&self) -> bool {
+        self.pipelined && self.update_app && cfg!(not(target_arch = "wasm32"))
+    }
+
+    /// Sends the schedule and world of the sub-app to its pipeline thread, which runs the schedule.
+    fn start_pipelined(&mut self) {
+        let thread = self
+            .pipeline_thread
+            .get_or_insert_with(PipelineThread::spawn);
+        let (schedule, world) = thread
+            .spare
+            .take()
+            .expect("the previous update of a pipelined sub-app didn't finish");
+        let schedule = std::mem::replace(&mut self.app.schedule, schedule);
+        let world = std::mem::replace(&mut self.app.world, world);
+        thread
+            .jobs
+            .send((schedule, world))
+            .expect("the thread of a pipelined sub-app stopped");
+    }
+
+    /// Waits for the schedule of the sub-app to run on its pipeline thread, then updates the
+    /// sub-apps of the sub-app.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic of the schedule if it panicked.
+    fn finish_pipelined(&mut self) {
+        let thread = self.pipeline_thread.as_mut().unwrap();
+        match thread
+            .results
+            .recv()
+            .expect("the thread of a pipelined sub-app stopped")
+        {
+            Ok((schedule, world)) => {
+                let schedule = std::mem::replace(&mut self.app.schedule, schedule);
+                let world = std::mem::replace(&mut self.app.world, world);
+                thread.spare = Some((schedule, world));
+            }
+            Err(payload) => {
+                self.pipeline_thread = None;
+                std::panic::resume_unwind(payload);
+            }
+        }
+        self.app.update_with(false);
+    }
+
+    /// Counts an update of the parent app, and returns whether the sub-app should update.
+    fn should_update(&mut self) -> bool {
+        if self.skipped_updates == 0 {
+            self.skipped_updates = self.update_interval - 1;
+            true
+        } else {
+            self.skipped_updates -= 1;
+            false
+        }
+    }
+}
+
+/// The thread running the [`Schedule`] of a pipelined [`SubApp`]. It stops when this is dropped.
+struct PipelineThread {
+    /// Sends the schedule and world of the sub-app to the thread.
+    jobs: Sender<(Schedule, World)>,
+    /// Receives them back once the schedule ran, or the panic of the schedule.
+    results: Receiver<std::thread::Result<(Schedule, World)>>,
+    /// Replaces the schedule and world of the sub-app while they are on the thread.
+    spare: Option<(Schedule, World)>,
+}
+
+impl PipelineThread {
+    fn spawn() -> Self {
+        let (jobs, job_receiver) = channel::<(Schedule, World)>();
+        let (result_sender, results) = channel();
+        std::thread::Builder::new()
+            .name("sub app".to_string())
+            .spawn(move || {
+                for (mut schedule, mut world) in job_receiver {
+                    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                        schedule.run(&mut world);
+                    }));
+                    if result_sender
+                        .send(result.map(|()| (schedule, world)))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            })
+            .expect("Failed to spawn the thread of a pipelined sub-app");
+        Self {
+            jobs,
+            results,
+            spare: Some((Schedule::default(), World::new())),
+        }
+    }
 }
 
 impl Debug for SubApp {
@@ -97,7 +364,11 @@ impl Debug for SubApp {
         f.debug_map()
             .entries(self.app.sub_apps.iter().map(|(k, v)| (k, v)))
             .finish()?;
-        write!(f, "}}")
+        write!(
+            f,
+            ", update_interval: {}, pipelined: {} }}",
+            self.update_interval, self.pipelined
+        )
     }
 }
 
@@ -138,32 +409,66 @@ impl App {
             sub_apps: HashMap::default(),
             plugin_registry: Vec::default(),
             plugin_name_added: Default::default(),
+            plugin_types_added: Default::default(),
+            plugins_resolving: Vec::new(),
+            plugins_state: PluginsState::Adding,
         }
     }
 
     /// Advances the execution of the [`Schedule`] by one cycle.
     ///
-    /// This method also updates sub apps.
+    /// This method also updates sub apps: [pipelined](SubApp::set_pipelined) sub-apps run in
+    /// parallel with the schedule, the other ones run after it.
     ///
-    /// See [`add_sub_app`](Self::add_sub_app) and [`run_once`](Schedule::run_once) for more details.
+    /// See [`insert_sub_app`](Self::insert_sub_app) and [`run_once`](Schedule::run_once) for more
+    /// details.
     pub fn update(&mut self) {
         #[cfg(feature = "trace")]
         let _bevy_frame_update_span = info_span!("frame").entered();
-        self.schedule.run(&mut self.world);
+        self.update_with(true);
+    }
+
+    /// Updates the sub-apps, running the [`Schedule`] first if `run_schedule` is `true`.
+    fn update_with(&mut self, run_schedule: bool) {
+        let mut pipelined = Vec::new();
+        let mut sequential = Vec::new();
         for sub_app in self.sub_apps.values_mut() {
-            (sub_app.runner)(&mut self.world, &mut sub_app.app);
+            if !sub_app.should_update() {
+                continue;
+            }
+            if sub_app.runs_pipelined() {
+                sub_app.extract(&mut self.world);
+                sub_app.start_pipelined();
+                pipelined.push(sub_app);
+            } else {
+                sequential.push(sub_app);
+            }
+        }
+
+        if run_schedule {
+            self.schedule.run(&mut self.world);
+        }
+        for sub_app in pipelined {
+            sub_app.finish_pipelined();
+        }
+
+        for sub_app in sequential {
+            sub_app.extract(&mut self.world);
+            sub_app.run();
         }
     }
 
     /// Starts the application by calling the app's [runner function](Self::set_runner).
     ///
-    /// Finalizes the [`App`] configuration. For general usage, see the example on the item
+    /// Finalizes the [`App`] configuration by [finishing](Self::finish) and
+    /// [cleaning up](Self::cleanup) its plugins. For general usage, see the example on the item
     /// level documentation.
     pub fn run(&mut self) {
         #[cfg(feature = "trace")]
         let _bevy_app_run_span = info_span!("bevy_app").entered();
 
         let mut app = std::mem::replace(self, App::empty());
+        app.cleanup();
         let runner = std::mem::replace(&mut app.runner, Box::new(run_once));
         (runner)(app);
     }
@@ -831,18 +1136,18 @@ impl App {
     ///
     /// # Panics
     ///
-    /// Panics if the plugin was already added to the application.
+    /// Panics if the plugin was already added to the application, if one of its
+    /// [dependencies](Plugin::dependencies) is missing, or if the plugins of the application
+    /// were already [finished](Self::finish).
     pub fn add_plugin<T>(&mut self, plugin: T) -> &mut Self
     where
         T: Plugin,
     {
-        match self.add_boxed_plugin(Box::new(plugin)) {
-            Ok(app) => app,
-            Err(AppError::DuplicatePlugin { plugin_name }) => panic!(
-                "Error adding plugin {}: : plugin was already added in application",
-                plugin_name
-            ),
+        let plugin_name = plugin.name().to_string();
+        if let Err(err) = self.add_boxed_plugin(Box::new(plugin)) {
+            panic!("Error adding plugin {plugin_name}: {err}");
         }
+        self
     }
 
     /// Boxed variant of `add_plugin`, can be used from a [`PluginGroup`]
@@ -851,16 +1156,125 @@ impl App {
         plugin: Box<dyn Plugin>,
     ) -> Result<&mut Self, AppError> {
         debug!("added plugin: {}", plugin.name());
-        if plugin.is_unique() && !self.plugin_name_added.insert(plugin.name().to_string()) {
-            Err(AppError::DuplicatePlugin {
+        if self.plugins_state != PluginsState::Adding {
+            return Err(AppError::PluginsFinished {
                 plugin_name: plugin.name().to_string(),
-            })?;
+            });
         }
+        if plugin.is_unique() && self.plugin_name_added.contains(plugin.name()) {
+            return Err(AppError::DuplicatePlugin {
+                plugin_name: plugin.name().to_string(),
+            });
+        }
+
+        let type_id = plugin.as_any().type_id();
+        self.plugins_resolving
+            .push((type_id, plugin.name().to_string()));
+        let result = self.add_plugin_dependencies(&*plugin);
+        self.plugins_resolving.pop();
+        result?;
+
+        if plugin.is_unique() {
+            self.plugin_name_added.insert(plugin.name().to_string());
+        }
+        self.plugin_types_added.insert(type_id);
         plugin.build(self);
         self.plugin_registry.push(plugin);
         Ok(self)
     }
 
+    /// Adds the missing dependencies of a plugin that have a default value.
+    fn add_plugin_dependencies(&mut self, plugin: &dyn Plugin) -> Result<(), AppError> {
+        for dependency in plugin.dependencies() {
+            if self.plugin_types_added.contains(&dependency.type_id()) {
+                continue;
+            }
+            if let Some(index) = self
+                .plugins_resolving
+                .iter()
+                .position(|(type_id, _)| *type_id == dependency.type_id())
+            {
+                let mut plugin_names: Vec<_> = self.plugins_resolving[index..]
+                    .iter()
+                    .map(|(_, name)| name.clone())
+                    .collect();
+                plugin_names.push(dependency.name().to_string());
+                return Err(AppError::DependencyCycle { plugin_names });
+            }
+            match dependency.create_default() {
+                Some(default) => {
+                    debug!(
+                        "adding the default {} needed by {}",
+                        dependency.name(),
+                        plugin.name()
+                    );
+                    self.add_boxed_plugin(default)?;
+                }
+                None => {
+                    return Err(AppError::MissingDependency {
+                        plugin_name: plugin.name().to_string(),
+                        dependency_name: dependency.name().to_string(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The configuration stage of the plugins of the application.
+    pub fn plugins_state(&self) -> PluginsState {
+        self.plugins_state
+    }
+
+    /// Calls [`Plugin::finish`] for every plugin, in the order they were added, including the
+    /// plugins of the [sub-apps](Self::insert_sub_app). Plugins can't be added afterwards.
+    ///
+    /// This is called by [`App::run`]. Apps updated manually should call it, as well as
+    /// [`cleanup`](Self::cleanup), once all their plugins are added. Does nothing if the plugins
+    /// were already finished.
+    pub fn finish(&mut self) {
+        if self.plugins_state != PluginsState::Adding {
+            return;
+        }
+        self.plugins_state = PluginsState::Finished;
+        for index in 0..self.plugin_registry.len() {
+            // The plugin is taken out of the registry so that it can configure the app.
+            let plugin = std::mem::replace(
+                &mut self.plugin_registry[index],
+                Box::new(PlaceholderPlugin),
+            );
+            plugin.finish(self);
+            self.plugin_registry[index] = plugin;
+        }
+        for sub_app in self.sub_apps.values_mut() {
+            sub_app.app.finish();
+        }
+    }
+
+    /// Calls [`Plugin::cleanup`] for every plugin, in the order they were added, including the
+    /// plugins of the [sub-apps](Self::insert_sub_app). The plugins are [finished](Self::finish)
+    /// first if needed.
+    ///
+    /// This is called by [`App::run`]. Does nothing if the plugins were already cleaned up.
+    pub fn cleanup(&mut self) {
+        self.finish();
+        if self.plugins_state == PluginsState::Cleaned {
+            return;
+        }
+        self.plugins_state = PluginsState::Cleaned;
+        for index in 0..self.plugin_registry.len() {
+            let plugin = std::mem::replace(
+                &mut self.plugin_registry[index],
+                Box::new(PlaceholderPlugin),
+            );
+            plugin.cleanup(self);
+            self.plugin_registry[index] = plugin;
+        }
+        for sub_app in self.sub_apps.values_mut() {
+            sub_app.app.cleanup();
+        }
+    }
+
     /// Checks if a [`Plugin`] has already been added.
     ///
     /// This can be used by plugins to check if a plugin they depend upon has already been
@@ -869,9 +1283,7 @@ impl App {
     where
         T: Plugin,
     {
-        self.plugin_registry
-            .iter()
-            .any(|p| p.downcast_ref::<T>().is_some())
+        self.plugin_types_added.contains(&TypeId::of::<T>())
     }
 
     /// Returns a vector of references to any plugins of type `T` that have been added.
@@ -985,23 +1397,53 @@ impl App {
     ///
     /// The provided function `f` is called by the [`update`](Self::update) method. The [`World`]
     /// parameter represents the main app world, while the [`App`] parameter is just a mutable
-    /// reference to the `SubApp` itself.
+    /// reference to the `SubApp` itself. Unlike the extract function of a [`SubApp`], the runner is
+    /// responsible for running the schedule of the sub-app.
     pub fn add_sub_app(
         &mut self,
         label: impl AppLabel,
         app: App,
         sub_app_runner: impl Fn(&mut World, &mut App) + 'static,
     ) -> &mut Self {
-        self.sub_apps.insert(
-            label.as_label(),
-            SubApp {
-                app,
-                runner: Box::new(sub_app_runner),
-            },
-        );
+        let mut sub_app = SubApp::new(app, sub_app_runner);
+        sub_app.update_app = false;
+        self.insert_sub_app(label, sub_app)
+    }
+
+    /// Adds a [`SubApp`] as a child of the current one, replacing any sub-app with the same
+    /// label.
+    pub fn insert_sub_app(&mut self, label: impl AppLabel, sub_app: SubApp) -> &mut Self {
+        self.sub_apps.insert(label.as_label(), sub_app);
         self
     }
 
+    /// Removes the [`SubApp`] with the given label, if it exists.
+    pub fn remove_sub_app(&mut self, label: impl AppLabel) -> Option<SubApp> {
+        self.sub_apps.remove(&label.as_label())
+    }
+
+    /// Returns the labels of the sub-apps of this [`App`].
+    pub fn sub_app_labels(&self) -> impl Iterator<Item = AppLabelId> + '_ {
+        self.sub_apps.keys().copied()
+    }
+
+    /// Retrieves the [`SubApp`] with the given label, to configure how it is updated, if it
+    /// exists. Otherwise returns an [`Err`] containing the given label.
+    pub fn get_sub_app_handle_mut(
+        &mut self,
+        label: impl AppLabel,
+    ) -> Result<&mut SubApp, AppLabelId> {
+        let label = label.as_label();
+        self.sub_apps.get_mut(&label).ok_or(label)
+    }
+
+    /// Retrieves the [`SubApp`] with the given label, if it exists. Otherwise returns an [`Err`]
+    /// containing the given label.
+    pub fn get_sub_app_handle(&self, label: impl AppLabel) -> Result<&SubApp, AppLabelId> {
+        let label = label.as_label();
+        self.sub_apps.get(&label).ok_or(label)
+    }
+
     /// Retrieves a `SubApp` stored inside this [`App`].
     ///
     /// # Panics
@@ -1059,9 +1501,24 @@ fn run_once(mut app: App) {
 #[derive(Debug, Clone, Default)]
 pub struct AppExit;
 
+/// Stands in for a plugin taken out of the registry while it [finishes](Plugin::finish) or
+/// [cleans up](Plugin::cleanup).
+struct PlaceholderPlugin;
+
+impl Plugin for PlaceholderPlugin {
+    fn build(&self, _app: &mut App) {}
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{App, Plugin};
+    use crate::{
+        self as bevy_app, App, AppLabel, NoopPluginGroup, Plugin, PluginDependency,
+        PluginGroupBuilder, PluginsState, SubApp,
+    };
+    use bevy_ecs::{
+        system::{ResMut, Resource},
+        world::World,
+    };
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -1103,4 +1560,321 @@ mod tests {
     fn can_add_twice_the_same_plugin_not_unique() {
         App::new().add_plugin(PluginD).add_plugin(PluginD);
     }
+
+    /// The names of the plugins, in the order they were built.
+    #[derive(Resource, Default)]
+    struct Built(Vec<&'static str>);
+
+    fn built(app: &mut App, name: &'static str) {
+        app.world
+            .get_resource_or_insert_with(Built::default)
+            .0
+            .push(name);
+    }
+
+    #[derive(Default)]
+    struct Physics;
+    impl Plugin for Physics {
+        fn build(&self, app: &mut App) {
+            built(app, "physics");
+        }
+    }
+
+    struct Vehicles;
+    impl Plugin for Vehicles {
+        fn build(&self, app: &mut App) {
+            built(app, "vehicles");
+        }
+        fn dependencies(&self) -> Vec<PluginDependency> {
+            vec![PluginDependency::or_default::<Physics>()]
+        }
+    }
+
+    struct Racing;
+    impl Plugin for Racing {
+        fn build(&self, app: &mut App) {
+            built(app, "racing");
+        }
+        fn dependencies(&self) -> Vec<PluginDependency> {
+            vec![PluginDependency::new::<Vehicles>()]
+        }
+    }
+
+    #[test]
+    fn default_dependencies_are_added() {
+        let mut app = App::new();
+        app.add_plugin(Vehicles).add_plugin(Racing);
+        assert_eq!(
+            app.world.resource::<Built>().0,
+            vec!["physics", "vehicles", "racing"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "depends on bevy_app::app::tests::Vehicles, which was not added")]
+    fn missing_dependency() {
+        App::new().add_plugin(Racing);
+    }
+
+    #[test]
+    #[should_panic(expected = "was already added in application")]
+    fn default_dependency_then_duplicate() {
+        App::new().add_plugin(Vehicles).add_plugin(Physics);
+    }
+
+    #[derive(Default)]
+    struct Chicken;
+    impl Plugin for Chicken {
+        fn build(&self, _app: &mut App) {}
+        fn dependencies(&self) -> Vec<PluginDependency> {
+            vec![PluginDependency::or_default::<Egg>()]
+        }
+    }
+
+    #[derive(Default)]
+    struct Egg;
+    impl Plugin for Egg {
+        fn build(&self, _app: &mut App) {}
+        fn dependencies(&self) -> Vec<PluginDependency> {
+            vec![PluginDependency::or_default::<Chicken>()]
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "plugins depend on each other: bevy_app::app::tests::Chicken -> bevy_app::app::tests::Egg -> bevy_app::app::tests::Chicken"
+    )]
+    fn dependency_cycle() {
+        App::new().add_plugin(Chicken);
+    }
+
+    #[test]
+    fn plugin_groups_are_built_after_their_dependencies() {
+        let mut app = App::new();
+        app.add_plugins(
+            PluginGroupBuilder::start::<NoopPluginGroup>()
+                .add(Racing)
+                .add(PluginA)
+                .add(Vehicles)
+                .add(Physics),
+        );
+        assert_eq!(
+            app.world.resource::<Built>().0,
+            vec!["physics", "vehicles", "racing"]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Error adding plugins in group bevy_app::plugin_group::NoopPluginGroup: plugins depend on each other: bevy_app::app::tests::Chicken -> bevy_app::app::tests::Egg -> bevy_app::app::tests::Chicken"
+    )]
+    fn plugin_group_dependency_cycle() {
+        App::new().add_plugins(
+            PluginGroupBuilder::start::<NoopPluginGroup>()
+                .add(PluginA)
+                .add(Chicken)
+                .add(Egg),
+        );
+    }
+
+    #[derive(Resource)]
+    struct Config(u32);
+
+    #[derive(Resource)]
+    struct Finished(u32);
+
+    struct FinishingPlugin;
+    impl Plugin for FinishingPlugin {
+        fn build(&self, _app: &mut App) {}
+        fn finish(&self, app: &mut App) {
+            // The config is inserted by a plugin added after this one.
+            let config = app.world.resource::<Config>().0;
+            assert!(app.is_plugin_added::<FinishingPlugin>());
+            app.insert_resource(Finished(config));
+        }
+        fn cleanup(&self, app: &mut App) {
+            app.world.remove_resource::<Config>();
+        }
+    }
+
+    struct ConfigPlugin;
+    impl Plugin for ConfigPlugin {
+        fn build(&self, app: &mut App) {
+            app.insert_resource(Config(3));
+        }
+    }
+
+    #[test]
+    fn finish_and_cleanup() {
+        let mut app = App::new();
+        app.add_plugin(FinishingPlugin).add_plugin(ConfigPlugin);
+        assert_eq!(app.plugins_state(), PluginsState::Adding);
+
+        app.finish();
+        assert_eq!(app.plugins_state(), PluginsState::Finished);
+        assert_eq!(app.world.resource::<Finished>().0, 3);
+        assert!(app.world.contains_resource::<Config>());
+
+        app.cleanup();
+        assert_eq!(app.plugins_state(), PluginsState::Cleaned);
+        assert!(!app.world.contains_resource::<Config>());
+    }
+
+    #[test]
+    fn run_finishes_plugins() {
+        App::new()
+            .add_plugin(FinishingPlugin)
+            .add_plugin(ConfigPlugin)
+            .set_runner(|app| {
+                assert_eq!(app.plugins_state(), PluginsState::Cleaned);
+                assert_eq!(app.world.resource::<Finished>().0, 3);
+            })
+            .run();
+    }
+
+    #[test]
+    #[should_panic(expected = "was added after the plugins of the application were finished")]
+    fn cant_add_plugins_once_finished() {
+        let mut app = App::new();
+        app.finish();
+        app.add_plugin(PluginA);
+    }
+
+    #[derive(AppLabel)]
+    struct SubAppA;
+
+    #[derive(AppLabel)]
+    struct SubAppB;
+
+    #[derive(Resource, Default)]
+    struct Frames(u32);
+
+    #[derive(Resource, Default)]
+    struct Extracted(Vec<u32>);
+
+    fn count_frames(mut frames: ResMut<Frames>) {
+        frames.0 += 1;
+    }
+
+    fn extracting_sub_app() -> SubApp {
+        let mut app = App::new();
+        app.init_resource::<Extracted>();
+        SubApp::new(app, |main_world, sub_app| {
+            let frames = main_world.resource::<Frames>().0;
+            sub_app.world.resource_mut::<Extracted>().0.push(frames);
+        })
+    }
+
+    fn main_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Frames>().add_system(count_frames);
+        app
+    }
+
+    #[test]
+    fn sub_apps_by_label() {
+        let mut app = main_app();
+        app.insert_sub_app(SubAppA, extracting_sub_app())
+            .insert_sub_app(SubAppB, extracting_sub_app());
+        assert_eq!(app.sub_app_labels().count(), 2);
+
+        app.update();
+        assert_eq!(app.sub_app(SubAppA).world.resource::<Extracted>().0, [1]);
+        app.sub_app_mut(SubAppA)
+            .insert_resource(Extracted(Vec::new()));
+
+        let removed = app.remove_sub_app(SubAppB).unwrap();
+        assert_eq!(removed.app.world.resource::<Extracted>().0, [1]);
+        assert!(app.get_sub_app(SubAppB).is_err());
+        assert!(app.get_sub_app_handle(SubAppB).is_err());
+
+        app.update();
+        assert_eq!(app.sub_app(SubAppA).world.resource::<Extracted>().0, [2]);
+    }
+
+    #[test]
+    fn sub_app_update_interval() {
+        let mut app = main_app();
+        let mut sub_app = extracting_sub_app();
+        sub_app
+            .app
+            .init_resource::<Frames>()
+            .add_system(count_frames);
+        sub_app.set_update_interval(3);
+        app.insert_sub_app(SubAppA, sub_app);
+
+        for _ in 0..7 {
+            app.update();
+        }
+        let sub_world = &app.sub_app(SubAppA).world;
+        assert_eq!(sub_world.resource::<Extracted>().0, [1, 4, 7]);
+        assert_eq!(sub_world.resource::<Frames>().0, 3);
+
+        app.get_sub_app_handle_mut(SubAppA)
+            .unwrap()
+            .set_update_interval(1);
+        app.update();
+        assert_eq!(
+            app.sub_app(SubAppA).world.resource::<Extracted>().0,
+            [1, 4, 7, 8]
+        );
+    }
+
+    #[test]
+    fn pipelined_sub_app() {
+        #[derive(Resource)]
+        struct MainThread(std::thread::ThreadId);
+
+        #[derive(Resource, Default)]
+        struct SubThread(Option<std::thread::ThreadId>);
+
+        // Exclusive systems run on the thread running the schedule.
+        fn check_thread(world: &mut World) {
+            let current = std::thread::current().id();
+            assert_ne!(world.resource::<MainThread>().0, current);
+            // The thread is reused between updates.
+            let mut sub_thread = world.resource_mut::<SubThread>();
+            assert_eq!(*sub_thread.0.get_or_insert(current), current);
+            world.resource_mut::<Frames>().0 += 1;
+        }
+
+        let mut app = main_app();
+        let mut sub_app = extracting_sub_app();
+        sub_app
+            .app
+            .insert_resource(MainThread(std::thread::current().id()))
+            .init_resource::<SubThread>()
+            .init_resource::<Frames>()
+            .add_system(check_thread);
+        sub_app.set_pipelined(true);
+        app.insert_sub_app(SubAppA, sub_app);
+
+        app.update();
+        app.update();
+        let sub_world = &app.sub_app(SubAppA).world;
+        // Pipelined sub-apps extract the state of the previous frame.
+        assert_eq!(sub_world.resource::<Extracted>().0, [0, 1]);
+        assert_eq!(sub_world.resource::<Frames>().0, 2);
+    }
+
+    #[test]
+    fn non_send_runner() {
+        let ran = std::rc::Rc::new(std::cell::Cell::new(false));
+        let runner_ran = ran.clone();
+        App::new().set_runner(move |_| runner_ran.set(true)).run();
+        assert!(ran.get());
+    }
+
+    #[test]
+    fn add_sub_app_runner_updates_the_sub_app() {
+        let mut app = main_app();
+        let mut sub_app = App::new();
+        sub_app.init_resource::<Frames>().add_system(count_frames);
+        app.add_sub_app(SubAppA, sub_app, |_, sub_app| {
+            sub_app.update();
+            sub_app.update();
+        });
+        app.update();
+        assert_eq!(app.sub_app(SubAppA).world.resource::<Frames>().0, 2);
+    }
 }
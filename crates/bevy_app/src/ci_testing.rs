@@ -1,18 +1,106 @@
 use crate::{app::AppExit, App};
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 
-use bevy_ecs::prelude::Resource;
-use bevy_utils::tracing::info;
+use bevy_ecs::{
+    event::Events,
+    prelude::{Local, Resource, World},
+};
+use bevy_utils::{get_short_name, tracing::info, HashMap};
+use std::path::{Path, PathBuf};
 
 /// A configuration struct for automated CI testing.
 ///
 /// It gets used when the `bevy_ci_testing` feature is enabled to automatically
 /// exit a Bevy app when run through the CI. This is needed because otherwise
 /// Bevy apps would be stuck in the game loop and wouldn't allow the CI to progress.
+///
+/// It can also script events sent and screenshots taken on given frames, to exercise an example
+/// before exiting:
+///
+/// ```ron
+/// (
+///     events: [
+///         (60, Event("KeyboardInput", "(scan_code: 57, key_code: Some(Space), state: Pressed)")),
+///         (61, Event("KeyboardInput", "(scan_code: 57, key_code: Some(Space), state: Released)")),
+///         (100, Screenshot),
+///         (300, AppExit),
+///     ],
+/// )
+/// ```
 #[derive(Deserialize, Resource)]
 pub struct CiTestingConfig {
     /// The number of frames after which Bevy should exit.
     pub exit_after: Option<u32>,
+    /// The events to send, with the frame they are sent on.
+    #[serde(default)]
+    pub events: Vec<CiTestingEventOnFrame>,
+}
+
+/// An event sent by the CI on the given frame, counted from `0`.
+#[derive(Deserialize)]
+pub struct CiTestingEventOnFrame(pub u32, pub CiTestingEvent);
+
+/// An event scripted in the [`CiTestingConfig`].
+#[derive(Deserialize)]
+pub enum CiTestingEvent {
+    /// Exits the app.
+    AppExit,
+    /// Sends an event of a type registered with [`App::add_ci_testing_event`], by short type
+    /// name, with its value in RON.
+    Event(String, String),
+    /// Takes a screenshot of the app with the backend set with
+    /// [`App::set_ci_testing_screenshot_backend`], saved to `screenshot-<frame>.png`.
+    Screenshot,
+}
+
+type SendEventFn = fn(&mut World, &str) -> Result<(), ron::error::SpannedError>;
+
+/// Captures a screenshot of the app to the given file.
+pub type CiTestingScreenshotFn = fn(&mut World, &Path);
+
+/// The backend taking the screenshots of [`CiTestingEvent::Screenshot`].
+#[derive(Resource)]
+struct CiTestingScreenshotBackend(CiTestingScreenshotFn);
+
+/// The event types that can be sent by a [`CiTestingEvent::Event`], by short type name.
+#[derive(Resource, Default)]
+struct CiTestingEventTypes(HashMap<String, SendEventFn>);
+
+impl App {
+    /// Allows the [`CiTestingConfig`] to send events of type `E`, named by their short type name.
+    ///
+    /// Plugins call this for the events they read, such as input events, so that examples can be
+    /// driven by the CI. The event must also be added with [`App::add_event`].
+    pub fn add_ci_testing_event<E>(&mut self) -> &mut Self
+    where
+        E: bevy_ecs::event::Event + DeserializeOwned,
+    {
+        fn send_event<E: bevy_ecs::event::Event + DeserializeOwned>(
+            world: &mut World,
+            value: &str,
+        ) -> Result<(), ron::error::SpannedError> {
+            let event: E = ron::from_str(value)?;
+            world.resource_mut::<Events<E>>().send(event);
+            Ok(())
+        }
+
+        self.world
+            .get_resource_or_insert_with(CiTestingEventTypes::default)
+            .0
+            .insert(get_short_name(std::any::type_name::<E>()), send_event::<E>);
+        self
+    }
+
+    /// Sets the function capturing the screenshots of the [`CiTestingConfig`].
+    ///
+    /// This is done by the plugin rendering the app. Without a backend, a
+    /// [`CiTestingEvent::Screenshot`] fails the CI run.
+    pub fn set_ci_testing_screenshot_backend(
+        &mut self,
+        capture: CiTestingScreenshotFn,
+    ) -> &mut Self {
+        self.insert_resource(CiTestingScreenshotBackend(capture))
+    }
 }
 
 fn ci_testing_exit_after(
@@ -29,6 +117,49 @@ fn ci_testing_exit_after(
     *current_frame += 1;
 }
 
+/// Sends the events of the [`CiTestingConfig`] scripted for the current frame.
+///
+/// Panics if an event can't be sent, to fail the CI run.
+fn ci_testing_send_events(world: &mut World, mut current_frame: Local<u32>) {
+    world.resource_scope(|world, config: bevy_ecs::prelude::Mut<CiTestingConfig>| {
+        for CiTestingEventOnFrame(_, event) in config
+            .events
+            .iter()
+            .filter(|CiTestingEventOnFrame(frame, _)| *frame == *current_frame)
+        {
+            match event {
+                CiTestingEvent::AppExit => {
+                    world.send_event(AppExit);
+                    info!("Exiting after {} frames. Test successful!", *current_frame);
+                }
+                CiTestingEvent::Event(name, value) => {
+                    let send_event = world
+                        .get_resource::<CiTestingEventTypes>()
+                        .and_then(|types| types.0.get(name).copied())
+                        .unwrap_or_else(|| {
+                            panic!("the event {name} can't be sent by the CI testing configuration")
+                        });
+                    if let Err(err) = send_event(world, value) {
+                        panic!("error deserializing the CI testing event {name}: {err}");
+                    }
+                }
+                CiTestingEvent::Screenshot => {
+                    let capture = world
+                        .get_resource::<CiTestingScreenshotBackend>()
+                        .map(|backend| backend.0)
+                        .unwrap_or_else(|| {
+                            panic!("no screenshot backend was set for the CI testing configuration")
+                        });
+                    let path = PathBuf::from(format!("screenshot-{}.png", *current_frame));
+                    info!("Taking a screenshot to {}", path.display());
+                    capture(world, &path);
+                }
+            }
+        }
+    });
+    *current_frame += 1;
+}
+
 pub(crate) fn setup_app(app: &mut App) -> &mut App {
     #[cfg(not(target_arch = "wasm32"))]
     let config: CiTestingConfig = {
@@ -47,7 +178,90 @@ pub(crate) fn setup_app(app: &mut App) -> &mut App {
     };
 
     app.insert_resource(config)
-        .add_system(ci_testing_exit_after);
+        .add_system(ci_testing_exit_after)
+        .add_system_to_stage(crate::CoreStage::First, ci_testing_send_events);
 
     app
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Jump {
+        height: f32,
+    }
+
+    fn app(config: &str) -> App {
+        let mut app = App::empty();
+        app.add_default_stages()
+            .add_event::<AppExit>()
+            .add_event::<Jump>()
+            .add_ci_testing_event::<Jump>()
+            .insert_resource(ron::from_str::<CiTestingConfig>(config).unwrap())
+            .add_system_to_stage(crate::CoreStage::First, ci_testing_send_events);
+        app
+    }
+
+    #[test]
+    fn scripted_events() {
+        let mut app = app(r#"(
+            events: [
+                (1, Event("Jump", "(height: 2.0)")),
+                (2, AppExit),
+            ],
+        )"#);
+        assert!(app.world.resource::<CiTestingConfig>().exit_after.is_none());
+
+        app.update();
+        assert!(app.world.resource::<Events<Jump>>().is_empty());
+        app.update();
+        let mut jumps = app.world.resource_mut::<Events<Jump>>();
+        assert_eq!(
+            jumps.drain().collect::<Vec<_>>(),
+            vec![Jump { height: 2.0 }]
+        );
+        assert!(app.world.resource::<Events<AppExit>>().is_empty());
+        app.update();
+        assert!(!app.world.resource::<Events<AppExit>>().is_empty());
+    }
+
+    #[test]
+    fn scripted_screenshots() {
+        #[derive(Resource, Default)]
+        struct Screenshots(Vec<PathBuf>);
+
+        fn capture(world: &mut World, path: &Path) {
+            world
+                .get_resource_or_insert_with(Screenshots::default)
+                .0
+                .push(path.to_owned());
+        }
+
+        let mut app = app(r#"(events: [(1, Screenshot), (3, Screenshot)])"#);
+        app.set_ci_testing_screenshot_backend(capture);
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(
+            app.world.resource::<Screenshots>().0,
+            vec![
+                PathBuf::from("screenshot-1.png"),
+                PathBuf::from("screenshot-3.png")
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no screenshot backend was set")]
+    fn screenshot_without_backend() {
+        app(r#"(events: [(0, Screenshot)])"#).update();
+    }
+
+    #[test]
+    #[should_panic(expected = "the event Fall can't be sent")]
+    fn unknown_event() {
+        app(r#"(events: [(0, Event("Fall", "()"))])"#).update();
+    }
+}
@@ -12,6 +12,10 @@ mod ci_testing;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
+#[cfg(feature = "bevy_ci_testing")]
+pub use ci_testing::{
+    CiTestingConfig, CiTestingEvent, CiTestingEventOnFrame, CiTestingScreenshotFn,
+};
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
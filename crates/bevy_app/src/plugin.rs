@@ -1,7 +1,7 @@
 use downcast_rs::{impl_downcast, Downcast};
 
 use crate::App;
-use std::any::Any;
+use std::any::{Any, TypeId};
 
 /// A collection of Bevy app logic and configuration.
 ///
@@ -13,9 +13,30 @@ use std::any::Any;
 /// should be overriden to return `false`. Plugins are considered duplicate if they have the same
 /// [`name()`](Self::name). The default `name()` implementation returns the type name, which means
 /// generic plugins with different type parameters will not be considered duplicates.
+///
+/// A plugin can declare the plugins it relies on with [`dependencies()`](Self::dependencies).
+/// They are built before it, and the [`App`] panics if one of them is missing.
+///
+/// Once all plugins are built, [`App::run`] calls the [`finish()`](Self::finish) then the
+/// [`cleanup()`](Self::cleanup) method of every plugin, in the order they were added.
 pub trait Plugin: Downcast + Any + Send + Sync {
     /// Configures the [`App`] to which this plugin is added.
     fn build(&self, app: &mut App);
+    /// The plugins that must be built before this one.
+    ///
+    /// In a [`PluginGroup`](crate::PluginGroup), plugins are built after their dependencies in
+    /// the group. A dependency that isn't added to the [`App`] yet is added with its default value
+    /// if it was declared with [`PluginDependency::or_default`], otherwise adding this plugin
+    /// panics.
+    fn dependencies(&self) -> Vec<PluginDependency> {
+        Vec::new()
+    }
+    /// Finishes configuring the [`App`] once all plugins are built, for example to use resources
+    /// inserted by plugins added after this one.
+    fn finish(&self, _app: &mut App) {}
+    /// Runs after every plugin [finished](Self::finish), for example to remove resources only
+    /// needed while configuring the [`App`].
+    fn cleanup(&self, _app: &mut App) {}
     /// Configures a name for the [`Plugin`] which is primarily used for debugging.
     fn name(&self) -> &str {
         std::any::type_name::<Self>()
@@ -29,6 +50,57 @@ pub trait Plugin: Downcast + Any + Send + Sync {
 
 impl_downcast!(Plugin);
 
+/// A dependency of a [`Plugin`] on another plugin type, see [`Plugin::dependencies`].
+#[derive(Clone, Copy)]
+pub struct PluginDependency {
+    type_id: TypeId,
+    name: &'static str,
+    default: Option<fn() -> Box<dyn Plugin>>,
+}
+
+impl PluginDependency {
+    /// The plugin `T` must be added to the [`App`] before the dependent plugin.
+    pub fn new<T: Plugin>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+            default: None,
+        }
+    }
+
+    /// The plugin `T` is added with its default value if it wasn't added to the [`App`] before
+    /// the dependent plugin.
+    pub fn or_default<T: Plugin + Default>() -> Self {
+        Self {
+            default: Some(|| Box::<T>::default()),
+            ..Self::new::<T>()
+        }
+    }
+
+    /// The [`TypeId`] of the plugin.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// The type name of the plugin.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub(crate) fn create_default(&self) -> Option<Box<dyn Plugin>> {
+        self.default.map(|default| default())
+    }
+}
+
+impl std::fmt::Debug for PluginDependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginDependency")
+            .field("name", &self.name)
+            .field("default", &self.default.is_some())
+            .finish()
+    }
+}
+
 /// A type representing an unsafe function that returns a mutable pointer to a [`Plugin`].
 /// It is used for dynamically loading plugins.
 ///
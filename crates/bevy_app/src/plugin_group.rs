@@ -167,26 +167,69 @@ impl PluginGroupBuilder {
     }
 
     /// Consumes the [`PluginGroupBuilder`] and [builds](Plugin::build) the contained [`Plugin`]s
-    /// in the order specified.
+    /// in the order specified. A plugin is moved after the plugins of the group it
+    /// [depends on](Plugin::dependencies).
     ///
     /// # Panics
     ///
-    /// Panics if one of the plugin in the group was already added to the application.
+    /// Panics if one of the plugin in the group was already added to the application, if a
+    /// dependency is missing, or if plugins of the group depend on each other.
     pub fn finish(mut self, app: &mut App) {
-        for ty in &self.order {
-            if let Some(entry) = self.plugins.remove(ty) {
-                if entry.enabled {
-                    debug!("added plugin: {}", entry.plugin.name());
-                    if let Err(AppError::DuplicatePlugin { plugin_name }) =
-                        app.add_boxed_plugin(entry.plugin)
-                    {
-                        panic!(
-                            "Error adding plugin {} in group {}: plugin was already added in application",
-                            plugin_name,
-                            self.group_name
-                        );
+        let mut remaining: Vec<_> = self
+            .order
+            .iter()
+            .filter_map(|ty| self.plugins.remove(ty).map(|entry| (*ty, entry)))
+            .filter(|(_, entry)| entry.enabled)
+            .map(|(ty, entry)| {
+                let dependencies = entry
+                    .plugin
+                    .dependencies()
+                    .iter()
+                    .map(|dependency| dependency.type_id())
+                    .collect::<Vec<_>>();
+                (ty, entry.plugin, dependencies)
+            })
+            .collect();
+
+        while !remaining.is_empty() {
+            // Builds the first plugin whose dependencies in the group are all built.
+            let next = remaining.iter().position(|(_, _, dependencies)| {
+                dependencies
+                    .iter()
+                    .all(|dependency| remaining.iter().all(|(ty, _, _)| ty != dependency))
+            });
+            let Some(next) = next else {
+                // Every remaining plugin depends on another one, so following these dependencies
+                // leads to a cycle.
+                let mut path = vec![0];
+                let cycle_start = loop {
+                    let (_, _, dependencies) = &remaining[*path.last().unwrap()];
+                    let next = remaining
+                        .iter()
+                        .position(|(ty, _, _)| dependencies.contains(ty))
+                        .unwrap();
+                    if let Some(start) = path.iter().position(|index| *index == next) {
+                        path.push(next);
+                        break start;
                     }
-                }
+                    path.push(next);
+                };
+                let err = AppError::DependencyCycle {
+                    plugin_names: path[cycle_start..]
+                        .iter()
+                        .map(|index| remaining[*index].1.name().to_string())
+                        .collect(),
+                };
+                panic!("Error adding plugins in group {}: {err}", self.group_name);
+            };
+            let (_, plugin, _) = remaining.remove(next);
+            debug!("added plugin: {}", plugin.name());
+            let plugin_name = plugin.name().to_string();
+            if let Err(err) = app.add_boxed_plugin(plugin) {
+                panic!(
+                    "Error adding plugin {plugin_name} in group {}: {err}",
+                    self.group_name
+                );
             }
         }
     }
@@ -1,17 +1,25 @@
 use crate::{
+    meta::{meta_path, LoadSettings},
     path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    processor::{
+        processed_asset_key, processed_asset_path, read_processed_asset, write_processed_asset,
+    },
+    Asset, AssetDynamic, AssetEvent, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel,
+    AssetLifecycleEvent, AssetLoader, AssetMetaError, AssetMetaFile, AssetProcessor, Assets,
+    Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState, LoaderSettings, RefChange,
+    RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{get_short_name, Entry, HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`.
@@ -21,6 +29,10 @@ pub enum AssetServerError {
     #[error("asset folder path is not a directory: {0}")]
     AssetFolderNotADirectory(String),
 
+    /// No asset source was added with the name of the asset path.
+    #[error("no asset source found with the name {0}")]
+    MissingAssetSource(String),
+
     /// No asset loader was found for the specified extensions.
     #[error("no `AssetLoader` found{}", format_missing_asset_ext(.extensions))]
     MissingAssetLoader {
@@ -30,17 +42,40 @@ pub enum AssetServerError {
         extensions: Vec<String>,
     },
 
+    /// No asset loader was found with the name given by the meta file of the asset.
+    #[error("no `AssetLoader` found with the name {name}")]
+    MissingAssetLoaderWithName {
+        /// The name of the asset loader, as given by the meta file.
+        name: String,
+    },
+
     /// The handle type does not match the type of the loaded asset.
     #[error("the given type does not match the type of the loaded asset")]
     IncorrectHandleType,
 
+    /// The asset source does not contain an asset with the requested label.
+    #[error("the asset {0:?} was not found in its source")]
+    MissingLabeledAsset(AssetPath<'static>),
+
+    /// Assets were loaded directly by each other in a cycle.
+    #[error("dependency cycle while loading assets directly: {}", format_dependency_cycle(.0))]
+    DependencyCycle(Vec<AssetPath<'static>>),
+
     /// Encountered an error while processing an asset.
     #[error("encountered an error while loading an asset: {0}")]
     AssetLoaderError(anyhow::Error),
 
+    /// Encountered an error while processing an asset.
+    #[error("encountered an error while processing an asset: {0}")]
+    AssetProcessorError(anyhow::Error),
+
     /// Encountered an error while reading an asset from disk.
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+
+    /// Encountered an error while reading the meta file of an asset.
+    #[error("encountered an error while reading the meta file of an asset: {0}")]
+    AssetMetaError(#[from] AssetMetaError),
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -55,10 +90,29 @@ fn format_missing_asset_ext(exts: &[String]) -> String {
     }
 }
 
+fn format_dependency_cycle(paths: &[AssetPath]) -> String {
+    paths
+        .iter()
+        .map(|path| path.to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// The loader of an asset source and the bytes it loads.
+struct PreparedSource {
+    asset_io: Arc<dyn AssetIo>,
+    loader: Arc<dyn AssetLoader>,
+    bytes: Vec<u8>,
+    meta: Option<Vec<u8>>,
+    settings: Option<LoadSettings>,
+}
+
 #[derive(Default)]
 pub(crate) struct AssetRefCounter {
     pub(crate) channel: Arc<RefChangeChannel>,
     pub(crate) ref_counts: Arc<RwLock<HashMap<HandleId, usize>>>,
+    /// The number of handle ids of each asset source with live handles.
+    pub(crate) source_ref_counts: Arc<RwLock<HashMap<SourcePathId, usize>>>,
     pub(crate) mark_unused_assets: Arc<Mutex<Vec<HandleId>>>,
 }
 
@@ -66,12 +120,27 @@ pub(crate) struct AssetRefCounter {
 ///
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: Arc<dyn AssetIo>,
+    /// The asset sources added with [`AssetServer::add_asset_source`], by name.
+    pub(crate) named_sources: RwLock<HashMap<String, Arc<dyn AssetIo>>>,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    name_to_loader_index: RwLock<HashMap<String, usize>>,
+    load_settings: RwLock<HashMap<SourcePathId, LoadSettings>>,
+    /// The sources loaded with [`LoadContext::load_direct`] by each asset source.
+    direct_dependencies: RwLock<HashMap<AssetPath<'static>, HashSet<SourcePathId>>>,
+    processors: RwLock<Vec<Arc<dyn AssetProcessor>>>,
+    extension_to_processor_index: RwLock<HashMap<String, usize>>,
+    imported_assets: RwLock<Option<PathBuf>>,
+    /// The asset types requested with [`AssetServer::load`], by source and label.
+    requested_types: RwLock<HashMap<SourcePathId, HashMap<LabelId, Uuid>>>,
+    /// The errors of the asset sources which failed to load.
+    load_errors: RwLock<HashMap<SourcePathId, Arc<AssetServerError>>>,
+    /// The loaded asset sources waiting for their recursive dependencies to load.
+    pending_dependencies: Mutex<HashSet<SourcePathId>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
 }
 
@@ -128,11 +197,21 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                name_to_loader_index: Default::default(),
+                load_settings: Default::default(),
+                direct_dependencies: Default::default(),
+                processors: Default::default(),
+                extension_to_processor_index: Default::default(),
+                imported_assets: Default::default(),
+                requested_types: Default::default(),
+                load_errors: Default::default(),
+                pending_dependencies: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
-                asset_io,
+                asset_io: asset_io.into(),
+                named_sources: Default::default(),
             }),
         }
     }
@@ -142,6 +221,32 @@ impl AssetServer {
         &*self.server.asset_io
     }
 
+    /// Adds an asset source named `name`, which loads the asset paths with this source name, such
+    /// as `name://textures/grass.png`.
+    ///
+    /// If a source was already added with this name, it is replaced.
+    pub fn add_asset_source<T: AssetIo>(&self, name: impl Into<String>, asset_io: T) {
+        self.server
+            .named_sources
+            .write()
+            .insert(name.into(), Arc::new(asset_io));
+    }
+
+    /// Returns the asset I/O of the asset source named `name`.
+    pub fn get_asset_source(&self, name: &str) -> Option<Arc<dyn AssetIo>> {
+        self.server.named_sources.read().get(name).cloned()
+    }
+
+    /// Returns the asset I/O of the asset source named `source`, or the default asset I/O.
+    fn source_asset_io(&self, source: Option<&str>) -> Result<Arc<dyn AssetIo>, AssetServerError> {
+        match source {
+            Some(name) => self
+                .get_asset_source(name)
+                .ok_or_else(|| AssetServerError::MissingAssetSource(name.to_string())),
+            None => Ok(self.server.asset_io.clone()),
+        }
+    }
+
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
         if self
             .server
@@ -159,7 +264,8 @@ impl AssetServer {
     /// Adds the provided asset loader to the server.
     ///
     /// If `loader` has one or more supported extensions in conflict with loaders that came before
-    /// it, it will replace them.
+    /// it, it will replace them. The loader can also be chosen by the meta file of an asset, with
+    /// its type name or short type name.
     pub fn add_loader<T>(&self, loader: T)
     where
         T: AssetLoader,
@@ -172,9 +278,45 @@ impl AssetServer {
                 .write()
                 .insert(extension.to_string(), loader_index);
         }
+        let type_name = std::any::type_name::<T>();
+        let mut name_to_loader_index = self.server.name_to_loader_index.write();
+        name_to_loader_index.insert(get_short_name(type_name), loader_index);
+        name_to_loader_index.insert(type_name.to_string(), loader_index);
         loaders.push(Arc::new(loader));
     }
 
+    /// Adds the provided asset processor to the server.
+    ///
+    /// If `processor` has one or more supported extensions in conflict with processors that came
+    /// before it, it will replace them.
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let mut processors = self.server.processors.write();
+        let processor_index = processors.len();
+        for extension in processor.extensions() {
+            self.server
+                .extension_to_processor_index
+                .write()
+                .insert(extension.to_string(), processor_index);
+        }
+        processors.push(Arc::new(processor));
+    }
+
+    /// Sets the folder where processed assets are cached.
+    ///
+    /// Assets are processed again when their source or their [`AssetProcessor::version`] changes.
+    /// Without an imported assets folder, assets are processed every time they are loaded.
+    pub fn set_imported_assets_folder<P: Into<PathBuf>>(&self, path: P) {
+        *self.server.imported_assets.write() = Some(path.into());
+    }
+
+    /// Returns the folder where processed assets are cached, if any.
+    pub fn imported_assets_folder(&self) -> Option<PathBuf> {
+        self.server.imported_assets.read().clone()
+    }
+
     /// Gets a strong handle for an asset with the provided id.
     pub fn get_handle<T: Asset, I: Into<HandleId>>(&self, id: I) -> Handle<T> {
         let sender = self.server.asset_ref_counter.channel.sender.clone();
@@ -200,6 +342,18 @@ impl AssetServer {
             })
     }
 
+    fn get_asset_loader_with_name(
+        &self,
+        name: &str,
+    ) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
+        let index = self.server.name_to_loader_index.read().get(name).copied();
+        index
+            .map(|index| self.server.loaders.read()[index].clone())
+            .ok_or_else(|| AssetServerError::MissingAssetLoaderWithName {
+                name: name.to_string(),
+            })
+    }
+
     fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
@@ -230,6 +384,20 @@ impl AssetServer {
         })
     }
 
+    fn get_path_asset_processor<P: AsRef<Path>>(&self, path: P) -> Option<Arc<dyn AssetProcessor>> {
+        let s = path.as_ref().file_name()?.to_str()?.to_lowercase();
+
+        let index_map = self.server.extension_to_processor_index.read();
+        let mut ext = s.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(&index) = index_map.get(ext) {
+                return Some(self.server.processors.read()[index].clone());
+            }
+        }
+        None
+    }
+
     /// Gets the source path of an asset from the provided handle.
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
@@ -252,6 +420,27 @@ impl AssetServer {
         }
     }
 
+    /// Gets the load state of an asset and of all of its recursive dependencies, the assets added
+    /// with [`LoadedAsset::add_dependency`](crate::LoadedAsset::add_dependency) by its loader.
+    ///
+    /// This method will only return [`LoadState::Loaded`] if the asset and all of its dependencies
+    /// were loaded successfully, and returns [`LoadState::Failed`] if any of them failed to load.
+    /// Dependencies which are not loaded yet are [`LoadState::Loading`], as they are queued for
+    /// loading along with the asset.
+    ///
+    /// Once an asset loaded by the asset server reaches this state, an
+    /// [`AssetEvent::LoadedWithDependencies`](crate::AssetEvent::LoadedWithDependencies) or
+    /// [`AssetEvent::Failed`](crate::AssetEvent::Failed) event is sent for it.
+    pub fn get_recursive_dependency_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+        match handle.into() {
+            HandleId::AssetPathId(id) => {
+                let asset_sources = self.server.asset_sources.read();
+                recursive_dependency_load_state(&asset_sources, id.source_path_id()).0
+            }
+            HandleId::Id(_, _) => LoadState::NotLoaded,
+        }
+    }
+
     /// Gets the overall load state of a group of assets from the provided handles.
     ///
     /// This method will only return [`LoadState::Loaded`] if all assets in the
@@ -301,9 +490,57 @@ impl AssetServer {
     /// [asset loader]: AssetLoader
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<T> {
+        let path = path.into();
+        let id = path.get_id();
+        // an asset that isn't loaded or loading is loaded again with its default settings
+        if !matches!(
+            self.get_load_state(id),
+            LoadState::Loaded | LoadState::Loading
+        ) {
+            self.server
+                .load_settings
+                .write()
+                .remove(&id.source_path_id());
+        }
+        self.load_typed(path)
+    }
+
+    fn load_typed<T: Asset>(&self, path: AssetPath<'_>) -> Handle<T> {
+        let id = path.get_id();
+        self.server
+            .requested_types
+            .write()
+            .entry(id.source_path_id())
+            .or_default()
+            .insert(id.label_id(), T::TYPE_UUID);
         self.load_untyped(path).typed()
     }
 
+    /// Queues an [`Asset`] at the provided path for loading with the given loader settings.
+    ///
+    /// The `settings` function is applied to the settings read from the `.meta` file of the asset,
+    /// or to the default settings, before they are passed to its loader through
+    /// [`LoadContext::settings`]. Loading fails if the loader reads another type of settings.
+    ///
+    /// The settings are kept when the asset is reloaded. If the asset is already loaded or
+    /// loading, they are only used once it is reloaded. They are dropped once the asset is freed,
+    /// or when it is loaded again with [`load`](AssetServer::load) while it isn't loaded.
+    ///
+    /// See [`load`](AssetServer::load).
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T: Asset, S: LoaderSettings, P: Into<AssetPath<'a>>>(
+        &self,
+        path: P,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Handle<T> {
+        let path = path.into();
+        self.server
+            .load_settings
+            .write()
+            .insert(path.get_id().source_path_id(), LoadSettings::new(settings));
+        self.load_typed(path)
+    }
+
     async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
@@ -338,6 +575,10 @@ impl AssetServer {
             }
 
             source_info.load_state = LoadState::Loading;
+            self.server
+                .load_errors
+                .write()
+                .remove(&asset_path_id.source_path_id());
             source_info.committed_assets.clear();
             source_info.version += 1;
             source_info.meta = None;
@@ -352,34 +593,22 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
-        // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
-            Ok(loader) => loader,
+        let source = match self.prepare_source(&asset_path).await {
+            Ok(source) => source,
             Err(err) => {
                 set_asset_failed();
                 return Err(err);
             }
         };
 
-        // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
-            Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
-            }
-        };
-
         // load the asset source using the corresponding AssetLoader
-        let mut load_context = LoadContext::new(
-            asset_path.path(),
-            &self.server.asset_ref_counter.channel,
-            self.asset_io(),
-            version,
-        );
+        let mut load_context = LoadContext::new(&asset_path, self, &*source.asset_io, version);
+        load_context.meta = source.meta.as_deref();
+        load_context.settings = source.settings.as_ref();
 
-        if let Err(err) = asset_loader
-            .load(&bytes, &mut load_context)
+        if let Err(err) = source
+            .loader
+            .load(&source.bytes, &mut load_context)
             .await
             .map_err(AssetServerError::AssetLoaderError)
         {
@@ -421,13 +650,162 @@ impl AssetServer {
             }
         }
 
-        self.asset_io()
-            .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        self.set_direct_dependencies(&asset_path, &load_context.direct_dependencies);
+
+        watch_source_for_changes(&*source.asset_io, asset_path.path()).unwrap();
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
+    /// Finds the loader of the asset source at `asset_path` and loads the bytes it loads.
+    async fn prepare_source(
+        &self,
+        asset_path: &AssetPath<'_>,
+    ) -> Result<PreparedSource, AssetServerError> {
+        let asset_io = self.source_asset_io(asset_path.source())?;
+        let path = asset_path.path();
+
+        // load the meta file, if any, which can choose the asset loader. sources without a meta
+        // file are loaded with the default settings of their loader
+        let meta = match asset_io.load_path(&meta_path(path)).await {
+            Ok(meta) => Some(meta),
+            Err(AssetIoError::NotFound(_)) => None,
+            Err(err) => return Err(err.into()),
+        };
+        let loader_name = meta
+            .as_deref()
+            .map(|meta| AssetMetaFile::read_loader(meta, path))
+            .transpose()?
+            .flatten();
+
+        // get the according asset loader, which loads the processed format of processed assets
+        let asset_processor = self.get_path_asset_processor(path);
+        let loader = match (&loader_name, &asset_processor) {
+            (Some(name), _) => self.get_asset_loader_with_name(name),
+            (None, Some(processor)) => self.get_asset_loader(processor.processed_extension()),
+            (None, None) => self.get_path_asset_loader(path),
+        }?;
+
+        // load the asset bytes
+        let bytes = asset_io.load_path(path).await?;
+        let bytes = match asset_processor {
+            Some(processor) => self.process_asset(&*processor, asset_path, &bytes).await?,
+            None => bytes,
+        };
+
+        let settings = self
+            .server
+            .load_settings
+            .read()
+            .get(&asset_path.get_id().source_path_id())
+            .cloned();
+        Ok(PreparedSource {
+            asset_io,
+            loader,
+            bytes,
+            meta,
+            settings,
+        })
+    }
+
+    /// Loads the asset at `asset_path` for [`LoadContext::load_direct`] and returns its value,
+    /// without adding it to its asset storage.
+    ///
+    /// `load_stack` holds the sources being loaded, to detect cycles.
+    pub(crate) async fn load_direct(
+        &self,
+        asset_path: &AssetPath<'_>,
+        load_stack: &[AssetPath<'static>],
+    ) -> Result<Box<dyn AssetDynamic>, AssetServerError> {
+        let source_path =
+            AssetPath::new_source_ref(asset_path.source(), asset_path.path(), None).to_owned();
+        if load_stack.contains(&source_path) {
+            let mut cycle = load_stack.to_vec();
+            cycle.push(source_path);
+            return Err(AssetServerError::DependencyCycle(cycle));
+        }
+
+        let source = self.prepare_source(&source_path).await?;
+        let mut load_context = LoadContext::new(&source_path, self, &*source.asset_io, 0);
+        load_context.meta = source.meta.as_deref();
+        load_context.settings = source.settings.as_ref();
+        load_context.load_stack = load_stack.to_vec();
+        load_context.load_stack.push(source_path.clone());
+        source
+            .loader
+            .load(&source.bytes, &mut load_context)
+            .await
+            .map_err(AssetServerError::AssetLoaderError)?;
+
+        self.set_direct_dependencies(&source_path, &load_context.direct_dependencies);
+        watch_source_for_changes(&*source.asset_io, source_path.path())?;
+        let label = asset_path.label().map(str::to_string);
+        for loaded_asset in load_context.labeled_assets.values() {
+            for dependency in &loaded_asset.dependencies {
+                self.load_untracked(dependency.clone(), false);
+            }
+        }
+        load_context
+            .labeled_assets
+            .remove(&label)
+            .and_then(|loaded_asset| loaded_asset.value)
+            .ok_or_else(|| AssetServerError::MissingLabeledAsset(asset_path.to_owned()))
+    }
+
+    fn set_direct_dependencies(
+        &self,
+        asset_path: &AssetPath<'_>,
+        dependencies: &HashSet<SourcePathId>,
+    ) {
+        let source_path =
+            AssetPath::new_source_ref(asset_path.source(), asset_path.path(), None).to_owned();
+        let mut direct_dependencies = self.server.direct_dependencies.write();
+        if dependencies.is_empty() {
+            direct_dependencies.remove(&source_path);
+        } else {
+            direct_dependencies.insert(source_path, dependencies.clone());
+        }
+    }
+
+    /// Processes the source `bytes` of the asset at `path`, reusing the cached processed asset if
+    /// its source and processor didn't change since it was cached.
+    async fn process_asset(
+        &self,
+        processor: &dyn AssetProcessor,
+        asset_path: &AssetPath<'_>,
+        bytes: &[u8],
+    ) -> Result<Vec<u8>, AssetServerError> {
+        let path = asset_path.path();
+        let processed_path = self.imported_assets_folder().and_then(|imported_assets| {
+            let processed_path = processed_asset_path(&imported_assets, asset_path, processor);
+            if processed_path.is_none() {
+                warn!(
+                    "the processed asset {:?} is not cached, since its path or source can't be used in the imported assets folder",
+                    asset_path
+                );
+            }
+            processed_path
+        });
+        let key = processed_asset_key(bytes, processor);
+        if let Some(processed) = processed_path
+            .as_ref()
+            .and_then(|processed_path| read_processed_asset(processed_path, &key))
+        {
+            return Ok(processed);
+        }
+
+        let processed = processor
+            .process(bytes, path)
+            .await
+            .map_err(AssetServerError::AssetProcessorError)?;
+        if let Some(processed_path) = processed_path {
+            if let Err(err) = write_processed_asset(&processed_path, &key, &processed) {
+                warn!("failed to cache the processed asset {:?}: {}", path, err);
+            }
+        }
+        Ok(processed)
+    }
+
     /// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
     ///
     /// See [`load`](AssetServer::load).
@@ -441,8 +819,60 @@ impl AssetServer {
     ///
     /// This is useful for custom hot-reloading or for supporting `watch_for_changes`
     /// in custom [`AssetIo`] implementations.
+    ///
+    /// The assets which loaded it with [`LoadContext::load_direct`] are reloaded too.
     pub fn reload_asset<'a, P: Into<AssetPath<'a>>>(&self, path: P) {
-        self.load_untracked(path.into(), true);
+        self.reload_with_dependents(path.into(), &mut HashSet::default());
+    }
+
+    /// Returns whether the source at `asset_path` was loaded, on its own or directly by another
+    /// asset.
+    #[cfg(feature = "filesystem_watcher")]
+    pub(crate) fn is_source_loaded(&self, asset_path: &AssetPath<'_>) -> bool {
+        let source_path_id = asset_path.get_id().source_path_id();
+        self.server
+            .asset_sources
+            .read()
+            .contains_key(&source_path_id)
+            || self
+                .server
+                .direct_dependencies
+                .read()
+                .values()
+                .any(|dependencies| dependencies.contains(&source_path_id))
+    }
+
+    fn reload_with_dependents(
+        &self,
+        asset_path: AssetPath<'_>,
+        reloaded: &mut HashSet<SourcePathId>,
+    ) {
+        let source_path_id = asset_path.get_id().source_path_id();
+        if !reloaded.insert(source_path_id) {
+            return;
+        }
+        let dependents: Vec<AssetPath<'static>> = self
+            .server
+            .direct_dependencies
+            .read()
+            .iter()
+            .filter(|(_, dependencies)| dependencies.contains(&source_path_id))
+            .map(|(dependent, _)| dependent.clone())
+            .collect();
+
+        // sources only loaded directly by other assets aren't loaded on their own
+        if dependents.is_empty()
+            || self
+                .server
+                .asset_sources
+                .read()
+                .contains_key(&source_path_id)
+        {
+            self.load_untracked(asset_path, true);
+        }
+        for dependent in dependents {
+            self.reload_with_dependents(dependent, reloaded);
+        }
     }
 
     pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
@@ -450,8 +880,10 @@ impl AssetServer {
         let owned_path = asset_path.to_owned();
         IoTaskPool::get()
             .spawn(async move {
+                let source_path_id = owned_path.get_id().source_path_id();
                 if let Err(err) = server.load_async(owned_path, force).await {
                     warn!("{}", err);
+                    server.set_load_error(source_path_id, err);
                 }
             })
             .detach();
@@ -466,6 +898,81 @@ impl AssetServer {
         asset_path.into()
     }
 
+    /// Records the error of an asset source which failed to load, and sends it in an
+    /// [`AssetEvent::Failed`](crate::AssetEvent::Failed) event for each of its assets.
+    fn set_load_error(&self, source_path_id: SourcePathId, error: AssetServerError) {
+        let error = Arc::new(error);
+        self.server
+            .load_errors
+            .write()
+            .insert(source_path_id, error.clone());
+        self.server
+            .pending_dependencies
+            .lock()
+            .remove(&source_path_id);
+
+        // the source may fail to load before its asset types are known, so the assets requested
+        // with a type are notified too
+        let mut asset_types = self
+            .server
+            .requested_types
+            .read()
+            .get(&source_path_id)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(source_info) = self.server.asset_sources.read().get(&source_path_id) {
+            asset_types.extend(&source_info.asset_types);
+        }
+        let asset_lifecycles = self.server.asset_lifecycles.read();
+        for (label_id, type_uuid) in asset_types {
+            if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
+                asset_lifecycle.load_failed(
+                    AssetPathId::new(source_path_id, label_id).into(),
+                    error.clone(),
+                );
+            }
+        }
+    }
+
+    /// Sends the [`AssetEvent::LoadedWithDependencies`](crate::AssetEvent::LoadedWithDependencies)
+    /// and [`AssetEvent::Failed`](crate::AssetEvent::Failed) events of the loaded asset sources
+    /// whose recursive dependencies finished loading.
+    pub(crate) fn update_dependency_load_states(&self) {
+        let mut pending_dependencies = self.server.pending_dependencies.lock();
+        if pending_dependencies.is_empty() {
+            return;
+        }
+        let asset_sources = self.server.asset_sources.read();
+        let asset_lifecycles = self.server.asset_lifecycles.read();
+        let load_errors = self.server.load_errors.read();
+        pending_dependencies.retain(|&source_path_id| {
+            let source_info = match asset_sources.get(&source_path_id) {
+                Some(source_info) => source_info,
+                None => return false,
+            };
+            let error = match recursive_dependency_load_state(&asset_sources, source_path_id) {
+                (LoadState::Loading, _) => return true,
+                (LoadState::Loaded, _) => None,
+                (LoadState::Failed, Some(failed)) => match load_errors.get(&failed) {
+                    Some(error) => Some(error.clone()),
+                    // the error is recorded right after the load state is set
+                    None => return true,
+                },
+                _ => return false,
+            };
+            for (&label_id, type_uuid) in &source_info.asset_types {
+                if let Some(asset_lifecycle) = asset_lifecycles.get(type_uuid) {
+                    let id = AssetPathId::new(source_path_id, label_id).into();
+                    match &error {
+                        Some(error) => asset_lifecycle.load_failed(id, error.clone()),
+                        None => asset_lifecycle.loaded_with_dependencies(id),
+                    }
+                }
+            }
+            false
+        });
+    }
+
     /// Loads assets from the specified folder recursively.
     ///
     /// # Errors
@@ -475,28 +982,28 @@ impl AssetServer {
     /// - If something unexpected happened while loading an asset, other
     /// [`AssetServerError`]s may be returned.
     #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
-    pub fn load_folder<P: AsRef<Path>>(
+    pub fn load_folder<'a, P: Into<AssetPath<'a>>>(
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
-            return Err(AssetServerError::AssetFolderNotADirectory(
-                path.to_str().unwrap().to_string(),
-            ));
+        let path = path.into();
+        let asset_io = self.source_asset_io(path.source())?;
+        if !asset_io.is_dir(path.path()) {
+            return Err(AssetServerError::AssetFolderNotADirectory(path.to_string()));
         }
 
         let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
+        for child_path in asset_io.read_directory(path.path())? {
+            let child_path = AssetPath::new_source_ref(path.source(), &child_path, None);
+            if asset_io.is_dir(child_path.path()) {
+                handles.extend(self.load_folder(child_path)?);
             } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
+                if self.get_path_asset_loader(child_path.path()).is_err()
+                    && self.get_path_asset_processor(child_path.path()).is_none()
+                {
                     continue;
                 }
-                let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string."));
-                handles.push(handle);
+                handles.push(self.load_untyped(child_path));
             }
         }
 
@@ -511,13 +1018,26 @@ impl AssetServer {
             let ref_counts = self.server.asset_ref_counter.ref_counts.read();
             let asset_sources = self.server.asset_sources.read();
             let asset_lifecycles = self.server.asset_lifecycles.read();
+            let mut requested_types = self.server.requested_types.write();
+            let mut freed_sources = HashSet::default();
             for potential_free in potential_frees.drain(..) {
                 if let Some(&0) = ref_counts.get(&potential_free) {
                     let type_uuid = match potential_free {
                         HandleId::Id(type_uuid, _) => Some(type_uuid),
-                        HandleId::AssetPathId(id) => asset_sources
-                            .get(&id.source_path_id())
-                            .and_then(|source_info| source_info.get_asset_type(id.label_id())),
+                        HandleId::AssetPathId(id) => {
+                            if let Entry::Occupied(mut entry) =
+                                requested_types.entry(id.source_path_id())
+                            {
+                                entry.get_mut().remove(&id.label_id());
+                                if entry.get().is_empty() {
+                                    entry.remove();
+                                }
+                            }
+                            freed_sources.insert(id.source_path_id());
+                            asset_sources
+                                .get(&id.source_path_id())
+                                .and_then(|source_info| source_info.get_asset_type(id.label_id()))
+                        }
                     };
 
                     if let Some(type_uuid) = type_uuid {
@@ -527,6 +1047,20 @@ impl AssetServer {
                     }
                 }
             }
+
+            // the load errors and settings of sources are kept until none of their assets are
+            // used anymore
+            if !freed_sources.is_empty() {
+                let source_ref_counts = self.server.asset_ref_counter.source_ref_counts.read();
+                let mut load_errors = self.server.load_errors.write();
+                let mut load_settings = self.server.load_settings.write();
+                for source_path_id in freed_sources {
+                    if !source_ref_counts.contains_key(&source_path_id) {
+                        load_errors.remove(&source_path_id);
+                        load_settings.remove(&source_path_id);
+                    }
+                }
+            }
         }
     }
 
@@ -534,6 +1068,7 @@ impl AssetServer {
     pub fn mark_unused_assets(&self) {
         let receiver = &self.server.asset_ref_counter.channel.receiver;
         let mut ref_counts = self.server.asset_ref_counter.ref_counts.write();
+        let mut source_ref_counts = self.server.asset_ref_counter.source_ref_counts.write();
         let mut potential_frees = None;
         loop {
             let ref_change = match receiver.try_recv() {
@@ -542,11 +1077,27 @@ impl AssetServer {
                 Err(TryRecvError::Disconnected) => panic!("RefChange channel disconnected."),
             };
             match ref_change {
-                RefChange::Increment(handle_id) => *ref_counts.entry(handle_id).or_insert(0) += 1,
+                RefChange::Increment(handle_id) => {
+                    let entry = ref_counts.entry(handle_id).or_insert(0);
+                    *entry += 1;
+                    if let (1, HandleId::AssetPathId(id)) = (*entry, handle_id) {
+                        *source_ref_counts.entry(id.source_path_id()).or_insert(0) += 1;
+                    }
+                }
                 RefChange::Decrement(handle_id) => {
                     let entry = ref_counts.entry(handle_id).or_insert(0);
                     *entry -= 1;
                     if *entry == 0 {
+                        if let HandleId::AssetPathId(id) = handle_id {
+                            if let Entry::Occupied(mut count) =
+                                source_ref_counts.entry(id.source_path_id())
+                            {
+                                *count.get_mut() -= 1;
+                                if *count.get() == 0 {
+                                    count.remove();
+                                }
+                            }
+                        }
                         potential_frees
                             .get_or_insert_with(|| {
                                 self.server.asset_ref_counter.mark_unused_assets.lock()
@@ -566,8 +1117,11 @@ impl AssetServer {
                 .take()
                 .expect("Asset should exist at this point.");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
-                let asset_path =
-                    AssetPath::new_ref(load_context.path, label.as_ref().map(|l| l.as_str()));
+                let asset_path = AssetPath::new_source_ref(
+                    load_context.source,
+                    load_context.path,
+                    label.as_ref().map(|l| l.as_str()),
+                );
                 asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
             } else {
                 panic!(
@@ -603,6 +1157,10 @@ impl AssetServer {
                                 source_info.committed_assets.insert(id.label_id());
                                 if source_info.is_loaded() {
                                     source_info.load_state = LoadState::Loaded;
+                                    self.server
+                                        .pending_dependencies
+                                        .lock()
+                                        .insert(id.source_path_id());
                                 }
                             }
                         }
@@ -621,6 +1179,17 @@ impl AssetServer {
                     }
                     assets.remove(handle_id);
                 }
+                Ok(AssetLifecycleEvent::LoadedWithDependencies(handle_id)) => {
+                    assets.send_load_event(AssetEvent::LoadedWithDependencies {
+                        handle: Handle::weak(handle_id),
+                    });
+                }
+                Ok(AssetLifecycleEvent::Failed(handle_id, error)) => {
+                    assets.send_load_event(AssetEvent::Failed {
+                        handle: Handle::weak(handle_id),
+                        error,
+                    });
+                }
                 Err(TryRecvError::Empty) => {
                     break;
                 }
@@ -630,6 +1199,63 @@ impl AssetServer {
     }
 }
 
+/// Watches the asset source at `path` and its meta file, even if it doesn't exist yet, so that
+/// creating it reloads the asset.
+fn watch_source_for_changes(asset_io: &dyn AssetIo, path: &Path) -> Result<(), AssetIoError> {
+    asset_io.watch_path_for_changes(path)?;
+    asset_io.watch_path_for_changes(&meta_path(path))
+}
+
+/// Returns the load state of the asset source `source_path_id` and of its recursive dependencies,
+/// along with the source which failed to load, if any.
+fn recursive_dependency_load_state(
+    asset_sources: &HashMap<SourcePathId, SourceInfo>,
+    source_path_id: SourcePathId,
+) -> (LoadState, Option<SourcePathId>) {
+    let source_load_state = |source_path_id| {
+        asset_sources
+            .get(&source_path_id)
+            .map_or(LoadState::NotLoaded, |info: &SourceInfo| info.load_state)
+    };
+    match source_load_state(source_path_id) {
+        LoadState::Loaded => {}
+        LoadState::Failed => return (LoadState::Failed, Some(source_path_id)),
+        load_state => return (load_state, None),
+    }
+
+    let mut load_state = LoadState::Loaded;
+    let mut visited = HashSet::default();
+    visited.insert(source_path_id);
+    let mut queue = vec![source_path_id];
+    while let Some(source_path_id) = queue.pop() {
+        let meta = match asset_sources
+            .get(&source_path_id)
+            .and_then(|info| info.meta.as_ref())
+        {
+            Some(meta) => meta,
+            None => continue,
+        };
+        for dependency in meta.assets.iter().flat_map(|asset| &asset.dependencies) {
+            let dependency_id = dependency.get_id().source_path_id();
+            if !visited.insert(dependency_id) {
+                continue;
+            }
+            match source_load_state(dependency_id) {
+                LoadState::Loaded => queue.push(dependency_id),
+                LoadState::Failed => return (LoadState::Failed, Some(dependency_id)),
+                LoadState::Unloaded => load_state = LoadState::Unloaded,
+                // dependencies are queued for loading along with the asset
+                LoadState::NotLoaded | LoadState::Loading => {
+                    if load_state == LoadState::Loaded {
+                        load_state = LoadState::Loading;
+                    }
+                }
+            }
+        }
+    }
+    (load_state, None)
+}
+
 fn free_unused_assets_system_impl(asset_server: &AssetServer) {
     asset_server.free_unused_assets();
     asset_server.mark_unused_assets();
@@ -640,11 +1266,16 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     free_unused_assets_system_impl(&asset_server);
 }
 
+/// A system for sending the events of the assets whose recursive dependencies finished loading.
+pub fn update_dependency_load_states_system(asset_server: Res<AssetServer>) {
+    asset_server.update_dependency_load_states();
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{loader::LoadedAsset, update_asset_storage_system};
-    use bevy_app::App;
+    use crate::{loader::LoadedAsset, update_asset_storage_system, MemoryAssetIo};
+    use bevy_app::{App, CoreStage};
     use bevy_ecs::prelude::*;
     use bevy_reflect::TypeUuid;
     use bevy_utils::BoxedFuture;
@@ -917,6 +1548,779 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    struct UppercaseProcessor {
+        version: u32,
+        processed: Arc<Mutex<usize>>,
+    }
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            *self.processed.lock() += 1;
+            Box::pin(async move { Ok(bytes.to_ascii_uppercase()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn processed_extension(&self) -> &str {
+            "upper"
+        }
+
+        fn version(&self) -> u32 {
+            self.version
+        }
+    }
+
+    type LoadedBytes = Arc<Mutex<Vec<Vec<u8>>>>;
+
+    #[derive(Default)]
+    struct UppercaseLoader(LoadedBytes);
+    impl AssetLoader for UppercaseLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            self.0.lock().push(bytes.to_vec());
+            ctx.set_default_asset(LoadedAsset::new(PngAsset));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["upper"]
+        }
+    }
+
+    fn setup_processed(dir: &Path, version: u32) -> (AssetServer, Arc<Mutex<usize>>, LoadedBytes) {
+        let asset_server = setup(dir.join("assets"));
+        asset_server.set_imported_assets_folder(dir.join("imported_assets"));
+        let processed = Arc::new(Mutex::new(0));
+        asset_server.add_processor(UppercaseProcessor {
+            version,
+            processed: processed.clone(),
+        });
+        let loader = UppercaseLoader::default();
+        let loaded = loader.0.clone();
+        asset_server.add_loader(loader);
+        asset_server.register_asset_type::<PngAsset>();
+        (asset_server, processed, loaded)
+    }
+
+    #[test]
+    fn test_processed_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("assets/text")).unwrap();
+        std::fs::write(dir.path().join("assets/text/hello.txt"), "hello").unwrap();
+        let (asset_server, processed, loaded) = setup_processed(dir.path(), 0);
+
+        let path: AssetPath = "text/hello.txt".into();
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        assert_eq!(*processed.lock(), 1);
+        assert_eq!(loaded.lock().as_slice(), [b"HELLO".to_vec()]);
+        assert_eq!(
+            asset_server.get_load_state(path.get_id()),
+            LoadState::Loading
+        );
+
+        let processed_path = dir.path().join("imported_assets/text/hello.txt.upper");
+        let imported_assets = dir.path().join("imported_assets");
+        let processor = UppercaseProcessor {
+            version: 0,
+            processed: Default::default(),
+        };
+        assert_eq!(
+            processed_asset_path(&imported_assets, &path, &processor),
+            Some(processed_path.clone())
+        );
+        // The assets of named sources are cached in their own folder.
+        assert_eq!(
+            processed_asset_path(
+                &imported_assets,
+                &AssetPath::from("text/hello.txt").with_source("text"),
+                &processor
+            ),
+            Some(imported_assets.join("@sources/text/text/hello.txt.upper"))
+        );
+        // Paths that would be outside of the imported assets folder, or in the folder of the named
+        // sources, are rejected.
+        for outside in [
+            "../hello.txt",
+            "text/../../hello.txt",
+            "/text/hello.txt",
+            "@sources/text/text/hello.txt",
+            "./@sources/text/text/hello.txt",
+        ] {
+            assert_eq!(
+                processed_asset_path(&imported_assets, &outside.into(), &processor),
+                None
+            );
+        }
+        for source in ["..", ".", "", "mods/..", "mods/text", "/mods"] {
+            assert_eq!(
+                processed_asset_path(
+                    &imported_assets,
+                    &AssetPath::from("hello.txt").with_source(source),
+                    &processor
+                ),
+                None
+            );
+        }
+        assert_eq!(std::fs::read(&processed_path).unwrap(), b"HELLO");
+        // The key is a hex-encoded BLAKE3 hash.
+        let key =
+            std::fs::read_to_string(dir.path().join("imported_assets/text/hello.txt.upper.hash"))
+                .unwrap();
+        assert!(blake3::Hash::from_hex(key).is_ok());
+
+        // The cached processed asset is loaded while the source doesn't change.
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        assert_eq!(*processed.lock(), 1);
+        assert_eq!(loaded.lock().len(), 2);
+        assert_eq!(loaded.lock()[1], b"HELLO");
+
+        // A changed source is processed again.
+        std::fs::write(dir.path().join("assets/text/hello.txt"), "bye").unwrap();
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        assert_eq!(*processed.lock(), 2);
+        assert_eq!(loaded.lock()[2], b"BYE");
+        assert_eq!(std::fs::read(&processed_path).unwrap(), b"BYE");
+
+        // A new version of the processor processes the source again.
+        let (asset_server, processed, _) = setup_processed(dir.path(), 1);
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        assert_eq!(*processed.lock(), 1);
+        let (asset_server, processed, _) = setup_processed(dir.path(), 1);
+        futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+        assert_eq!(*processed.lock(), 0);
+    }
+
+    #[test]
+    fn test_processed_asset_without_imported_assets() {
+        let dir = create_dir_and_file("hello.txt");
+        let asset_server = setup(dir.path());
+        asset_server.register_asset_type::<PngAsset>();
+        let processed = Arc::new(Mutex::new(0));
+        asset_server.add_processor(UppercaseProcessor {
+            version: 0,
+            processed: processed.clone(),
+        });
+
+        // The processed format needs a loader.
+        let path: AssetPath = "hello.txt".into();
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+            .unwrap_err();
+        assert!(match err {
+            AssetServerError::MissingAssetLoader { extensions } => extensions == ["upper"],
+            _ => false,
+        });
+        assert_eq!(*processed.lock(), 0);
+
+        asset_server.add_loader(UppercaseLoader::default());
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+        assert_eq!(*processed.lock(), 2);
+        assert!(asset_server.imported_assets_folder().is_none());
+    }
+
+    #[derive(serde::Deserialize, Debug, Default, PartialEq, Eq, Clone)]
+    #[serde(default)]
+    struct NumberSettings {
+        scale: u32,
+        negate: bool,
+    }
+
+    #[derive(Default)]
+    struct NumberLoader(Arc<Mutex<Vec<NumberSettings>>>);
+    impl AssetLoader for NumberLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                self.0.lock().push(ctx.settings::<NumberSettings>()?);
+                ctx.set_default_asset(LoadedAsset::new(PngAsset));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["number"]
+        }
+    }
+
+    #[test]
+    fn test_meta_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("image.png"), []).unwrap();
+        std::fs::write(
+            dir.path().join("image.png.meta"),
+            r#"(loader: Some("NumberLoader"), settings: (scale: 2))"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("one.number"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FailingLoader);
+        asset_server.add_loader(FakePngLoader);
+        let loader = NumberLoader::default();
+        let settings = loader.0.clone();
+        asset_server.add_loader(loader);
+        asset_server.register_asset_type::<PngAsset>();
+
+        // The meta file chooses the loader and its settings.
+        futures_lite::future::block_on(asset_server.load_async("image.png".into(), true)).unwrap();
+        // Assets without a meta file are loaded with the default settings.
+        futures_lite::future::block_on(asset_server.load_async("one.number".into(), true)).unwrap();
+        assert_eq!(
+            *settings.lock(),
+            [
+                NumberSettings {
+                    scale: 2,
+                    negate: false
+                },
+                NumberSettings::default()
+            ]
+        );
+
+        // Loaders can also be chosen by their full type name.
+        std::fs::write(
+            dir.path().join("image.png.meta"),
+            format!(
+                "(loader: Some({:?}))",
+                std::any::type_name::<FailingLoader>()
+            ),
+        )
+        .unwrap();
+        let err = futures_lite::future::block_on(asset_server.load_async("image.png".into(), true))
+            .unwrap_err();
+        assert!(matches!(err, AssetServerError::AssetLoaderError(_)));
+
+        std::fs::write(
+            dir.path().join("image.png.meta"),
+            r#"(loader: Some("MissingLoader"))"#,
+        )
+        .unwrap();
+        let err = futures_lite::future::block_on(asset_server.load_async("image.png".into(), true))
+            .unwrap_err();
+        assert!(match err {
+            AssetServerError::MissingAssetLoaderWithName { name } => name == "MissingLoader",
+            _ => false,
+        });
+
+        // Meta files that can't be read fail the load, instead of being ignored.
+        std::fs::remove_file(dir.path().join("image.png.meta")).unwrap();
+        std::fs::create_dir(dir.path().join("image.png.meta")).unwrap();
+        let err = futures_lite::future::block_on(asset_server.load_async("image.png".into(), true))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AssetServerError::AssetIoError(AssetIoError::Io(_))
+        ));
+        std::fs::remove_dir(dir.path().join("image.png.meta")).unwrap();
+
+        std::fs::write(dir.path().join("image.png.meta"), "(loader: 3)").unwrap();
+        let err = futures_lite::future::block_on(asset_server.load_async("image.png".into(), true))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AssetServerError::AssetMetaError(AssetMetaError::InvalidMeta { .. })
+        ));
+        assert_eq!(
+            asset_server.get_load_state(AssetPath::from("image.png").get_id()),
+            LoadState::Failed
+        );
+    }
+
+    #[test]
+    fn test_load_with_settings() {
+        let dir = create_dir_and_file("one.number");
+        std::fs::write(dir.path().join("one.number.meta"), "(settings: (scale: 3))").unwrap();
+        let asset_server = setup(dir.path());
+        let loader = NumberLoader::default();
+        let settings = loader.0.clone();
+        asset_server.add_loader(loader);
+        asset_server.register_asset_type::<PngAsset>();
+
+        let handle: Handle<PngAsset> =
+            asset_server.load_with_settings("one.number", |settings: &mut NumberSettings| {
+                settings.negate = true;
+            });
+        let path = asset_server.get_handle_path(&handle).unwrap().to_owned();
+        // The settings are kept when the asset is reloaded.
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        assert_eq!(
+            settings.lock().last(),
+            Some(&NumberSettings {
+                scale: 3,
+                negate: true
+            })
+        );
+
+        let _: Handle<PngAsset> = asset_server.load_with_settings("one.number", |_: &mut u32| {});
+        let err = futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap_err();
+        assert!(matches!(err, AssetServerError::AssetLoaderError(_)));
+    }
+
+    #[derive(Debug, TypeUuid)]
+    #[uuid = "5a7b3e0f-6fd5-4a8c-9d0c-0b2b5e4b1a7e"]
+    struct Text(String);
+
+    struct TextLoader;
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            let text = String::from_utf8_lossy(bytes).to_string();
+            ctx.set_labeled_asset("upper", LoadedAsset::new(Text(text.to_uppercase())));
+            ctx.set_default_asset(LoadedAsset::new(Text(text)));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    /// Loads the text at the path given by its source, and records it.
+    #[derive(Default)]
+    struct MaterialLoader(Arc<Mutex<Vec<String>>>);
+    impl AssetLoader for MaterialLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let path = String::from_utf8_lossy(bytes).to_string();
+                let text: Text = ctx.load_direct(path.as_str()).await?;
+                self.0.lock().push(text.0.clone());
+                ctx.set_default_asset(LoadedAsset::new(Text(format!("material {}", text.0))));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["mat"]
+        }
+    }
+
+    fn setup_direct(dir: &Path) -> (AssetServer, Arc<Mutex<Vec<String>>>) {
+        let asset_server = setup(dir);
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(FakePngLoader);
+        let loader = MaterialLoader::default();
+        let loaded = loader.0.clone();
+        asset_server.add_loader(loader);
+        asset_server.register_asset_type::<Text>();
+        (asset_server, loaded)
+    }
+
+    fn is_dependency_cycle(err: &AssetServerError) -> bool {
+        match err {
+            AssetServerError::DependencyCycle(_) => true,
+            AssetServerError::AssetLoaderError(err) => err
+                .downcast_ref::<AssetServerError>()
+                .is_some_and(is_dependency_cycle),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_load_direct() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("color.txt"), "red").unwrap();
+        std::fs::write(dir.path().join("red.mat"), "color.txt").unwrap();
+        std::fs::write(dir.path().join("upper.mat"), "color.txt#upper").unwrap();
+        std::fs::write(dir.path().join("missing.mat"), "color.txt#lower").unwrap();
+        std::fs::write(dir.path().join("image.png"), []).unwrap();
+        std::fs::write(dir.path().join("wrong.mat"), "image.png").unwrap();
+        let (asset_server, loaded) = setup_direct(dir.path());
+
+        futures_lite::future::block_on(asset_server.load_async("red.mat".into(), true)).unwrap();
+        futures_lite::future::block_on(asset_server.load_async("upper.mat".into(), true)).unwrap();
+        assert_eq!(*loaded.lock(), ["red", "RED"]);
+        // The asset loaded directly isn't added to its asset storage.
+        assert_eq!(
+            asset_server.get_load_state(AssetPath::from("color.txt").get_id()),
+            LoadState::NotLoaded
+        );
+
+        let err =
+            futures_lite::future::block_on(asset_server.load_async("missing.mat".into(), true))
+                .unwrap_err();
+        let AssetServerError::AssetLoaderError(err) = err else {
+            panic!("unexpected error {err}");
+        };
+        assert!(matches!(
+            err.downcast_ref::<AssetServerError>(),
+            Some(AssetServerError::MissingLabeledAsset(_))
+        ));
+
+        let err = futures_lite::future::block_on(asset_server.load_async("wrong.mat".into(), true))
+            .unwrap_err();
+        let AssetServerError::AssetLoaderError(err) = err else {
+            panic!("unexpected error {err}");
+        };
+        assert!(matches!(
+            err.downcast_ref::<AssetServerError>(),
+            Some(AssetServerError::IncorrectHandleType)
+        ));
+    }
+
+    #[test]
+    fn test_load_direct_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.mat"), "b.mat").unwrap();
+        std::fs::write(dir.path().join("b.mat"), "a.mat").unwrap();
+        std::fs::write(dir.path().join("self.mat"), "self.mat").unwrap();
+        let (asset_server, loaded) = setup_direct(dir.path());
+
+        let err = futures_lite::future::block_on(asset_server.load_async("a.mat".into(), true))
+            .unwrap_err();
+        assert!(is_dependency_cycle(&err), "{err}");
+        let err = futures_lite::future::block_on(asset_server.load_async("self.mat".into(), true))
+            .unwrap_err();
+        assert!(is_dependency_cycle(&err), "{err}");
+        assert!(loaded.lock().is_empty());
+
+        assert_eq!(
+            AssetServerError::DependencyCycle(vec!["a.mat".into(), "b.mat".into(), "a.mat".into()])
+                .to_string(),
+            "dependency cycle while loading assets directly: a.mat -> b.mat -> a.mat"
+        );
+    }
+
+    #[test]
+    fn test_load_direct_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("color.txt"), "red").unwrap();
+        std::fs::write(dir.path().join("paint.mat"), "color.txt").unwrap();
+        std::fs::write(dir.path().join("wall.mat"), "paint.mat").unwrap();
+        let (asset_server, loaded) = setup_direct(dir.path());
+
+        futures_lite::future::block_on(asset_server.load_async("wall.mat".into(), true)).unwrap();
+        assert_eq!(*loaded.lock(), ["red", "material red"]);
+
+        // Changing an asset reloads the asset which loaded it directly, even through another one.
+        std::fs::write(dir.path().join("color.txt"), "blue").unwrap();
+        asset_server.reload_asset("color.txt");
+        let start = std::time::Instant::now();
+        while loaded.lock().len() < 4 {
+            assert!(
+                start.elapsed() < std::time::Duration::from_secs(5),
+                "wall.mat wasn't reloaded"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(loaded.lock()[2..], ["blue", "material blue"]);
+
+        // Sources only loaded directly aren't loaded on their own.
+        for path in ["color.txt", "paint.mat"] {
+            assert_eq!(
+                asset_server.get_load_state(AssetPath::from(path).get_id()),
+                LoadState::NotLoaded
+            );
+        }
+    }
+
+    /// Loads a text with the paths on each of its lines as dependencies.
+    struct ListLoader;
+    impl AssetLoader for ListLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            let text = String::from_utf8_lossy(bytes).to_string();
+            let dependencies = text
+                .lines()
+                .map(|line| AssetPath::from(line).to_owned())
+                .collect();
+            ctx.set_default_asset(
+                LoadedAsset::new(Text(text.clone())).with_dependencies(dependencies),
+            );
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["list"]
+        }
+    }
+
+    fn setup_dependencies(dir: &Path) -> App {
+        let asset_server = setup(dir);
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(ListLoader);
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<Text>())
+            .insert_resource(asset_server)
+            .add_event::<AssetEvent<Text>>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_dependency_load_states_system)
+            .add_system_to_stage(CoreStage::Update, update_asset_storage_system::<Text>)
+            .add_system_to_stage(CoreStage::PostUpdate, Assets::<Text>::asset_event_system);
+        app
+    }
+
+    /// Updates the app until `done` returns true for the events it sent.
+    fn update_until(
+        app: &mut App,
+        done: impl Fn(&[AssetEvent<Text>]) -> bool,
+    ) -> Vec<AssetEvent<Text>> {
+        let start = std::time::Instant::now();
+        let mut events = Vec::new();
+        while !done(&events) {
+            assert!(
+                start.elapsed() < std::time::Duration::from_secs(5),
+                "missing asset events, got {events:?}"
+            );
+            app.update();
+            events.extend(app.world.resource_mut::<Events<AssetEvent<Text>>>().drain());
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        events
+    }
+
+    #[test]
+    fn test_loaded_with_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("scene.list"), "mesh.txt\nmaterial.list").unwrap();
+        // dependency cycles don't prevent the assets from being loaded with their dependencies
+        std::fs::write(dir.path().join("material.list"), "texture.txt\nscene.list").unwrap();
+        std::fs::write(dir.path().join("mesh.txt"), "mesh").unwrap();
+        std::fs::write(dir.path().join("texture.txt"), "texture").unwrap();
+        let mut app = setup_dependencies(dir.path());
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let scene: Handle<Text> = asset_server.load("scene.list");
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&scene),
+            LoadState::Loading
+        );
+        let events = update_until(&mut app, |events| {
+            events.iter().any(|event| {
+                matches!(event, AssetEvent::LoadedWithDependencies { handle } if *handle == scene)
+            })
+        });
+        assert!(events
+            .iter()
+            .all(|event| !matches!(event, AssetEvent::Failed { .. })));
+
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&scene),
+            LoadState::Loaded
+        );
+        for path in ["mesh.txt", "material.list", "texture.txt"] {
+            assert_eq!(
+                asset_server.get_load_state(AssetPath::from(path).get_id()),
+                LoadState::Loaded
+            );
+        }
+        assert_eq!(
+            asset_server
+                .get_recursive_dependency_load_state(AssetPath::from("other.list").get_id()),
+            LoadState::NotLoaded
+        );
+    }
+
+    #[test]
+    fn test_failed_dependency() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("scene.list"), "mesh.txt\nmaterial.list").unwrap();
+        std::fs::write(dir.path().join("material.list"), "missing.txt").unwrap();
+        std::fs::write(dir.path().join("mesh.txt"), "mesh").unwrap();
+        let mut app = setup_dependencies(dir.path());
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let scene: Handle<Text> = asset_server.load("scene.list");
+        let missing: Handle<Text> = asset_server.load("missing.list");
+        let failed = |events: &[AssetEvent<Text>], expected: &Handle<Text>| {
+            events.iter().find_map(|event| match event {
+                AssetEvent::Failed { handle, error } if handle == expected => Some(error.clone()),
+                _ => None,
+            })
+        };
+        let events = update_until(&mut app, |events| {
+            failed(events, &scene).is_some() && failed(events, &missing).is_some()
+        });
+
+        // the error of the dependency is sent for the assets depending on it
+        for handle in [&scene, &missing] {
+            assert!(matches!(
+                *failed(&events, handle).unwrap(),
+                AssetServerError::AssetIoError(AssetIoError::NotFound(_))
+            ));
+            assert_eq!(
+                asset_server.get_recursive_dependency_load_state(handle),
+                LoadState::Failed
+            );
+        }
+        assert_eq!(asset_server.get_load_state(&scene), LoadState::Loaded);
+        assert!(!events.iter().any(|event| matches!(
+            event,
+            AssetEvent::LoadedWithDependencies { handle } if *handle == scene
+        )));
+    }
+
+    #[test]
+    fn test_free_failed_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = setup_dependencies(dir.path());
+
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let missing: Handle<Text> = asset_server.load("missing.txt");
+        let source_path_id = AssetPath::from("missing.txt").get_id().source_path_id();
+        update_until(&mut app, |events| {
+            events.iter().any(
+                |event| matches!(event, AssetEvent::Failed { handle, .. } if *handle == missing),
+            )
+        });
+        assert!(asset_server
+            .server
+            .requested_types
+            .read()
+            .contains_key(&source_path_id));
+        assert!(asset_server
+            .server
+            .load_errors
+            .read()
+            .contains_key(&source_path_id));
+
+        // the error is kept while another asset of the source is used
+        let labeled: Handle<Text> = asset_server.get_handle("missing.txt#labeled");
+        drop(missing);
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        assert!(asset_server
+            .server
+            .load_errors
+            .read()
+            .contains_key(&source_path_id));
+
+        drop(labeled);
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        assert!(!asset_server
+            .server
+            .requested_types
+            .read()
+            .contains_key(&source_path_id));
+        assert!(!asset_server
+            .server
+            .load_errors
+            .read()
+            .contains_key(&source_path_id));
+    }
+
+    #[test]
+    fn test_drop_stale_load_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = setup_dependencies(dir.path());
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let source_path_id = AssetPath::from("missing.txt").get_id().source_path_id();
+
+        let missing: Handle<Text> =
+            asset_server.load_with_settings("missing.txt", |_: &mut u32| {});
+        update_until(&mut app, |events| {
+            events.iter().any(
+                |event| matches!(event, AssetEvent::Failed { handle, .. } if *handle == missing),
+            )
+        });
+        assert!(asset_server
+            .server
+            .load_settings
+            .read()
+            .contains_key(&source_path_id));
+
+        // the settings are dropped once the asset is freed
+        drop(missing);
+        asset_server.mark_unused_assets();
+        asset_server.free_unused_assets();
+        assert!(!asset_server
+            .server
+            .load_settings
+            .read()
+            .contains_key(&source_path_id));
+
+        // a plain load of an asset that failed to load drops its settings
+        let missing: Handle<Text> =
+            asset_server.load_with_settings("missing.txt", |_: &mut u32| {});
+        update_until(&mut app, |events| {
+            events.iter().any(
+                |event| matches!(event, AssetEvent::Failed { handle, .. } if *handle == missing),
+            )
+        });
+        let _: Handle<Text> = asset_server.load("missing.txt");
+        assert!(!asset_server
+            .server
+            .load_settings
+            .read()
+            .contains_key(&source_path_id));
+    }
+
+    #[test]
+    fn test_named_asset_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("color.txt"), "red").unwrap();
+        let mut app = setup_dependencies(dir.path());
+        let memory = MemoryAssetIo::default();
+        memory.insert("color.txt", &b"blue"[..]);
+        memory.insert("colors/green.txt", &b"green"[..]);
+        memory.insert("scene.list", &b"memory://color.txt\ncolor.txt"[..]);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        asset_server.add_asset_source("memory", memory.clone());
+
+        let red: Handle<Text> = asset_server.load("color.txt");
+        let blue: Handle<Text> = asset_server.load("memory://color.txt");
+        let upper_blue: Handle<Text> = asset_server.load("memory://color.txt#upper");
+        let scene: Handle<Text> = asset_server.load("memory://scene.list");
+        let missing: Handle<Text> = asset_server.load("mods://color.txt");
+        let folder = asset_server.load_folder("memory://colors").unwrap();
+        assert_eq!(folder.len(), 1);
+        let green = folder[0].clone().typed::<Text>();
+
+        let events = update_until(&mut app, |events| {
+            [&red, &blue, &upper_blue, &scene, &green, &missing]
+                .iter()
+                .all(|expected| {
+                    events.iter().any(|event| match event {
+                        AssetEvent::LoadedWithDependencies { handle }
+                        | AssetEvent::Failed { handle, .. } => handle == *expected,
+                        _ => false,
+                    })
+                })
+        });
+        let texts = app.world.resource::<Assets<Text>>();
+        assert_eq!(texts.get(&red).unwrap().0, "red");
+        assert_eq!(texts.get(&blue).unwrap().0, "blue");
+        assert_eq!(texts.get(&upper_blue).unwrap().0, "BLUE");
+        assert_eq!(texts.get(&green).unwrap().0, "green");
+        assert_eq!(
+            asset_server.get_handle_path(&green).unwrap(),
+            AssetPath::from("memory://colors/green.txt")
+        );
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(&scene),
+            LoadState::Loaded
+        );
+        assert!(events.iter().any(|event| matches!(
+            event,
+            AssetEvent::Failed { handle, error }
+                if *handle == missing
+                    && matches!(**error, AssetServerError::MissingAssetSource(ref name) if name == "mods")
+        )));
+
+        assert!(matches!(
+            asset_server.load_folder("memory://missing"),
+            Err(AssetServerError::AssetFolderNotADirectory(path)) if path == "memory://missing"
+        ));
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
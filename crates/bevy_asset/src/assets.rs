@@ -1,6 +1,7 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-    RefChange, ReflectAsset, ReflectHandle,
+    update_asset_storage_system, Asset, AssetIo, AssetLoader, AssetProcessor, AssetServer,
+    AssetServerError, AssetStage, Handle, HandleId, MemoryAssetIo, RefChange, ReflectAsset,
+    ReflectHandle, EMBEDDED_ASSET_SOURCE,
 };
 use bevy_app::{App, AppTypeRegistry};
 use bevy_ecs::{
@@ -11,7 +12,7 @@ use bevy_ecs::{
 use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect};
 use bevy_utils::HashMap;
 use crossbeam_channel::Sender;
-use std::fmt::Debug;
+use std::{fmt::Debug, path::PathBuf, sync::Arc};
 
 /// Events that involve assets of type `T`.
 ///
@@ -24,6 +25,20 @@ pub enum AssetEvent<T: Asset> {
     Modified { handle: Handle<T> },
     #[allow(missing_docs)]
     Removed { handle: Handle<T> },
+    /// Sent once an asset loaded by the [`AssetServer`] and all of its recursive dependencies are
+    /// loaded, see [`AssetServer::get_recursive_dependency_load_state`].
+    LoadedWithDependencies {
+        #[allow(missing_docs)]
+        handle: Handle<T>,
+    },
+    /// Sent when an asset requested from the [`AssetServer`] or one of its recursive dependencies
+    /// failed to load.
+    Failed {
+        #[allow(missing_docs)]
+        handle: Handle<T>,
+        /// The error of the asset source that failed to load.
+        error: Arc<AssetServerError>,
+    },
 }
 
 impl<T: Asset> Debug for AssetEvent<T> {
@@ -50,6 +65,21 @@ impl<T: Asset> Debug for AssetEvent<T> {
                 ))
                 .field("handle", &handle.id())
                 .finish(),
+            AssetEvent::LoadedWithDependencies { handle } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::LoadedWithDependencies",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id())
+                .finish(),
+            AssetEvent::Failed { handle, error } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::Failed",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id())
+                .field("error", error)
+                .finish(),
         }
     }
 }
@@ -225,6 +255,11 @@ impl<T: Asset> Assets<T> {
         asset
     }
 
+    /// Queues an event sent by the [`AssetServer`] about the loading of an asset.
+    pub(crate) fn send_load_event(&mut self, event: AssetEvent<T>) {
+        self.events.send(event);
+    }
+
     /// Clears the inner asset map, removing all key-value pairs.
     ///
     /// Keeps the allocated memory for reuse.
@@ -319,6 +354,31 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Adds an asset processor `T` using default values.
+    ///
+    /// The default values may come from the `World` or from `T::default()`.
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld;
+
+    /// Adds the provided asset processor to the application.
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
+
+    /// Adds an asset source named `name` to the application. See
+    /// [`AssetServer::add_asset_source`].
+    fn add_asset_source<T>(&mut self, name: impl Into<String>, asset_io: T) -> &mut Self
+    where
+        T: AssetIo;
+
+    /// Adds a file embedded in the binary to the [`EMBEDDED_ASSET_SOURCE`], at the given path.
+    ///
+    /// See [`embedded_asset!`](crate::embedded_asset).
+    fn add_embedded_asset<P>(&mut self, path: P, bytes: &'static [u8]) -> &mut Self
+    where
+        P: Into<PathBuf>;
 }
 
 impl AddAsset for App {
@@ -403,6 +463,69 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn init_asset_processor<T>(&mut self) -> &mut Self
+    where
+        T: AssetProcessor + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_processor(result)
+    }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .resource_mut::<AssetServer>()
+            .add_processor(processor);
+        self
+    }
+
+    fn add_asset_source<T>(&mut self, name: impl Into<String>, asset_io: T) -> &mut Self
+    where
+        T: AssetIo,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .add_asset_source(name, asset_io);
+        self
+    }
+
+    fn add_embedded_asset<P>(&mut self, path: P, bytes: &'static [u8]) -> &mut Self
+    where
+        P: Into<PathBuf>,
+    {
+        let asset_io = self
+            .world
+            .resource::<AssetServer>()
+            .get_asset_source(EMBEDDED_ASSET_SOURCE)
+            .expect("the embedded asset source should be added by the `AssetPlugin`");
+        asset_io
+            .downcast_ref::<MemoryAssetIo>()
+            .expect("the embedded asset source should be a `MemoryAssetIo`")
+            .insert(path, bytes);
+        self
+    }
+}
+
+/// Embeds a file in the binary, to be loaded from the
+/// [`EMBEDDED_ASSET_SOURCE`](crate::EMBEDDED_ASSET_SOURCE).
+///
+/// The file is included with [`include_bytes!`], relative to the current file. Its asset path is
+/// made of the name of the current crate and of the given path, such as
+/// `embedded://my_crate/shaders/outline.wgsl` for `embedded_asset!(app, "shaders/outline.wgsl")`
+/// in the crate `my_crate`.
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path_str: expr) => {{
+        use $crate::AddAsset as _;
+        let crate_name = module_path!().split("::").next().unwrap();
+        $app.add_embedded_asset(
+            ::std::path::Path::new(crate_name).join($path_str),
+            include_bytes!($path_str),
+        );
+    }};
 }
 
 /// Loads an internal asset.
@@ -485,7 +608,7 @@ macro_rules! load_internal_binary_asset {
 mod tests {
     use bevy_app::App;
 
-    use crate::{AddAsset, Assets};
+    use crate::{AddAsset, AssetServer, Assets, EMBEDDED_ASSET_SOURCE, MEMORY_ASSET_SOURCE};
 
     #[test]
     fn asset_overwriting() {
@@ -502,4 +625,25 @@ mod tests {
         let assets_after = app.world.resource_mut::<Assets<MyAsset>>();
         assert!(assets_after.get(&handle).is_some());
     }
+
+    #[test]
+    fn embedded_asset() {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::CorePlugin::default())
+            .add_plugin(crate::AssetPlugin::default());
+        crate::embedded_asset!(app, "assets.rs");
+
+        let asset_server = app.world.resource::<AssetServer>();
+        assert!(asset_server.get_asset_source(MEMORY_ASSET_SOURCE).is_some());
+        let embedded = asset_server
+            .get_asset_source(EMBEDDED_ASSET_SOURCE)
+            .unwrap();
+        assert_eq!(
+            futures_lite::future::block_on(
+                embedded.load_path(std::path::Path::new("bevy_asset/assets.rs"))
+            )
+            .unwrap(),
+            include_bytes!("assets.rs")
+        );
+    }
 }
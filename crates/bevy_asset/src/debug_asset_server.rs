@@ -77,6 +77,7 @@ impl Plugin for DebugAssetServerPlugin {
         debug_asset_app.add_plugin(AssetPlugin {
             asset_folder: "crates".to_string(),
             watch_for_changes: true,
+            ..Default::default()
         });
         app.insert_non_send_resource(DebugAssetApp(debug_asset_app));
         app.add_system(run_debug_asset_app);
@@ -101,7 +102,9 @@ pub(crate) fn sync_debug_assets<T: Asset + Clone>(
     for changed in changed_shaders.iter_current_update_events() {
         let debug_handle = match changed {
             AssetEvent::Created { handle } | AssetEvent::Modified { handle } => handle,
-            AssetEvent::Removed { .. } => continue,
+            AssetEvent::Removed { .. }
+            | AssetEvent::LoadedWithDependencies { .. }
+            | AssetEvent::Failed { .. } => continue,
         };
         if let Some(handle) = handle_map.handles.get(debug_handle) {
             if let Some(debug_asset) = debug_assets.get(debug_handle) {
@@ -30,4 +30,12 @@ impl FilesystemWatcher {
     pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         self.watcher.watch(path.as_ref(), RecursiveMode::Recursive)
     }
+
+    /// Watch for the creation of a file that doesn't exist yet, by watching its parent directory.
+    pub fn watch_missing<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        match path.as_ref().parent() {
+            Some(parent) => self.watcher.watch(parent, RecursiveMode::NonRecursive),
+            None => Ok(()),
+        }
+    }
 }
@@ -1,5 +1,5 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
+use crate::{filesystem_watcher::FilesystemWatcher, AssetPath, AssetServer};
 use crate::{AssetIo, AssetIoError, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
@@ -124,9 +124,13 @@ impl AssetIo for FileAssetIo {
             let path = self.root_path.join(_path);
             let mut watcher = self.filesystem_watcher.write();
             if let Some(ref mut watcher) = *watcher {
-                watcher
-                    .watch(&path)
-                    .map_err(|_error| AssetIoError::PathWatchError(path))?;
+                // files that don't exist yet, such as meta files, are watched for their creation
+                let result = if path.exists() {
+                    watcher.watch(&path)
+                } else {
+                    watcher.watch_missing(&path)
+                };
+                result.map_err(|_error| AssetIoError::PathWatchError(path))?;
             }
         }
 
@@ -160,18 +164,33 @@ impl AssetIo for FileAssetIo {
 }
 
 /// Watches for file changes in the local file system.
+///
+/// The changes are watched in the default asset source and in the named asset sources which are
+/// a [`FileAssetIo`].
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
+    let named_sources: Vec<_> = asset_server
+        .server
+        .named_sources
+        .read()
+        .iter()
+        .map(|(name, asset_io)| (Some(name.clone()), asset_io.clone()))
+        .collect();
+    let sources =
+        std::iter::once((None, asset_server.server.asset_io.clone())).chain(named_sources);
+    for (source, asset_io) in sources {
+        if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+            reload_changed_assets(&asset_server, asset_io, source.as_deref());
+        }
+    }
+}
+
+#[cfg(feature = "filesystem_watcher")]
+fn reload_changed_assets(asset_server: &AssetServer, asset_io: &FileAssetIo, source: Option<&str>) {
     let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
     let watcher = asset_io.filesystem_watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
@@ -180,19 +199,34 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
             };
-            if let notify::event::Event {
-                kind: notify::event::EventKind::Modify(_),
-                paths,
-                ..
-            } = event
-            {
-                for path in &paths {
-                    if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path.into(), true);
-                    }
+            let is_modify = matches!(event.kind, notify::event::EventKind::Modify(_));
+            // creating or removing a meta file changes the settings of its asset
+            let is_create_or_remove = matches!(
+                event.kind,
+                notify::event::EventKind::Create(_) | notify::event::EventKind::Remove(_)
+            );
+            for path in &event.paths {
+                if changed.contains(path) {
+                    continue;
+                }
+                let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
+                // a changed meta file reloads the asset it belongs to
+                let is_meta = relative_path.extension() == Some("meta".as_ref());
+                if !is_modify && !(is_meta && is_create_or_remove) {
+                    continue;
+                }
+                let relative_path = if is_meta {
+                    relative_path.with_extension("")
+                } else {
+                    relative_path.to_owned()
+                };
+                let asset_path = AssetPath::new_source_ref(source, &relative_path, None);
+                // the directories of missing meta files are watched, which also reports changes
+                // to the other files in them
+                if asset_server.is_source_loaded(&asset_path) {
+                    asset_server.reload_asset(asset_path);
+                    changed.insert(path.clone());
                 }
-                changed.extend(paths);
             }
         }
     }
@@ -0,0 +1,132 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// I/O implementation for assets stored in memory.
+///
+/// The asset files are added with [`MemoryAssetIo::insert`], from bytes built at runtime or
+/// compiled into the binary. Clones of a memory asset I/O share the same files, so that files can
+/// still be added once it is registered as an asset source. Directories are implied by the paths of
+/// the files.
+///
+/// The [`AssetPlugin`](crate::AssetPlugin) registers two memory asset sources: the
+/// [`MEMORY_ASSET_SOURCE`](crate::MEMORY_ASSET_SOURCE), which is useful for tests and generated
+/// assets, and the [`EMBEDDED_ASSET_SOURCE`](crate::EMBEDDED_ASSET_SOURCE) of the assets added with
+/// [`embedded_asset!`](crate::embedded_asset).
+#[derive(Default, Clone)]
+pub struct MemoryAssetIo {
+    files: Arc<RwLock<HashMap<PathBuf, Cow<'static, [u8]>>>>,
+}
+
+impl MemoryAssetIo {
+    /// Adds the file at `path` with the given content, replacing the previous content of the file.
+    pub fn insert<P: Into<PathBuf>, B: Into<Cow<'static, [u8]>>>(&self, path: P, bytes: B) {
+        self.files.write().insert(path.into(), bytes.into());
+    }
+
+    /// Removes the file at `path`, returning `true` if it existed.
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.files.write().remove(path.as_ref()).is_some()
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.files
+                .read()
+                .get(path)
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        if !self.is_dir(path) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        let entries: HashSet<PathBuf> = self
+            .files
+            .read()
+            .keys()
+            .filter_map(|file| {
+                let child = file.strip_prefix(path).ok()?.components().next()?;
+                Some(path.join(child))
+            })
+            .collect();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let files = self.files.read();
+        if files.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if files
+            .keys()
+            .any(|file| file != path && file.starts_with(path))
+        {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_and_directories() {
+        let asset_io = MemoryAssetIo::default();
+        asset_io.insert("textures/grass.png", &b"grass"[..]);
+        asset_io.insert("textures/water/deep.png", vec![1, 2]);
+        asset_io.clone().insert("scene.gltf", &b"scene"[..]);
+
+        assert_eq!(
+            futures_lite::future::block_on(asset_io.load_path(Path::new("scene.gltf"))).unwrap(),
+            b"scene"
+        );
+        assert!(matches!(
+            futures_lite::future::block_on(asset_io.load_path(Path::new("textures"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+        assert!(asset_io.is_dir(Path::new("")));
+        assert!(asset_io.is_dir(Path::new("textures/water")));
+        assert!(asset_io.is_file(Path::new("textures/water/deep.png")));
+        assert!(!asset_io.is_file(Path::new("textures/sand.png")));
+
+        let mut entries: Vec<_> = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            [
+                PathBuf::from("textures/grass.png"),
+                PathBuf::from("textures/water")
+            ]
+        );
+
+        assert!(asset_io.remove("scene.gltf"));
+        assert!(!asset_io.is_file(Path::new("scene.gltf")));
+    }
+}
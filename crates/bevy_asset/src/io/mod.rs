@@ -5,6 +5,7 @@ mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod memory_asset_io;
 mod metadata;
 
 #[cfg(target_os = "android")]
@@ -14,6 +15,7 @@ pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use memory_asset_io::*;
 pub use metadata::*;
 
 use anyhow::Result;
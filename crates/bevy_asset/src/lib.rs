@@ -24,7 +24,9 @@ mod handle;
 mod info;
 mod io;
 mod loader;
+mod meta;
 mod path;
+mod processor;
 mod reflect;
 
 /// The `bevy_asset` prelude.
@@ -43,7 +45,9 @@ pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
+pub use meta::*;
 pub use path::*;
+pub use processor::*;
 pub use reflect::*;
 
 use bevy_app::{prelude::Plugin, App};
@@ -58,10 +62,20 @@ pub enum AssetStage {
     AssetEvents,
 }
 
+/// The name of the asset source of the assets embedded in the binary with [`embedded_asset!`].
+pub const EMBEDDED_ASSET_SOURCE: &str = "embedded";
+
+/// The name of the [`MemoryAssetIo`] asset source added by the [`AssetPlugin`].
+pub const MEMORY_ASSET_SOURCE: &str = "memory";
+
 /// Adds support for Assets to an App.
 ///
 /// Assets are typed collections with change tracking, which are added as App Resources. Examples of
 /// assets: textures, sounds, 3d models, maps, scenes
+///
+/// Besides the default asset source, the [`AssetServer`] gets the [`EMBEDDED_ASSET_SOURCE`] and
+/// [`MEMORY_ASSET_SOURCE`] asset sources. Other sources can be added with
+/// [`AddAsset::add_asset_source`].
 #[derive(Debug, Clone)]
 pub struct AssetPlugin {
     /// The base folder where assets are loaded from, relative to the executable.
@@ -69,6 +83,11 @@ pub struct AssetPlugin {
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
     pub watch_for_changes: bool,
+    /// The folder where processed assets are cached, relative to the executable. See
+    /// [`AssetProcessor`].
+    ///
+    /// Processed assets are not cached on the wasm32 arch nor android os.
+    pub imported_assets_folder: String,
 }
 
 impl Default for AssetPlugin {
@@ -76,6 +95,7 @@ impl Default for AssetPlugin {
         Self {
             asset_folder: "assets".to_string(),
             watch_for_changes: false,
+            imported_assets_folder: "imported_assets".to_string(),
         }
     }
 }
@@ -102,8 +122,18 @@ impl Plugin for AssetPlugin {
         if !app.world.contains_resource::<AssetServer>() {
             let source = self.create_platform_default_asset_io();
             let asset_server = AssetServer::with_boxed_io(source);
+            #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+            asset_server.set_imported_assets_folder(
+                FileAssetIo::get_base_path().join(&self.imported_assets_folder),
+            );
             app.insert_resource(asset_server);
         }
+        let asset_server = app.world.resource::<AssetServer>();
+        for name in [EMBEDDED_ASSET_SOURCE, MEMORY_ASSET_SOURCE] {
+            if asset_server.get_asset_source(name).is_none() {
+                asset_server.add_asset_source(name, MemoryAssetIo::default());
+            }
+        }
 
         app.add_stage_before(
             bevy_app::CoreStage::PreUpdate,
@@ -119,6 +149,10 @@ impl Plugin for AssetPlugin {
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system,
+        )
+        .add_system_to_stage(
+            bevy_app::CoreStage::PreUpdate,
+            asset_server::update_dependency_load_states_system,
         );
 
         #[cfg(all(
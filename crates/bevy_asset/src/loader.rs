@@ -1,15 +1,17 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    meta::{read_settings, LoadSettings},
+    path::{AssetPath, SourcePathId},
+    AssetIo, AssetIoError, AssetMeta, AssetMetaError, AssetServer, AssetServerError, Assets,
+    Handle, HandleId, LoaderSettings, RefChangeChannel,
 };
 use anyhow::Error;
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
 use bevy_reflect::{TypeUuid, TypeUuidDynamic};
-use bevy_utils::{BoxedFuture, HashMap};
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 /// A loader for an asset source.
 ///
@@ -123,23 +125,38 @@ pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
+    pub(crate) source: Option<&'a str>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) meta: Option<&'a [u8]>,
+    pub(crate) settings: Option<&'a LoadSettings>,
+    pub(crate) asset_server: &'a AssetServer,
+    /// The sources being loaded, from the asset loaded by the asset server to this one.
+    pub(crate) load_stack: Vec<AssetPath<'static>>,
+    pub(crate) direct_dependencies: HashSet<SourcePathId>,
 }
 
 impl<'a> LoadContext<'a> {
     pub(crate) fn new(
-        path: &'a Path,
-        ref_change_channel: &'a RefChangeChannel,
+        asset_path: &'a AssetPath<'_>,
+        asset_server: &'a AssetServer,
         asset_io: &'a dyn AssetIo,
         version: usize,
     ) -> Self {
+        let source = asset_path.source();
+        let path = asset_path.path();
         Self {
-            ref_change_channel,
+            ref_change_channel: &asset_server.server.asset_ref_counter.channel,
             asset_io,
             labeled_assets: Default::default(),
             version,
+            source,
             path,
+            meta: None,
+            settings: None,
+            asset_server,
+            load_stack: vec![AssetPath::new_source_ref(source, path, None).to_owned()],
+            direct_dependencies: Default::default(),
         }
     }
 
@@ -148,6 +165,16 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Gets the name of the asset source of this load context, or `None` for the default source.
+    pub fn source(&self) -> Option<&str> {
+        self.source
+    }
+
+    /// Gets the asset path of the asset with the given label in the source of this load context.
+    pub fn get_asset_path<'b>(&'b self, label: Option<&'b str>) -> AssetPath<'b> {
+        AssetPath::new_source_ref(self.source, self.path, label)
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -163,7 +190,7 @@ impl<'a> LoadContext<'a> {
         assert!(!label.is_empty());
         self.labeled_assets
             .insert(Some(label.to_string()), asset.into());
-        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+        self.get_handle(self.get_asset_path(Some(label)))
     }
 
     /// Gets a handle to an asset of type `T` from its id.
@@ -172,11 +199,49 @@ impl<'a> LoadContext<'a> {
     }
 
     /// Reads the contents of the file at the specified path through the [`AssetIo`] associated
-    /// with this context.
+    /// with this context, which is the asset I/O of its asset source.
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
         self.asset_io.load_path(path.as_ref()).await
     }
 
+    /// Loads the asset at the provided path with its loader and returns it, without adding it to
+    /// its asset storage.
+    ///
+    /// This lets a loader use the value of another asset, such as a material definition reading
+    /// the image it references. The asset is loaded on every call, and its other labeled assets
+    /// are dropped. When it changes, the asset of this load context is reloaded.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`AssetServerError::DependencyCycle`] if the asset is already being loaded by
+    /// this load context or the ones loading it, and with
+    /// [`AssetServerError::IncorrectHandleType`] if the asset isn't of type `T`.
+    pub async fn load_direct<'b, T: Asset, P: Into<AssetPath<'b>>>(
+        &mut self,
+        path: P,
+    ) -> Result<T, AssetServerError> {
+        let asset_path = path.into();
+        self.direct_dependencies
+            .insert(asset_path.get_id().source_path_id());
+        let asset = self
+            .asset_server
+            .load_direct(&asset_path, &self.load_stack)
+            .await?;
+        asset
+            .downcast::<T>()
+            .map(|asset| *asset)
+            .map_err(|_| AssetServerError::IncorrectHandleType)
+    }
+
+    /// Reads the settings of the loader from the `.meta` file of the asset source, with the settings
+    /// given to [`AssetServer::load_with_settings`] applied on top of them.
+    ///
+    /// Returns the default settings if the asset has no meta file and was loaded without settings.
+    /// See [`AssetMetaFile`](crate::AssetMetaFile).
+    pub fn settings<S: LoaderSettings>(&self) -> Result<S, AssetMetaError> {
+        read_settings(self.path, self.meta, self.settings)
+    }
+
     /// Generates metadata for the assets managed by this load context.
     pub fn get_asset_metas(&self) -> Vec<AssetMeta> {
         let mut asset_metas = Vec::new();
@@ -222,6 +287,10 @@ pub enum AssetLifecycleEvent<T> {
     Create(AssetResult<T>),
     /// An asset was freed.
     Free(HandleId),
+    /// An asset and all of its recursive dependencies were loaded.
+    LoadedWithDependencies(HandleId),
+    /// An asset or one of its recursive dependencies failed to load.
+    Failed(HandleId, Arc<AssetServerError>),
 }
 
 /// A trait for sending lifecycle notifications from assets in the asset server.
@@ -230,6 +299,10 @@ pub trait AssetLifecycle: Downcast + Send + Sync + 'static {
     fn create_asset(&self, id: HandleId, asset: Box<dyn AssetDynamic>, version: usize);
     /// Notifies the asset server that an asset was freed.
     fn free_asset(&self, id: HandleId);
+    /// Notifies the asset server that an asset and all of its recursive dependencies were loaded.
+    fn loaded_with_dependencies(&self, id: HandleId);
+    /// Notifies the asset server that an asset or one of its recursive dependencies failed to load.
+    fn load_failed(&self, id: HandleId, error: Arc<AssetServerError>);
 }
 impl_downcast!(AssetLifecycle);
 
@@ -254,6 +327,18 @@ impl<T: AssetDynamic> AssetLifecycle for AssetLifecycleChannel<T> {
     fn free_asset(&self, id: HandleId) {
         self.sender.send(AssetLifecycleEvent::Free(id)).unwrap();
     }
+
+    fn loaded_with_dependencies(&self, id: HandleId) {
+        self.sender
+            .send(AssetLifecycleEvent::LoadedWithDependencies(id))
+            .unwrap();
+    }
+
+    fn load_failed(&self, id: HandleId, error: Arc<AssetServerError>) {
+        self.sender
+            .send(AssetLifecycleEvent::Failed(id, error))
+            .unwrap();
+    }
 }
 
 impl<T> Default for AssetLifecycleChannel<T> {
@@ -0,0 +1,215 @@
+use serde::{de::DeserializeOwned, de::IgnoredAny, Deserialize, Serialize};
+use std::{
+    any::{Any, TypeId},
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The contents of the `.meta` file of an asset source.
+///
+/// The meta file of an asset is read from the path of its source with `.meta` appended, such as
+/// `textures/normal.png.meta` for `textures/normal.png`. It can choose the
+/// [`AssetLoader`](crate::AssetLoader) of the asset, by type name or short type name, instead of
+/// its extension, and set the settings of that loader:
+///
+/// ```ron
+/// (
+///     loader: Some("ImageTextureLoader"),
+///     settings: (
+///         is_srgb: false,
+///     ),
+/// )
+/// ```
+///
+/// Loaders read their settings with [`LoadContext::settings`](crate::LoadContext::settings).
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct AssetMetaFile<S> {
+    /// The name of the loader of the asset. The loader is chosen by extension when it is `None`.
+    #[serde(default)]
+    pub loader: Option<String>,
+    /// The settings of the loader.
+    #[serde(default)]
+    pub settings: S,
+}
+
+impl AssetMetaFile<IgnoredAny> {
+    /// Reads the loader chosen by the meta file, ignoring its settings.
+    pub(crate) fn read_loader(bytes: &[u8], path: &Path) -> Result<Option<String>, AssetMetaError> {
+        read_meta::<IgnoredAny>(bytes, path).map(|meta| meta.loader)
+    }
+}
+
+/// The settings of an [`AssetLoader`](crate::AssetLoader), read from the `.meta` file of an
+/// asset or set with [`AssetServer::load_with_settings`](crate::AssetServer::load_with_settings).
+pub trait LoaderSettings: Default + DeserializeOwned + Send + Sync + 'static {}
+
+impl<T> LoaderSettings for T where T: Default + DeserializeOwned + Send + Sync + 'static {}
+
+/// Errors that occur while reading the settings of an asset.
+#[derive(Error, Debug)]
+pub enum AssetMetaError {
+    /// The meta file of the asset could not be deserialized.
+    #[error("invalid meta file for {path:?}: {error}")]
+    InvalidMeta {
+        /// The path of the asset source.
+        path: PathBuf,
+        /// The deserialization error.
+        error: ron::error::SpannedError,
+    },
+
+    /// The settings given when loading the asset are not the settings of its loader.
+    #[error("the asset {path:?} was loaded with settings of type {given}, but its loader expects {expected}")]
+    MismatchedSettings {
+        /// The path of the asset source.
+        path: PathBuf,
+        /// The type of the settings given to [`AssetServer::load_with_settings`](crate::AssetServer::load_with_settings).
+        given: &'static str,
+        /// The type of the settings read by the loader.
+        expected: &'static str,
+    },
+}
+
+type ApplySettingsFn = dyn Fn(&mut dyn Any) + Send + Sync;
+
+/// Settings given to [`AssetServer::load_with_settings`](crate::AssetServer::load_with_settings),
+/// applied on top of the settings of the meta file.
+#[derive(Clone)]
+pub(crate) struct LoadSettings {
+    type_id: TypeId,
+    type_name: &'static str,
+    apply: Arc<ApplySettingsFn>,
+}
+
+impl LoadSettings {
+    pub(crate) fn new<S: LoaderSettings>(
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            type_id: TypeId::of::<S>(),
+            type_name: std::any::type_name::<S>(),
+            apply: Arc::new(move |value: &mut dyn Any| {
+                if let Some(value) = value.downcast_mut::<S>() {
+                    settings(value);
+                }
+            }),
+        }
+    }
+}
+
+/// Returns the settings of the asset at `path`, from its meta file and the settings it was loaded
+/// with.
+pub(crate) fn read_settings<S: LoaderSettings>(
+    path: &Path,
+    meta: Option<&[u8]>,
+    load_settings: Option<&LoadSettings>,
+) -> Result<S, AssetMetaError> {
+    let mut settings = match meta {
+        Some(meta) => read_meta::<S>(meta, path)?.settings,
+        None => S::default(),
+    };
+    if let Some(load_settings) = load_settings {
+        if load_settings.type_id != TypeId::of::<S>() {
+            return Err(AssetMetaError::MismatchedSettings {
+                path: path.to_owned(),
+                given: load_settings.type_name,
+                expected: std::any::type_name::<S>(),
+            });
+        }
+        (load_settings.apply)(&mut settings);
+    }
+    Ok(settings)
+}
+
+fn read_meta<S: Default + DeserializeOwned>(
+    bytes: &[u8],
+    path: &Path,
+) -> Result<AssetMetaFile<S>, AssetMetaError> {
+    ron::de::from_bytes(bytes).map_err(|error| AssetMetaError::InvalidMeta {
+        path: path.to_owned(),
+        error,
+    })
+}
+
+/// Returns the path of the meta file of the asset source at `path`.
+pub fn meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = OsString::from(path);
+    meta_path.push(".meta");
+    meta_path.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(default)]
+    struct ImageSettings {
+        is_srgb: bool,
+        label: String,
+    }
+
+    impl Default for ImageSettings {
+        fn default() -> Self {
+            Self {
+                is_srgb: true,
+                label: String::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn settings_from_meta() {
+        let path = Path::new("normal.png");
+        let meta = br#"(loader: Some("ImageLoader"), settings: (is_srgb: false))"#;
+        assert_eq!(
+            AssetMetaFile::read_loader(meta, path).unwrap().as_deref(),
+            Some("ImageLoader")
+        );
+        assert_eq!(
+            read_settings::<ImageSettings>(path, Some(meta), None).unwrap(),
+            ImageSettings {
+                is_srgb: false,
+                label: String::new(),
+            }
+        );
+        assert_eq!(
+            read_settings::<ImageSettings>(path, None, None).unwrap(),
+            ImageSettings::default()
+        );
+        assert!(matches!(
+            read_settings::<ImageSettings>(path, Some(b"(settings: 3)"), None),
+            Err(AssetMetaError::InvalidMeta { .. })
+        ));
+    }
+
+    #[test]
+    fn load_settings_override_meta() {
+        let path = Path::new("normal.png");
+        let meta = br#"(settings: (is_srgb: false))"#;
+        let load_settings =
+            LoadSettings::new(|settings: &mut ImageSettings| settings.label = "normal".into());
+        assert_eq!(
+            read_settings::<ImageSettings>(path, Some(meta), Some(&load_settings)).unwrap(),
+            ImageSettings {
+                is_srgb: false,
+                label: "normal".to_string(),
+            }
+        );
+
+        let load_settings = LoadSettings::new(|_: &mut u32| {});
+        assert!(matches!(
+            read_settings::<ImageSettings>(path, None, Some(&load_settings)),
+            Err(AssetMetaError::MismatchedSettings { given: "u32", .. })
+        ));
+    }
+
+    #[test]
+    fn meta_file_path() {
+        assert_eq!(
+            meta_path(Path::new("textures/normal.png")),
+            Path::new("textures/normal.png.meta")
+        );
+    }
+}
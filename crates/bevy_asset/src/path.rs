@@ -3,13 +3,21 @@ use bevy_utils::AHasher;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    fmt::{self, Display},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
 
 /// Represents a path to an asset in the file system.
+///
+/// An asset path can name the asset source it is loaded from, registered with
+/// [`AssetServer::add_asset_source`](crate::AssetServer::add_asset_source), such as
+/// `mods://textures/grass.png`. Paths without a source name are loaded from the default source of
+/// the asset server.
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<Cow<'a, str>>,
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
 }
@@ -19,6 +27,21 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new_ref(path: &'a Path, label: Option<&'a str>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
+            path: Cow::Borrowed(path),
+            label: label.map(Cow::Borrowed),
+        }
+    }
+
+    /// Creates a new asset path in the asset source named `source` using borrowed information.
+    #[inline]
+    pub fn new_source_ref(
+        source: Option<&'a str>,
+        path: &'a Path,
+        label: Option<&'a str>,
+    ) -> AssetPath<'a> {
+        AssetPath {
+            source: source.map(Cow::Borrowed),
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
         }
@@ -28,17 +51,31 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new(path: PathBuf, label: Option<String>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
         }
     }
 
+    /// Returns this asset path in the asset source named `source`.
+    #[inline]
+    pub fn with_source(mut self, source: impl Into<Cow<'a, str>>) -> AssetPath<'a> {
+        self.source = Some(source.into());
+        self
+    }
+
     /// Constructs an identifier from this asset path.
     #[inline]
     pub fn get_id(&self) -> AssetPathId {
         AssetPathId::from(self)
     }
 
+    /// Gets the name of the asset source, or `None` for the default source.
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_ref().map(|source| source.as_ref())
+    }
+
     /// Gets the sub-asset label.
     #[inline]
     pub fn label(&self) -> Option<&str> {
@@ -55,6 +92,10 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
         AssetPath {
+            source: self
+                .source
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
             path: Cow::Owned(self.path.to_path_buf()),
             label: self
                 .label
@@ -64,6 +105,19 @@ impl<'a> AssetPath<'a> {
     }
 }
 
+impl<'a> Display for AssetPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(source) = self.source() {
+            write!(f, "{source}://")?;
+        }
+        write!(f, "{}", self.path.display())?;
+        if let Some(label) = self.label() {
+            write!(f, "#{label}")?;
+        }
+        Ok(())
+    }
+}
+
 /// An unique identifier to an asset path.
 #[derive(
     Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize, Reflect,
@@ -85,14 +139,25 @@ pub struct SourcePathId(u64);
 #[reflect_value(PartialEq, Hash, Serialize, Deserialize)]
 pub struct LabelId(u64);
 
-impl<'a> From<&'a Path> for SourcePathId {
-    fn from(value: &'a Path) -> Self {
+impl SourcePathId {
+    /// Creates the id of the source path `path` in the asset source named `source`.
+    pub fn new(source: Option<&str>, path: &Path) -> Self {
         let mut hasher = get_hasher();
-        value.hash(&mut hasher);
+        // paths of the default source keep the id of their path
+        if let Some(source) = source {
+            source.hash(&mut hasher);
+        }
+        path.hash(&mut hasher);
         SourcePathId(hasher.finish())
     }
 }
 
+impl<'a> From<&'a Path> for SourcePathId {
+    fn from(value: &'a Path) -> Self {
+        SourcePathId::new(None, value)
+    }
+}
+
 impl From<AssetPathId> for SourcePathId {
     fn from(id: AssetPathId) -> Self {
         id.source_path_id()
@@ -114,6 +179,10 @@ impl<'a> From<Option<&'a str>> for LabelId {
 }
 
 impl AssetPathId {
+    pub(crate) fn new(source_path_id: SourcePathId, label_id: LabelId) -> Self {
+        AssetPathId(source_path_id, label_id)
+    }
+
     /// Gets the id of the source path.
     pub fn source_path_id(&self) -> SourcePathId {
         self.0
@@ -137,7 +206,7 @@ where
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.source(), asset_path.path()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -146,7 +215,7 @@ where
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::new(asset_path.source(), asset_path.path()),
             LabelId::from(asset_path.label()),
         )
     }
@@ -154,10 +223,15 @@ impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
 
 impl<'a> From<&'a str> for AssetPath<'a> {
     fn from(asset_path: &'a str) -> Self {
+        let (source, asset_path) = match asset_path.split_once("://") {
+            Some((source, asset_path)) => (Some(source), asset_path),
+            None => (None, asset_path),
+        };
         let mut parts = asset_path.splitn(2, '#');
         let path = Path::new(parts.next().expect("Path must be set."));
         let label = parts.next();
         AssetPath {
+            source: source.map(Cow::Borrowed),
             path: Cow::Borrowed(path),
             label: label.map(Cow::Borrowed),
         }
@@ -173,6 +247,7 @@ impl<'a> From<&'a String> for AssetPath<'a> {
 impl<'a> From<&'a Path> for AssetPath<'a> {
     fn from(path: &'a Path) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: None,
         }
@@ -182,6 +257,7 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
 impl<'a> From<PathBuf> for AssetPath<'a> {
     fn from(path: PathBuf) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: None,
         }
@@ -190,12 +266,46 @@ impl<'a> From<PathBuf> for AssetPath<'a> {
 
 impl<'a> From<String> for AssetPath<'a> {
     fn from(asset_path: String) -> Self {
-        let mut parts = asset_path.splitn(2, '#');
-        let path = PathBuf::from(parts.next().expect("Path must be set."));
-        let label = parts.next().map(String::from);
-        AssetPath {
-            path: Cow::Owned(path),
-            label: label.map(Cow::Owned),
-        }
+        AssetPath::from(asset_path.as_str()).to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_asset_path() {
+        let path = AssetPath::from("mods://textures/grass.png#mip");
+        assert_eq!(path.source(), Some("mods"));
+        assert_eq!(path.path(), Path::new("textures/grass.png"));
+        assert_eq!(path.label(), Some("mip"));
+        assert_eq!(path.to_string(), "mods://textures/grass.png#mip");
+        assert_eq!(AssetPath::from(path.to_string()), path);
+
+        let path = AssetPath::from("textures/grass.png");
+        assert_eq!(path.source(), None);
+        assert_eq!(path.to_string(), "textures/grass.png");
+        assert_eq!(
+            AssetPath::from("memory://textures/grass.png"),
+            path.clone().with_source("memory")
+        );
+    }
+
+    #[test]
+    fn source_path_ids() {
+        let path = Path::new("textures/grass.png");
+        assert_eq!(
+            AssetPath::from(path).get_id().source_path_id(),
+            SourcePathId::from(path)
+        );
+        assert_ne!(
+            AssetPath::from("mods://textures/grass.png").get_id(),
+            AssetPath::from(path).get_id()
+        );
+        assert_ne!(
+            AssetPath::from("mods://textures/grass.png").get_id(),
+            AssetPath::from("memory://textures/grass.png").get_id()
+        );
     }
 }
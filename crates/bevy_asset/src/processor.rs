@@ -0,0 +1,150 @@
+use crate::{AssetIoError, AssetPath};
+use anyhow::Error;
+use bevy_utils::BoxedFuture;
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// A processor for an asset source.
+///
+/// Processors turn asset sources into another format before they are loaded, for example to
+/// compress textures or to convert models to a format that is faster to load. When an asset with
+/// one of the [`extensions`](AssetProcessor::extensions) of a processor is loaded, the processed
+/// bytes are loaded by the [`AssetLoader`](crate::AssetLoader) of its
+/// [`processed_extension`](AssetProcessor::processed_extension).
+///
+/// If the [`AssetServer`](crate::AssetServer) has an imported assets folder, the processed bytes
+/// are cached there, so that sources are only processed again when they or their processor change.
+/// When the asset server watches for changes, modified sources are processed again as they are
+/// reloaded.
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the bytes of the asset source at `path` in an asynchronous closure.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions supported by this asset processor, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns the extension of the processed format, without the preceding dot.
+    fn processed_extension(&self) -> &str;
+
+    /// Returns the version of this asset processor.
+    ///
+    /// It should be increased whenever the processed output changes, so that the assets cached
+    /// with a previous version are processed again.
+    fn version(&self) -> u32 {
+        0
+    }
+
+    /// Returns the name of this asset processor. Defaults to its type name.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Returns the serialized settings of this asset processor that change the processed output,
+    /// so that the cached assets are processed again when they change. Defaults to no settings.
+    fn settings(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+/// Returns the key of the asset processed by `processor` from the source `bytes`.
+///
+/// The key is a BLAKE3 hash, which is stable across platforms and versions, so that it can be
+/// stored along with the cached processed asset.
+pub(crate) fn processed_asset_key(bytes: &[u8], processor: &dyn AssetProcessor) -> blake3::Hash {
+    let mut hasher = blake3::Hasher::new();
+    // each field is prefixed by its length, so that different fields never hash the same bytes
+    for field in [
+        bytes,
+        processor.name().as_bytes(),
+        processor.processed_extension().as_bytes(),
+        &processor.version().to_le_bytes(),
+        &processor.settings(),
+    ] {
+        hasher.update(&(field.len() as u64).to_le_bytes());
+        hasher.update(field);
+    }
+    hasher.finalize()
+}
+
+/// The folder of the imported assets folder where the processed assets of named asset sources are
+/// cached, in a subfolder named after their source. The paths of the default source can't use it.
+pub const NAMED_SOURCES_FOLDER: &str = "@sources";
+
+/// Returns the path where the asset at `asset_path` is cached once processed by `processor`.
+///
+/// The processed extension is appended to the source path, so that sources which only differ by
+/// their extension don't share the same processed asset. The assets of named sources are cached in
+/// the [`NAMED_SOURCES_FOLDER`]. Returns `None` if the path is absolute or contains `..`, if the
+/// name of its source isn't a single folder name, or if a path of the default source starts with
+/// the [`NAMED_SOURCES_FOLDER`], since its processed asset could be outside of `imported_assets` or
+/// shared with another asset.
+pub fn processed_asset_path(
+    imported_assets: &Path,
+    asset_path: &AssetPath,
+    processor: &dyn AssetProcessor,
+) -> Option<PathBuf> {
+    let mut processed_path = imported_assets.to_path_buf();
+    if let Some(source) = asset_path.source() {
+        let mut components = Path::new(source).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None) if name == source => {}
+            _ => return None,
+        }
+        processed_path.push(NAMED_SOURCES_FOLDER);
+        processed_path.push(source);
+    }
+    let mut first = true;
+    for component in asset_path.path().components() {
+        match component {
+            Component::Normal(component) => {
+                if first && asset_path.source().is_none() && component == NAMED_SOURCES_FOLDER {
+                    return None;
+                }
+                first = false;
+                processed_path.push(component);
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    let mut processed_path = processed_path.into_os_string();
+    processed_path.push(".");
+    processed_path.push(processor.processed_extension());
+    Some(processed_path.into())
+}
+
+fn key_path(processed_path: &Path) -> PathBuf {
+    let mut key_path = OsString::from(processed_path);
+    key_path.push(".hash");
+    key_path.into()
+}
+
+/// Reads the cached processed asset at `processed_path`, if it was processed with `key`.
+pub(crate) fn read_processed_asset(processed_path: &Path, key: &blake3::Hash) -> Option<Vec<u8>> {
+    let cached_key = fs::read_to_string(key_path(processed_path)).ok()?;
+    if blake3::Hash::from_hex(cached_key.trim()).ok()? != *key {
+        return None;
+    }
+    fs::read(processed_path).ok()
+}
+
+/// Caches the processed asset at `processed_path`, along with its `key`.
+pub(crate) fn write_processed_asset(
+    processed_path: &Path,
+    key: &blake3::Hash,
+    bytes: &[u8],
+) -> Result<(), AssetIoError> {
+    if let Some(parent) = processed_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(processed_path, bytes)?;
+    fs::write(key_path(processed_path), key.to_hex().as_str())?;
+    Ok(())
+}
@@ -18,7 +18,7 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::entity::Entity;
+use bevy_ecs::entity::{Disabled, Entity};
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 use bevy_utils::{Duration, HashSet, Instant};
 use std::borrow::Cow;
@@ -49,7 +49,9 @@ impl Plugin for CorePlugin {
             tick_global_task_pools_on_main_thread.at_end(),
         );
 
-        app.register_type::<Entity>().register_type::<Name>();
+        app.register_type::<Entity>()
+            .register_type::<Disabled>()
+            .register_type::<Name>();
 
         register_rust_types(app);
         register_math_types(app);
@@ -2,7 +2,7 @@ use bevy_macro_utils::{get_lit_str, Symbol};
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, parse_quote, DeriveInput, Error, Ident, Path, Result};
+use syn::{parse_macro_input, parse_quote, DeriveInput, Error, ExprPath, Ident, Path, Result};
 
 pub fn derive_resource(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
@@ -33,6 +33,36 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
 
+    let on_add = attrs.on_add.map(|path| quote! { hooks.on_add(#path); });
+    let on_insert = attrs
+        .on_insert
+        .map(|path| quote! { hooks.on_insert(#path); });
+    let on_remove = attrs
+        .on_remove
+        .map(|path| quote! { hooks.on_remove(#path); });
+    let register_component_hooks = (on_add.is_some() || on_insert.is_some() || on_remove.is_some())
+        .then(|| {
+            quote! {
+                fn register_component_hooks(hooks: &mut #bevy_ecs_path::component::ComponentHooks) {
+                    #on_add
+                    #on_insert
+                    #on_remove
+                }
+            }
+        });
+
+    let register_required_components = (!attrs.require.is_empty()).then(|| {
+        let require = attrs.require.iter().map(|(ty, constructor)| match constructor {
+            Some(constructor) => quote! { required.register_with::<#ty>(#constructor); },
+            None => quote! { required.register::<#ty>(); },
+        });
+        quote! {
+            fn register_required_components(required: &mut #bevy_ecs_path::component::RequiredComponents) {
+                #(#require)*
+            }
+        }
+    });
+
     ast.generics
         .make_where_clause()
         .predicates
@@ -44,15 +74,28 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             type Storage = #storage;
+
+            #register_component_hooks
+
+            #register_required_components
         }
     })
 }
 
 pub const COMPONENT: Symbol = Symbol("component");
 pub const STORAGE: Symbol = Symbol("storage");
+pub const ON_ADD: Symbol = Symbol("on_add");
+pub const ON_INSERT: Symbol = Symbol("on_insert");
+pub const ON_REMOVE: Symbol = Symbol("on_remove");
+pub const REQUIRE: Symbol = Symbol("require");
 
 struct Attrs {
     storage: StorageTy,
+    on_add: Option<ExprPath>,
+    on_insert: Option<ExprPath>,
+    on_remove: Option<ExprPath>,
+    /// The required components, with their constructor if they don't use their default value.
+    require: Vec<(Path, Option<ExprPath>)>,
 }
 
 #[derive(Clone, Copy)]
@@ -70,11 +113,15 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
 
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        on_add: None,
+        on_insert: None,
+        on_remove: None,
+        require: Vec::new(),
     };
 
     for meta in meta_items {
         use syn::{
-            Meta::NameValue,
+            Meta::{List, NameValue, Path},
             NestedMeta::{Lit, Meta},
         };
         match meta {
@@ -93,6 +140,31 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                     }
                 };
             }
+            Meta(NameValue(m)) if m.path == ON_ADD => {
+                attrs.on_add = Some(get_lit_str(ON_ADD, &m.lit)?.parse()?);
+            }
+            Meta(NameValue(m)) if m.path == ON_INSERT => {
+                attrs.on_insert = Some(get_lit_str(ON_INSERT, &m.lit)?.parse()?);
+            }
+            Meta(NameValue(m)) if m.path == ON_REMOVE => {
+                attrs.on_remove = Some(get_lit_str(ON_REMOVE, &m.lit)?.parse()?);
+            }
+            Meta(List(list)) if list.path == REQUIRE => {
+                for nested in list.nested {
+                    match nested {
+                        Meta(Path(path)) => attrs.require.push((path, None)),
+                        Meta(NameValue(m)) => {
+                            let constructor = get_lit_str(REQUIRE, &m.lit)?.parse()?;
+                            attrs.require.push((m.path, Some(constructor)));
+                        }
+                        other => return Err(Error::new_spanned(
+                            other,
+                            "expected a component type, or a component type with a constructor \
+                                 like `Component = \"path::to::constructor\"`",
+                        )),
+                    }
+                }
+            }
             Meta(meta_item) => {
                 return Err(Error::new_spanned(
                     meta_item.path(),
@@ -9,7 +9,7 @@ use crate::{
         Archetype, ArchetypeId, Archetypes, BundleComponentStatus, ComponentStatus,
         SpawnBundleStatus,
     },
-    component::{Component, ComponentId, Components, StorageType, Tick},
+    component::{Component, ComponentId, Components, RequiredComponent, StorageType, Tick},
     entity::{Entities, Entity, EntityLocation},
     storage::{SparseSetIndex, SparseSets, Storages, Table},
 };
@@ -230,6 +230,48 @@ macro_rules! tuple_impl {
 
 all_tuples!(tuple_impl, 0, 15, B);
 
+/// The part of [`Bundle`] used to write components to storage, which is also implemented by
+/// [`ComponentPtrs`] for bundles assembled at runtime.
+pub(crate) trait DynamicBundle {
+    /// Calls `func` on each value, in the order of the component ids of the [`BundleInfo`].
+    fn get_components(self, func: &mut impl FnMut(OwningPtr<'_>));
+}
+
+impl<B: Bundle> DynamicBundle for B {
+    #[inline]
+    fn get_components(self, func: &mut impl FnMut(OwningPtr<'_>)) {
+        Bundle::get_components(self, func);
+    }
+}
+
+/// Component values of a bundle assembled at runtime, given as pointers in the order of the
+/// component ids of its [`BundleInfo`].
+pub(crate) struct ComponentPtrs<I>(pub(crate) I);
+
+impl<'a, I: Iterator<Item = OwningPtr<'a>>> DynamicBundle for ComponentPtrs<I> {
+    #[inline]
+    fn get_components(self, func: &mut impl FnMut(OwningPtr<'_>)) {
+        self.0.for_each(func);
+    }
+}
+
+/// Component values of a bundle followed by the values of the missing components it requires,
+/// matching the bundle returned by [`Bundles::with_required_components`].
+pub(crate) struct WithRequiredComponents<'a, B> {
+    pub(crate) bundle: B,
+    pub(crate) required: &'a [RequiredComponent],
+}
+
+impl<B: DynamicBundle> DynamicBundle for WithRequiredComponents<'_, B> {
+    #[inline]
+    fn get_components(self, func: &mut impl FnMut(OwningPtr<'_>)) {
+        self.bundle.get_components(&mut *func);
+        for required in self.required {
+            (required.constructor)(&mut *func);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct BundleId(usize);
 
@@ -255,6 +297,9 @@ pub struct BundleInfo {
     pub(crate) id: BundleId,
     pub(crate) component_ids: Vec<ComponentId>,
     pub(crate) storage_types: Vec<StorageType>,
+    /// The components required by the components of this bundle, directly or not, that are not
+    /// part of the bundle.
+    pub(crate) required_components: Vec<RequiredComponent>,
 }
 
 impl BundleInfo {
@@ -273,6 +318,14 @@ impl BundleInfo {
         &self.storage_types
     }
 
+    /// Returns the components that are inserted along with this bundle when they are missing.
+    ///
+    /// See [`Component::register_required_components`].
+    #[inline]
+    pub fn required_components(&self) -> &[RequiredComponent] {
+        &self.required_components
+    }
+
     pub(crate) fn get_bundle_inserter<'a, 'b>(
         &'b self,
         entities: &'a mut Entities,
@@ -373,7 +426,7 @@ impl BundleInfo {
     /// `entity`, `bundle` must match this [`BundleInfo`]'s type
     #[inline]
     #[allow(clippy::too_many_arguments)]
-    unsafe fn write_components<T: Bundle, S: BundleComponentStatus>(
+    unsafe fn write_components<T: DynamicBundle, S: BundleComponentStatus>(
         &self,
         table: &mut Table,
         sparse_sets: &mut SparseSets,
@@ -521,7 +574,7 @@ impl<'a, 'b> BundleInserter<'a, 'b> {
     /// `entity` must currently exist in the source archetype for this inserter. `archetype_index`
     /// must be `entity`'s location in the archetype. `T` must match this [`BundleInfo`]'s type
     #[inline]
-    pub unsafe fn insert<T: Bundle>(
+    pub unsafe fn insert<T: DynamicBundle>(
         &mut self,
         entity: Entity,
         archetype_index: usize,
@@ -648,7 +701,7 @@ impl<'a, 'b> BundleSpawner<'a, 'b> {
     /// # Safety
     /// `entity` must be allocated (but non-existent), `T` must match this [`BundleInfo`]'s type
     #[inline]
-    pub unsafe fn spawn_non_existent<T: Bundle>(
+    pub unsafe fn spawn_non_existent<T: DynamicBundle>(
         &mut self,
         entity: Entity,
         bundle: T,
@@ -672,7 +725,7 @@ impl<'a, 'b> BundleSpawner<'a, 'b> {
     /// # Safety
     /// `T` must match this [`BundleInfo`]'s type
     #[inline]
-    pub unsafe fn spawn<T: Bundle>(&mut self, bundle: T) -> Entity {
+    pub unsafe fn spawn<T: DynamicBundle>(&mut self, bundle: T) -> Entity {
         let entity = self.entities.alloc();
         // SAFETY: entity is allocated (but non-existent), `T` matches this BundleInfo's type
         self.spawn_non_existent(entity, bundle);
@@ -684,6 +737,8 @@ impl<'a, 'b> BundleSpawner<'a, 'b> {
 pub struct Bundles {
     bundle_infos: Vec<BundleInfo>,
     bundle_ids: HashMap<TypeId, BundleId>,
+    /// The bundles assembled at runtime, by their component ids.
+    dynamic_bundle_ids: bevy_utils::HashMap<Vec<ComponentId>, BundleId>,
 }
 
 impl Bundles {
@@ -717,6 +772,77 @@ impl Bundles {
         // SAFETY: index either exists, or was initialized
         unsafe { self.bundle_infos.get_unchecked(id.0) }
     }
+
+    /// Initializes a [`BundleInfo`] for a bundle assembled at runtime from the components of
+    /// `component_ids`, in that order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_ids` has duplicates.
+    ///
+    /// # Safety
+    ///
+    /// Every [`ComponentId`] of `component_ids` must be valid for `components`.
+    pub(crate) unsafe fn init_dynamic_info<'a>(
+        &'a mut self,
+        components: &mut Components,
+        component_ids: &[ComponentId],
+    ) -> &'a BundleInfo {
+        let id = match self.dynamic_bundle_ids.get(component_ids) {
+            Some(&id) => id,
+            None => {
+                let id = BundleId(self.bundle_infos.len());
+                let bundle_info =
+                    initialize_bundle("dynamic bundle", component_ids.to_vec(), id, components);
+                self.bundle_infos.push(bundle_info);
+                self.dynamic_bundle_ids.insert(component_ids.to_vec(), id);
+                id
+            }
+        };
+        self.bundle_infos.get_unchecked(id.0)
+    }
+
+    /// Returns the bundle to insert in place of the bundle of `bundle_id` on an entity of
+    /// `archetype`, or on a new entity if it is `None`, along with the required components that it
+    /// adds. Those components must be written after the ones of the bundle, using
+    /// [`WithRequiredComponents`].
+    ///
+    /// # Safety
+    ///
+    /// `bundle_id` must be the id of a bundle of `self` whose components are valid for
+    /// `components`.
+    pub(crate) unsafe fn with_required_components(
+        &mut self,
+        components: &mut Components,
+        bundle_id: BundleId,
+        archetype: Option<&Archetype>,
+    ) -> (BundleId, Vec<RequiredComponent>) {
+        let bundle_info = self.bundle_infos.get_unchecked(bundle_id.0);
+        if bundle_info.required_components.is_empty() {
+            return (bundle_id, Vec::new());
+        }
+        let missing: Vec<RequiredComponent> = bundle_info
+            .required_components
+            .iter()
+            .filter(|required| {
+                !matches!(archetype, Some(archetype) if archetype.contains(required.component_id))
+            })
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            return (bundle_id, missing);
+        }
+        let component_ids: Vec<ComponentId> = bundle_info
+            .component_ids
+            .iter()
+            .copied()
+            .chain(missing.iter().map(|required| required.component_id))
+            .collect();
+        // SAFETY: the required components are not part of the bundle, and were initialized along
+        // with the components that require them.
+        let id = self.init_dynamic_info(components, &component_ids).id();
+        (id, missing)
+    }
 }
 
 /// # Safety
@@ -745,9 +871,27 @@ unsafe fn initialize_bundle(
         bundle_type_name
     );
 
+    // Walk the requirements breadth-first, so that the closest requirement of a component
+    // decides its constructor.
+    let mut required_components: Vec<RequiredComponent> = Vec::new();
+    let mut next = 0;
+    let mut requiring = component_ids.clone();
+    while let Some(&component_id) = requiring.get(next) {
+        next += 1;
+        // SAFETY: component_id exists, and so do the components it requires
+        let component_info = components.get_info_unchecked(component_id);
+        for required in component_info.required_components() {
+            if !requiring.contains(&required.component_id) {
+                requiring.push(required.component_id);
+                required_components.push(required.clone());
+            }
+        }
+    }
+
     BundleInfo {
         id,
         component_ids,
         storage_types,
+        required_components,
     }
 }
@@ -7,6 +7,8 @@ use crate::{
 };
 use bevy_ptr::UnsafeCellDeref;
 use std::ops::{Deref, DerefMut};
+#[cfg(feature = "track_change_detection")]
+use std::{borrow::Cow, cell::Cell, sync::Mutex};
 
 /// The (arbitrarily chosen) minimum number of world tick increments between `check_tick` scans.
 ///
@@ -23,13 +25,74 @@ pub const CHECK_TICK_THRESHOLD: u32 = 518_400_000;
 /// Changes stop being detected once they become this old.
 pub const MAX_CHANGE_AGE: u32 = u32::MAX - (2 * CHECK_TICK_THRESHOLD - 1);
 
+/// The name of the system that last changed a value, or `None` if the value was last changed
+/// outside of any system, for example directly through the [`World`](crate::world::World).
+///
+/// Only recorded when the `track_change_detection` feature is enabled.
+#[cfg(feature = "track_change_detection")]
+pub type ChangedBy = Option<&'static str>;
+
+#[cfg(feature = "track_change_detection")]
+thread_local! {
+    static CURRENT_SYSTEM: Cell<ChangedBy> = const { Cell::new(None) };
+}
+
+/// Returns the name of the system running on this thread, if any.
+#[cfg(feature = "track_change_detection")]
+#[inline]
+pub(crate) fn current_system() -> ChangedBy {
+    CURRENT_SYSTEM.with(Cell::get)
+}
+
+/// Records the system with the given name as the author of the changes made on this thread,
+/// until the returned guard is dropped.
+// The `Cow` is needed to tell borrowed names, which are already `'static`, from owned ones.
+#[cfg(feature = "track_change_detection")]
+#[allow(clippy::ptr_arg)]
+pub(crate) fn track_changes_by(name: &Cow<'static, str>) -> ChangeAuthorGuard {
+    let name = match name {
+        Cow::Borrowed(name) => name,
+        Cow::Owned(name) => intern_system_name(name),
+    };
+    ChangeAuthorGuard(CURRENT_SYSTEM.with(|current| current.replace(Some(name))))
+}
+
+/// Leaks each distinct system name once, so that ticks can refer to them without allocating.
+#[cfg(feature = "track_change_detection")]
+fn intern_system_name(name: &str) -> &'static str {
+    static NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+    let mut names = NAMES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(interned) = names.iter().find(|interned| **interned == name) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.into());
+    names.push(interned);
+    interned
+}
+
+/// Restores the previous change author when dropped.
+#[cfg(feature = "track_change_detection")]
+pub(crate) struct ChangeAuthorGuard(ChangedBy);
+
+#[cfg(feature = "track_change_detection")]
+impl Drop for ChangeAuthorGuard {
+    fn drop(&mut self) {
+        CURRENT_SYSTEM.with(|current| current.set(self.0));
+    }
+}
+
 /// Types that implement reliable change detection.
 ///
 /// ## Example
 /// Using types that implement [`DetectChanges`], such as [`ResMut`], provide
 /// a way to query if a value has been mutated in another system.
 /// Normally change detecting is triggered by either [`DerefMut`] or [`AsMut`], however
-/// it can be manually triggered via [`DetectChanges::set_changed`].
+/// it can be manually triggered via [`DetectChangesMut::set_changed`].
+///
+/// Read-only types like [`Ref`] and [`Res`](crate::system::Res) only implement
+/// [`DetectChanges`], while types giving mutable access also implement [`DetectChangesMut`].
 ///
 /// ```
 /// use bevy_ecs::prelude::*;
@@ -47,26 +110,13 @@ pub const MAX_CHANGE_AGE: u32 = u32::MAX - (2 * CHECK_TICK_THRESHOLD - 1);
 /// ```
 ///
 pub trait DetectChanges {
-    /// The type contained within this smart pointer
-    ///
-    /// For example, for `Res<T>` this would be `T`.
-    type Inner: ?Sized;
-
     /// Returns `true` if this value was added after the system last ran.
     fn is_added(&self) -> bool;
 
     /// Returns `true` if this value was added or mutably dereferenced after the system last ran.
     fn is_changed(&self) -> bool;
 
-    /// Flags this value as having been changed.
-    ///
-    /// Mutably accessing this smart pointer will automatically flag this value as having been changed.
-    /// However, mutation through interior mutability requires manual reporting.
-    ///
-    /// **Note**: This operation cannot be undone.
-    fn set_changed(&mut self);
-
-    /// Returns the change tick recording the previous time this data was changed.
+    /// Returns the change tick recording the time this data was most recently changed.
     ///
     /// Note that components and resources are also marked as changed upon insertion.
     ///
@@ -75,13 +125,36 @@ pub trait DetectChanges {
     /// [`SystemParam`](crate::system::SystemParam).
     fn last_changed(&self) -> u32;
 
-    /// Manually sets the change tick recording the previous time this data was mutated.
+    /// Returns the name of the system that most recently changed this value,
+    /// or `None` if it was last changed outside of any system.
+    #[cfg(feature = "track_change_detection")]
+    fn changed_by(&self) -> ChangedBy;
+}
+
+/// Types that implement reliable change detection, and give mutable access to their data.
+///
+/// See [`DetectChanges`] for more details.
+pub trait DetectChangesMut: DetectChanges {
+    /// The type contained within this smart pointer
+    ///
+    /// For example, for `ResMut<T>` this would be `T`.
+    type Inner: ?Sized;
+
+    /// Flags this value as having been changed.
+    ///
+    /// Mutably accessing this smart pointer will automatically flag this value as having been changed.
+    /// However, mutation through interior mutability requires manual reporting.
+    ///
+    /// **Note**: This operation cannot be undone.
+    fn set_changed(&mut self);
+
+    /// Manually sets the change tick recording the time this data was most recently mutated.
     ///
     /// # Warning
     /// This is a complex and error-prone operation, primarily intended for use with rollback networking strategies.
-    /// If you merely want to flag this data as changed, use [`set_changed`](DetectChanges::set_changed) instead.
-    /// If you want to avoid triggering change detection, use [`bypass_change_detection`](DetectChanges::bypass_change_detection) instead.
-    fn set_last_changed(&mut self, last_change_tick: u32);
+    /// If you merely want to flag this data as changed, use [`set_changed`](DetectChangesMut::set_changed) instead.
+    /// If you want to avoid triggering change detection, use [`bypass_change_detection`](DetectChangesMut::bypass_change_detection) instead.
+    fn set_last_changed(&mut self, last_changed: u32);
 
     /// Manually bypasses change detection, allowing you to mutate the underlying value without updating the change tick.
     ///
@@ -95,8 +168,6 @@ pub trait DetectChanges {
 macro_rules! change_detection_impl {
     ($name:ident < $( $generics:tt ),+ >, $target:ty, $($traits:ident)?) => {
         impl<$($generics),* : ?Sized $(+ $traits)?> DetectChanges for $name<$($generics),*> {
-            type Inner = $target;
-
             #[inline]
             fn is_added(&self) -> bool {
                 self.ticks
@@ -112,33 +183,54 @@ macro_rules! change_detection_impl {
             }
 
             #[inline]
-            fn set_changed(&mut self) {
-                self.ticks
-                    .changed
-                    .set_changed(self.ticks.change_tick);
+            fn last_changed(&self) -> u32 {
+                self.ticks.changed.tick
             }
 
+            #[cfg(feature = "track_change_detection")]
             #[inline]
-            fn last_changed(&self) -> u32 {
-                self.ticks.last_change_tick
+            fn changed_by(&self) -> ChangedBy {
+                self.ticks.changed.changed_by
             }
+        }
+
+        impl<$($generics),*: ?Sized $(+ $traits)?> Deref for $name<$($generics),*> {
+            type Target = $target;
 
             #[inline]
-            fn set_last_changed(&mut self, last_change_tick: u32) {
-                self.ticks.last_change_tick = last_change_tick
+            fn deref(&self) -> &Self::Target {
+                self.value
             }
+        }
 
+        impl<$($generics),* $(: $traits)?> AsRef<$target> for $name<$($generics),*> {
             #[inline]
-            fn bypass_change_detection(&mut self) -> &mut Self::Inner {
-                self.value
+            fn as_ref(&self) -> &$target {
+                self.deref()
             }
         }
+    };
+}
 
-        impl<$($generics),*: ?Sized $(+ $traits)?> Deref for $name<$($generics),*> {
-            type Target = $target;
+macro_rules! change_detection_mut_impl {
+    ($name:ident < $( $generics:tt ),+ >, $target:ty, $($traits:ident)?) => {
+        impl<$($generics),* : ?Sized $(+ $traits)?> DetectChangesMut for $name<$($generics),*> {
+            type Inner = $target;
 
             #[inline]
-            fn deref(&self) -> &Self::Target {
+            fn set_changed(&mut self) {
+                self.ticks
+                    .changed
+                    .set_changed(self.ticks.change_tick);
+            }
+
+            #[inline]
+            fn set_last_changed(&mut self, last_changed: u32) {
+                self.ticks.changed.set_changed(last_changed);
+            }
+
+            #[inline]
+            fn bypass_change_detection(&mut self) -> &mut Self::Inner {
                 self.value
             }
         }
@@ -151,13 +243,6 @@ macro_rules! change_detection_impl {
             }
         }
 
-        impl<$($generics),* $(: $traits)?> AsRef<$target> for $name<$($generics),*> {
-            #[inline]
-            fn as_ref(&self) -> &$target {
-                self.deref()
-            }
-        }
-
         impl<$($generics),* $(: $traits)?> AsMut<$target> for $name<$($generics),*> {
             #[inline]
             fn as_mut(&mut self) -> &mut $target {
@@ -181,7 +266,7 @@ macro_rules! impl_methods {
             /// Maps to an inner value by applying a function to the contained reference, without flagging a change.
             ///
             /// You should never modify the argument passed to the closure -- if you want to modify the data
-            /// without flagging a change, consider using [`DetectChanges::bypass_change_detection`] to make your intent explicit.
+            /// without flagging a change, consider using [`DetectChangesMut::bypass_change_detection`] to make your intent explicit.
             ///
             /// ```rust
             /// # use bevy_ecs::prelude::*;
@@ -228,6 +313,44 @@ macro_rules! impl_debug {
     };
 }
 
+/// The change ticks of a value borrowed immutably, in a [`Ref`].
+#[derive(Clone, Copy)]
+pub(crate) struct RefTicks<'a> {
+    pub(crate) added: &'a Tick,
+    pub(crate) changed: &'a Tick,
+    pub(crate) last_change_tick: u32,
+    pub(crate) change_tick: u32,
+}
+
+impl<'a> RefTicks<'a> {
+    /// # Safety
+    /// The cells must not be mutably aliased while the ticks are alive.
+    #[inline]
+    pub(crate) unsafe fn from_tick_cells(
+        cells: TickCells<'a>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        Self {
+            added: cells.added.deref(),
+            changed: cells.changed.deref(),
+            last_change_tick,
+            change_tick,
+        }
+    }
+}
+
+impl<'a> From<Ticks<'a>> for RefTicks<'a> {
+    fn from(ticks: Ticks<'a>) -> Self {
+        Self {
+            added: ticks.added,
+            changed: ticks.changed,
+            last_change_tick: ticks.last_change_tick,
+            change_tick: ticks.change_tick,
+        }
+    }
+}
+
 pub(crate) struct Ticks<'a> {
     pub(crate) added: &'a mut Tick,
     pub(crate) changed: &'a mut Tick,
@@ -295,6 +418,7 @@ where
 }
 
 change_detection_impl!(ResMut<'a, T>, T, Resource);
+change_detection_mut_impl!(ResMut<'a, T>, T, Resource);
 impl_methods!(ResMut<'a, T>, T, Resource);
 impl_debug!(ResMut<'a, T>, Resource);
 
@@ -327,6 +451,7 @@ pub struct NonSendMut<'a, T: ?Sized + 'static> {
 }
 
 change_detection_impl!(NonSendMut<'a, T>, T,);
+change_detection_mut_impl!(NonSendMut<'a, T>, T,);
 impl_methods!(NonSendMut<'a, T>, T,);
 impl_debug!(NonSendMut<'a, T>,);
 
@@ -373,9 +498,74 @@ where
 }
 
 change_detection_impl!(Mut<'a, T>, T,);
+change_detection_mut_impl!(Mut<'a, T>, T,);
 impl_methods!(Mut<'a, T>, T,);
 impl_debug!(Mut<'a, T>,);
 
+/// Shared borrow of an entity's component, with access to its change detection.
+///
+/// Similar to [`Mut`], but immutable, so it doesn't require unique access. This is the query item
+/// to use instead of `&T` in order to know when the component was last changed:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// fn report_damage(query: Query<(Entity, Ref<Health>)>) {
+///     for (entity, health) in &query {
+///         if health.is_changed() && !health.is_added() {
+///             println!("{:?} now has {} health, since tick {}", entity, health.0, health.last_changed());
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(report_damage);
+/// ```
+pub struct Ref<'a, T: ?Sized> {
+    pub(crate) value: &'a T,
+    pub(crate) ticks: RefTicks<'a>,
+}
+
+impl<'a, T: ?Sized> Ref<'a, T> {
+    /// Returns the reference wrapped by this type, losing the change detection information.
+    #[inline]
+    pub fn into_inner(self) -> &'a T {
+        self.value
+    }
+
+    /// Maps to an inner value by applying a function to the contained reference, keeping the
+    /// change detection information.
+    pub fn map<U: ?Sized>(self, f: impl FnOnce(&T) -> &U) -> Ref<'a, U> {
+        Ref {
+            value: f(self.value),
+            ticks: self.ticks,
+        }
+    }
+}
+
+impl<'w, 'a, T> IntoIterator for &'a Ref<'w, T>
+where
+    &'a T: IntoIterator,
+{
+    type Item = <&'a T as IntoIterator>::Item;
+    type IntoIter = <&'a T as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.value.into_iter()
+    }
+}
+
+impl<'a, T: ?Sized> From<Mut<'a, T>> for Ref<'a, T> {
+    fn from(mut_ref: Mut<'a, T>) -> Self {
+        Self {
+            value: mut_ref.value,
+            ticks: mut_ref.ticks.into(),
+        }
+    }
+}
+
+change_detection_impl!(Ref<'a, T>, T,);
+impl_debug!(Ref<'a, T>,);
+
 /// Unique mutable borrow of resources or an entity's component.
 ///
 /// Similar to [`Mut`], but not generic over the component type, instead
@@ -392,7 +582,7 @@ pub struct MutUntyped<'a> {
 impl<'a> MutUntyped<'a> {
     /// Returns the pointer to the value, without marking it as changed.
     ///
-    /// In order to mark the value as changed, you need to call [`set_changed`](DetectChanges::set_changed) manually.
+    /// In order to mark the value as changed, you need to call [`set_changed`](DetectChangesMut::set_changed) manually.
     #[inline]
     pub fn into_inner(self) -> PtrMut<'a> {
         self.value
@@ -400,8 +590,6 @@ impl<'a> MutUntyped<'a> {
 }
 
 impl<'a> DetectChanges for MutUntyped<'a> {
-    type Inner = PtrMut<'a>;
-
     #[inline]
     fn is_added(&self) -> bool {
         self.ticks
@@ -417,18 +605,28 @@ impl<'a> DetectChanges for MutUntyped<'a> {
     }
 
     #[inline]
-    fn set_changed(&mut self) {
-        self.ticks.changed.set_changed(self.ticks.change_tick);
+    fn last_changed(&self) -> u32 {
+        self.ticks.changed.tick
     }
 
+    #[cfg(feature = "track_change_detection")]
     #[inline]
-    fn last_changed(&self) -> u32 {
-        self.ticks.last_change_tick
+    fn changed_by(&self) -> ChangedBy {
+        self.ticks.changed.changed_by
+    }
+}
+
+impl<'a> DetectChangesMut for MutUntyped<'a> {
+    type Inner = PtrMut<'a>;
+
+    #[inline]
+    fn set_changed(&mut self) {
+        self.ticks.changed.set_changed(self.ticks.change_tick);
     }
 
     #[inline]
-    fn set_last_changed(&mut self, last_change_tick: u32) {
-        self.ticks.last_change_tick = last_change_tick;
+    fn set_last_changed(&mut self, last_changed: u32) {
+        self.ticks.changed.set_changed(last_changed);
     }
 
     #[inline]
@@ -451,16 +649,23 @@ mod tests {
 
     use crate::{
         self as bevy_ecs,
-        change_detection::{Mut, NonSendMut, ResMut, Ticks, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
+        change_detection::{
+            DetectChanges, DetectChangesMut, Mut, NonSendMut, Ref, ResMut, Ticks,
+            CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE,
+        },
         component::{Component, ComponentTicks, Tick},
         query::ChangeTrackers,
-        system::{IntoSystem, Query, System},
+        system::{IntoSystem, Query, Res, System},
         world::World,
     };
 
     #[derive(Component)]
     struct C;
 
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct S(u32);
+
     #[derive(Resource)]
     struct R;
 
@@ -635,4 +840,180 @@ mod tests {
         // Modifying one field of a component should flag a change for the entire component.
         assert!(component_ticks.is_changed(last_change_tick, change_tick));
     }
+
+    #[test]
+    fn ref_query_change_detection() {
+        let mut world = World::new();
+        let entity = world.spawn((C, S(0))).id();
+        let spawn_tick = world.read_change_tick();
+        world.clear_trackers();
+
+        let mut query = world.query::<(Ref<C>, Ref<S>)>();
+        let (c, s) = query.single(&world);
+        assert!(!c.is_added() && !c.is_changed());
+        assert!(!s.is_added() && !s.is_changed());
+        assert_eq!(c.last_changed(), spawn_tick);
+        assert_eq!(s.last_changed(), spawn_tick);
+
+        world.get_mut::<S>(entity).unwrap().0 = 1;
+        let change_tick = world.read_change_tick();
+        let (c, s) = query.single(&world);
+        assert!(!c.is_changed());
+        assert!(s.is_changed() && !s.is_added());
+        assert_eq!(s.last_changed(), change_tick);
+        assert_eq!(s.0, 1);
+
+        let s = world.entity(entity).get_ref::<S>().unwrap();
+        assert!(s.is_changed());
+        assert_eq!(s.last_changed(), change_tick);
+    }
+
+    #[test]
+    fn sparse_set_mut_detects_changes() {
+        let mut world = World::new();
+        let entity = world.spawn(S(0)).id();
+        world.clear_trackers();
+
+        let mut query = world.query::<&mut S>();
+        let mut s = query.single_mut(&mut world);
+        assert!(!s.is_changed());
+        s.0 = 1;
+        assert!(s.is_changed());
+        assert!(world.entity(entity).get_ref::<S>().unwrap().is_changed());
+    }
+
+    #[test]
+    fn res_last_changed() {
+        fn last_changed(res: Res<Counter>) -> u32 {
+            res.last_changed()
+        }
+
+        fn increment(mut res: ResMut<Counter>) {
+            res.0 += 1;
+        }
+
+        #[derive(Resource)]
+        struct Counter(u32);
+
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let insert_tick = world.read_change_tick();
+
+        let mut last_changed_system = IntoSystem::into_system(last_changed);
+        last_changed_system.initialize(&mut world);
+        assert_eq!(last_changed_system.run((), &mut world), insert_tick);
+
+        let mut increment_system = IntoSystem::into_system(increment);
+        increment_system.initialize(&mut world);
+        increment_system.run((), &mut world);
+        let increment_tick = world.resource_ref::<Counter>().last_changed();
+        assert!(increment_tick != insert_tick);
+        assert_eq!(last_changed_system.run((), &mut world), increment_tick);
+    }
+
+    #[test]
+    fn set_last_changed() {
+        let mut world = World::new();
+        let entity = world.spawn(S(0)).id();
+
+        let mut s = world.get_mut::<S>(entity).unwrap();
+        s.set_last_changed(42);
+        assert_eq!(s.last_changed(), 42);
+        assert_eq!(
+            world.entity(entity).get_ref::<S>().unwrap().last_changed(),
+            42
+        );
+    }
+
+    #[test]
+    fn map_ref() {
+        struct Outer(i64);
+
+        let (last_change_tick, change_tick) = (2, 3);
+        let component_ticks = ComponentTicks {
+            added: Tick::new(1),
+            changed: Tick::new(3),
+        };
+        let outer = Outer(64);
+        let outer = Ref {
+            value: &outer,
+            ticks: super::RefTicks {
+                added: &component_ticks.added,
+                changed: &component_ticks.changed,
+                last_change_tick,
+                change_tick,
+            },
+        };
+        assert!(outer.is_changed() && !outer.is_added());
+
+        let inner = outer.map(|x| &x.0);
+        assert_eq!(*inner, 64);
+        assert!(inner.is_changed() && !inner.is_added());
+        assert_eq!(inner.last_changed(), 3);
+    }
+
+    #[test]
+    fn ref_from_mut() {
+        let mut component_ticks = ComponentTicks {
+            added: Tick::new(1),
+            changed: Tick::new(2),
+        };
+        let mut value = C;
+        let mut_ref = Mut {
+            value: &mut value,
+            ticks: Ticks {
+                added: &mut component_ticks.added,
+                changed: &mut component_ticks.changed,
+                last_change_tick: 3,
+                change_tick: 4,
+            },
+        };
+
+        let into_ref: Ref<C> = mut_ref.into();
+        assert_eq!(1, into_ref.ticks.added.tick);
+        assert_eq!(2, into_ref.ticks.changed.tick);
+        assert_eq!(3, into_ref.ticks.last_change_tick);
+        assert_eq!(4, into_ref.ticks.change_tick);
+    }
+
+    #[cfg(feature = "track_change_detection")]
+    #[test]
+    fn changed_by_records_system_name() {
+        use crate::schedule::{Stage, SystemStage};
+
+        fn touch_component(mut query: Query<&mut S>) {
+            for mut s in &mut query {
+                s.0 += 1;
+            }
+        }
+
+        fn touch_resource(mut res: ResMut<R>) {
+            res.set_changed();
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn(S(0)).id();
+        world.insert_resource(R);
+        assert_eq!(
+            world.entity(entity).get_ref::<S>().unwrap().changed_by(),
+            None
+        );
+        assert_eq!(world.resource_ref::<R>().changed_by(), None);
+
+        let mut stage = SystemStage::parallel()
+            .with_system(touch_component)
+            .with_system(touch_resource);
+        stage.run(&mut world);
+
+        let changed_by = world.entity(entity).get_ref::<S>().unwrap().changed_by();
+        assert!(changed_by.unwrap().ends_with("touch_component"));
+        let changed_by = world.resource_ref::<R>().changed_by();
+        assert!(changed_by.unwrap().ends_with("touch_resource"));
+
+        world.get_mut::<S>(entity).unwrap().0 = 0;
+        assert_eq!(
+            world.entity(entity).get_ref::<S>().unwrap().changed_by(),
+            None
+        );
+    }
 }
@@ -1,9 +1,14 @@
 //! Types for declaring and storing [`Component`]s.
 
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::{current_system, ChangedBy};
 use crate::{
+    archetype::Archetype,
     change_detection::MAX_CHANGE_AGE,
+    entity::Entity,
     storage::{SparseSetIndex, Storages},
     system::Resource,
+    world::World,
 };
 pub use bevy_ecs_macros::Component;
 use bevy_ptr::{OwningPtr, UnsafeCellDeref};
@@ -13,6 +18,7 @@ use std::{
     any::{Any, TypeId},
     borrow::Cow,
     mem::needs_drop,
+    sync::Arc,
 };
 
 /// A data type that can be used to store data for an [entity].
@@ -110,8 +116,80 @@ use std::{
 ///
 /// [orphan rule]: https://doc.rust-lang.org/book/ch10-02-traits.html#implementing-a-trait-on-a-type
 /// [newtype pattern]: https://doc.rust-lang.org/book/ch19-03-advanced-traits.html#using-the-newtype-pattern-to-implement-external-traits-on-external-types
+///
+/// # Lifecycle hooks
+///
+/// Components can react synchronously to being added to, inserted on or removed from an entity
+/// through [`ComponentHooks`]. Hooks that are part of the component's definition can be declared
+/// with the `on_add`, `on_insert` and `on_remove` attributes of the derive:
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, component::ComponentId};
+/// #[derive(Component)]
+/// #[component(on_add = "count_enemy")]
+/// struct Enemy;
+///
+/// #[derive(Resource, Default)]
+/// struct EnemyCount(usize);
+///
+/// fn count_enemy(world: &mut World, _entity: Entity, _component_id: ComponentId) {
+///     world.resource_mut::<EnemyCount>().0 += 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<EnemyCount>();
+/// world.spawn(Enemy);
+/// assert_eq!(world.resource::<EnemyCount>().0, 1);
+/// ```
+///
+/// Hooks can also be registered for any component from outside of its definition with
+/// [`World::register_component_hooks`].
+///
+/// # Required components
+///
+/// A component can require other components: whenever it is inserted on an entity, the
+/// required components that the entity doesn't have yet are inserted along with it. They are
+/// declared with the `require` attribute of the derive, and use their [`Default`] value unless a
+/// constructor function is given:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component, Default, Debug, PartialEq)]
+/// struct Velocity(f32);
+///
+/// #[derive(Component, Debug, PartialEq)]
+/// struct Mass(f32);
+///
+/// fn unit_mass() -> Mass {
+///     Mass(1.0)
+/// }
+///
+/// #[derive(Component)]
+/// #[component(require(Velocity, Mass = "unit_mass"))]
+/// struct RigidBody;
+///
+/// let mut world = World::new();
+/// let body = world.spawn(RigidBody).id();
+/// assert_eq!(world.get::<Velocity>(body), Some(&Velocity(0.0)));
+/// assert_eq!(world.get::<Mass>(body), Some(&Mass(1.0)));
+///
+/// // Components that are given explicitly, or that the entity already has, are left untouched.
+/// let heavy = world.spawn((RigidBody, Mass(10.0))).id();
+/// assert_eq!(world.get::<Mass>(heavy), Some(&Mass(10.0)));
+/// ```
+///
+/// Requirements are transitive: the components required by a required component are inserted
+/// too. The closest requirement decides the value of a component that is required several times.
 pub trait Component: Send + Sync + 'static {
     type Storage: ComponentStorage;
+
+    /// Called when this component type is initialized in a [`World`], to register the hooks that
+    /// are part of its definition.
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {}
+
+    /// Called when this component type is initialized in a [`World`], to register the components
+    /// that must be inserted along with it.
+    fn register_required_components(_required: &mut RequiredComponents) {}
 }
 
 pub struct TableStorage;
@@ -156,10 +234,186 @@ pub enum StorageType {
     SparseSet,
 }
 
+/// A function that is called when a component is added to, inserted on or removed from an entity.
+///
+/// It is given the entity and the [`ComponentId`] of the component the hook was triggered for.
+/// See [`ComponentHooks`] for more details.
+pub type ComponentHook = fn(&mut World, Entity, ComponentId);
+
+/// The lifecycle hooks of a component type.
+///
+/// Hooks run synchronously, with exclusive access to the [`World`], whenever the component is
+/// added, inserted or removed through [`World`], [`EntityMut`](crate::world::EntityMut) or
+/// [`Commands`](crate::system::Commands), including when spawning and despawning entities:
+///
+/// - `on_add` runs after the component was inserted on an entity that did not have it before.
+/// - `on_insert` runs after the component was inserted on an entity, whether it replaced a
+///   previous value or not. It runs after `on_add`.
+/// - `on_remove` runs before the component is removed from an entity, including when the entity
+///   is despawned, so the value can still be read.
+///
+/// Each component type has at most one hook of each kind. Hooks are free to change the rest of the
+/// [`World`], including the components of the entity they were triggered for, but must not despawn
+/// that entity.
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Option<ComponentHook>,
+    pub(crate) on_insert: Option<ComponentHook>,
+    pub(crate) on_remove: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Registers a hook that runs after the component is added to an entity that did not have it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component already has an `on_add` hook.
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_add(hook)
+            .expect("Component already has an on_add hook")
+    }
+
+    /// Registers a hook that runs after the component is inserted on an entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component already has an `on_insert` hook.
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_insert(hook)
+            .expect("Component already has an on_insert hook")
+    }
+
+    /// Registers a hook that runs before the component is removed from an entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component already has an `on_remove` hook.
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_remove(hook)
+            .expect("Component already has an on_remove hook")
+    }
+
+    /// Fallible version of [`ComponentHooks::on_add`].
+    ///
+    /// Returns `None` if the component already has an `on_add` hook.
+    pub fn try_on_add(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_add.is_some() {
+            return None;
+        }
+        self.on_add = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`ComponentHooks::on_insert`].
+    ///
+    /// Returns `None` if the component already has an `on_insert` hook.
+    pub fn try_on_insert(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_insert.is_some() {
+            return None;
+        }
+        self.on_insert = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`ComponentHooks::on_remove`].
+    ///
+    /// Returns `None` if the component already has an `on_remove` hook.
+    pub fn try_on_remove(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_remove.is_some() {
+            return None;
+        }
+        self.on_remove = Some(hook);
+        Some(self)
+    }
+
+    /// Returns `true` if no hook is registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.on_add.is_none() && self.on_insert.is_none() && self.on_remove.is_none()
+    }
+}
+
+/// Constructs the value of a required component, and passes a pointer to it to the given function.
+pub type RequiredComponentConstructor =
+    Arc<dyn Fn(&mut dyn FnMut(OwningPtr<'_>)) + Send + Sync + 'static>;
+
+type InitComponentFn = fn(&mut Components, &mut Storages) -> ComponentId;
+
+/// The components that a [`Component`] requires, registered in
+/// [`Component::register_required_components`].
+///
+/// See the [`Component`] documentation for more details.
+#[derive(Default)]
+pub struct RequiredComponents {
+    required: Vec<(TypeId, InitComponentFn, RequiredComponentConstructor)>,
+}
+
+impl RequiredComponents {
+    /// Requires the component `C`, using its [`Default`] value when it's missing.
+    pub fn register<C: Component + Default>(&mut self) -> &mut Self {
+        self.register_with(C::default)
+    }
+
+    /// Requires the component `C`, using the value returned by `constructor` when it's missing.
+    ///
+    /// Registering the same component twice keeps the first constructor.
+    pub fn register_with<C: Component>(
+        &mut self,
+        constructor: impl Fn() -> C + Send + Sync + 'static,
+    ) -> &mut Self {
+        let type_id = TypeId::of::<C>();
+        if !self.required.iter().any(|(id, ..)| *id == type_id) {
+            self.required.push((
+                type_id,
+                Components::init_component::<C>,
+                Arc::new(move |func| OwningPtr::make(constructor(), func)),
+            ));
+        }
+        self
+    }
+
+    fn init(self, components: &mut Components, storages: &mut Storages) -> Vec<RequiredComponent> {
+        self.required
+            .into_iter()
+            .map(|(_, init_component, constructor)| RequiredComponent {
+                component_id: init_component(components, storages),
+                constructor,
+            })
+            .collect()
+    }
+}
+
+/// A component that is inserted along with another one when it is missing.
+///
+/// See [`Component::register_required_components`].
+#[derive(Clone)]
+pub struct RequiredComponent {
+    pub(crate) component_id: ComponentId,
+    pub(crate) constructor: RequiredComponentConstructor,
+}
+
+impl RequiredComponent {
+    /// Returns the [`ComponentId`] of the required component.
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+}
+
+impl std::fmt::Debug for RequiredComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequiredComponent")
+            .field("component_id", &self.component_id)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug)]
 pub struct ComponentInfo {
     id: ComponentId,
     descriptor: ComponentDescriptor,
+    hooks: ComponentHooks,
+    required_components: Vec<RequiredComponent>,
 }
 
 impl ComponentInfo {
@@ -204,8 +458,25 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    /// Returns the lifecycle hooks of this component.
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
+    /// Returns the components that are directly required by this component.
+    #[inline]
+    pub fn required_components(&self) -> &[RequiredComponent] {
+        &self.required_components
+    }
+
     fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
-        ComponentInfo { id, descriptor }
+        ComponentInfo {
+            id,
+            descriptor,
+            hooks: ComponentHooks::default(),
+            required_components: Vec::new(),
+        }
     }
 }
 
@@ -374,16 +645,23 @@ impl Components {
     #[inline]
     pub fn init_component<T: Component>(&mut self, storages: &mut Storages) -> ComponentId {
         let type_id = TypeId::of::<T>();
+        if let Some(&index) = self.indices.get(&type_id) {
+            return ComponentId(index);
+        }
 
-        let Components {
-            indices,
-            components,
-            ..
-        } = self;
-        let index = indices.entry(type_id).or_insert_with(|| {
-            Components::init_component_inner(components, storages, ComponentDescriptor::new::<T>())
-        });
-        ComponentId(*index)
+        let index = Components::init_component_inner(
+            &mut self.components,
+            storages,
+            ComponentDescriptor::new::<T>(),
+        );
+        self.indices.insert(type_id, index);
+        T::register_component_hooks(&mut self.components[index].hooks);
+        // The required components are initialized once `T` is, so that components can require
+        // each other.
+        let mut required = RequiredComponents::default();
+        T::register_required_components(&mut required);
+        self.components[index].required_components = required.init(self, storages);
+        ComponentId(index)
     }
 
     pub fn init_component_with_descriptor(
@@ -434,6 +712,49 @@ impl Components {
         self.components.get_unchecked(id.0)
     }
 
+    /// Returns a mutable reference to the [`ComponentHooks`] of the component with the given `id`.
+    #[inline]
+    pub(crate) fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
+        self.components.get_mut(id.0).map(|info| &mut info.hooks)
+    }
+
+    /// Collects the hooks to run after inserting the components `component_ids` on an entity
+    /// that was in `archetype`: the `on_add` hooks of the components missing from `archetype`,
+    /// followed by the `on_insert` hooks of all of them.
+    ///
+    /// Pass `None` as `archetype` for entities that are being spawned.
+    pub(crate) fn insert_hooks(
+        &self,
+        component_ids: &[ComponentId],
+        archetype: Option<&Archetype>,
+    ) -> Vec<(ComponentHook, ComponentId)> {
+        let mut hooks = Vec::new();
+        for &id in component_ids {
+            let info = &self.components[id.0];
+            if let Some(on_add) = info.hooks.on_add {
+                if !matches!(archetype, Some(archetype) if archetype.contains(id)) {
+                    hooks.push((on_add, id));
+                }
+            }
+        }
+        for &id in component_ids {
+            if let Some(on_insert) = self.components[id.0].hooks.on_insert {
+                hooks.push((on_insert, id));
+            }
+        }
+        hooks
+    }
+
+    /// Collects the `on_remove` hooks to run before removing the components `component_ids`.
+    pub(crate) fn remove_hooks(
+        &self,
+        component_ids: impl Iterator<Item = ComponentId>,
+    ) -> Vec<(ComponentHook, ComponentId)> {
+        component_ids
+            .filter_map(|id| Some((self.components[id.0].hooks.on_remove?, id)))
+            .collect()
+    }
+
     /// Type-erased equivalent of [`Components::component_id`].
     #[inline]
     pub fn get_id(&self, type_id: TypeId) -> Option<ComponentId> {
@@ -522,13 +843,42 @@ impl Components {
 #[derive(Copy, Clone, Debug)]
 pub struct Tick {
     pub(crate) tick: u32,
+    #[cfg(feature = "track_change_detection")]
+    pub(crate) changed_by: ChangedBy,
 }
 
 impl Tick {
+    /// Creates a new tick.
+    ///
+    /// With the `track_change_detection` feature, the system currently running on this thread
+    /// is recorded as having set the tick.
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    pub fn new(tick: u32) -> Self {
+        Self {
+            tick,
+            changed_by: current_system(),
+        }
+    }
+
+    /// Creates a new tick.
+    ///
+    /// With the `track_change_detection` feature, the system currently running on this thread
+    /// is recorded as having set the tick.
+    #[cfg(not(feature = "track_change_detection"))]
+    #[inline]
     pub const fn new(tick: u32) -> Self {
         Self { tick }
     }
 
+    /// Returns the name of the system that last set this tick,
+    /// or `None` if it was set outside of any system.
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    pub fn changed_by(&self) -> ChangedBy {
+        self.changed_by
+    }
+
     #[inline]
     /// Returns `true` if the tick is older than the system last's run.
     pub fn is_older_than(&self, last_change_tick: u32, change_tick: u32) -> bool {
@@ -571,6 +921,10 @@ impl Tick {
     #[inline]
     pub fn set_changed(&mut self, change_tick: u32) {
         self.tick = change_tick;
+        #[cfg(feature = "track_change_detection")]
+        {
+            self.changed_by = current_system();
+        }
     }
 }
 
@@ -620,6 +974,14 @@ impl ComponentTicks {
         }
     }
 
+    /// Returns the name of the system that last added or mutably dereferenced the component,
+    /// or `None` if it was changed outside of any system.
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    pub fn changed_by(&self) -> ChangedBy {
+        self.changed.changed_by
+    }
+
     /// Manually sets the change tick.
     ///
     /// This is normally done automatically via the [`DerefMut`](std::ops::DerefMut) implementation
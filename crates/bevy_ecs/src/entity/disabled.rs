@@ -0,0 +1,140 @@
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::ReflectComponent;
+use crate::{self as bevy_ecs, component::Component};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+/// A marker component for entities that are temporarily disabled.
+///
+/// Disabled entities keep all of their components, but are skipped by every query that doesn't
+/// explicitly opt in to them, as if they had been despawned. This is useful for entities that are
+/// expensive to create and are only needed from time to time, like pooled projectiles.
+///
+/// Queries are filtered at the archetype level, when the [`QueryState`](crate::query::QueryState)
+/// matches archetypes, so that disabled entities don't cost anything during iteration. A query
+/// opts in to disabled entities by mentioning `Disabled` in its data or as a top-level filter:
+///
+/// ```
+/// # use bevy_ecs::{entity::Disabled, prelude::*};
+/// #[derive(Component)]
+/// struct Projectile;
+///
+/// let mut world = World::new();
+/// world.spawn(Projectile);
+/// world.spawn((Projectile, Disabled));
+///
+/// // Disabled entities are skipped by default.
+/// assert_eq!(world.query::<&Projectile>().iter(&world).count(), 1);
+/// // Queries that mention `Disabled` see them.
+/// assert_eq!(world.query::<(&Projectile, Option<&Disabled>)>().iter(&world).count(), 2);
+/// assert_eq!(world.query_filtered::<&Projectile, With<Disabled>>().iter(&world).count(), 1);
+/// ```
+///
+/// Filters nested in [`Or`](crate::query::Or) do not opt in to disabled entities.
+///
+/// The component uses table storage, so that disabled entities are stored in their own tables,
+/// which queries iterating over tables can skip entirely.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect))]
+#[cfg_attr(feature = "bevy_reflect", reflect(Component))]
+pub struct Disabled;
+
+#[cfg(test)]
+mod tests {
+    use super::Disabled;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[test]
+    fn queries_skip_disabled_entities() {
+        let mut world = World::new();
+        world.spawn(A);
+        let disabled = world.spawn((A, Disabled)).id();
+
+        let mut query = world.query::<&A>();
+        assert_eq!(query.iter(&world).count(), 1);
+        assert!(query.get(&world, disabled).is_err());
+        assert_eq!(
+            world
+                .query::<(&A, Option<&Disabled>)>()
+                .iter(&world)
+                .count(),
+            2
+        );
+        assert_eq!(
+            world
+                .query_filtered::<&A, With<Disabled>>()
+                .iter(&world)
+                .count(),
+            1
+        );
+        assert_eq!(
+            world
+                .query_filtered::<&A, Option<&Disabled>>()
+                .iter(&world)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn or_filters_do_not_opt_in() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn((A, Disabled));
+
+        assert_eq!(
+            world
+                .query_filtered::<&A, Or<(With<Disabled>, With<A>)>>()
+                .iter(&world)
+                .count(),
+            1
+        );
+        assert_eq!(
+            world
+                .query_filtered::<&A, Or<(Changed<Disabled>, With<A>)>>()
+                .iter(&world)
+                .count(),
+            1
+        );
+        // A top-level change filter opts in.
+        assert_eq!(
+            world
+                .query_filtered::<&A, Changed<Disabled>>()
+                .iter(&world)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn systems_skip_disabled_entities() {
+        #[derive(Resource, Default)]
+        struct Counts(Vec<(usize, usize)>);
+
+        fn count(
+            enabled: Query<&A>,
+            all: Query<&A, Option<&Disabled>>,
+            mut counts: ResMut<Counts>,
+        ) {
+            counts.0.push((enabled.iter().count(), all.iter().count()));
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let entity = world.spawn(A).id();
+        world.spawn((A, Disabled));
+        let mut stage = SystemStage::single(count);
+        stage.run(&mut world);
+
+        world.entity_mut(entity).insert(Disabled);
+        stage.run(&mut world);
+        world.entity_mut(entity).remove::<Disabled>();
+        stage.run(&mut world);
+
+        assert_eq!(world.resource::<Counts>().0, vec![(1, 2), (0, 2), (1, 2)]);
+    }
+}
@@ -1,8 +1,8 @@
-use crate::entity::Entity;
+use crate::entity::{Entities, Entity};
 use bevy_utils::{Entry, HashMap};
 use std::fmt;
 
-/// The errors that might be returned while using [`MapEntities::map_entities`].
+/// The errors that might be returned while using [`EntityMap::get`].
 #[derive(Debug)]
 pub enum MapEntitiesError {
     EntityNotFound(Entity),
@@ -24,7 +24,8 @@ impl fmt::Display for MapEntitiesError {
 ///
 /// As entity IDs are valid only for the [`World`] they're sourced from, using [`Entity`]
 /// as references in components copied from another world will be invalid. This trait
-/// allows defining custom mappings for these references via [`EntityMap`].
+/// allows defining custom mappings for these references via [`EntityMapper`], which reserves
+/// an entity for each reference that isn't mapped yet.
 ///
 /// Implementing this trait correctly is required for properly loading components
 /// with entity references from scenes.
@@ -33,7 +34,7 @@ impl fmt::Display for MapEntitiesError {
 ///
 /// ```rust
 /// use bevy_ecs::prelude::*;
-/// use bevy_ecs::entity::{EntityMap, MapEntities, MapEntitiesError};
+/// use bevy_ecs::entity::{EntityMapper, MapEntities};
 ///
 /// #[derive(Component)]
 /// struct Spring {
@@ -42,21 +43,20 @@ impl fmt::Display for MapEntitiesError {
 /// }
 ///
 /// impl MapEntities for Spring {
-///     fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
-///         self.a = entity_map.get(self.a)?;
-///         self.b = entity_map.get(self.b)?;
-///         Ok(())
+///     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+///         self.a = entity_mapper.get_or_reserve(self.a);
+///         self.b = entity_mapper.get_or_reserve(self.b);
 ///     }
 /// }
 /// ```
 ///
 /// [`World`]: crate::world::World
 pub trait MapEntities {
-    /// Updates all [`Entity`] references stored inside using `entity_map`.
+    /// Updates all [`Entity`] references stored inside using `entity_mapper`.
     ///
     /// Implementors should look up any and all [`Entity`] values stored within and
-    /// update them to the mapped values via `entity_map`.
-    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError>;
+    /// update them to the mapped values via `entity_mapper`.
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper);
 }
 
 /// A mapping from one set of entities to another.
@@ -118,3 +118,132 @@ impl EntityMap {
         self.map.is_empty()
     }
 }
+
+/// Maps the entities of another world to entities of this one, reserving an entity for each
+/// entity that hasn't been mapped yet.
+///
+/// Unlike [`EntityMap::get`], mapping an entity never fails, and always gives the same result
+/// for the same entity. This makes it possible to map references to entities that haven't been
+/// received yet, like a network message referring to an entity whose spawn message is late: the
+/// reserved entity is the one that gets the components of the remote entity once they arrive.
+///
+/// Reserved entities are spawned without components the next time the [`World`] spawns an
+/// entity, like the entities reserved by [`Commands::spawn_empty`], and
+/// [`World::get_or_spawn`] can be used to access them right away. Mapping also takes generations into
+/// account: a remote entity reusing the index of a despawned one is mapped to a new entity.
+///
+/// [`MapEntities`] implementations receive the mapper, so references are reserved while they are
+/// mapped. Scenes use it when they are written to a world, and systems can get one from
+/// [`Commands::entity_mapper`].
+///
+/// ```
+/// # use bevy_ecs::{entity::{EntityMap, EntityMapper, MapEntities}, prelude::*};
+/// #[derive(Component)]
+/// struct Target(Entity);
+///
+/// impl MapEntities for Target {
+///     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+///         self.0 = entity_mapper.get_or_reserve(self.0);
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let mut map = EntityMap::default();
+/// let (remote_missile, remote_target) = (Entity::from_raw(3), Entity::from_raw(7));
+///
+/// // The missile arrives first, targeting an entity that hasn't been received yet.
+/// let mut mapper = EntityMapper::new(&mut map, world.entities());
+/// let missile = mapper.get_or_reserve(remote_missile);
+/// let mut target = Target(remote_target);
+/// target.map_entities(&mut mapper);
+/// world.get_or_spawn(missile).unwrap().insert(target);
+///
+/// // Later messages about the target are applied to the same entity.
+/// let mut mapper = EntityMapper::new(&mut map, world.entities());
+/// let target = world.get::<Target>(missile).unwrap().0;
+/// assert_eq!(mapper.get_or_reserve(remote_target), target);
+/// ```
+///
+/// [`World`]: crate::world::World
+/// [`World::get_or_spawn`]: crate::world::World::get_or_spawn
+/// [`Commands::spawn_empty`]: crate::system::Commands::spawn_empty
+/// [`Commands::entity_mapper`]: crate::system::Commands::entity_mapper
+pub struct EntityMapper<'m> {
+    map: &'m mut EntityMap,
+    entities: &'m Entities,
+}
+
+impl<'m> EntityMapper<'m> {
+    /// Creates a mapper that adds the entities it reserves in `entities` to `map`.
+    pub fn new(map: &'m mut EntityMap, entities: &'m Entities) -> Self {
+        Self { map, entities }
+    }
+
+    /// Returns the entity `entity` is mapped to, reserving a new entity if it isn't mapped yet.
+    pub fn get_or_reserve(&mut self, entity: Entity) -> Entity {
+        *self
+            .map
+            .entry(entity)
+            .or_insert_with(|| self.entities.reserve_entity())
+    }
+
+    /// Returns the entity `entity` is mapped to, without reserving a new entity if it isn't
+    /// mapped yet.
+    pub fn get(&self, entity: Entity) -> Option<Entity> {
+        self.map.get(entity).ok()
+    }
+
+    /// Returns the underlying [`EntityMap`].
+    pub fn map(&self) -> &EntityMap {
+        self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::World;
+
+    #[test]
+    fn entity_mapper_is_consistent() {
+        let mut world = World::new();
+        let local = world.spawn_empty().id();
+        let mut map = EntityMap::default();
+        let remote = Entity::from_raw(10);
+        map.insert(remote, local);
+
+        let mut mapper = EntityMapper::new(&mut map, world.entities());
+        assert_eq!(mapper.get_or_reserve(remote), local);
+        let a = mapper.get_or_reserve(Entity::from_raw(0));
+        let b = mapper.get_or_reserve(Entity::from_bits(1 << 32));
+        assert_ne!(a, b);
+        assert_eq!(mapper.get_or_reserve(Entity::from_raw(0)), a);
+        assert_eq!(mapper.map().len(), 3);
+
+        world.flush();
+        assert!(world.get_entity(a).is_some());
+        assert!(world.get_entity(b).is_some());
+    }
+
+    #[test]
+    fn entity_mapper_maps_entities() {
+        struct Link(Entity);
+
+        impl MapEntities for Link {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                self.0 = entity_mapper.get_or_reserve(self.0);
+            }
+        }
+
+        let world = World::new();
+        let mut map = EntityMap::default();
+        let remote = Entity::from_raw(5);
+        let mut link = Link(remote);
+
+        let mut mapper = EntityMapper::new(&mut map, world.entities());
+        assert_eq!(mapper.get(remote), None);
+        link.map_entities(&mut mapper);
+        assert_eq!(mapper.get(remote), Some(link.0));
+        assert_eq!(mapper.get_or_reserve(remote), link.0);
+    }
+}
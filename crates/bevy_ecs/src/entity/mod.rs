@@ -30,13 +30,15 @@
 //! [`World::despawn`]: crate::world::World::despawn
 //! [`EntityMut::insert`]: crate::world::EntityMut::insert
 //! [`EntityMut::remove`]: crate::world::EntityMut::remove
+mod disabled;
 mod map_entities;
 
+pub use disabled::Disabled;
 pub use map_entities::*;
 
 use crate::{archetype::ArchetypeId, storage::SparseSetIndex};
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt, mem, sync::atomic::Ordering};
+use std::{convert::TryFrom, fmt, mem, ops::Range, sync::atomic::Ordering};
 
 #[cfg(target_has_atomic = "64")]
 use std::sync::atomic::AtomicI64 as AtomicIdCursor;
@@ -213,6 +215,9 @@ impl Entity {
     /// Returns the generation of this Entity's index. The generation is incremented each time an
     /// entity with a given index is despawned. This serves as a "count" of the number of times a
     /// given index has been reused (index, generation) pairs uniquely identify a given Entity.
+    ///
+    /// The generation wraps around to 0 after [`u32::MAX`], so an `Entity` kept around while its
+    /// index is reused 2^32 times can alias a newer entity.
     #[inline]
     pub const fn generation(self) -> u32 {
         self.generation
@@ -326,6 +331,58 @@ pub struct Entities {
     free_cursor: AtomicIdCursor,
     /// Stores the number of free entities for [`len`](Entities::len)
     len: u32,
+    /// The ranges of indices set aside by [`reserve_namespace`](Entities::reserve_namespace).
+    /// Their free indices are tracked here instead of in `pending`.
+    namespaces: Vec<ReservedNamespace>,
+}
+
+#[derive(Debug)]
+struct ReservedNamespace {
+    range: Range<u32>,
+    /// The free indices of the namespace, with the next one to allocate at the end.
+    free: Vec<u32>,
+}
+
+/// A range of entity indices set aside from the regular allocation of a [`World`].
+///
+/// Entities are only spawned with the indices of a namespace through
+/// [`World::spawn_in`](crate::world::World::spawn_in), or when their id is chosen with
+/// [`World::get_or_spawn`](crate::world::World::get_or_spawn). Despawned entities give their
+/// index back to the namespace, with an incremented generation.
+///
+/// This lets several worlds agree on who allocates which entity ids, for example a server and
+/// its clients reserving a disjoint range per client: entities spawned in a range can then be
+/// sent to the other peers, and spawned there with the same [`Entity`] values.
+///
+/// A namespace is only valid for the [`World`] it was reserved in, and is released when the
+/// entities of the [`World`] are cleared.
+///
+/// [`World`]: crate::world::World
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntityNamespace(usize);
+
+/// An error that occurs when reserving an [`EntityNamespace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReserveNamespaceError {
+    /// The entity uses an index of the requested range.
+    IndexInUse(Entity),
+    /// The requested range overlaps with the range of another namespace.
+    Overlapping(Range<u32>),
+}
+
+impl std::error::Error for ReserveNamespaceError {}
+
+impl fmt::Display for ReserveNamespaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReserveNamespaceError::IndexInUse(entity) => {
+                write!(f, "the entity index {} is already in use", entity.index())
+            }
+            ReserveNamespaceError::Overlapping(range) => {
+                write!(f, "the range overlaps with the namespace {:?}", range)
+            }
+        }
+    }
 }
 
 impl Entities {
@@ -335,6 +392,7 @@ impl Entities {
             pending: Vec::new(),
             free_cursor: AtomicIdCursor::new(0),
             len: 0,
+            namespaces: Vec::new(),
         }
     }
 
@@ -438,6 +496,98 @@ impl Entities {
         }
     }
 
+    /// Sets aside the indices of `range`, so that they are only allocated with
+    /// [`alloc_in`](Entities::alloc_in) or when allocating a specific entity ID.
+    ///
+    /// Fails if an index of `range` is in use, or belongs to another namespace.
+    pub fn reserve_namespace(
+        &mut self,
+        range: Range<u32>,
+    ) -> Result<EntityNamespace, ReserveNamespaceError> {
+        self.verify_flushed();
+
+        if let Some(namespace) = self.namespaces.iter().find(|namespace| {
+            namespace.range.start < range.end && range.start < namespace.range.end
+        }) {
+            return Err(ReserveNamespaceError::Overlapping(namespace.range.clone()));
+        }
+
+        let existing =
+            range.start.min(self.meta.len() as u32)..range.end.min(self.meta.len() as u32);
+        let free_existing = self
+            .pending
+            .iter()
+            .filter(|index| existing.contains(index))
+            .count();
+        if free_existing != existing.len() {
+            let index = existing
+                .clone()
+                .find(|index| !self.pending.contains(index))
+                .unwrap();
+            return Err(ReserveNamespaceError::IndexInUse(Entity {
+                generation: self.meta[index as usize].generation,
+                index,
+            }));
+        }
+
+        self.pending.retain(|index| !existing.contains(index));
+        if range.end as usize > self.meta.len() {
+            self.pending.extend((self.meta.len() as u32)..range.start);
+            self.meta.resize(range.end as usize, EntityMeta::EMPTY);
+        }
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+
+        self.namespaces.push(ReservedNamespace {
+            free: range.clone().rev().collect(),
+            range,
+        });
+        Ok(EntityNamespace(self.namespaces.len() - 1))
+    }
+
+    /// Returns the range of indices of `namespace`.
+    pub fn namespace_range(&self, namespace: EntityNamespace) -> Option<Range<u32>> {
+        self.namespaces
+            .get(namespace.0)
+            .map(|namespace| namespace.range.clone())
+    }
+
+    /// Allocate an entity ID in `namespace`, with the lowest free index.
+    ///
+    /// Returns [`None`] if all of the indices of `namespace` are in use.
+    pub fn alloc_in(&mut self, namespace: EntityNamespace) -> Option<Entity> {
+        self.verify_flushed();
+        let index = self.namespaces.get_mut(namespace.0)?.free.pop()?;
+        self.len += 1;
+        Some(Entity {
+            generation: self.meta[index as usize].generation,
+            index,
+        })
+    }
+
+    /// Removes `index` from the free indices, returning `true` if it was free.
+    fn take_free_index(&mut self, index: u32) -> bool {
+        if let Some(namespace) = self
+            .namespaces
+            .iter_mut()
+            .find(|namespace| namespace.range.contains(&index))
+        {
+            match namespace.free.iter().position(|item| *item == index) {
+                Some(position) => {
+                    namespace.free.remove(position);
+                    true
+                }
+                None => false,
+            }
+        } else if let Some(position) = self.pending.iter().position(|item| *item == index) {
+            self.pending.swap_remove(position);
+            let new_free_cursor = self.pending.len() as IdCursor;
+            *self.free_cursor.get_mut() = new_free_cursor;
+            true
+        } else {
+            false
+        }
+    }
+
     /// Allocate a specific entity ID, overwriting its generation.
     ///
     /// Returns the location of the entity currently using the given ID, if any. Location should be
@@ -453,10 +603,7 @@ impl Entities {
                 .resize(entity.index as usize + 1, EntityMeta::EMPTY);
             self.len += 1;
             None
-        } else if let Some(index) = self.pending.iter().position(|item| *item == entity.index) {
-            self.pending.swap_remove(index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+        } else if self.take_free_index(entity.index) {
             self.len += 1;
             None
         } else {
@@ -488,10 +635,7 @@ impl Entities {
                 .resize(entity.index as usize + 1, EntityMeta::EMPTY);
             self.len += 1;
             AllocAtWithoutReplacement::DidNotExist
-        } else if let Some(index) = self.pending.iter().position(|item| *item == entity.index) {
-            self.pending.swap_remove(index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+        } else if self.take_free_index(entity.index) {
             self.len += 1;
             AllocAtWithoutReplacement::DidNotExist
         } else {
@@ -519,14 +663,21 @@ impl Entities {
         if meta.generation != entity.generation {
             return None;
         }
-        meta.generation += 1;
+        meta.generation = meta.generation.wrapping_add(1);
 
         let loc = mem::replace(&mut meta.location, EntityMeta::EMPTY.location);
 
-        self.pending.push(entity.index);
-
-        let new_free_cursor = self.pending.len() as IdCursor;
-        *self.free_cursor.get_mut() = new_free_cursor;
+        if let Some(namespace) = self
+            .namespaces
+            .iter_mut()
+            .find(|namespace| namespace.range.contains(&entity.index))
+        {
+            namespace.free.push(entity.index);
+        } else {
+            self.pending.push(entity.index);
+            let new_free_cursor = self.pending.len() as IdCursor;
+            *self.free_cursor.get_mut() = new_free_cursor;
+        }
         self.len -= 1;
         Some(loc)
     }
@@ -558,6 +709,7 @@ impl Entities {
         self.pending.clear();
         *self.free_cursor.get_mut() = 0;
         self.len = 0;
+        self.namespaces.clear();
     }
 
     /// Returns `Ok(Location { archetype: Archetype::invalid(), index: undefined })` for pending entities.
@@ -799,4 +951,87 @@ mod tests {
         const C4: u32 = Entity::from_bits(0x00dd_00ff_0000_0000).generation();
         assert_eq!(0x00dd_00ff, C4);
     }
+
+    #[test]
+    fn generation_wraps_around() {
+        let mut entities = Entities::new();
+        let old = Entity::new(0, u32::MAX);
+        assert!(entities.alloc_at(old).is_none());
+        assert!(entities.free(old).is_some());
+        assert!(!entities.contains(old));
+
+        let new = entities.alloc();
+        assert_eq!(new, Entity::new(0, 0));
+        assert!(entities.contains(new));
+        assert!(entities.free(old).is_none());
+        assert_eq!(Entity::from_bits(old.to_bits()), old);
+    }
+
+    #[test]
+    fn namespaces_are_not_allocated_from() {
+        let mut entities = Entities::new();
+        let first = entities.alloc();
+        let namespace = entities.reserve_namespace(4..8).unwrap();
+        assert_eq!(entities.namespace_range(namespace), Some(4..8));
+
+        let regular: Vec<_> = (0..8).map(|_| entities.alloc().index()).collect();
+        assert_eq!(regular, vec![3, 2, 1, 8, 9, 10, 11, 12]);
+        assert_eq!(entities.reserve_entity().index(), 13);
+        entities.flush_as_invalid();
+
+        let in_namespace = entities.alloc_in(namespace).unwrap();
+        assert_eq!(in_namespace, Entity::new(4, 0));
+        entities.free(in_namespace);
+        entities.free(first);
+        let reused = entities.alloc_in(namespace).unwrap();
+        assert_eq!(reused, Entity::new(4, 1));
+        assert_eq!(entities.alloc(), Entity::new(0, 1));
+
+        for index in 5..8 {
+            assert_eq!(entities.alloc_in(namespace).unwrap().index(), index);
+        }
+        assert!(entities.alloc_in(namespace).is_none());
+        assert_eq!(entities.len(), 14);
+    }
+
+    #[test]
+    fn reserve_namespace_errors() {
+        let mut entities = Entities::new();
+        let first = entities.alloc();
+        let second = entities.alloc();
+        entities.free(first);
+        entities.reserve_namespace(0..1).unwrap();
+        assert_eq!(
+            entities.reserve_namespace(0..4),
+            Err(ReserveNamespaceError::Overlapping(0..1))
+        );
+        assert_eq!(
+            entities.reserve_namespace(1..4),
+            Err(ReserveNamespaceError::IndexInUse(second))
+        );
+        entities.reserve_namespace(2..4).unwrap();
+    }
+
+    #[test]
+    fn spawn_in_namespace() {
+        use crate::world::World;
+
+        let mut world = World::new();
+        let namespace = world.reserve_entity_namespace(100..102).unwrap();
+        let a = world.spawn_in(namespace, ()).unwrap().id();
+        assert_eq!(a, Entity::new(100, 0));
+
+        // Entities spawned in the namespace of another world get the same ids.
+        let mut other = World::new();
+        let namespace = other.reserve_entity_namespace(100..102).unwrap();
+        assert!(other.get_or_spawn(a).is_some());
+        assert!(other.get_entity(a).is_some());
+        assert_eq!(other.spawn_in(namespace, ()).unwrap().id().index(), 101);
+        assert!(other.spawn_in(namespace, ()).is_none());
+
+        other.despawn(a);
+        let b = other.spawn_in(namespace, ()).unwrap().id();
+        assert_eq!(b, Entity::new(100, 1));
+        assert!(other.get_entity(a).is_none());
+    }
 }
@@ -1,9 +1,16 @@
 //! Event handling types.
 
 use crate as bevy_ecs;
+use crate::change_detection::DetectChangesMut;
 use crate::system::{Local, Res, ResMut, Resource, SystemParam};
-use bevy_utils::tracing::trace;
+use crate::world::{FromWorld, World};
+use bevy_utils::tracing::{trace, warn};
+use std::collections::VecDeque;
 use std::ops::{Deref, DerefMut};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 use std::{fmt, hash::Hash, marker::PhantomData};
 
 /// A type that can be stored in an [`Events<E>`] resource
@@ -53,8 +60,8 @@ struct EventInstance<E: Event> {
     pub event: E,
 }
 
-/// An event collection that represents the events that occurred within the last two
-/// [`Events::update`] calls.
+/// An event collection that represents the events that occurred within the last
+/// [`Events::update`] calls, two by default.
 /// Events can be written to using an [`EventWriter`]
 /// and are typically cheaply read using an [`EventReader`].
 ///
@@ -74,7 +81,32 @@ struct EventInstance<E: Event> {
 /// Events will persist across a single frame boundary and so ordering of event producers and
 /// consumers is not critical (although poorly-planned ordering may cause accumulating lag).
 /// If events are not handled by the end of the frame after they are updated, they will be
-/// dropped silently.
+/// dropped, and the readers that missed them report it according to the [`EventLossPolicy`].
+///
+/// How long events are kept can be changed for each event type with
+/// [`Events::set_retention`], for example for readers that don't run every frame:
+///
+/// ```
+/// use bevy_ecs::event::{EventRetention, Events};
+///
+/// struct Jump;
+///
+/// let mut events = Events::<Jump>::default();
+/// events.set_retention(EventRetention::UntilRead);
+/// let mut reader = events.register_reader();
+///
+/// events.send(Jump);
+/// // Many frames later, the event is still there, since the reader hasn't read it yet.
+/// for _ in 0..10 {
+///     events.update();
+/// }
+/// assert_eq!(reader.iter(&events).count(), 1);
+///
+/// // Now that all registered readers read it, the event is dropped after two updates.
+/// events.update();
+/// events.update();
+/// assert!(events.is_empty());
+/// ```
 ///
 /// # Example
 /// ```
@@ -105,15 +137,14 @@ struct EventInstance<E: Event> {
 ///
 /// # Details
 ///
-/// [`Events`] is implemented using a variation of a double buffer strategy.
-/// Each call to [`update`](Events::update) swaps buffers and clears out the oldest one.
-/// - [`EventReader`]s will read events from both buffers.
+/// [`Events`] keeps the events in a single queue, and each call to [`update`](Events::update)
+/// drops the oldest events according to the [`EventRetention`]. With the default retention:
 /// - [`EventReader`]s that read at least once per update will never drop events.
 /// - [`EventReader`]s that read once within two updates might still receive some events
 /// - [`EventReader`]s that read after two updates are guaranteed to drop all events that occurred
 /// before those updates.
 ///
-/// The buffers in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
+/// The queue in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
 ///
 /// An alternative call pattern would be to call [`update`](Events::update)
 /// manually across frames to control when events are cleared.
@@ -126,68 +157,118 @@ struct EventInstance<E: Event> {
 ///
 #[derive(Debug, Resource)]
 pub struct Events<E: Event> {
-    /// Holds the oldest still active events.
-    /// Note that a.start_event_count + a.len() should always === events_b.start_event_count.
-    events_a: EventSequence<E>,
-    /// Holds the newer events.
-    events_b: EventSequence<E>,
+    /// The events that haven't been dropped yet, oldest first.
+    events: VecDeque<EventInstance<E>>,
+    /// The id of the first event sent after each of the last updates, oldest first.
+    update_starts: VecDeque<usize>,
     event_count: usize,
+    retention: EventRetention,
+    loss_policy: EventLossPolicy,
+    /// The position of each reader registered with [`Events::register_reader`], shared with it.
+    readers: Vec<Arc<AtomicUsize>>,
 }
 
 // Derived Default impl would incorrectly require E: Default
 impl<E: Event> Default for Events<E> {
     fn default() -> Self {
         Self {
-            events_a: Default::default(),
-            events_b: Default::default(),
+            events: Default::default(),
+            // The events sent before the first update
+            update_starts: VecDeque::from([0]),
             event_count: Default::default(),
+            retention: Default::default(),
+            loss_policy: Default::default(),
+            readers: Default::default(),
         }
     }
 }
 
 impl<E: Event> Events<E> {
+    /// Returns the id of the oldest event that hasn't been dropped yet.
     pub fn oldest_event_count(&self) -> usize {
-        self.events_a
-            .start_event_count
-            .min(self.events_b.start_event_count)
+        self.event_count - self.events.len()
     }
 }
 
-#[derive(Debug)]
-struct EventSequence<E: Event> {
-    events: Vec<EventInstance<E>>,
-    start_event_count: usize,
+/// How long [`Events`] keeps events before [`Events::update`] drops them.
+///
+/// Readers that don't read events before they are dropped miss them, which they report according
+/// to the [`EventLossPolicy`] of the [`Events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventRetention {
+    /// Events are dropped by the given number of updates after they were sent, which must be at
+    /// least one.
+    ///
+    /// The default is two updates, so that events sent during a frame can be read until the end of
+    /// the next one.
+    Updates(usize),
+    /// Only the given number of most recent events are kept by each update, however old they are.
+    Count(usize),
+    /// Events are kept until every reader registered with [`Events::register_reader`] has read
+    /// them, and at least for two updates.
+    ///
+    /// [`EventReader`]s are registered when their system is initialized. A registered reader that
+    /// stops reading, for example because its system no longer runs, keeps every newer event alive
+    /// until it is dropped.
+    UntilRead,
 }
 
-// Derived Default impl would incorrectly require E: Default
-impl<E: Event> Default for EventSequence<E> {
+impl Default for EventRetention {
     fn default() -> Self {
-        Self {
-            events: Default::default(),
-            start_event_count: Default::default(),
-        }
+        EventRetention::Updates(2)
     }
 }
 
-impl<E: Event> Deref for EventSequence<E> {
-    type Target = Vec<EventInstance<E>>;
+/// What a reader does when it misses events, because they were dropped before it read them.
+///
+/// Missed events are ignored by default, since readers of systems that don't run every frame
+/// usually miss events on purpose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventLossPolicy {
+    /// Missed events are ignored.
+    #[default]
+    Ignore,
+    /// A warning is logged.
+    Warn,
+    /// The reader panics.
+    Panic,
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.events
+/// Reads events of type `T` in order and tracks which events have already been read.
+#[derive(SystemParam, Debug)]
+pub struct EventReader<'w, 's, E: Event> {
+    reader: Local<'s, RegisteredEventReader<E>>,
+    events: Res<'w, Events<E>>,
+}
+
+/// The [`ManualEventReader`] of an [`EventReader`], registered with the [`Events`] when its system
+/// is initialized, so that it counts for [`EventRetention::UntilRead`].
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct RegisteredEventReader<E: Event>(ManualEventReader<E>);
+
+impl<E: Event> FromWorld for RegisteredEventReader<E> {
+    fn from_world(world: &mut World) -> Self {
+        let reader = match world.get_resource_mut::<Events<E>>() {
+            Some(mut events) => events.bypass_change_detection().register_reader(),
+            None => ManualEventReader::default(),
+        };
+        RegisteredEventReader(reader)
     }
 }
 
-impl<E: Event> DerefMut for EventSequence<E> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.events
+impl<E: Event> Deref for RegisteredEventReader<E> {
+    type Target = ManualEventReader<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
-/// Reads events of type `T` in order and tracks which events have already been read.
-#[derive(SystemParam, Debug)]
-pub struct EventReader<'w, 's, E: Event> {
-    reader: Local<'s, ManualEventReader<E>>,
-    events: Res<'w, Events<E>>,
+impl<E: Event> DerefMut for RegisteredEventReader<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
 }
 
 impl<'w, 's, E: Event> EventReader<'w, 's, E> {
@@ -324,6 +405,11 @@ impl<'w, 's, E: Event> EventWriter<'w, 's, E> {
 #[derive(Debug)]
 pub struct ManualEventReader<E: Event> {
     last_event_count: usize,
+    /// Whether the reader has a position in the events, so that the events dropped after it
+    /// are missed. Default readers only get one when they first read.
+    positioned: bool,
+    /// The position shared with the [`Events`] this reader is registered with.
+    registration: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<E>,
 }
 
@@ -331,6 +417,8 @@ impl<E: Event> Default for ManualEventReader<E> {
     fn default() -> Self {
         ManualEventReader {
             last_event_count: 0,
+            positioned: false,
+            registration: None,
             _marker: Default::default(),
         }
     }
@@ -352,21 +440,37 @@ impl<E: Event> ManualEventReader<E> {
         events: &'a Events<E>,
     ) -> impl DoubleEndedIterator<Item = (&'a E, EventId<E>)>
            + ExactSizeIterator<Item = (&'a E, EventId<E>)> {
-        let a_index = (self.last_event_count).saturating_sub(events.events_a.start_event_count);
-        let b_index = (self.last_event_count).saturating_sub(events.events_b.start_event_count);
-        let a = events.events_a.get(a_index..).unwrap_or_default();
-        let b = events.events_b.get(b_index..).unwrap_or_default();
+        let missed = self.missed_events(events);
+        if missed > 0 && self.positioned {
+            events.report_missed_events(missed);
+        }
+        self.positioned = true;
 
-        let unread_count = a.len() + b.len();
+        let start = self
+            .last_event_count
+            .saturating_sub(events.oldest_event_count())
+            .min(events.events.len());
+        let unread = events.events.range(start..);
         // Ensure `len` is implemented correctly
-        debug_assert_eq!(unread_count, self.len(events));
-        self.last_event_count = events.event_count - unread_count;
-        // Iterate the oldest first, then the newer events
-        let iterator = a.iter().chain(b.iter());
-        iterator
+        debug_assert_eq!(unread.len(), self.len(events));
+        self.last_event_count = events.event_count - unread.len();
+
+        let ManualEventReader {
+            last_event_count,
+            registration,
+            ..
+        } = self;
+        if let Some(registration) = registration {
+            registration.store(*last_event_count, Ordering::Relaxed);
+        }
+        unread
             .map(|e| (&e.event, e.event_id))
-            .with_exact_size(unread_count)
-            .inspect(move |(_, id)| self.last_event_count = (id.id + 1).max(self.last_event_count))
+            .inspect(move |(_, id)| {
+                *last_event_count = (id.id + 1).max(*last_event_count);
+                if let Some(registration) = registration {
+                    registration.store(*last_event_count, Ordering::Relaxed);
+                }
+            })
     }
 
     /// See [`EventReader::len`]
@@ -374,7 +478,6 @@ impl<E: Event> ManualEventReader<E> {
         // The number of events in this reader is the difference between the most recent event
         // and the last event seen by it. This will be at most the number of events contained
         // with the events (any others have already been dropped)
-        // TODO: Warn when there are dropped events, or return e.g. a `Result<usize, (usize, usize)>`
         events
             .event_count
             .saturating_sub(self.last_event_count)
@@ -394,60 +497,6 @@ impl<E: Event> ManualEventReader<E> {
     }
 }
 
-trait IteratorExt {
-    fn with_exact_size(self, len: usize) -> ExactSize<Self>
-    where
-        Self: Sized,
-    {
-        ExactSize::new(self, len)
-    }
-}
-impl<I> IteratorExt for I where I: Iterator {}
-
-#[must_use = "iterators are lazy and do nothing unless consumed"]
-#[derive(Clone)]
-struct ExactSize<I> {
-    iter: I,
-    len: usize,
-}
-impl<I> ExactSize<I> {
-    fn new(iter: I, len: usize) -> Self {
-        ExactSize { iter, len }
-    }
-}
-
-impl<I: Iterator> Iterator for ExactSize<I> {
-    type Item = I::Item;
-
-    #[inline]
-    fn next(&mut self) -> Option<I::Item> {
-        self.iter.next().map(|e| {
-            self.len -= 1;
-            e
-        })
-    }
-
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
-    }
-}
-
-impl<I: DoubleEndedIterator> DoubleEndedIterator for ExactSize<I> {
-    #[inline]
-    fn next_back(&mut self) -> Option<I::Item> {
-        self.iter.next_back().map(|e| {
-            self.len -= 1;
-            e
-        })
-    }
-}
-impl<I: Iterator> ExactSizeIterator for ExactSize<I> {
-    fn len(&self) -> usize {
-        self.len
-    }
-}
-
 impl<E: Event> Events<E> {
     /// "Sends" an `event` by writing it to the current event buffer. [`EventReader`]s can then read
     /// the event.
@@ -460,7 +509,7 @@ impl<E: Event> Events<E> {
 
         let event_instance = EventInstance { event_id, event };
 
-        self.events_b.push(event_instance);
+        self.events.push_back(event_instance);
         self.event_count += 1;
     }
 
@@ -473,8 +522,15 @@ impl<E: Event> Events<E> {
     }
 
     /// Gets a new [`ManualEventReader`]. This will include all events already in the event buffers.
+    ///
+    /// The reader starts at [`Events::oldest_event_count`], so events dropped before it was
+    /// created are not reported as missed.
     pub fn get_reader(&self) -> ManualEventReader<E> {
-        ManualEventReader::default()
+        ManualEventReader {
+            last_event_count: self.oldest_event_count(),
+            positioned: true,
+            ..Default::default()
+        }
     }
 
     /// Gets a new [`ManualEventReader`]. This will ignore all events already in the event buffers.
@@ -482,20 +538,98 @@ impl<E: Event> Events<E> {
     pub fn get_reader_current(&self) -> ManualEventReader<E> {
         ManualEventReader {
             last_event_count: self.event_count,
+            positioned: true,
             ..Default::default()
         }
     }
 
-    /// Swaps the event buffers and clears the oldest event buffer. In general, this should be
-    /// called once per frame/update.
-    pub fn update(&mut self) {
-        std::mem::swap(&mut self.events_a, &mut self.events_b);
-        self.events_b.clear();
-        self.events_b.start_event_count = self.event_count;
-        debug_assert_eq!(
-            self.events_a.start_event_count + self.events_a.len(),
-            self.events_b.start_event_count
+    /// Gets a new [`ManualEventReader`] registered with these events, which will include all
+    /// events already in the event buffers.
+    ///
+    /// With [`EventRetention::UntilRead`], events are kept until every registered reader has read
+    /// them. The reader is unregistered when it is dropped.
+    pub fn register_reader(&mut self) -> ManualEventReader<E> {
+        let registration = Arc::new(AtomicUsize::new(self.oldest_event_count()));
+        self.readers.push(registration.clone());
+        ManualEventReader {
+            registration: Some(registration),
+            ..self.get_reader()
+        }
+    }
+
+    /// Returns how long events are kept.
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Sets how long events are kept, from the next [`Events::update`] on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `retention` is [`EventRetention::Updates`] with less than one update.
+    pub fn set_retention(&mut self, retention: EventRetention) {
+        assert!(
+            !matches!(retention, EventRetention::Updates(0)),
+            "events must be kept for at least one update"
         );
+        self.retention = retention;
+    }
+
+    /// Returns what readers do when they miss events.
+    pub fn loss_policy(&self) -> EventLossPolicy {
+        self.loss_policy
+    }
+
+    /// Sets what readers do when they miss events.
+    pub fn set_loss_policy(&mut self, loss_policy: EventLossPolicy) {
+        self.loss_policy = loss_policy;
+    }
+
+    fn report_missed_events(&self, missed: usize) {
+        let message = || {
+            format!(
+                "A reader missed {} events of type {}, which were dropped before it read them. \
+                Consider changing their `EventRetention`.",
+                missed,
+                std::any::type_name::<E>()
+            )
+        };
+        match self.loss_policy {
+            EventLossPolicy::Ignore => {}
+            EventLossPolicy::Warn => warn!("{}", message()),
+            EventLossPolicy::Panic => panic!("{}", message()),
+        }
+    }
+
+    /// Drops the events that are too old to be kept according to the [`EventRetention`]. In
+    /// general, this should be called once per frame/update.
+    pub fn update(&mut self) {
+        let kept_updates = match self.retention {
+            EventRetention::Updates(updates) => updates,
+            EventRetention::Count(_) => 1,
+            EventRetention::UntilRead => 2,
+        };
+        self.update_starts.push_back(self.event_count);
+        while self.update_starts.len() > kept_updates {
+            self.update_starts.pop_front();
+        }
+        // Readers are unregistered once only `Events` holds their position.
+        self.readers
+            .retain(|registration| Arc::strong_count(registration) > 1);
+
+        let oldest_kept = match self.retention {
+            EventRetention::Updates(_) => self.update_starts[0],
+            EventRetention::Count(count) => self.event_count.saturating_sub(count),
+            EventRetention::UntilRead => self
+                .readers
+                .iter()
+                .map(|registration| registration.load(Ordering::Relaxed))
+                .fold(self.update_starts[0], usize::min),
+        };
+        let dropped = oldest_kept
+            .saturating_sub(self.oldest_event_count())
+            .min(self.events.len());
+        self.events.drain(..dropped);
     }
 
     /// A system that calls [`Events::update`] once per frame.
@@ -503,23 +637,15 @@ impl<E: Event> Events<E> {
         events.update();
     }
 
-    #[inline]
-    fn reset_start_event_count(&mut self) {
-        self.events_a.start_event_count = self.event_count;
-        self.events_b.start_event_count = self.event_count;
-    }
-
     /// Removes all events.
     #[inline]
     pub fn clear(&mut self) {
-        self.reset_start_event_count();
-        self.events_a.clear();
-        self.events_b.clear();
+        self.events.clear();
     }
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.events_a.len() + self.events_b.len()
+        self.events.len()
     }
 
     /// Returns true if there are no events in this collection.
@@ -530,13 +656,8 @@ impl<E: Event> Events<E> {
 
     /// Creates a draining iterator that removes all events.
     pub fn drain(&mut self) -> impl Iterator<Item = E> + '_ {
-        self.reset_start_event_count();
-
-        // Drain the oldest events first, then the newest
-        self.events_a
-            .drain(..)
-            .chain(self.events_b.drain(..))
-            .map(|i| i.event)
+        // Drain the oldest events first
+        self.events.drain(..).map(|i| i.event)
     }
 
     /// Iterates over events that happened since the last "update" call.
@@ -548,7 +669,10 @@ impl<E: Event> Events<E> {
     pub fn iter_current_update_events(
         &self,
     ) -> impl DoubleEndedIterator<Item = &E> + ExactSizeIterator<Item = &E> {
-        self.events_b.iter().map(|i| &i.event)
+        let start = self.update_starts[self.update_starts.len() - 1]
+            .saturating_sub(self.oldest_event_count())
+            .min(self.events.len());
+        self.events.range(start..).map(|i| &i.event)
     }
 }
 
@@ -567,7 +691,7 @@ impl<E: Event> std::iter::Extend<E> for Events<E> {
             EventInstance { event_id, event }
         });
 
-        self.events_b.extend(events);
+        self.events.extend(events);
 
         trace!(
             "Events::extend() -> ids: ({}..{})",
@@ -861,4 +985,140 @@ mod tests {
         }
         read_for::<EmptyTestEvent>();
     }
+
+    #[test]
+    fn test_retention_updates() {
+        let mut events = Events::<TestEvent>::default();
+        events.set_retention(EventRetention::Updates(3));
+        let mut reader = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.send(TestEvent { i: 1 });
+        assert_eq!(events.iter_current_update_events().count(), 1);
+        events.update();
+        assert_eq!(events.iter_current_update_events().count(), 0);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+
+        events.update();
+        assert_eq!(events.len(), 1);
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_retention_count() {
+        let mut events = Events::<TestEvent>::default();
+        events.set_retention(EventRetention::Count(2));
+        let mut reader = events.get_reader();
+
+        events.extend((0..3).map(|i| TestEvent { i }));
+        // Events are only dropped by updates.
+        assert_eq!(events.len(), 3);
+        for _ in 0..5 {
+            events.update();
+        }
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 1 }, TestEvent { i: 2 }]
+        );
+        assert_eq!(reader.missed_events(&events), 0);
+    }
+
+    #[test]
+    fn test_retention_until_read() {
+        let mut events = Events::<TestEvent>::default();
+        events.set_retention(EventRetention::UntilRead);
+        let mut slow_reader = events.register_reader();
+        let mut fast_reader = events.register_reader();
+
+        events.send(TestEvent { i: 0 });
+        assert_eq!(
+            get_events(&events, &mut fast_reader),
+            vec![TestEvent { i: 0 }]
+        );
+        for _ in 0..5 {
+            events.update();
+        }
+        events.send(TestEvent { i: 1 });
+        assert_eq!(
+            get_events(&events, &mut slow_reader),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+        events.update();
+        assert_eq!(events.len(), 1);
+
+        // Dropped readers are unregistered.
+        drop(fast_reader);
+        events.update();
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_retention_until_read_event_reader() {
+        let mut world = World::new();
+        let mut events = Events::<TestEvent>::default();
+        events.set_retention(EventRetention::UntilRead);
+        world.insert_resource(events);
+        let mut state = SystemState::<EventReader<TestEvent>>::new(&mut world);
+
+        world.send_event(TestEvent { i: 0 });
+        for _ in 0..5 {
+            world.resource_mut::<Events<TestEvent>>().update();
+        }
+        let mut reader = state.get(&world);
+        assert_eq!(
+            reader.iter().copied().collect::<Vec<_>>(),
+            vec![TestEvent { i: 0 }]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A reader missed 1 events")]
+    fn test_loss_policy_panic() {
+        let mut events = Events::<TestEvent>::default();
+        events.set_loss_policy(EventLossPolicy::Panic);
+        let mut reader = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.update();
+        reader.iter(&events).count();
+    }
+
+    #[test]
+    fn test_loss_policy_default_ignores() {
+        let mut events = Events::<TestEvent>::default();
+        assert_eq!(events.loss_policy(), EventLossPolicy::Ignore);
+        let mut reader = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.update();
+        assert_eq!(reader.iter(&events).count(), 0);
+    }
+
+    #[test]
+    fn test_loss_policy_new_reader() {
+        let mut events = Events::<TestEvent>::default();
+        events.set_loss_policy(EventLossPolicy::Panic);
+        events.send(TestEvent { i: 0 });
+        events.update();
+        events.update();
+
+        // Events dropped before a reader first reads aren't missed.
+        let mut reader = ManualEventReader::default();
+        events.send(TestEvent { i: 1 });
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 1 }]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_retention_zero_updates() {
+        Events::<TestEvent>::default().set_retention(EventRetention::Updates(0));
+    }
 }
@@ -216,6 +216,8 @@ pub struct FilteredAccess<T: SparseSetIndex> {
     access: Access<T>,
     with: FixedBitSet,
     without: FixedBitSet,
+    /// The accessed elements, except the ones only accessed by filters nested in [`Or`](crate::query::Or).
+    mentioned: FixedBitSet,
 }
 impl<T: SparseSetIndex + fmt::Debug> fmt::Debug for FilteredAccess<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -233,6 +235,7 @@ impl<T: SparseSetIndex> Default for FilteredAccess<T> {
             access: Access::default(),
             with: Default::default(),
             without: Default::default(),
+            mentioned: Default::default(),
         }
     }
 }
@@ -261,15 +264,22 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     /// Adds access to the element given by `index`.
     pub fn add_read(&mut self, index: T) {
         self.access.add_read(index.clone());
+        self.add_mentioned(&index);
         self.add_with(index);
     }
 
     /// Adds exclusive access to the element given by `index`.
     pub fn add_write(&mut self, index: T) {
         self.access.add_write(index.clone());
+        self.add_mentioned(&index);
         self.add_with(index);
     }
 
+    fn add_mentioned(&mut self, index: &T) {
+        self.mentioned.grow(index.sparse_set_index() + 1);
+        self.mentioned.insert(index.sparse_set_index());
+    }
+
     /// Retains only combinations where the element given by `index` is also present.
     pub fn add_with(&mut self, index: T) {
         self.with.grow(index.sparse_set_index() + 1);
@@ -282,6 +292,19 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.without.insert(index.sparse_set_index());
     }
 
+    /// Returns `true` if the element given by `index` is required to be present or absent, or is
+    /// accessed outside of the filters nested in an [`Or`](crate::query::Or).
+    pub fn mentions(&self, index: T) -> bool {
+        let index = index.sparse_set_index();
+        self.mentioned.contains(index) || self.with.contains(index) || self.without.contains(index)
+    }
+
+    /// Forgets the elements accessed since this access was `previous`, so that they are not
+    /// [mentioned](FilteredAccess::mentions) anymore. The access itself is kept.
+    pub(crate) fn forget_mentions_since(&mut self, previous: &FilteredAccess<T>) {
+        self.mentioned.clone_from(&previous.mentioned);
+    }
+
     pub fn extend_intersect_filter(&mut self, other: &FilteredAccess<T>) {
         self.without.intersect_with(&other.without);
         self.with.intersect_with(&other.with);
@@ -289,6 +312,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
 
     pub fn extend_access(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
+        self.mentioned.union_with(&other.mentioned);
     }
 
     /// Returns `true` if this and `other` can be active at the same time.
@@ -312,6 +336,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.access.extend(&access.access);
         self.with.union_with(&access.with);
         self.without.union_with(&access.without);
+        self.mentioned.union_with(&access.mentioned);
     }
 
     /// Sets the underlying unfiltered access as having access to all indexed elements.
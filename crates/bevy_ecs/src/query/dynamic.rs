@@ -0,0 +1,822 @@
+use crate::{
+    archetype::{
+        Archetype, ArchetypeComponentId, ArchetypeEntity, ArchetypeGeneration, ArchetypeId,
+    },
+    change_detection::{DetectChangesMut, MutUntyped, Ticks},
+    component::ComponentId,
+    entity::{Disabled, Entity, EntityLocation},
+    query::{Access, FilteredAccess, QueryEntityError},
+    world::{get_component, get_component_and_ticks, World, WorldId},
+};
+use bevy_ptr::{Ptr, PtrMut};
+use fixedbitset::FixedBitSet;
+use std::{any::TypeId, fmt, ptr::NonNull};
+
+/// How a [`DynamicQueryState`] accesses one of its components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicAccess {
+    /// The component is accessed immutably, as a [`Ptr`].
+    Read,
+    /// The component is accessed mutably, as a [`MutUntyped`].
+    Write,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DynamicTerm {
+    component_id: ComponentId,
+    access: DynamicAccess,
+}
+
+/// Builds a [`DynamicQueryState`] from [`ComponentId`]s known only at runtime.
+///
+/// This is the dynamic equivalent of the [`WorldQuery`](crate::query::WorldQuery) types: each
+/// call to [`read`](Self::read), [`write`](Self::write), [`optional_read`](Self::optional_read)
+/// or [`optional_write`](Self::optional_write) adds a component to the items returned by the
+/// query, in order, while [`with`](Self::with) and [`without`](Self::without) only filter the
+/// matched entities.
+///
+/// Like other queries, the built query skips [`Disabled`] entities, unless the component id of
+/// [`Disabled`] is one of its terms or filters.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, query::QueryBuilder};
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// #[derive(Component)]
+/// struct Velocity(f32);
+///
+/// let mut world = World::new();
+/// world.spawn((Position(0.0), Velocity(1.0)));
+/// world.spawn(Position(5.0));
+///
+/// let position_id = world.init_component::<Position>();
+/// let velocity_id = world.init_component::<Velocity>();
+/// let mut query = QueryBuilder::new(&world)
+///     .write(position_id)
+///     .read(velocity_id)
+///     .build();
+///
+/// for mut item in query.iter_mut(&mut world) {
+///     // SAFETY: the pointers come from the component ids of `Velocity` and `Position`.
+///     let velocity = unsafe { item.get(1).unwrap().deref::<Velocity>().0 };
+///     let position = unsafe { item.get_mut(0).unwrap().deref_mut::<Position>() };
+///     position.0 += velocity;
+/// }
+/// ```
+pub struct QueryBuilder<'w> {
+    world: &'w World,
+    terms: Vec<DynamicTerm>,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+    component_access: FilteredAccess<ComponentId>,
+}
+
+impl<'w> QueryBuilder<'w> {
+    /// Creates a new builder for a query on the given [`World`].
+    pub fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            terms: Vec::new(),
+            with: Vec::new(),
+            without: Vec::new(),
+            component_access: FilteredAccess::default(),
+        }
+    }
+
+    /// Adds the component `component_id` to the query items, which only match entities that have
+    /// it. The component is accessed immutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` does not belong to the world, or if the component is already
+    /// accessed mutably by this query.
+    pub fn read(&mut self, component_id: ComponentId) -> &mut Self {
+        self.add_term(component_id, DynamicAccess::Read, false)
+    }
+
+    /// Adds the component `component_id` to the query items, which only match entities that have
+    /// it. The component is accessed mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` does not belong to the world, or if the component is already
+    /// accessed by this query.
+    pub fn write(&mut self, component_id: ComponentId) -> &mut Self {
+        self.add_term(component_id, DynamicAccess::Write, false)
+    }
+
+    /// Adds the component `component_id` to the query items, if the entity has it. The component
+    /// is accessed immutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` does not belong to the world, or if the component is already
+    /// accessed mutably by this query.
+    pub fn optional_read(&mut self, component_id: ComponentId) -> &mut Self {
+        self.add_term(component_id, DynamicAccess::Read, true)
+    }
+
+    /// Adds the component `component_id` to the query items, if the entity has it. The component
+    /// is accessed mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` does not belong to the world, or if the component is already
+    /// accessed by this query.
+    pub fn optional_write(&mut self, component_id: ComponentId) -> &mut Self {
+        self.add_term(component_id, DynamicAccess::Write, true)
+    }
+
+    /// Only matches entities that have the component `component_id`, without accessing it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` does not belong to the world.
+    pub fn with(&mut self, component_id: ComponentId) -> &mut Self {
+        self.validate_component(component_id);
+        self.component_access.add_with(component_id);
+        self.with.push(component_id);
+        self
+    }
+
+    /// Only matches entities that don't have the component `component_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` does not belong to the world.
+    pub fn without(&mut self, component_id: ComponentId) -> &mut Self {
+        self.validate_component(component_id);
+        self.component_access.add_without(component_id);
+        self.without.push(component_id);
+        self
+    }
+
+    /// Creates the [`DynamicQueryState`] described by this builder.
+    pub fn build(&mut self) -> DynamicQueryState {
+        // If `Disabled` isn't registered yet, the query can't mention it, and it is looked up again
+        // when matching new archetypes.
+        let disabled = self.world.components().get_id(TypeId::of::<Disabled>());
+        let include_disabled =
+            matches!(disabled, Some(disabled) if self.component_access.mentions(disabled));
+        let mut state = DynamicQueryState {
+            world_id: self.world.id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+            matched_archetypes: Default::default(),
+            matched_archetype_ids: Vec::new(),
+            archetype_component_access: Default::default(),
+            component_access: self.component_access.clone(),
+            terms: self.terms.clone(),
+            with: self.with.clone(),
+            without: self.without.clone(),
+            include_disabled,
+            disabled,
+        };
+        state.update_archetypes(self.world);
+        state
+    }
+
+    fn add_term(
+        &mut self,
+        component_id: ComponentId,
+        access: DynamicAccess,
+        optional: bool,
+    ) -> &mut Self {
+        let name = self.validate_component(component_id);
+        if optional {
+            // Optional components don't filter the query, so only their access is added, as is
+            // done for `Option<T>`.
+            let mut intermediate = self.component_access.clone();
+            Self::add_access(&mut intermediate, name, component_id, access);
+            self.component_access.extend_access(&intermediate);
+        } else {
+            Self::add_access(&mut self.component_access, name, component_id, access);
+            self.with.push(component_id);
+        }
+        self.terms.push(DynamicTerm {
+            component_id,
+            access,
+        });
+        self
+    }
+
+    fn add_access(
+        component_access: &mut FilteredAccess<ComponentId>,
+        name: &str,
+        component_id: ComponentId,
+        access: DynamicAccess,
+    ) {
+        match access {
+            DynamicAccess::Read => {
+                assert!(
+                    !component_access.access().has_write(component_id),
+                    "&{} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                    name,
+                );
+                component_access.add_read(component_id);
+            }
+            DynamicAccess::Write => {
+                assert!(
+                    !component_access.access().has_read(component_id),
+                    "&mut {} conflicts with a previous access in this query. Mutable component access must be unique.",
+                    name,
+                );
+                component_access.add_write(component_id);
+            }
+        }
+    }
+
+    fn validate_component(&self, component_id: ComponentId) -> &'w str {
+        self.world
+            .components()
+            .get_info(component_id)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Component {:?} does not exist in the World this query is built for.",
+                    component_id
+                )
+            })
+            .name()
+    }
+}
+
+/// The state of a query built at runtime with a [`QueryBuilder`].
+///
+/// Like [`QueryState`](crate::query::QueryState), it caches the archetypes matched by the query
+/// and keeps track of its [`FilteredAccess`], so that its access can be checked against the access
+/// of other queries and systems. Use a [`DynamicSystem`](crate::system::DynamicSystem) to run
+/// dynamic queries in a schedule, where their access is taken into account by the executor.
+pub struct DynamicQueryState {
+    world_id: WorldId,
+    archetype_generation: ArchetypeGeneration,
+    matched_archetypes: FixedBitSet,
+    // NOTE: we maintain both a ArchetypeId bitset and a vec because iterating the vec is faster
+    matched_archetype_ids: Vec<ArchetypeId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+    component_access: FilteredAccess<ComponentId>,
+    terms: Vec<DynamicTerm>,
+    with: Vec<ComponentId>,
+    without: Vec<ComponentId>,
+    include_disabled: bool,
+    disabled: Option<ComponentId>,
+}
+
+impl fmt::Debug for DynamicQueryState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DynamicQueryState terms: {:?} matched_archetype_ids: {}",
+            self.terms,
+            self.matched_archetype_ids.len()
+        )
+    }
+}
+
+impl DynamicQueryState {
+    /// Returns the [`ComponentId`] and [`DynamicAccess`] of each component of the query items.
+    pub fn components(&self) -> impl Iterator<Item = (ComponentId, DynamicAccess)> + '_ {
+        self.terms
+            .iter()
+            .map(|term| (term.component_id, term.access))
+    }
+
+    /// Returns `true` if this query does not access any component mutably.
+    pub fn is_read_only(&self) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.access == DynamicAccess::Read)
+    }
+
+    /// Returns the component access of this query, which can be checked against the access of
+    /// other queries with [`FilteredAccess::is_compatible`].
+    pub fn component_access(&self) -> &FilteredAccess<ComponentId> {
+        &self.component_access
+    }
+
+    /// Returns the archetype component access of this query for the archetypes it has matched so
+    /// far.
+    pub fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    /// Checks if the given world is the same as the query's, and matches the archetypes created
+    /// since the last update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `world.id()` does not equal the current [`DynamicQueryState`] internal id.
+    pub fn update_archetypes(&mut self, world: &World) {
+        self.validate_world(world);
+        let archetypes = world.archetypes();
+        let new_generation = archetypes.generation();
+        let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
+        let archetype_index_range = old_generation.value()..new_generation.value();
+
+        if self.disabled.is_none() {
+            self.disabled = world.components().get_id(TypeId::of::<Disabled>());
+        }
+        for archetype_index in archetype_index_range {
+            self.new_archetype(&archetypes[ArchetypeId::new(archetype_index)]);
+        }
+    }
+
+    #[inline]
+    pub fn validate_world(&self, world: &World) {
+        assert!(
+            world.id() == self.world_id,
+            "Attempted to use {} with a mismatched World. QueryStates can only be used with the World they were created from.",
+                std::any::type_name::<Self>(),
+        );
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype) {
+        if !self.include_disabled
+            && matches!(self.disabled, Some(disabled) if archetype.contains(disabled))
+        {
+            return;
+        }
+        if !self.with.iter().all(|&id| archetype.contains(id))
+            || self.without.iter().any(|&id| archetype.contains(id))
+        {
+            return;
+        }
+        for term in &self.terms {
+            if let Some(archetype_component_id) =
+                archetype.get_archetype_component_id(term.component_id)
+            {
+                match term.access {
+                    DynamicAccess::Read => self
+                        .archetype_component_access
+                        .add_read(archetype_component_id),
+                    DynamicAccess::Write => self
+                        .archetype_component_access
+                        .add_write(archetype_component_id),
+                }
+            }
+        }
+        let archetype_index = archetype.id().index();
+        self.matched_archetypes.grow(archetype_index + 1);
+        self.matched_archetypes.set(archetype_index, true);
+        self.matched_archetype_ids.push(archetype.id());
+    }
+
+    /// Returns an [`Iterator`] over the query items of the given [`World`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query accesses a component mutably. Use [`DynamicQueryState::iter_mut`]
+    /// instead.
+    pub fn iter<'w, 's>(&'s mut self, world: &'w World) -> DynamicQueryIter<'w, 's> {
+        self.assert_read_only();
+        self.update_archetypes(world);
+        // SAFETY: the query is read-only and the world is borrowed immutably
+        unsafe {
+            DynamicQueryIter::new(
+                world,
+                self,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the query items of the given [`World`], with mutable access to
+    /// the components accessed with [`DynamicAccess::Write`].
+    pub fn iter_mut<'w, 's>(&'s mut self, world: &'w mut World) -> DynamicQueryIter<'w, 's> {
+        self.update_archetypes(world);
+        let change_tick = world.change_tick();
+        // SAFETY: the world is borrowed mutably, and the builder checked that mutable accesses
+        // are unique
+        unsafe { DynamicQueryIter::new(world, self, world.last_change_tick(), change_tick) }
+    }
+
+    /// Gets the query item for the given [`Entity`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query accesses a component mutably. Use [`DynamicQueryState::get_mut`]
+    /// instead.
+    pub fn get<'w>(
+        &mut self,
+        world: &'w World,
+        entity: Entity,
+    ) -> Result<DynamicQueryItem<'w>, QueryEntityError> {
+        self.assert_read_only();
+        self.update_archetypes(world);
+        // SAFETY: the query is read-only and the world is borrowed immutably
+        unsafe {
+            self.get_unchecked_manual(
+                world,
+                entity,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Gets the query item for the given [`Entity`], with mutable access to the components
+    /// accessed with [`DynamicAccess::Write`].
+    pub fn get_mut<'w>(
+        &mut self,
+        world: &'w mut World,
+        entity: Entity,
+    ) -> Result<DynamicQueryItem<'w>, QueryEntityError> {
+        self.update_archetypes(world);
+        let change_tick = world.change_tick();
+        // SAFETY: the world is borrowed mutably, and the builder checked that mutable accesses
+        // are unique
+        unsafe { self.get_unchecked_manual(world, entity, world.last_change_tick(), change_tick) }
+    }
+
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    pub(crate) unsafe fn get_unchecked_manual<'w>(
+        &self,
+        world: &'w World,
+        entity: Entity,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Result<DynamicQueryItem<'w>, QueryEntityError> {
+        let location = world
+            .entities()
+            .get(entity)
+            .ok_or(QueryEntityError::NoSuchEntity(entity))?;
+        if !self
+            .matched_archetypes
+            .contains(location.archetype_id.index())
+        {
+            return Err(QueryEntityError::QueryDoesNotMatch(entity));
+        }
+        Ok(self.fetch(world, entity, location, last_change_tick, change_tick))
+    }
+
+    /// # Safety
+    ///
+    /// `location` must be the location of `entity`, in an archetype matched by this query. This
+    /// does not check for mutable query correctness.
+    unsafe fn fetch<'w>(
+        &self,
+        world: &'w World,
+        entity: Entity,
+        location: EntityLocation,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> DynamicQueryItem<'w> {
+        let components = self
+            .terms
+            .iter()
+            .map(|term| match term.access {
+                DynamicAccess::Read => get_component(world, term.component_id, entity, location)
+                    .map(DynamicComponent::Read),
+                DynamicAccess::Write => {
+                    get_component_and_ticks(world, term.component_id, entity, location).map(
+                        |(value, ticks)| {
+                            DynamicComponent::Write(MutUntyped {
+                                value: value.assert_unique(),
+                                ticks: Ticks::from_tick_cells(ticks, last_change_tick, change_tick),
+                            })
+                        },
+                    )
+                }
+            })
+            .collect();
+        DynamicQueryItem { entity, components }
+    }
+
+    pub(crate) fn assert_read_only(&self) {
+        assert!(
+            self.is_read_only(),
+            "This query accesses components mutably, which requires exclusive access to the World."
+        );
+    }
+}
+
+/// A component of a [`DynamicQueryItem`].
+pub enum DynamicComponent<'w> {
+    /// A component accessed with [`DynamicAccess::Read`].
+    Read(Ptr<'w>),
+    /// A component accessed with [`DynamicAccess::Write`].
+    Write(MutUntyped<'w>),
+}
+
+impl fmt::Debug for DynamicComponent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DynamicComponent::Read(ptr) => f.debug_tuple("Read").field(&ptr.as_ptr()).finish(),
+            DynamicComponent::Write(value) => f.debug_tuple("Write").field(value).finish(),
+        }
+    }
+}
+
+/// An item returned by a [`DynamicQueryState`]: an entity and its components, in the order they
+/// were added to the [`QueryBuilder`].
+///
+/// Components added with [`QueryBuilder::optional_read`] or [`QueryBuilder::optional_write`] are
+/// `None` when the entity doesn't have them.
+#[derive(Debug)]
+pub struct DynamicQueryItem<'w> {
+    entity: Entity,
+    components: Vec<Option<DynamicComponent<'w>>>,
+}
+
+impl<'w> DynamicQueryItem<'w> {
+    /// Returns the [`Entity`] this item belongs to.
+    #[inline]
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns a pointer to the component at `index`, or `None` if it is an optional component
+    /// the entity doesn't have.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Ptr<'_>> {
+        match self.components[index].as_ref()? {
+            DynamicComponent::Read(ptr) => Some(*ptr),
+            // SAFETY: the pointer is only borrowed immutably for the lifetime of `&self`
+            DynamicComponent::Write(value) => unsafe {
+                Some(Ptr::new(NonNull::new_unchecked(value.value.as_ptr())))
+            },
+        }
+    }
+
+    /// Returns a mutable pointer to the component at `index` and marks it as changed, or returns
+    /// `None` if it is an optional component the entity doesn't have.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, or if the component is not accessed with
+    /// [`DynamicAccess::Write`].
+    pub fn get_mut(&mut self, index: usize) -> Option<PtrMut<'_>> {
+        match self.components[index].as_mut()? {
+            DynamicComponent::Read(_) => panic!(
+                "Component {} of this query item is accessed immutably and can't be mutated.",
+                index
+            ),
+            DynamicComponent::Write(value) => {
+                value.set_changed();
+                // SAFETY: the pointer is borrowed mutably for the lifetime of `&mut self`
+                unsafe { Some(PtrMut::new(NonNull::new_unchecked(value.value.as_ptr()))) }
+            }
+        }
+    }
+
+    /// Consumes the item and returns its components.
+    pub fn into_components(self) -> Vec<Option<DynamicComponent<'w>>> {
+        self.components
+    }
+}
+
+/// An [`Iterator`] over the items of a [`DynamicQueryState`].
+///
+/// This struct is created by [`DynamicQueryState::iter`] and [`DynamicQueryState::iter_mut`], or
+/// by the same methods of [`DynamicQuery`](crate::system::DynamicQuery).
+pub struct DynamicQueryIter<'w, 's> {
+    world: &'w World,
+    state: &'s DynamicQueryState,
+    archetype_ids: std::slice::Iter<'s, ArchetypeId>,
+    archetype_id: ArchetypeId,
+    entities: &'w [ArchetypeEntity],
+    index: usize,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<'w, 's> DynamicQueryIter<'w, 's> {
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    pub(crate) unsafe fn new(
+        world: &'w World,
+        state: &'s DynamicQueryState,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        Self {
+            world,
+            state,
+            archetype_ids: state.matched_archetype_ids.iter(),
+            archetype_id: ArchetypeId::EMPTY,
+            entities: &[],
+            index: 0,
+            last_change_tick,
+            change_tick,
+        }
+    }
+}
+
+impl<'w, 's> Iterator for DynamicQueryIter<'w, 's> {
+    type Item = DynamicQueryItem<'w>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(archetype_entity) = self.entities.get(self.index) {
+                let location = EntityLocation {
+                    archetype_id: self.archetype_id,
+                    index: self.index,
+                };
+                self.index += 1;
+                // SAFETY: the entity is in an archetype matched by the query, and mutable access
+                // was checked when creating the iterator
+                return Some(unsafe {
+                    self.state.fetch(
+                        self.world,
+                        archetype_entity.entity(),
+                        location,
+                        self.last_change_tick,
+                        self.change_tick,
+                    )
+                });
+            }
+            self.archetype_id = *self.archetype_ids.next()?;
+            self.entities = self.world.archetypes()[self.archetype_id].entities();
+            self.index = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Disabled,
+        query::{DynamicComponent, QueryBuilder, QueryEntityError},
+        world::World,
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(u32);
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn read_and_filter() {
+        let mut world = World::new();
+        let e1 = world.spawn((A(1), B(10))).id();
+        let e2 = world.spawn((A(2), C)).id();
+        world.spawn(B(30));
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+        let c = world.init_component::<C>();
+
+        let mut query = QueryBuilder::new(&world).read(a).build();
+        let mut values: Vec<_> = query
+            .iter(&world)
+            .map(|item| {
+                // SAFETY: `a` is the id of `A`
+                let value = unsafe { item.get(0).unwrap().deref::<A>().0 };
+                (item.entity(), value)
+            })
+            .collect();
+        values.sort();
+        assert_eq!(values, vec![(e1, 1), (e2, 2)]);
+
+        let mut query = QueryBuilder::new(&world).read(a).without(c).build();
+        let entities: Vec<_> = query.iter(&world).map(|item| item.entity()).collect();
+        assert_eq!(entities, vec![e1]);
+
+        let mut query = QueryBuilder::new(&world).with(c).read(a).build();
+        let entities: Vec<_> = query.iter(&world).map(|item| item.entity()).collect();
+        assert_eq!(entities, vec![e2]);
+
+        let mut query = QueryBuilder::new(&world).read(a).optional_read(b).build();
+        let mut values: Vec<_> = query
+            .iter(&world)
+            .map(|item| {
+                // SAFETY: `b` is the id of `B`
+                let value = item.get(1).map(|b| unsafe { b.deref::<B>().0 });
+                (item.entity(), value)
+            })
+            .collect();
+        values.sort();
+        assert_eq!(values, vec![(e1, Some(10)), (e2, None)]);
+    }
+
+    #[test]
+    fn write_marks_changed() {
+        let mut world = World::new();
+        let entity = world.spawn((A(1), B(2))).id();
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+        world.clear_trackers();
+
+        let mut query = QueryBuilder::new(&world).write(a).optional_write(b).build();
+        let mut item = query.get_mut(&mut world, entity).unwrap();
+        // SAFETY: `a` is the id of `A`
+        unsafe { item.get_mut(0).unwrap().deref_mut::<A>().0 = 5 };
+        match item.into_components().pop().unwrap().unwrap() {
+            DynamicComponent::Write(b) => assert!(!b.is_changed()),
+            DynamicComponent::Read(_) => unreachable!(),
+        }
+
+        assert_eq!(world.get::<A>(entity), Some(&A(5)));
+        assert!(world
+            .entity(entity)
+            .get_change_ticks::<A>()
+            .unwrap()
+            .is_changed(world.last_change_tick(), world.read_change_tick()));
+        assert!(!world
+            .entity(entity)
+            .get_change_ticks::<B>()
+            .unwrap()
+            .is_changed(world.last_change_tick(), world.read_change_tick()));
+    }
+
+    #[test]
+    fn matches_new_archetypes() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let mut query = QueryBuilder::new(&world).read(a).build();
+        assert_eq!(query.iter(&world).count(), 0);
+
+        let entity = world.spawn((A(1), C)).id();
+        assert_eq!(query.iter(&world).count(), 1);
+        assert!(query.get(&world, entity).is_ok());
+
+        let other = world.spawn(C).id();
+        assert_eq!(
+            query.get(&world, other).unwrap_err(),
+            QueryEntityError::QueryDoesNotMatch(other)
+        );
+        world.despawn(other);
+        assert_eq!(
+            query.get(&world, other).unwrap_err(),
+            QueryEntityError::NoSuchEntity(other)
+        );
+    }
+
+    #[test]
+    fn skips_disabled_entities() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        // `Disabled` is registered after the query is built.
+        let mut query = QueryBuilder::new(&world).read(a).build();
+        world.spawn(A(1));
+        let disabled = world.spawn((A(2), Disabled)).id();
+        assert_eq!(query.iter(&world).count(), 1);
+        assert_eq!(
+            query.get(&world, disabled).unwrap_err(),
+            QueryEntityError::QueryDoesNotMatch(disabled)
+        );
+
+        let disabled_id = world.init_component::<Disabled>();
+        let mut query = QueryBuilder::new(&world)
+            .read(a)
+            .optional_read(disabled_id)
+            .build();
+        assert_eq!(query.iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn access_matches_static_queries() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        let dynamic = QueryBuilder::new(&world).write(a).optional_read(b).build();
+        let typed = world.query::<(&mut A, Option<&B>)>();
+        assert_eq!(
+            format!("{:?}", dynamic.component_access()),
+            format!("{:?}", typed.component_access)
+        );
+
+        let reader = world.query::<&A>();
+        assert!(!dynamic
+            .component_access()
+            .is_compatible(&reader.component_access));
+        let without = QueryBuilder::new(&world).write(a).without(b).build();
+        let with = world.query_filtered::<&A, bevy_ecs::query::With<B>>();
+        assert!(without
+            .component_access()
+            .is_compatible(&with.component_access));
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_access_panics() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        QueryBuilder::new(&world).read(a).write(a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_only_iter_panics_on_write() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let mut query = QueryBuilder::new(&world).write(a).build();
+        query.iter(&world);
+    }
+}
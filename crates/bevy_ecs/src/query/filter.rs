@@ -374,6 +374,9 @@ macro_rules! impl_query_filter_tuple {
                     }
                 )*
 
+                // The filters nested in `Or` only match some of the entities, so their accesses
+                // don't count as mentions, which opt in to disabled entities.
+                _intersected_access.forget_mentions_since(access);
                 *access = _intersected_access;
             }
 
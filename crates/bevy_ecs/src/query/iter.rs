@@ -39,6 +39,12 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryIter<'w, 's, Q, F> {
             cursor: QueryIterationCursor::init(world, query_state, last_change_tick, change_tick),
         }
     }
+
+    /// Returns the entity of the item returned by the most recent call to [`Iterator::next`].
+    #[inline]
+    pub(crate) fn last_entity(&self) -> Option<Entity> {
+        self.cursor.last_entity()
+    }
 }
 
 impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Iterator for QueryIter<'w, 's, Q, F> {
@@ -218,6 +224,132 @@ where
 {
 }
 
+/// An [`Iterator`] over the query items generated from a list of distinct [`Entity`]s.
+///
+/// Items are returned in the order of the list. Unlike [`QueryManyIter`], this is an [`Iterator`]
+/// for queries with mutable access too, since each entity is only visited once.
+///
+/// This struct is created by the [`Query::iter_many_unique_mut`](crate::system::Query::iter_many_unique_mut),
+/// [`Query::iter_sorted_by_key`](crate::system::Query::iter_sorted_by_key) and
+/// [`Query::iter_sorted_by_key_mut`](crate::system::Query::iter_sorted_by_key_mut) methods, and
+/// for each group of a [`GroupBy`].
+pub struct QueryManyUniqueIter<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> {
+    inner: QueryManyIter<'w, 's, Q, F, std::vec::IntoIter<Entity>>,
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryManyUniqueIter<'w, 's, Q, F> {
+    /// # Safety
+    /// `entities` must not contain duplicates.
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `query_state.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`](crate::world::WorldId) is unsound.
+    pub(crate) unsafe fn new(
+        world: &'w World,
+        query_state: &'s QueryState<Q, F>,
+        entities: Vec<Entity>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        QueryManyUniqueIter {
+            inner: QueryManyIter::new(world, query_state, entities, last_change_tick, change_tick),
+        }
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Iterator for QueryManyUniqueIter<'w, 's, Q, F> {
+    type Item = Q::Item<'w>;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: The entities are distinct, so the items can't alias.
+        unsafe { self.inner.fetch_next_aliased_unchecked() }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.entity_iter.len()))
+    }
+}
+
+// This is correct as [`QueryManyUniqueIter`] always returns `None` once exhausted.
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> FusedIterator
+    for QueryManyUniqueIter<'w, 's, Q, F>
+{
+}
+
+/// An [`Iterator`] over groups of query items sharing the same key.
+///
+/// Groups are returned in increasing order of their key, each as the key and a
+/// [`QueryManyUniqueIter`] over the items of the group. Since the groups are disjoint, the items of
+/// all of them can be held at the same time, even for queries with mutable access.
+///
+/// This struct is created by the [`Query::iter_grouped_by_key`](crate::system::Query::iter_grouped_by_key)
+/// and [`Query::iter_grouped_by_key_mut`](crate::system::Query::iter_grouped_by_key_mut) methods.
+pub struct GroupBy<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, K> {
+    world: &'w World,
+    query_state: &'s QueryState<Q, F>,
+    groups: std::vec::IntoIter<(K, Vec<Entity>)>,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, K> GroupBy<'w, 's, Q, F, K> {
+    /// # Safety
+    /// An entity must not appear twice in `groups`.
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `query_state.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`](crate::world::WorldId) is unsound.
+    pub(crate) unsafe fn new(
+        world: &'w World,
+        query_state: &'s QueryState<Q, F>,
+        groups: Vec<(K, Vec<Entity>)>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        GroupBy {
+            world,
+            query_state,
+            groups: groups.into_iter(),
+            last_change_tick,
+            change_tick,
+        }
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, K> Iterator for GroupBy<'w, 's, Q, F, K> {
+    type Item = (K, QueryManyUniqueIter<'w, 's, Q, F>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, entities) = self.groups.next()?;
+        // SAFETY: The groups are disjoint, and the other invariants are upheld by the caller of
+        // `GroupBy::new`.
+        let iter = unsafe {
+            QueryManyUniqueIter::new(
+                self.world,
+                self.query_state,
+                entities,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        };
+        Some((key, iter))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.groups.size_hint()
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, K> ExactSizeIterator
+    for GroupBy<'w, 's, Q, F, K>
+{
+}
+
+// This is correct as [`GroupBy`] always returns `None` once exhausted.
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, K> FusedIterator for GroupBy<'w, 's, Q, F, K> {}
+
 /// An iterator over `K`-sized combinations of query items without repetition.
 ///
 /// A combination is an arrangement of a collection of items where order does not matter.
@@ -559,6 +691,19 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryIterationCursor<'w, 's,
         }
     }
 
+    /// Returns the entity of the item returned by the most recent `next` call.
+    #[inline]
+    fn last_entity(&self) -> Option<Entity> {
+        let index = self.current_index.checked_sub(1)?;
+        if Self::IS_DENSE {
+            self.table_entities.get(index).copied()
+        } else {
+            self.archetype_entities
+                .get(index)
+                .map(ArchetypeEntity::entity)
+        }
+    }
+
     /// How many values will this cursor return at most?
     ///
     /// Note that if `Q::IS_ARCHETYPAL && F::IS_ARCHETYPAL`, the return value
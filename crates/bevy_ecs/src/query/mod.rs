@@ -1,10 +1,12 @@
 mod access;
+mod dynamic;
 mod fetch;
 mod filter;
 mod iter;
 mod state;
 
 pub use access::*;
+pub use dynamic::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
@@ -60,7 +62,7 @@ impl<T> DebugCheckedUnwrap for Option<T> {
 mod tests {
     use super::{ReadOnlyWorldQuery, WorldQuery};
     use crate::prelude::{AnyOf, Entity, Or, QueryState, With, Without};
-    use crate::query::{ArchetypeFilter, QueryCombinationIter};
+    use crate::query::{ArchetypeFilter, QueryCombinationIter, QueryEntityError};
     use crate::system::{IntoSystem, Query, System, SystemState};
     use crate::{self as bevy_ecs, component::Component, world::World};
     use std::any::type_name;
@@ -710,6 +712,122 @@ mod tests {
         }
     }
 
+    #[test]
+    fn iter_many_unique() {
+        let mut world = World::new();
+        let a = world.spawn(A(1)).id();
+        let b = world.spawn(A(2)).id();
+        let c = world.spawn(B(3)).id();
+
+        let mut query = world.query::<&mut A>();
+        let items: Vec<_> = query
+            .iter_many_unique_mut(&mut world, [b, c, a])
+            .unwrap()
+            .collect();
+        assert_eq!(items.len(), 2);
+        for mut item in items {
+            item.0 *= 10;
+        }
+        assert_eq!(world.get::<A>(a), Some(&A(10)));
+        assert_eq!(world.get::<A>(b), Some(&A(20)));
+
+        assert_eq!(
+            query.iter_many_unique_mut(&mut world, [a, b, a]).err(),
+            Some(QueryEntityError::AliasedMutability(a))
+        );
+    }
+
+    #[test]
+    fn iter_sorted_by_key() {
+        let mut world = World::new();
+        // Spread the entities over several archetypes and storages.
+        let e3 = world.spawn(A(3)).id();
+        let e1 = world.spawn((A(1), B(0))).id();
+        let e2a = world.spawn((A(2), Sparse(0))).id();
+        let e0 = world.spawn(A(0)).id();
+        let e2b = world.spawn((A(2), B(0))).id();
+
+        let mut query = world.query::<(Entity, &A)>();
+        let sorted: Vec<_> = query
+            .iter_sorted_by_key(&world, |(_, a)| a.0)
+            .map(|(entity, _)| entity)
+            .collect();
+        // `e2a` and `e2b` have equal keys and are returned in storage order.
+        let storage_order: Vec<_> = query
+            .iter(&world)
+            .map(|(entity, _)| entity)
+            .filter(|&entity| entity == e2a || entity == e2b)
+            .collect();
+        assert_eq!(sorted[..2], [e0, e1]);
+        assert_eq!(sorted[2..4], storage_order[..]);
+        assert_eq!(sorted[4], e3);
+
+        let mut query = world.query::<&mut A>();
+        for (i, mut a) in query
+            .iter_sorted_by_key_mut(&mut world, |a| std::cmp::Reverse(a.0))
+            .enumerate()
+        {
+            a.0 = i;
+        }
+        assert_eq!(world.get::<A>(e3), Some(&A(0)));
+        assert_eq!(world.get::<A>(e0), Some(&A(4)));
+
+        // The same methods are available on queries in systems.
+        fn system(mut query: Query<&mut A>) {
+            let keys: Vec<_> = query.iter_sorted_by_key(|a| a.0).map(|a| a.0).collect();
+            assert_eq!(keys, [0, 1, 2, 3, 4]);
+            for mut a in query.iter_sorted_by_key_mut(|a| a.0) {
+                a.0 += 1;
+            }
+        }
+        let mut system = IntoSystem::into_system(system);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        assert_eq!(world.get::<A>(e0), Some(&A(5)));
+    }
+
+    #[test]
+    fn iter_grouped_by_key() {
+        let mut world = World::new();
+        world.spawn((A(1), B(1)));
+        world.spawn((A(0), B(2)));
+        world.spawn((A(1), B(3), Sparse(0)));
+        world.spawn((A(0), B(4)));
+        world.spawn(A(1));
+
+        let mut query = world.query::<(&A, &B)>();
+        let groups: Vec<(usize, Vec<usize>)> = query
+            .iter_grouped_by_key(&world, |(a, _)| a.0)
+            .map(|(key, group)| (key, group.map(|(_, b)| b.0).collect()))
+            .collect();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, 0);
+        assert_eq!(groups[1].0, 1);
+        let mut group_0 = groups[0].1.clone();
+        group_0.sort_unstable();
+        assert_eq!(group_0, [2, 4]);
+        let mut group_1 = groups[1].1.clone();
+        group_1.sort_unstable();
+        assert_eq!(group_1, [1, 3]);
+
+        // The items of different groups can be held at the same time.
+        let mut query = world.query::<(&A, &mut B)>();
+        let mut groups = query.iter_grouped_by_key_mut(&mut world, |(a, _)| a.0);
+        let (_, group_0) = groups.next().unwrap();
+        let (_, group_1) = groups.next().unwrap();
+        assert!(groups.next().is_none());
+        for ((_, mut b0), (_, mut b1)) in group_0.zip(group_1) {
+            std::mem::swap(&mut b0.0, &mut b1.0);
+        }
+        let mut values: Vec<_> = world
+            .query::<(&A, &B)>()
+            .iter(&world)
+            .map(|(a, b)| (a.0, b.0))
+            .collect();
+        values.sort_unstable();
+        assert_eq!(values, [(0, 1), (0, 3), (1, 2), (1, 4)]);
+    }
+
     #[test]
     fn mut_to_immut_query_methods_have_immut_item() {
         #[derive(Component)]
@@ -724,6 +842,11 @@ mod tests {
         let _: Option<[&Foo; 2]> = q.iter_combinations::<2>(&world).next();
         let _: Option<&Foo> = q.iter_manual(&world).next();
         let _: Option<&Foo> = q.iter_many(&world, [e]).next();
+        let _: Option<&Foo> = q.iter_sorted_by_key(&world, |_| 0).next();
+        let _: Option<(i32, &Foo)> = q
+            .iter_grouped_by_key(&world, |_| 0)
+            .next()
+            .and_then(|(key, mut group)| Some((key, group.next()?)));
         q.for_each(&world, |_: &Foo| ());
 
         let _: Option<&Foo> = q.get(&world, e).ok();
@@ -738,6 +861,11 @@ mod tests {
         let _: Option<&Foo> = q.iter().next();
         let _: Option<[&Foo; 2]> = q.iter_combinations::<2>().next();
         let _: Option<&Foo> = q.iter_many([e]).next();
+        let _: Option<&Foo> = q.iter_sorted_by_key(|_| 0).next();
+        let _: Option<&Foo> = q
+            .iter_grouped_by_key(|_| 0)
+            .next()
+            .and_then(|(_, mut group)| group.next());
         q.for_each(|_: &Foo| ());
 
         let _: Option<&Foo> = q.get(e).ok();
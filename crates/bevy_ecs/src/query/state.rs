@@ -1,10 +1,11 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
     component::ComponentId,
-    entity::Entity,
+    entity::{Disabled, Entity},
     prelude::FromWorld,
     query::{
-        Access, DebugCheckedUnwrap, FilteredAccess, QueryCombinationIter, QueryIter, WorldQuery,
+        Access, DebugCheckedUnwrap, FilteredAccess, GroupBy, QueryCombinationIter, QueryIter,
+        QueryManyUniqueIter, WorldQuery,
     },
     storage::TableId,
     world::{World, WorldId},
@@ -12,6 +13,7 @@ use crate::{
 use bevy_tasks::ComputeTaskPool;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Instrument;
+use bevy_utils::HashSet;
 use fixedbitset::FixedBitSet;
 use std::{borrow::Borrow, fmt, mem::MaybeUninit};
 
@@ -35,6 +37,8 @@ pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    /// The id of the [`Disabled`] component, if the query doesn't match disabled entities.
+    disabled: Option<ComponentId>,
 }
 
 impl<Q: WorldQuery, F: ReadOnlyWorldQuery> std::fmt::Debug for QueryState<Q, F> {
@@ -111,6 +115,9 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        let disabled_id = world.init_component::<Disabled>();
+        let disabled = (!component_access.mentions(disabled_id)).then_some(disabled_id);
+
         let mut state = Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -122,6 +129,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
+            disabled,
         };
         state.update_archetypes(world);
         state
@@ -168,6 +176,9 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
 
     /// Creates a new [`Archetype`].
     pub fn new_archetype(&mut self, archetype: &Archetype) {
+        if matches!(self.disabled, Some(disabled) if archetype.contains(disabled)) {
+            return;
+        }
         if Q::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
         {
@@ -676,6 +687,116 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the query items generated from a list of distinct
+    /// [`Entity`]s, or a [`QueryEntityError::AliasedMutability`] error if the list contains an
+    /// entity more than once.
+    ///
+    /// Items are returned in the order of the list of entities.
+    /// Entities that don't match the query are skipped.
+    #[inline]
+    pub fn iter_many_unique_mut<'w, 's, EntityList: IntoIterator>(
+        &'s mut self,
+        world: &'w mut World,
+        entities: EntityList,
+    ) -> Result<QueryManyUniqueIter<'w, 's, Q, F>, QueryEntityError>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        self.update_archetypes(world);
+        // SAFETY: Query has unique world access.
+        unsafe {
+            self.iter_many_unique_unchecked_manual(
+                entities,
+                world,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the read-only query items, sorted by the key computed by `key`.
+    ///
+    /// Items with equal keys are returned in storage order.
+    #[inline]
+    pub fn iter_sorted_by_key<'w, 's, K: Ord>(
+        &'s mut self,
+        world: &'w World,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> QueryManyUniqueIter<'w, 's, Q::ReadOnly, F::ReadOnly> {
+        self.update_archetypes(world);
+        // SAFETY: query is read only
+        unsafe {
+            self.as_readonly().iter_sorted_by_key_unchecked_manual(
+                world,
+                key,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the query items, sorted by the key computed by `key` from
+    /// their read-only version.
+    ///
+    /// Items with equal keys are returned in storage order.
+    #[inline]
+    pub fn iter_sorted_by_key_mut<'w, 's, K: Ord>(
+        &'s mut self,
+        world: &'w mut World,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> QueryManyUniqueIter<'w, 's, Q, F> {
+        self.update_archetypes(world);
+        // SAFETY: Query has unique world access.
+        unsafe {
+            self.iter_sorted_by_key_unchecked_manual(
+                world,
+                key,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the groups of read-only query items that share the same key,
+    /// computed by `key`. See [`GroupBy`] for details.
+    #[inline]
+    pub fn iter_grouped_by_key<'w, 's, K: Ord>(
+        &'s mut self,
+        world: &'w World,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> GroupBy<'w, 's, Q::ReadOnly, F::ReadOnly, K> {
+        self.update_archetypes(world);
+        // SAFETY: query is read only
+        unsafe {
+            self.as_readonly().iter_grouped_by_key_unchecked_manual(
+                world,
+                key,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the groups of query items that share the same key, computed by
+    /// `key` from their read-only version. See [`GroupBy`] for details.
+    #[inline]
+    pub fn iter_grouped_by_key_mut<'w, 's, K: Ord>(
+        &'s mut self,
+        world: &'w mut World,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> GroupBy<'w, 's, Q, F, K> {
+        self.update_archetypes(world);
+        // SAFETY: Query has unique world access.
+        unsafe {
+            self.iter_grouped_by_key_unchecked_manual(
+                world,
+                key,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the query results for the given [`World`].
     ///
     /// # Safety
@@ -755,6 +876,127 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         QueryManyIter::new(world, self, entities, last_change_tick, change_tick)
     }
 
+    /// Returns an [`Iterator`] for the given [`World`] and list of distinct [`Entity`]'s, where
+    /// the last change and the current change tick are given.
+    ///
+    /// Returns a [`QueryEntityError::AliasedMutability`] error if the list contains an entity more
+    /// than once.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn iter_many_unique_unchecked_manual<'w, 's, EntityList: IntoIterator>(
+        &'s self,
+        entities: EntityList,
+        world: &'w World,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Result<QueryManyUniqueIter<'w, 's, Q, F>, QueryEntityError>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        let entities: Vec<Entity> = entities
+            .into_iter()
+            .map(|entity| *entity.borrow())
+            .collect();
+        let mut seen = HashSet::with_capacity(entities.len());
+        if let Some(&entity) = entities.iter().find(|&&entity| !seen.insert(entity)) {
+            return Err(QueryEntityError::AliasedMutability(entity));
+        }
+        Ok(QueryManyUniqueIter::new(
+            world,
+            self,
+            entities,
+            last_change_tick,
+            change_tick,
+        ))
+    }
+
+    /// Returns an [`Iterator`] over the query items sorted by `key`, for the given [`World`], where
+    /// the last change and the current change tick are given.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn iter_sorted_by_key_unchecked_manual<'w, 's, K: Ord>(
+        &'s self,
+        world: &'w World,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> QueryManyUniqueIter<'w, 's, Q, F> {
+        let entities = self
+            .sort_by_key_unchecked_manual(world, key, last_change_tick, change_tick)
+            .into_iter()
+            .map(|(_, entity)| entity)
+            .collect();
+        // SAFETY: The query returns each entity once.
+        QueryManyUniqueIter::new(world, self, entities, last_change_tick, change_tick)
+    }
+
+    /// Returns an [`Iterator`] over the groups of query items sharing the same key computed by
+    /// `key`, for the given [`World`], where the last change and the current change tick are given.
+    ///
+    /// # Safety
+    ///
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    pub(crate) unsafe fn iter_grouped_by_key_unchecked_manual<'w, 's, K: Ord>(
+        &'s self,
+        world: &'w World,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> GroupBy<'w, 's, Q, F, K> {
+        let mut groups: Vec<(K, Vec<Entity>)> = Vec::new();
+        for (key, entity) in
+            self.sort_by_key_unchecked_manual(world, key, last_change_tick, change_tick)
+        {
+            match groups.last_mut() {
+                Some((group_key, entities)) if *group_key == key => entities.push(entity),
+                _ => groups.push((key, vec![entity])),
+            }
+        }
+        // SAFETY: The query returns each entity once, so the groups are disjoint.
+        GroupBy::new(world, self, groups, last_change_tick, change_tick)
+    }
+
+    /// Returns the entities matching the query with their key computed by `key`, sorted by key.
+    /// Entities with equal keys keep their storage order.
+    ///
+    /// # Safety
+    ///
+    /// `world` must allow read access to the components of the query.
+    /// This does not validate that `world.id()` matches `self.world_id`. Calling this on a `world`
+    /// with a mismatched [`WorldId`] is unsound.
+    unsafe fn sort_by_key_unchecked_manual<K: Ord>(
+        &self,
+        world: &World,
+        mut key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Vec<(K, Entity)> {
+        let mut iter =
+            self.as_readonly()
+                .iter_unchecked_manual(world, last_change_tick, change_tick);
+        let mut keyed = Vec::with_capacity(iter.size_hint().0);
+        while let Some(item) = iter.next() {
+            let entity = iter.last_entity().debug_checked_unwrap();
+            keyed.push((key(&item), entity));
+        }
+        // A stable sort, to keep the storage order of equal keys.
+        keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+        keyed
+    }
+
     /// Returns an [`Iterator`] over all possible combinations of `K` query results for the
     /// given [`World`] without repetition.
     /// This can only be called for read-only queries.
@@ -0,0 +1,695 @@
+use std::fmt;
+
+use crate::{
+    entity::Entity,
+    reflect::{ReflectComponent, ReflectEntityName, ReflectResource},
+    world::{EntityRef, World},
+};
+use bevy_reflect::{GetPath, Reflect, ReflectRef, TypeRegistration, TypeRegistry};
+use serde::{Deserialize, Serialize};
+
+/// A headless view of the [`World`] built on reflection, shared by editors and remote tooling.
+///
+/// The inspector enumerates entities with their components and the resources of a world as a
+/// tree of [`EntityNode`]s, [`ComponentNode`]s and [`FieldNode`]s, and applies
+/// [`InspectorPatch`]es to the values it describes. Only the types registered in the
+/// [`TypeRegistry`] with [`ReflectComponent`] or [`ReflectResource`] can be inspected.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, reflect::{InspectorFilter, InspectorPatch, WorldInspector}};
+/// # use bevy_reflect::{Reflect, TypeRegistry};
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Health {
+///     current: u32,
+///     max: u32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Health>();
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Health { current: 5, max: 10 }).id();
+///
+/// let inspector = WorldInspector::new(&registry);
+/// let tree = inspector.entities(&world, &InspectorFilter::default());
+/// let health = &tree[0].components[0];
+/// assert_eq!(health.short_name, "Health");
+/// assert_eq!(health.value.as_ref().unwrap().children[0].path.as_deref(), Some("current"));
+///
+/// let patch = InspectorPatch::component(entity, "Health", "current", Box::new(10u32));
+/// inspector.apply_patch(&mut world, &patch).unwrap();
+/// assert_eq!(world.get::<Health>(entity).unwrap().current, 10);
+/// ```
+pub struct WorldInspector<'a> {
+    registry: &'a TypeRegistry,
+}
+
+/// A filter selecting the entities returned by [`WorldInspector::entities`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InspectorFilter {
+    name: Option<String>,
+}
+
+impl InspectorFilter {
+    /// Only keeps the entities whose name contains `name`.
+    ///
+    /// The name of an entity is read from its component registered with [`ReflectEntityName`],
+    /// like `bevy_core::Name`. Entities without a name never match.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    fn matches(&self, name: Option<&str>) -> bool {
+        match (&self.name, name) {
+            (None, _) => true,
+            (Some(filter), Some(name)) => name.contains(filter.as_str()),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// An entity and its components, as returned by [`WorldInspector::entities`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityNode {
+    pub entity: Entity,
+    /// The name of the entity, if it has a component registered with [`ReflectEntityName`].
+    pub name: Option<String>,
+    pub components: Vec<ComponentNode>,
+}
+
+/// A component of an [`EntityNode`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentNode {
+    pub type_name: String,
+    pub short_name: String,
+    /// The reflected value of the component, or `None` if it isn't registered with
+    /// [`ReflectComponent`].
+    pub value: Option<FieldNode>,
+}
+
+/// A resource, as returned by [`WorldInspector::resources`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceNode {
+    pub type_name: String,
+    pub short_name: String,
+    pub value: FieldNode,
+}
+
+/// A reflected value and its fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldNode {
+    /// The name of the field in its parent: a field name, an index, or the debug representation
+    /// of a map key. Empty for the root value of a component or resource.
+    pub name: String,
+    /// The path of the value from the root of its component or resource, as accepted by
+    /// [`GetPath`] and [`InspectorPatch`].
+    ///
+    /// This is `None` for values that can't be reached by a path, like the fields of tuples,
+    /// enums and maps.
+    pub path: Option<String>,
+    pub type_name: String,
+    pub kind: FieldKind,
+    pub children: Vec<FieldNode>,
+}
+
+/// The shape of the value described by a [`FieldNode`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldKind {
+    Struct,
+    TupleStruct,
+    Tuple,
+    List,
+    Array,
+    Map,
+    /// An enum, with the name of its current variant.
+    Enum(String),
+    /// An opaque value, with its debug representation.
+    Value(String),
+}
+
+/// The component or resource modified by an [`InspectorPatch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchTarget {
+    Component { entity: Entity, type_name: String },
+    Resource { type_name: String },
+}
+
+impl PatchTarget {
+    /// The type name of the component or resource, either the full or the short type name.
+    pub fn type_name(&self) -> &str {
+        match self {
+            PatchTarget::Component { type_name, .. } | PatchTarget::Resource { type_name } => {
+                type_name
+            }
+        }
+    }
+}
+
+/// An edit applied to a reflected value with [`WorldInspector::apply_patch`].
+#[derive(Debug)]
+pub struct InspectorPatch {
+    pub target: PatchTarget,
+    /// The path of the modified value from the root of the component or resource, as accepted
+    /// by [`GetPath`]. An empty path replaces the whole value.
+    pub path: String,
+    /// The new value, which must have the same type name as the value at `path`.
+    pub value: Box<dyn Reflect>,
+}
+
+impl InspectorPatch {
+    /// Creates a patch setting the value at `path` in the component named `type_name` of
+    /// `entity`.
+    pub fn component(
+        entity: Entity,
+        type_name: impl Into<String>,
+        path: impl Into<String>,
+        value: Box<dyn Reflect>,
+    ) -> Self {
+        Self {
+            target: PatchTarget::Component {
+                entity,
+                type_name: type_name.into(),
+            },
+            path: path.into(),
+            value,
+        }
+    }
+
+    /// Creates a patch setting the value at `path` in the resource named `type_name`.
+    pub fn resource(
+        type_name: impl Into<String>,
+        path: impl Into<String>,
+        value: Box<dyn Reflect>,
+    ) -> Self {
+        Self {
+            target: PatchTarget::Resource {
+                type_name: type_name.into(),
+            },
+            path: path.into(),
+            value,
+        }
+    }
+}
+
+/// An error that occurs when applying an [`InspectorPatch`] with [`WorldInspector::apply_patch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InspectorError {
+    /// The type isn't registered with [`ReflectComponent`] or [`ReflectResource`].
+    UnregisteredType(String),
+    /// The entity doesn't exist.
+    NoSuchEntity(Entity),
+    /// The entity doesn't have the component.
+    MissingComponent { entity: Entity, type_name: String },
+    /// The resource doesn't exist.
+    MissingResource(String),
+    /// The path doesn't lead to a value.
+    InvalidPath { path: String, error: String },
+    /// The value at the path doesn't have the type of the patch value.
+    TypeMismatch {
+        path: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl std::error::Error for InspectorError {}
+
+impl fmt::Display for InspectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InspectorError::UnregisteredType(type_name) => {
+                write!(f, "The type {type_name} is not registered for reflection")
+            }
+            InspectorError::NoSuchEntity(entity) => {
+                write!(f, "The entity {entity:?} does not exist")
+            }
+            InspectorError::MissingComponent { entity, type_name } => {
+                write!(f, "The entity {entity:?} has no component {type_name}")
+            }
+            InspectorError::MissingResource(type_name) => {
+                write!(f, "The resource {type_name} does not exist")
+            }
+            InspectorError::InvalidPath { path, error } => {
+                write!(f, "Invalid path `{path}`: {error}")
+            }
+            InspectorError::TypeMismatch {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "The value at `{path}` has type {expected}, but the patch value has type {found}"
+            ),
+        }
+    }
+}
+
+impl<'a> WorldInspector<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Returns the entities of `world` matching `filter`, ordered by [`Entity`].
+    pub fn entities(&self, world: &World, filter: &InspectorFilter) -> Vec<EntityNode> {
+        let mut entities: Vec<_> = world.iter_entities().collect();
+        entities.sort();
+        entities
+            .into_iter()
+            .filter_map(|entity| {
+                let entity_ref = world.entity(entity);
+                let name = self.name(world, &entity_ref);
+                filter
+                    .matches(name.as_deref())
+                    .then(|| self.entity_node(world, &entity_ref, name))
+            })
+            .collect()
+    }
+
+    /// Returns the components of `entity`, or `None` if it doesn't exist.
+    pub fn entity(&self, world: &World, entity: Entity) -> Option<EntityNode> {
+        let entity_ref = world.get_entity(entity)?;
+        let name = self.name(world, &entity_ref);
+        Some(self.entity_node(world, &entity_ref, name))
+    }
+
+    /// Returns the resources of `world` registered with [`ReflectResource`], ordered by type name.
+    pub fn resources(&self, world: &World) -> Vec<ResourceNode> {
+        let mut resources: Vec<_> = world
+            .storages()
+            .resources
+            .iter()
+            .filter(|(_, data)| data.is_present())
+            .filter_map(|(component_id, _)| {
+                let type_id = world.components().get_info(component_id)?.type_id()?;
+                let registration = self.registry.get(type_id)?;
+                let value = registration.data::<ReflectResource>()?.reflect(world)?;
+                Some(ResourceNode {
+                    type_name: registration.type_name().to_string(),
+                    short_name: registration.short_name().to_string(),
+                    value: field_node(String::new(), Some(String::new()), value),
+                })
+            })
+            .collect();
+        resources.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+        resources
+    }
+
+    /// Sets the value at the path of `patch` to the value of `patch`.
+    ///
+    /// The modified component or resource is only marked as changed if the patch succeeds.
+    pub fn apply_patch(
+        &self,
+        world: &mut World,
+        patch: &InspectorPatch,
+    ) -> Result<(), InspectorError> {
+        let registration = self.registration(patch.target.type_name())?;
+        let unregistered = || InspectorError::UnregisteredType(patch.target.type_name().into());
+        match &patch.target {
+            PatchTarget::Component { entity, type_name } => {
+                let reflect_component = registration
+                    .data::<ReflectComponent>()
+                    .ok_or_else(unregistered)?;
+                if world.get_entity(*entity).is_none() {
+                    return Err(InspectorError::NoSuchEntity(*entity));
+                }
+                let missing = || InspectorError::MissingComponent {
+                    entity: *entity,
+                    type_name: type_name.clone(),
+                };
+                let component = reflect_component
+                    .reflect(world, *entity)
+                    .ok_or_else(missing)?;
+                check_patch(component, patch)?;
+                let mut component = reflect_component
+                    .reflect_mut(world, *entity)
+                    .ok_or_else(missing)?;
+                apply_patch(&mut *component, patch);
+            }
+            PatchTarget::Resource { type_name } => {
+                let reflect_resource = registration
+                    .data::<ReflectResource>()
+                    .ok_or_else(unregistered)?;
+                let missing = || InspectorError::MissingResource(type_name.clone());
+                let resource = reflect_resource.reflect(world).ok_or_else(missing)?;
+                check_patch(resource, patch)?;
+                let mut resource = reflect_resource.reflect_mut(world).ok_or_else(missing)?;
+                apply_patch(&mut *resource, patch);
+            }
+        }
+        Ok(())
+    }
+
+    fn registration(&self, type_name: &str) -> Result<&'a TypeRegistration, InspectorError> {
+        self.registry
+            .get_with_name(type_name)
+            .or_else(|| self.registry.get_with_short_name(type_name))
+            .ok_or_else(|| InspectorError::UnregisteredType(type_name.to_string()))
+    }
+
+    fn name(&self, world: &World, entity: &EntityRef) -> Option<String> {
+        entity.archetype().components().find_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            let registration = self.registry.get(type_id)?;
+            let reflect_name = registration.data::<ReflectEntityName>()?;
+            let component = registration
+                .data::<ReflectComponent>()?
+                .reflect(world, entity.id())?;
+            reflect_name.name(component).map(ToString::to_string)
+        })
+    }
+
+    fn entity_node(&self, world: &World, entity: &EntityRef, name: Option<String>) -> EntityNode {
+        let components = entity
+            .archetype()
+            .components()
+            .filter_map(|component_id| {
+                let info = world.components().get_info(component_id)?;
+                let registration = info
+                    .type_id()
+                    .and_then(|type_id| self.registry.get(type_id));
+                let value = registration
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                    .and_then(|reflect_component| reflect_component.reflect(world, entity.id()));
+                Some(ComponentNode {
+                    type_name: info.name().to_string(),
+                    short_name: registration.map_or_else(
+                        || bevy_utils::get_short_name(info.name()),
+                        |registration| registration.short_name().to_string(),
+                    ),
+                    value: value.map(|value| field_node(String::new(), Some(String::new()), value)),
+                })
+            })
+            .collect();
+        EntityNode {
+            entity: entity.id(),
+            name,
+            components,
+        }
+    }
+}
+
+fn check_patch(root: &dyn Reflect, patch: &InspectorPatch) -> Result<(), InspectorError> {
+    let value = root
+        .path(&patch.path)
+        .map_err(|error| InspectorError::InvalidPath {
+            path: patch.path.clone(),
+            error: error.to_string(),
+        })?;
+    if value.type_name() != patch.value.type_name() {
+        return Err(InspectorError::TypeMismatch {
+            path: patch.path.clone(),
+            expected: value.type_name().to_string(),
+            found: patch.value.type_name().to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn apply_patch(root: &mut dyn Reflect, patch: &InspectorPatch) {
+    // The path and the type of the value were validated by `check_patch`.
+    root.path_mut(&patch.path)
+        .unwrap()
+        .apply(patch.value.as_ref());
+}
+
+fn field_node(name: String, path: Option<String>, value: &dyn Reflect) -> FieldNode {
+    let child_path = |segment: String| {
+        path.as_ref().map(|path| {
+            if path.is_empty() {
+                segment.trim_start_matches('.').to_string()
+            } else {
+                format!("{path}{segment}")
+            }
+        })
+    };
+    let (kind, children) = match value.reflect_ref() {
+        ReflectRef::Struct(value) => (
+            FieldKind::Struct,
+            (0..value.field_len())
+                .map(|index| {
+                    let name = value.name_at(index).unwrap_or_default().to_string();
+                    let path = child_path(format!(".{name}"));
+                    field_node(name, path, value.field_at(index).unwrap())
+                })
+                .collect(),
+        ),
+        ReflectRef::TupleStruct(value) => (
+            FieldKind::TupleStruct,
+            (0..value.field_len())
+                .map(|index| {
+                    let path = child_path(format!(".{index}"));
+                    field_node(index.to_string(), path, value.field(index).unwrap())
+                })
+                .collect(),
+        ),
+        ReflectRef::Tuple(value) => (
+            FieldKind::Tuple,
+            (0..value.field_len())
+                .map(|index| field_node(index.to_string(), None, value.field(index).unwrap()))
+                .collect(),
+        ),
+        ReflectRef::List(value) => (
+            FieldKind::List,
+            value
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    field_node(index.to_string(), child_path(format!("[{index}]")), item)
+                })
+                .collect(),
+        ),
+        ReflectRef::Array(value) => (
+            FieldKind::Array,
+            value
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    field_node(index.to_string(), child_path(format!("[{index}]")), item)
+                })
+                .collect(),
+        ),
+        ReflectRef::Map(value) => (
+            FieldKind::Map,
+            value
+                .iter()
+                .map(|(key, item)| field_node(format!("{key:?}"), None, item))
+                .collect(),
+        ),
+        ReflectRef::Enum(value) => (
+            FieldKind::Enum(value.variant_name().to_string()),
+            value
+                .iter_fields()
+                .enumerate()
+                .map(|(index, field)| {
+                    let name = field
+                        .name()
+                        .map_or_else(|| index.to_string(), str::to_string);
+                    field_node(name, None, field.value())
+                })
+                .collect(),
+        ),
+        ReflectRef::Value(value) => (FieldKind::Value(format!("{value:?}")), Vec::new()),
+    };
+    FieldNode {
+        name,
+        path,
+        type_name: value.type_name().to_string(),
+        kind,
+        children,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        self as bevy_ecs, change_detection::DetectChanges, component::Component, system::Resource,
+    };
+    use bevy_reflect::TypeRegistry;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component, EntityName)]
+    struct Label(String);
+
+    impl AsRef<str> for Label {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    #[derive(Component)]
+    struct Opaque;
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Settings {
+        volumes: Vec<u8>,
+        mode: Option<u32>,
+    }
+
+    fn setup() -> (World, TypeRegistry, Entity, Entity) {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Position>();
+        registry.register::<Label>();
+        registry.register::<Settings>();
+        let mut world = World::new();
+        let player = world
+            .spawn((Position { x: 1.0, y: 2.0 }, Label("player".to_string())))
+            .id();
+        let crate_entity = world.spawn((Label("crate".to_string()), Opaque)).id();
+        world.insert_resource(Settings {
+            volumes: vec![3, 4],
+            mode: Some(7),
+        });
+        (world, registry, player, crate_entity)
+    }
+
+    #[test]
+    fn entity_tree() {
+        let (world, registry, player, crate_entity) = setup();
+        let inspector = WorldInspector::new(&registry);
+
+        let tree = inspector.entities(&world, &InspectorFilter::default());
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].entity, player);
+        assert_eq!(tree[0].name.as_deref(), Some("player"));
+        assert_eq!(tree[1].entity, crate_entity);
+        assert_eq!(tree[1].name.as_deref(), Some("crate"));
+
+        let position = tree[0]
+            .components
+            .iter()
+            .find(|component| component.short_name == "Position")
+            .unwrap();
+        let value = position.value.as_ref().unwrap();
+        assert_eq!(value.kind, FieldKind::Struct);
+        assert_eq!(value.path.as_deref(), Some(""));
+        assert_eq!(value.children.len(), 2);
+        assert_eq!(value.children[1].name, "y");
+        assert_eq!(value.children[1].path.as_deref(), Some("y"));
+        assert_eq!(value.children[1].kind, FieldKind::Value("2.0".to_string()));
+
+        let opaque = tree[1]
+            .components
+            .iter()
+            .find(|component| component.short_name == "Opaque")
+            .unwrap();
+        assert!(opaque.value.is_none());
+
+        assert_eq!(inspector.entity(&world, crate_entity).unwrap(), tree[1]);
+    }
+
+    #[test]
+    fn filter_by_name() {
+        let (mut world, registry, player, _) = setup();
+        world.spawn(Position::default());
+        let inspector = WorldInspector::new(&registry);
+
+        let tree = inspector.entities(&world, &InspectorFilter::default().with_name("play"));
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].entity, player);
+        assert_eq!(
+            inspector
+                .entities(&world, &InspectorFilter::default())
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn resource_tree() {
+        let (world, registry, _, _) = setup();
+        let inspector = WorldInspector::new(&registry);
+
+        let resources = inspector.resources(&world);
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].short_name, "Settings");
+        let volumes = &resources[0].value.children[0];
+        assert_eq!(volumes.kind, FieldKind::List);
+        assert_eq!(volumes.children[1].path.as_deref(), Some("volumes[1]"));
+        let mode = &resources[0].value.children[1];
+        assert_eq!(mode.kind, FieldKind::Enum("Some".to_string()));
+        assert_eq!(mode.children[0].path, None);
+    }
+
+    #[test]
+    fn apply_patches() {
+        let (mut world, registry, player, crate_entity) = setup();
+        let inspector = WorldInspector::new(&registry);
+
+        let patch = InspectorPatch::component(player, "Position", "x", Box::new(5.0f32));
+        inspector.apply_patch(&mut world, &patch).unwrap();
+        assert_eq!(world.get::<Position>(player).unwrap().x, 5.0);
+
+        let patch = InspectorPatch::resource(
+            std::any::type_name::<Settings>(),
+            "volumes[0]",
+            Box::new(9u8),
+        );
+        inspector.apply_patch(&mut world, &patch).unwrap();
+        assert_eq!(world.resource::<Settings>().volumes, vec![9, 4]);
+
+        let patch = InspectorPatch::component(player, "Position", "x", Box::new(5u32));
+        assert_eq!(
+            inspector.apply_patch(&mut world, &patch),
+            Err(InspectorError::TypeMismatch {
+                path: "x".to_string(),
+                expected: "f32".to_string(),
+                found: "u32".to_string(),
+            })
+        );
+
+        let patch = InspectorPatch::component(player, "Position", "z", Box::new(5.0f32));
+        assert!(matches!(
+            inspector.apply_patch(&mut world, &patch),
+            Err(InspectorError::InvalidPath { .. })
+        ));
+
+        let patch = InspectorPatch::component(crate_entity, "Position", "x", Box::new(5.0f32));
+        assert_eq!(
+            inspector.apply_patch(&mut world, &patch),
+            Err(InspectorError::MissingComponent {
+                entity: crate_entity,
+                type_name: "Position".to_string(),
+            })
+        );
+
+        let patch = InspectorPatch::component(player, "Opaque", "", Box::new(5.0f32));
+        assert_eq!(
+            inspector.apply_patch(&mut world, &patch),
+            Err(InspectorError::UnregisteredType("Opaque".to_string()))
+        );
+    }
+
+    #[test]
+    fn failed_patches_do_not_trigger_change_detection() {
+        let (mut world, registry, player, _) = setup();
+        let inspector = WorldInspector::new(&registry);
+        world.clear_trackers();
+
+        let patch = InspectorPatch::component(player, "Position", "x", Box::new(5u32));
+        assert!(inspector.apply_patch(&mut world, &patch).is_err());
+        assert!(!world
+            .entity(player)
+            .get_ref::<Position>()
+            .unwrap()
+            .is_changed());
+
+        let patch = InspectorPatch::component(player, "Position", "x", Box::new(5.0f32));
+        inspector.apply_patch(&mut world, &patch).unwrap();
+        assert!(world
+            .entity(player)
+            .get_ref::<Position>()
+            .unwrap()
+            .is_changed());
+    }
+}
@@ -3,7 +3,7 @@
 use crate::{
     change_detection::Mut,
     component::Component,
-    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    entity::{Entity, EntityMap, EntityMapper, MapEntities},
     system::Resource,
     world::{FromWorld, World},
 };
@@ -12,6 +12,9 @@ use bevy_reflect::{
     ReflectSerialize,
 };
 
+mod inspector;
+pub use inspector::*;
+
 /// A struct used to operate on reflected [`Component`] of a type.
 ///
 /// A [`ReflectComponent`] for type `T` can be obtained via
@@ -404,18 +407,20 @@ impl<C: Resource + Reflect + FromWorld> FromType<C> for ReflectResource {
 impl_reflect_value!(Entity(Hash, PartialEq, Serialize, Deserialize));
 impl_from_reflect_value!(Entity);
 
+/// A struct used to map the entity references of a reflected [`Component`] with an
+/// [`EntityMapper`].
 #[derive(Clone)]
 pub struct ReflectMapEntities {
-    map_entities: fn(&mut World, &EntityMap) -> Result<(), MapEntitiesError>,
+    map_entities: fn(&mut World, &mut EntityMap),
 }
 
 impl ReflectMapEntities {
-    pub fn map_entities(
-        &self,
-        world: &mut World,
-        entity_map: &EntityMap,
-    ) -> Result<(), MapEntitiesError> {
-        (self.map_entities)(world, entity_map)
+    /// Maps the entity references of the component of each entity in the values of `entity_map`.
+    ///
+    /// The references that aren't in `entity_map` yet are mapped to newly reserved entities,
+    /// which are added to `entity_map` and spawned without components.
+    pub fn map_entities(&self, world: &mut World, entity_map: &mut EntityMap) {
+        (self.map_entities)(world, entity_map);
     }
 }
 
@@ -423,12 +428,59 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
     fn from_type() -> Self {
         ReflectMapEntities {
             map_entities: |world, entity_map| {
-                for entity in entity_map.values() {
-                    if let Some(mut component) = world.get_mut::<C>(entity) {
-                        component.map_entities(entity_map)?;
+                let mapped_entities: Vec<Entity> = entity_map.values().collect();
+                {
+                    // Entities are reserved through a shared borrow of the world, while the
+                    // components are mapped.
+                    let world: &World = world;
+                    let mut entity_mapper = EntityMapper::new(entity_map, world.entities());
+                    for entity in mapped_entities {
+                        let Some(entity_ref) = world.get_entity(entity) else {
+                            continue;
+                        };
+                        // SAFETY: no other reference to the component exists, and reserving
+                        // entities doesn't move components.
+                        if let Some(mut component) = unsafe {
+                            entity_ref.get_unchecked_mut::<C>(
+                                world.last_change_tick(),
+                                world.read_change_tick(),
+                            )
+                        } {
+                            component.map_entities(&mut entity_mapper);
+                        }
                     }
                 }
-                Ok(())
+                world.flush();
+            },
+        }
+    }
+}
+
+/// A struct used to read the name of an entity from a reflected [`Component`].
+///
+/// Components naming their entity, like `bevy_core::Name`, register this type data with
+/// `#[reflect(EntityName)]`, so that tools like the [`WorldInspector`] can display and filter
+/// entities by name without depending on the concrete component type.
+#[derive(Clone)]
+pub struct ReflectEntityName {
+    name: fn(&dyn Reflect) -> Option<&str>,
+}
+
+impl ReflectEntityName {
+    /// Returns the name stored in `component`, or `None` if it is not of the type this
+    /// [`ReflectEntityName`] was created for.
+    pub fn name<'a>(&self, component: &'a dyn Reflect) -> Option<&'a str> {
+        (self.name)(component)
+    }
+}
+
+impl<C: Component + Reflect + AsRef<str>> FromType<C> for ReflectEntityName {
+    fn from_type() -> Self {
+        ReflectEntityName {
+            name: |component| {
+                component
+                    .downcast_ref::<C>()
+                    .map(|component| component.as_ref())
             },
         }
     }
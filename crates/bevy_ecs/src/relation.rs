@@ -0,0 +1,601 @@
+//! Types for declaring and maintaining relations between entities.
+//!
+//! A relation is a directed link of a given kind `R` from a *source* entity to a *target* entity.
+//! The links are stored on both ends: the source holds a [`Targets<R>`] component listing every
+//! entity it points to, and each target holds a [`TargetedBy<R>`] component listing every entity
+//! pointing at it.
+//!
+//! Both components are kept in sync by the relation API on [`EntityMut`] and
+//! [`EntityCommands`](crate::system::EntityCommands), and are cleaned up automatically when either
+//! end of a relation is despawned.
+//!
+//! # Example
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! struct Owns;
+//! impl Relation for Owns {}
+//!
+//! let mut world = World::new();
+//! let sword = world.spawn_empty().id();
+//! let player = world.spawn_empty().add_target::<Owns>(sword).id();
+//!
+//! assert_eq!(&**world.get::<Targets<Owns>>(player).unwrap(), &[sword]);
+//! assert_eq!(&**world.get::<TargetedBy<Owns>>(sword).unwrap(), &[player]);
+//!
+//! world.despawn(sword);
+//! assert!(world.get::<Targets<Owns>>(player).is_none());
+//! ```
+
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::{ReflectComponent, ReflectMapEntities};
+use crate::{
+    component::{Component, ComponentHooks, ComponentId, TableStorage},
+    entity::{Entity, EntityMapper, MapEntities},
+    query::{ReadOnlyWorldQuery, WorldQuery},
+    system::{Command, EntityCommands, Query},
+    world::{EntityMut, FromWorld, World},
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+use bevy_utils::HashSet;
+use std::{collections::VecDeque, fmt, marker::PhantomData, ops::Deref, slice};
+
+/// A kind of relation between two entities.
+///
+/// Relation kinds are usually zero-sized marker types, and are never stored in the [`World`]
+/// themselves. They only parameterize the [`Targets`] and [`TargetedBy`] components.
+///
+/// ```
+/// # use bevy_ecs::relation::Relation;
+/// /// The source entity owns the target entity.
+/// struct Owns;
+/// impl Relation for Owns {}
+/// ```
+pub trait Relation: Send + Sync + 'static {}
+
+/// Lists the entities that this entity relates to through the relation kind `R`.
+///
+/// This component can only be changed through the relation API on [`EntityMut`] and
+/// [`EntityCommands`](crate::system::EntityCommands), so that it always mirrors the
+/// [`TargetedBy<R>`] components of its targets. It is removed once the entity has no targets left.
+///
+/// Removing this component, either directly or by despawning the entity, removes the entity from
+/// the [`TargetedBy<R>`] components of its targets.
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect))]
+#[cfg_attr(feature = "bevy_reflect", reflect(Component, MapEntities))]
+pub struct Targets<R: Relation> {
+    entities: Vec<Entity>,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    marker: PhantomData<R>,
+}
+
+/// Lists the entities that relate to this entity through the relation kind `R`.
+///
+/// This is the reverse side of [`Targets<R>`], and is maintained the same way.
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect))]
+#[cfg_attr(feature = "bevy_reflect", reflect(Component, MapEntities))]
+pub struct TargetedBy<R: Relation> {
+    entities: Vec<Entity>,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    marker: PhantomData<R>,
+}
+
+macro_rules! impl_relation_component {
+    ($name:ident, $on_remove:ident) => {
+        impl<R: Relation> Component for $name<R> {
+            type Storage = TableStorage;
+
+            fn register_component_hooks(hooks: &mut ComponentHooks) {
+                hooks.on_remove($on_remove::<R>);
+            }
+        }
+
+        impl<R: Relation> $name<R> {
+            fn new(entity: Entity) -> Self {
+                Self {
+                    entities: vec![entity],
+                    marker: PhantomData,
+                }
+            }
+        }
+
+        impl<R: Relation> fmt::Debug for $name<R> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&self.entities)
+                    .finish()
+            }
+        }
+
+        // Reflection needs a way to create an instance to apply a patch on top of. The entity list
+        // should only ever be filled through the relation API, so an empty list is used.
+        impl<R: Relation> FromWorld for $name<R> {
+            fn from_world(_world: &mut World) -> Self {
+                Self {
+                    entities: Vec::new(),
+                    marker: PhantomData,
+                }
+            }
+        }
+
+        impl<R: Relation> MapEntities for $name<R> {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                // The other end of a relation may live outside of the mapped entities, in which
+                // case it keeps pointing at the same entity.
+                for entity in &mut self.entities {
+                    if let Some(mapped_entity) = entity_mapper.get(*entity) {
+                        *entity = mapped_entity;
+                    }
+                }
+            }
+        }
+
+        impl<R: Relation> Deref for $name<R> {
+            type Target = [Entity];
+
+            fn deref(&self) -> &Self::Target {
+                &self.entities
+            }
+        }
+
+        impl<'a, R: Relation> IntoIterator for &'a $name<R> {
+            type Item = &'a Entity;
+
+            type IntoIter = slice::Iter<'a, Entity>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.entities.iter()
+            }
+        }
+    };
+}
+
+impl_relation_component!(Targets, unlink_targets);
+impl_relation_component!(TargetedBy, unlink_sources);
+
+/// Removes `source` from the [`TargetedBy<R>`] component of every entity it targets.
+fn unlink_targets<R: Relation>(world: &mut World, source: Entity, _: ComponentId) {
+    let targets = match world.get::<Targets<R>>(source) {
+        Some(targets) => targets.entities.clone(),
+        None => return,
+    };
+    for target in targets {
+        if target != source {
+            remove_source::<R>(world, target, source);
+        }
+    }
+}
+
+/// Removes `target` from the [`Targets<R>`] component of every entity targeting it.
+fn unlink_sources<R: Relation>(world: &mut World, target: Entity, _: ComponentId) {
+    let sources = match world.get::<TargetedBy<R>>(target) {
+        Some(sources) => sources.entities.clone(),
+        None => return,
+    };
+    for source in sources {
+        if source != target {
+            remove_target_unchecked::<R>(world, source, target);
+        }
+    }
+}
+
+fn remove_source<R: Relation>(world: &mut World, target: Entity, source: Entity) {
+    let mut target = match world.get_entity_mut(target) {
+        Some(target) => target,
+        None => return,
+    };
+    if let Some(mut sources) = target.get_mut::<TargetedBy<R>>() {
+        sources.entities.retain(|entity| *entity != source);
+        if sources.entities.is_empty() {
+            target.remove::<TargetedBy<R>>();
+        }
+    }
+}
+
+fn remove_target_unchecked<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    let mut source = match world.get_entity_mut(source) {
+        Some(source) => source,
+        None => return,
+    };
+    if let Some(mut targets) = source.get_mut::<Targets<R>>() {
+        targets.entities.retain(|entity| *entity != target);
+        if targets.entities.is_empty() {
+            source.remove::<Targets<R>>();
+        }
+    }
+}
+
+fn add_target<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    assert!(
+        world.entities.contains(target),
+        "Could not relate entity {source:?} to entity {target:?} because the target doesn't exist in this World."
+    );
+
+    let mut source_mut = world.entity_mut(source);
+    if let Some(mut targets) = source_mut.get_mut::<Targets<R>>() {
+        if targets.entities.contains(&target) {
+            return;
+        }
+        targets.entities.push(target);
+    } else {
+        source_mut.insert(Targets::<R>::new(target));
+    }
+
+    let mut target_mut = world.entity_mut(target);
+    if let Some(mut sources) = target_mut.get_mut::<TargetedBy<R>>() {
+        sources.entities.push(source);
+    } else {
+        target_mut.insert(TargetedBy::<R>::new(source));
+    }
+}
+
+fn remove_target<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    remove_target_unchecked::<R>(world, source, target);
+    remove_source::<R>(world, target, source);
+}
+
+fn clear_targets<R: Relation>(world: &mut World, source: Entity) {
+    // The `on_remove` hook of `Targets<R>` unlinks the targets.
+    if let Some(mut source) = world.get_entity_mut(source) {
+        source.remove::<Targets<R>>();
+    }
+}
+
+impl<'w> EntityMut<'w> {
+    /// Relates this entity to `target` through the relation kind `R`.
+    ///
+    /// This adds `target` to the [`Targets<R>`] of this entity, and this entity to the
+    /// [`TargetedBy<R>`] of `target`. Adding a relation that already exists does nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` does not exist.
+    pub fn add_target<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| add_target::<R>(world, source, target));
+        self
+    }
+
+    /// Removes the relation of kind `R` from this entity to `target`, if it exists.
+    pub fn remove_target<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| remove_target::<R>(world, source, target));
+        self
+    }
+
+    /// Removes every relation of kind `R` from this entity to other entities.
+    pub fn clear_targets<R: Relation>(&mut self) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| clear_targets::<R>(world, source));
+        self
+    }
+}
+
+impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
+    /// Relates this entity to `target` through the relation kind `R`.
+    ///
+    /// See [`EntityMut::add_target`] for more details.
+    pub fn add_target<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.commands().add(AddTarget::<R> {
+            source,
+            target,
+            marker: PhantomData,
+        });
+        self
+    }
+
+    /// Removes the relation of kind `R` from this entity to `target`, if it exists.
+    ///
+    /// See [`EntityMut::remove_target`] for more details.
+    pub fn remove_target<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.commands().add(RemoveTarget::<R> {
+            source,
+            target,
+            marker: PhantomData,
+        });
+        self
+    }
+
+    /// Removes every relation of kind `R` from this entity to other entities.
+    ///
+    /// See [`EntityMut::clear_targets`] for more details.
+    pub fn clear_targets<R: Relation>(&mut self) -> &mut Self {
+        let source = self.id();
+        self.commands().add(ClearTargets::<R> {
+            source,
+            marker: PhantomData,
+        });
+        self
+    }
+}
+
+/// Command that relates an entity to another one. See [`EntityCommands::add_target`].
+pub struct AddTarget<R: Relation> {
+    /// Entity the relation starts from
+    pub source: Entity,
+    /// Entity the relation points to
+    pub target: Entity,
+    /// The relation kind
+    pub marker: PhantomData<R>,
+}
+
+impl<R: Relation> Command for AddTarget<R> {
+    fn write(self, world: &mut World) {
+        add_target::<R>(world, self.source, self.target);
+    }
+}
+
+/// Command that removes a relation between two entities. See [`EntityCommands::remove_target`].
+pub struct RemoveTarget<R: Relation> {
+    /// Entity the relation starts from
+    pub source: Entity,
+    /// Entity the relation points to
+    pub target: Entity,
+    /// The relation kind
+    pub marker: PhantomData<R>,
+}
+
+impl<R: Relation> Command for RemoveTarget<R> {
+    fn write(self, world: &mut World) {
+        remove_target::<R>(world, self.source, self.target);
+    }
+}
+
+/// Command that removes every relation of a kind starting from an entity.
+/// See [`EntityCommands::clear_targets`].
+pub struct ClearTargets<R: Relation> {
+    /// Entity the relations start from
+    pub source: Entity,
+    /// The relation kind
+    pub marker: PhantomData<R>,
+}
+
+impl<R: Relation> Command for ClearTargets<R> {
+    fn write(self, world: &mut World) {
+        clear_targets::<R>(world, self.source);
+    }
+}
+
+/// An extension trait for [`Query`] that adds relation related methods.
+pub trait RelationQueryExt<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> {
+    /// Returns an [`Iterator`] of [`Entity`]s over every entity reachable from `entity` by
+    /// repeatedly following the queried relation component.
+    ///
+    /// Can only be called on a [`Query`] of [`Targets`] or [`TargetedBy`]
+    /// (i.e. `Query<&Targets<R>>`). Querying [`Targets`] walks the relation forwards, querying
+    /// [`TargetedBy`] walks it backwards.
+    ///
+    /// Traverses the relation graph breadth-first. Each entity is visited at most once, so cycles
+    /// are allowed, and `entity` itself is only returned if it can be reached from itself.
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::relation::RelationQueryExt;
+    /// struct Contains;
+    /// impl Relation for Contains {}
+    ///
+    /// fn system(query: Query<Entity, With<Targets<Contains>>>, contents: Query<&Targets<Contains>>) {
+    ///     for container in &query {
+    ///         for item in contents.iter_related(container) {
+    ///             // Do something!
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    fn iter_related<C>(&'w self, entity: Entity) -> RelatedIter<'w, 's, Q, F, C>
+    where
+        C: Deref<Target = [Entity]> + Component,
+        Q::ReadOnly: WorldQuery<Item<'w> = &'w C>;
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> RelationQueryExt<'w, 's, Q, F>
+    for Query<'w, 's, Q, F>
+{
+    fn iter_related<C>(&'w self, entity: Entity) -> RelatedIter<'w, 's, Q, F, C>
+    where
+        C: Deref<Target = [Entity]> + Component,
+        Q::ReadOnly: WorldQuery<Item<'w> = &'w C>,
+    {
+        RelatedIter::new(self, entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over the entities reachable from an [`Entity`] through a
+/// relation.
+///
+/// Traverses the relation graph breadth-first, visiting each entity at most once.
+pub struct RelatedIter<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, C>
+where
+    C: Deref<Target = [Entity]> + Component,
+    Q::ReadOnly: WorldQuery<Item<'w> = &'w C>,
+{
+    query: &'w Query<'w, 's, Q, F>,
+    visited: HashSet<Entity>,
+    queue: VecDeque<Entity>,
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, C> RelatedIter<'w, 's, Q, F, C>
+where
+    C: Deref<Target = [Entity]> + Component,
+    Q::ReadOnly: WorldQuery<Item<'w> = &'w C>,
+{
+    /// Returns a new [`RelatedIter`].
+    pub fn new(query: &'w Query<'w, 's, Q, F>, entity: Entity) -> Self {
+        let mut iter = RelatedIter {
+            query,
+            visited: HashSet::default(),
+            queue: VecDeque::new(),
+        };
+        iter.enqueue_related(entity);
+        iter
+    }
+
+    fn enqueue_related(&mut self, entity: Entity) {
+        if let Ok(related) = self.query.get(entity) {
+            for &related in related.iter() {
+                if self.visited.insert(related) {
+                    self.queue.push_back(related);
+                }
+            }
+        }
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery, C> Iterator for RelatedIter<'w, 's, Q, F, C>
+where
+    C: Deref<Target = [Entity]> + Component,
+    Q::ReadOnly: WorldQuery<Item<'w> = &'w C>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.queue.pop_front()?;
+        self.enqueue_related(entity);
+        Some(entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RelationQueryExt, TargetedBy, Targets};
+    use crate::{
+        entity::{EntityMap, EntityMapper, MapEntities},
+        prelude::*,
+        system::{CommandQueue, SystemState},
+    };
+
+    struct Owns;
+    impl Relation for Owns {}
+
+    struct Likes;
+    impl Relation for Likes {}
+
+    fn targets<R: Relation>(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<Targets<R>>(entity)
+            .map(|targets| targets.to_vec())
+            .unwrap_or_default()
+    }
+
+    fn sources<R: Relation>(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<TargetedBy<R>>(entity)
+            .map(|sources| sources.to_vec())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn add_and_remove_targets() {
+        let mut world = World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world
+            .entity_mut(a)
+            .add_target::<Owns>(b)
+            .add_target::<Owns>(c)
+            .add_target::<Owns>(c);
+        world.entity_mut(b).add_target::<Likes>(c);
+
+        assert_eq!(targets::<Owns>(&world, a), vec![b, c]);
+        assert_eq!(sources::<Owns>(&world, b), vec![a]);
+        assert_eq!(sources::<Owns>(&world, c), vec![a]);
+        assert_eq!(sources::<Likes>(&world, c), vec![b]);
+
+        world.entity_mut(a).remove_target::<Owns>(b);
+        assert_eq!(targets::<Owns>(&world, a), vec![c]);
+        assert!(!world.entity(b).contains::<TargetedBy<Owns>>());
+
+        world.entity_mut(a).clear_targets::<Owns>();
+        assert!(!world.entity(a).contains::<Targets<Owns>>());
+        assert!(!world.entity(c).contains::<TargetedBy<Owns>>());
+        assert_eq!(sources::<Likes>(&world, c), vec![b]);
+    }
+
+    #[test]
+    fn despawn_cleans_up_relations() {
+        let mut world = World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world
+            .entity_mut(a)
+            .add_target::<Owns>(b)
+            .add_target::<Owns>(c);
+        world
+            .entity_mut(b)
+            .add_target::<Owns>(c)
+            .add_target::<Owns>(b);
+
+        world.despawn(c);
+        assert_eq!(targets::<Owns>(&world, a), vec![b]);
+        assert_eq!(targets::<Owns>(&world, b), vec![b]);
+
+        world.despawn(a);
+        assert_eq!(sources::<Owns>(&world, b), vec![b]);
+
+        world.despawn(b);
+        assert_eq!(world.entities().len(), 0);
+    }
+
+    #[test]
+    fn relation_commands() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let [a, b] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(a).add_target::<Owns>(b);
+        let c = commands.spawn_empty().add_target::<Owns>(b).id();
+        queue.apply(&mut world);
+
+        assert_eq!(sources::<Owns>(&world, b), vec![a, c]);
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(a).remove_target::<Owns>(b);
+        commands.entity(c).clear_targets::<Owns>();
+        queue.apply(&mut world);
+
+        assert!(!world.entity(b).contains::<TargetedBy<Owns>>());
+    }
+
+    #[test]
+    fn iter_related() {
+        let mut world = World::new();
+        let [a, b, c, d] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world
+            .entity_mut(a)
+            .add_target::<Owns>(b)
+            .add_target::<Owns>(c);
+        world.entity_mut(c).add_target::<Owns>(d);
+        world.entity_mut(d).add_target::<Owns>(a);
+
+        let mut system_state =
+            SystemState::<(Query<&Targets<Owns>>, Query<&TargetedBy<Owns>>)>::new(&mut world);
+        let (targets, sources) = system_state.get(&world);
+
+        assert_eq!(targets.iter_related(a).collect::<Vec<_>>(), [b, c, d, a]);
+        assert_eq!(sources.iter_related(b).collect::<Vec<_>>(), [a, d, c]);
+    }
+
+    #[test]
+    fn map_relation_entities() {
+        let mut world = World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        world
+            .entity_mut(a)
+            .add_target::<Owns>(b)
+            .add_target::<Owns>(c);
+
+        let mut entity_map = EntityMap::default();
+        entity_map.insert(b, c);
+
+        let mut targets = world.entity_mut(a).remove::<Targets<Owns>>().unwrap();
+        targets.map_entities(&mut EntityMapper::new(&mut entity_map, world.entities()));
+        assert_eq!(&*targets, &[c, c]);
+    }
+}
@@ -1,24 +1,44 @@
-use bevy_utils::tracing::info;
+use std::{fmt, path::Path};
+
+use bevy_utils::tracing::{info, warn};
 use fixedbitset::FixedBitSet;
 
+use crate as bevy_ecs;
 use crate::component::ComponentId;
-use crate::schedule::{AmbiguityDetection, GraphNode, SystemContainer, SystemStage};
+use crate::schedule::{AmbiguityDetection, GraphNode, Schedule, SystemContainer, SystemStage};
+use crate::system::Resource;
 use crate::world::World;
 
 use super::SystemLabelId;
 
+/// Two systems of a [`SystemStage`] whose execution order is ambiguous, and the data they
+/// conflict on.
+///
+/// See [`ReportExecutionOrderAmbiguities`](super::ReportExecutionOrderAmbiguities) for more
+/// information on ambiguities.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct SystemOrderAmbiguity {
-    segment: SystemStageSegment,
+pub struct SystemOrderAmbiguity {
+    /// The label of the stage the systems are in, formatted with [`Debug`](fmt::Debug).
+    ///
+    /// This is `None` for ambiguities returned by [`SystemStage::ambiguities`], and filled in by
+    /// [`Schedule::ambiguities`].
+    pub stage: Option<String>,
+    /// The part of the stage the systems are in.
+    pub segment: SystemStageSegment,
     // Note: In order for comparisons to work correctly,
     // `system_names` and `conflicts` must be sorted at all times.
-    system_names: [String; 2],
-    conflicts: Vec<String>,
+    /// The names of the two systems, sorted.
+    pub system_names: [String; 2],
+    /// The names of the components and resources both systems access, with at least one of them
+    /// accessing it mutably, sorted.
+    ///
+    /// This is empty if one of the systems is exclusive.
+    pub conflicts: Vec<String>,
 }
 
 /// Which part of a [`SystemStage`] was a [`SystemOrderAmbiguity`] detected in?
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
-enum SystemStageSegment {
+pub enum SystemStageSegment {
     Parallel,
     ExclusiveAtStart,
     ExclusiveBeforeCommands,
@@ -68,11 +88,199 @@ impl SystemOrderAmbiguity {
         conflicts.sort();
 
         Self {
+            stage: None,
             system_names,
             conflicts,
             segment,
         }
     }
+
+    /// Returns the line identifying this ambiguity in an ambiguity baseline file.
+    ///
+    /// Conflicts are not part of the line, so that a pair of systems accessing more data in
+    /// a new version doesn't count as a new ambiguity.
+    ///
+    /// See [`assert_no_new_ambiguities`].
+    pub fn baseline_entry(&self) -> String {
+        let [system_a, system_b] = &self.system_names;
+        format!(
+            "{} | {:?} | {} | {}",
+            self.stage.as_deref().unwrap_or("-"),
+            self.segment,
+            system_a,
+            system_b
+        )
+    }
+}
+
+impl fmt::Display for SystemOrderAmbiguity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [system_a, system_b] = &self.system_names;
+        if let Some(stage) = &self.stage {
+            write!(f, "{stage}: ")?;
+        }
+        write!(f, "{system_a:?} and {system_b:?} ({})", self.segment.desc())?;
+        if !self.conflicts.is_empty() {
+            write!(f, ", conflicts: {:?}", self.conflicts)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ambiguities left out of [`SystemStage::ambiguities`] and of the
+/// [`ReportExecutionOrderAmbiguities`](super::ReportExecutionOrderAmbiguities) logs.
+///
+/// Unlike [`AmbiguityDetection`], which is configured on each system, this resource can
+/// silence ambiguities between systems you don't control, like the ones of third-party plugins.
+///
+/// ```
+/// # use bevy_ecs::schedule::IgnoredAmbiguities;
+/// # fn physics() {}
+/// # fn animation() {}
+/// let ignored = IgnoredAmbiguities::default()
+///     .ignore_crate("bevy_ui")
+///     .ignore_pair(physics, animation);
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct IgnoredAmbiguities {
+    crates: Vec<String>,
+    pairs: Vec<[String; 2]>,
+}
+
+impl IgnoredAmbiguities {
+    /// Ignores ambiguities between two systems that are both defined in the crate `name`.
+    ///
+    /// Ambiguities between a system of the crate and a system of another crate are still
+    /// reported.
+    #[must_use]
+    pub fn ignore_crate(mut self, name: impl Into<String>) -> Self {
+        self.crates.push(name.into());
+        self
+    }
+
+    /// Ignores ambiguities between the two systems, in any stage.
+    ///
+    /// Systems are identified by the type name of the system function or type, which
+    /// is what [`System::name`](crate::system::System::name) returns for function systems.
+    #[must_use]
+    pub fn ignore_pair<A, B>(self, _system_a: A, _system_b: B) -> Self {
+        self.ignore_pair_by_name(std::any::type_name::<A>(), std::any::type_name::<B>())
+    }
+
+    /// Ignores ambiguities between the two systems with the given names, in any stage.
+    #[must_use]
+    pub fn ignore_pair_by_name(
+        mut self,
+        system_a: impl Into<String>,
+        system_b: impl Into<String>,
+    ) -> Self {
+        let mut pair = [system_a.into(), system_b.into()];
+        pair.sort();
+        self.pairs.push(pair);
+        self
+    }
+
+    /// Returns `true` if `ambiguity` is ignored.
+    pub fn is_ignored(&self, ambiguity: &SystemOrderAmbiguity) -> bool {
+        let in_ignored_crate = |name: &str| {
+            let crate_name = name.split("::").next().unwrap_or(name);
+            self.crates.iter().any(|ignored| ignored == crate_name)
+        };
+        self.pairs.contains(&ambiguity.system_names)
+            || ambiguity
+                .system_names
+                .iter()
+                .all(|name| in_ignored_crate(name))
+    }
+}
+
+/// Panics if `ambiguities` contains ambiguities that are not listed in the baseline file at
+/// `baseline_path`.
+///
+/// The baseline file has one [`SystemOrderAmbiguity::baseline_entry`] per line, and can contain
+/// empty lines and comments starting with `#`. If the `BEVY_UPDATE_AMBIGUITY_BASELINE`
+/// environment variable is set, the file is overwritten with the current ambiguities instead.
+///
+/// Entries of the baseline that no longer occur are only logged, so that fixing an ambiguity
+/// doesn't break the check. This is meant to be used in tests, so that CI fails when new
+/// execution order ambiguities are introduced, for example by a plugin upgrade:
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::assert_no_new_ambiguities;
+/// # fn build_schedule() -> Schedule { Schedule::default() }
+/// let mut world = World::new();
+/// let mut schedule = build_schedule();
+/// schedule.initialize(&mut world).unwrap();
+/// assert_no_new_ambiguities(&schedule.ambiguities(&world), "tests/ambiguities.txt");
+/// ```
+///
+/// # Panics
+///
+/// Panics if the baseline file can't be read or written.
+pub fn assert_no_new_ambiguities(
+    ambiguities: &[SystemOrderAmbiguity],
+    baseline_path: impl AsRef<Path>,
+) {
+    let baseline_path = baseline_path.as_ref();
+    if std::env::var_os("BEVY_UPDATE_AMBIGUITY_BASELINE").is_some() {
+        let mut baseline = String::new();
+        for ambiguity in ambiguities {
+            baseline.push_str(&ambiguity.baseline_entry());
+            baseline.push('\n');
+        }
+        std::fs::write(baseline_path, baseline).unwrap_or_else(|err| {
+            panic!(
+                "Could not write ambiguity baseline {}: {err}",
+                baseline_path.display()
+            )
+        });
+        return;
+    }
+
+    let baseline = std::fs::read_to_string(baseline_path).unwrap_or_else(|err| {
+        panic!(
+            "Could not read ambiguity baseline {}: {err}",
+            baseline_path.display()
+        )
+    });
+    let new_ambiguities = new_ambiguities(ambiguities, &baseline);
+    if !new_ambiguities.is_empty() {
+        use std::fmt::Write;
+        let mut message = format!(
+            "Found {} execution order ambiguities missing from {}:\n",
+            new_ambiguities.len(),
+            baseline_path.display()
+        );
+        for ambiguity in new_ambiguities {
+            writeln!(message, " -- {ambiguity}").unwrap();
+            writeln!(message, "    {}", ambiguity.baseline_entry()).unwrap();
+        }
+        panic!("{}", message);
+    }
+}
+
+/// Returns the ambiguities without an entry in `baseline`, logging the entries of `baseline`
+/// that no longer occur.
+fn new_ambiguities<'a>(
+    ambiguities: &'a [SystemOrderAmbiguity],
+    baseline: &str,
+) -> Vec<&'a SystemOrderAmbiguity> {
+    let entries: Vec<_> = ambiguities
+        .iter()
+        .map(SystemOrderAmbiguity::baseline_entry)
+        .collect();
+    for line in baseline.lines().map(str::trim) {
+        if !line.is_empty() && !line.starts_with('#') && !entries.iter().any(|e| e == line) {
+            warn!("Execution order ambiguity no longer occurs: {}", line);
+        }
+    }
+    ambiguities
+        .iter()
+        .zip(&entries)
+        .filter(|(_, entry)| !baseline.lines().any(|line| line.trim() == entry.as_str()))
+        .map(|(ambiguity, _)| ambiguity)
+        .collect()
 }
 
 impl SystemStage {
@@ -93,6 +301,7 @@ impl SystemStage {
                 system_names: [system_a, system_b],
                 conflicts,
                 segment,
+                ..
             } in &ambiguities
             {
                 // If the ambiguity occurred in a different segment than the previous one, write a header for the segment.
@@ -112,16 +321,14 @@ impl SystemStage {
         }
     }
 
-    /// Returns all execution order ambiguities between systems.
+    /// Returns all execution order ambiguities between systems, sorted by
+    /// [`SystemStageSegment`] and system names.
     ///
-    /// Returns 4 vectors of ambiguities for each stage, in the following order:
-    /// - parallel
-    /// - exclusive at start,
-    /// - exclusive before commands
-    /// - exclusive at end
+    /// Ambiguities ignored by the [`IgnoredAmbiguities`] resource of `world` are left out.
     ///
-    /// The result may be incorrect if this stage has not been initialized with `world`.
-    fn ambiguities(&self, world: &World) -> Vec<SystemOrderAmbiguity> {
+    /// The result may be incorrect if this stage has not been initialized with `world`,
+    /// see [`SystemStage::initialize`].
+    pub fn ambiguities(&self, world: &World) -> Vec<SystemOrderAmbiguity> {
         let parallel = find_ambiguities(&self.parallel).into_iter().map(
             |(system_a_index, system_b_index, component_ids)| {
                 SystemOrderAmbiguity::from_raw(
@@ -179,6 +386,9 @@ impl SystemStage {
             .chain(before_commands)
             .chain(at_end)
             .collect();
+        if let Some(ignored) = world.get_resource::<IgnoredAmbiguities>() {
+            ambiguities.retain(|ambiguity| !ignored.is_ignored(ambiguity));
+        }
         ambiguities.sort();
         ambiguities
     }
@@ -192,6 +402,42 @@ impl SystemStage {
     }
 }
 
+impl Schedule {
+    /// Returns the execution order ambiguities of all [`SystemStage`]s of this schedule,
+    /// including the ones of nested schedules, in stage order.
+    ///
+    /// The [`stage`](SystemOrderAmbiguity::stage) of each ambiguity is set to the label of its
+    /// stage, prefixed with the labels of the parent stages for nested schedules, as in
+    /// `Update/Physics`.
+    ///
+    /// The result may be incorrect if this schedule has not been initialized with `world`,
+    /// see [`Schedule::initialize`].
+    pub fn ambiguities(&self, world: &World) -> Vec<SystemOrderAmbiguity> {
+        let mut ambiguities = Vec::new();
+        for (label, stage) in self.iter_stages() {
+            let label = format!("{label:?}");
+            if let Some(stage) = stage.downcast_ref::<SystemStage>() {
+                ambiguities.extend(stage.ambiguities(world).into_iter().map(|mut ambiguity| {
+                    ambiguity.stage = Some(label.clone());
+                    ambiguity
+                }));
+            } else if let Some(schedule) = stage.downcast_ref::<Schedule>() {
+                ambiguities.extend(
+                    schedule
+                        .ambiguities(world)
+                        .into_iter()
+                        .map(|mut ambiguity| {
+                            ambiguity.stage =
+                                Some(format!("{}/{}", label, ambiguity.stage.unwrap()));
+                            ambiguity
+                        }),
+                );
+            }
+        }
+        ambiguities
+    }
+}
+
 /// Returns vector containing all pairs of indices of systems with ambiguous execution order,
 /// along with specific components that have triggered the warning.
 /// Systems must be topologically sorted beforehand.
@@ -282,6 +528,7 @@ mod tests {
     use crate as bevy_ecs;
     use crate::event::Events;
     use crate::prelude::*;
+    use crate::schedule::{assert_no_new_ambiguities, IgnoredAmbiguities};
 
     #[derive(Resource)]
     struct R;
@@ -571,6 +818,7 @@ mod tests {
             ambiguities,
             vec![
                 SystemOrderAmbiguity {
+                    stage: None,
                     system_names: [
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_a".to_string(),
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_b".to_string()
@@ -579,6 +827,7 @@ mod tests {
                     segment: SystemStageSegment::Parallel,
                 },
                 SystemOrderAmbiguity {
+                    stage: None,
                     system_names: [
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_a".to_string(),
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_d".to_string()
@@ -587,6 +836,7 @@ mod tests {
                     segment: SystemStageSegment::Parallel,
                 },
                 SystemOrderAmbiguity {
+                    stage: None,
                     system_names: [
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_b".to_string(),
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_e".to_string()
@@ -595,6 +845,7 @@ mod tests {
                     segment: SystemStageSegment::Parallel,
                 },
                 SystemOrderAmbiguity {
+                    stage: None,
                     system_names: [
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_d".to_string(),
                         "bevy_ecs::schedule::ambiguity_detection::tests::system_e".to_string()
@@ -605,4 +856,114 @@ mod tests {
             ]
         );
     }
+
+    fn ambiguous_schedule() -> Schedule {
+        #[derive(StageLabel)]
+        struct Inner;
+
+        Schedule::default()
+            .with_stage(
+                "first",
+                SystemStage::parallel()
+                    .with_system(system_a)
+                    .with_system(system_b),
+            )
+            .with_stage(
+                "nested",
+                Schedule::default().with_stage(
+                    Inner,
+                    SystemStage::parallel()
+                        .with_system(system_c)
+                        .with_system(system_d),
+                ),
+            )
+    }
+
+    #[test]
+    fn schedule_ambiguities() {
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut schedule = ambiguous_schedule();
+        schedule.initialize(&mut world).unwrap();
+
+        let ambiguities = schedule.ambiguities(&world);
+        assert_eq!(ambiguities.len(), 2);
+        assert_eq!(ambiguities[0].stage.as_deref(), Some("first"));
+        assert_eq!(ambiguities[1].stage.as_deref(), Some("nested/Inner"));
+        assert_eq!(
+            ambiguities[1].baseline_entry(),
+            "nested/Inner | Parallel | \
+            bevy_ecs::schedule::ambiguity_detection::tests::system_c | \
+            bevy_ecs::schedule::ambiguity_detection::tests::system_d"
+        );
+    }
+
+    #[test]
+    fn ignored_ambiguities() {
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut schedule = ambiguous_schedule();
+        schedule.initialize(&mut world).unwrap();
+
+        world.insert_resource(IgnoredAmbiguities::default().ignore_pair(system_d, system_c));
+        let ambiguities = schedule.ambiguities(&world);
+        assert_eq!(ambiguities.len(), 1);
+        assert_eq!(ambiguities[0].stage.as_deref(), Some("first"));
+
+        world.insert_resource(IgnoredAmbiguities::default().ignore_crate("bevy_ecs"));
+        assert!(schedule.ambiguities(&world).is_empty());
+
+        world.insert_resource(IgnoredAmbiguities::default().ignore_crate("bevy"));
+        assert_eq!(schedule.ambiguities(&world).len(), 2);
+    }
+
+    #[test]
+    fn ambiguity_baseline() {
+        let mut world = World::new();
+        world.insert_resource(R);
+        let mut schedule = ambiguous_schedule();
+        schedule.initialize(&mut world).unwrap();
+        let ambiguities = schedule.ambiguities(&world);
+
+        let baseline = format!(
+            "# Known ambiguities\n\n{}\nstage | Parallel | removed_a | removed_b\n",
+            ambiguities[0].baseline_entry()
+        );
+        assert_eq!(
+            super::new_ambiguities(&ambiguities, &baseline),
+            vec![&ambiguities[1]]
+        );
+
+        let baseline_path = std::env::temp_dir().join(format!(
+            "bevy_ecs_ambiguity_baseline_{}.txt",
+            std::process::id()
+        ));
+        let baseline: String = ambiguities
+            .iter()
+            .map(|ambiguity| ambiguity.baseline_entry() + "\n")
+            .collect();
+        std::fs::write(&baseline_path, baseline).unwrap();
+        assert_no_new_ambiguities(&ambiguities, &baseline_path);
+        std::fs::remove_file(&baseline_path).unwrap();
+    }
+
+    #[test]
+    fn dependency_cycle_error() {
+        #[derive(SystemLabel)]
+        struct First;
+        #[derive(SystemLabel)]
+        struct Second;
+
+        let mut world = World::new();
+        let mut stage = SystemStage::parallel()
+            .with_system(system_a.label(First).after(Second))
+            .with_system(system_b.label(Second).after(First));
+        let cycle = stage.initialize(&mut world).unwrap_err();
+        assert_eq!(cycle.nodes_description, "parallel systems");
+        assert_eq!(cycle.nodes.len(), 2);
+        assert!(cycle
+            .nodes
+            .iter()
+            .any(|(name, _)| name.ends_with("::system_a")));
+    }
 }
@@ -15,6 +15,7 @@ mod system_container;
 mod system_descriptor;
 mod system_set;
 
+pub use ambiguity_detection::*;
 pub use executor::*;
 pub use executor_parallel::*;
 pub use graph_utils::GraphNode;
@@ -371,6 +372,22 @@ impl Schedule {
         }
     }
 
+    /// Initializes all [`SystemStage`]s of this schedule, including the ones of nested
+    /// schedules, without running them.
+    ///
+    /// See [`SystemStage::initialize`].
+    pub fn initialize(&mut self, world: &mut World) -> Result<(), DependencyCycle> {
+        for label in &self.stage_order {
+            let stage = self.stages.get_mut(label).unwrap();
+            if let Some(stage) = stage.downcast_mut::<SystemStage>() {
+                stage.initialize(world)?;
+            } else if let Some(schedule) = stage.downcast_mut::<Schedule>() {
+                schedule.initialize(world)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Iterates over all of schedule's stages and their labels, in execution order.
     pub fn iter_stages(&self) -> impl Iterator<Item = (StageLabelId, &dyn Stage)> {
         self.stage_order
@@ -59,9 +59,37 @@ impl_downcast!(Stage);
 ///
 /// The checker may report a system more times than the amount of constraints it would actually need
 /// to have unambiguous order with regards to a group of already-constrained systems.
+///
+/// The same ambiguities are available as data through [`SystemStage::ambiguities`] and
+/// [`Schedule::ambiguities`], which can be checked in CI with
+/// [`assert_no_new_ambiguities`](super::assert_no_new_ambiguities). Ambiguities you can't fix
+/// can be silenced with [`IgnoredAmbiguities`](super::IgnoredAmbiguities).
 #[derive(Resource, Default)]
 pub struct ReportExecutionOrderAmbiguities;
 
+/// A dependency cycle found while sorting the systems or run criteria of a [`SystemStage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCycle {
+    /// The kind of nodes that form the cycle, such as `"parallel systems"`.
+    pub nodes_description: &'static str,
+    /// The names of the nodes in the cycle, each wanting to run after the next one (and the
+    /// last after the first), with the labels that caused it.
+    pub nodes: Vec<(String, String)>,
+}
+
+impl std::error::Error for DependencyCycle {}
+
+impl std::fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Found a dependency cycle in {}:", self.nodes_description)?;
+        for (name, labels) in &self.nodes {
+            writeln!(f, " - {name}")?;
+            writeln!(f, "    wants to be after (because of labels: {labels})")?;
+        }
+        write!(f, " - {}", self.nodes[0].0)
+    }
+}
+
 /// Stores and executes systems. Execution order is not defined unless explicitly specified;
 /// see `SystemDescriptor` documentation.
 pub struct SystemStage {
@@ -427,8 +455,43 @@ impl SystemStage {
         }
     }
 
+    /// Initializes the systems and run criteria added since the last call, and sorts them in
+    /// topological order, without running them.
+    ///
+    /// This is done automatically when the stage runs, but can be used to inspect the stage,
+    /// for example with [`SystemStage::ambiguities`], without running any system.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stage was already initialized with or run on another [`World`].
+    pub fn initialize(&mut self, world: &mut World) -> Result<(), DependencyCycle> {
+        if let Some(world_id) = self.world_id {
+            assert!(
+                world.id() == world_id,
+                "Cannot run SystemStage on two different Worlds"
+            );
+        } else {
+            self.world_id = Some(world.id());
+        }
+
+        if self.systems_modified {
+            self.initialize_systems(world);
+            self.rebuild_orders_and_dependencies()?;
+            self.systems_modified = false;
+            self.executor.rebuild_cached_data(&self.parallel);
+            self.executor_modified = false;
+            if world.contains_resource::<ReportExecutionOrderAmbiguities>() {
+                self.report_ambiguities(world);
+            }
+            if let Some(resource_id) = self.must_read_resource {
+                self.check_uses_resource(resource_id, world);
+            }
+        }
+        Ok(())
+    }
+
     /// Rearranges all systems in topological orders. Systems must be initialized.
-    fn rebuild_orders_and_dependencies(&mut self) {
+    fn rebuild_orders_and_dependencies(&mut self) -> Result<(), DependencyCycle> {
         // This assertion exists to document that the number of systems in a stage is limited
         // to guarantee that change detection never yields false positives. However, it's possible
         // (but still unlikely) to circumvent this by abusing exclusive or chained systems.
@@ -446,56 +509,47 @@ impl SystemStage {
                 && self.uninitialized_before_commands.is_empty()
                 && self.uninitialized_at_end.is_empty()
         );
-        fn unwrap_dependency_cycle_error<Node: GraphNode, Output, Labels: std::fmt::Debug>(
+        fn into_dependency_cycle<Node: GraphNode, Output, Labels: std::fmt::Debug>(
             result: Result<Output, DependencyGraphError<Labels>>,
             nodes: &[Node],
             nodes_description: &'static str,
-        ) -> Output {
-            match result {
-                Ok(output) => output,
-                Err(DependencyGraphError::GraphCycles(cycle)) => {
-                    use std::fmt::Write;
-                    let mut message = format!("Found a dependency cycle in {nodes_description}:");
-                    writeln!(message).unwrap();
-                    for (index, labels) in &cycle {
-                        writeln!(message, " - {}", nodes[*index].name()).unwrap();
-                        writeln!(
-                            message,
-                            "    wants to be after (because of labels: {:?})",
-                            labels,
-                        )
-                        .unwrap();
-                    }
-                    writeln!(message, " - {}", cycle[0].0).unwrap();
-                    panic!("{}", message);
-                }
-            }
+        ) -> Result<Output, DependencyCycle> {
+            result.map_err(|DependencyGraphError::GraphCycles(cycle)| DependencyCycle {
+                nodes_description,
+                nodes: cycle
+                    .iter()
+                    .map(|(index, labels)| {
+                        (nodes[*index].name().into_owned(), format!("{labels:?}"))
+                    })
+                    .collect(),
+            })
         }
-        let run_criteria_labels = unwrap_dependency_cycle_error(
+        let run_criteria_labels = into_dependency_cycle(
             self.process_run_criteria(),
             &self.run_criteria,
             "run criteria",
-        );
-        unwrap_dependency_cycle_error(
+        )?;
+        into_dependency_cycle(
             process_systems(&mut self.parallel, &run_criteria_labels),
             &self.parallel,
             "parallel systems",
-        );
-        unwrap_dependency_cycle_error(
+        )?;
+        into_dependency_cycle(
             process_systems(&mut self.exclusive_at_start, &run_criteria_labels),
             &self.exclusive_at_start,
             "exclusive systems at start of stage",
-        );
-        unwrap_dependency_cycle_error(
+        )?;
+        into_dependency_cycle(
             process_systems(&mut self.exclusive_before_commands, &run_criteria_labels),
             &self.exclusive_before_commands,
             "exclusive systems before commands of stage",
-        );
-        unwrap_dependency_cycle_error(
+        )?;
+        into_dependency_cycle(
             process_systems(&mut self.exclusive_at_end, &run_criteria_labels),
             &self.exclusive_at_end,
             "exclusive systems at end of stage",
-        );
+        )?;
+        Ok(())
     }
 
     fn check_uses_resource(&self, resource_id: ComponentId, world: &World) {
@@ -692,28 +746,10 @@ fn process_systems(
 
 impl Stage for SystemStage {
     fn run(&mut self, world: &mut World) {
-        if let Some(world_id) = self.world_id {
-            assert!(
-                world.id() == world_id,
-                "Cannot run SystemStage on two different Worlds"
-            );
-        } else {
-            self.world_id = Some(world.id());
+        if let Err(cycle) = self.initialize(world) {
+            panic!("{}", cycle);
         }
-
-        if self.systems_modified {
-            self.initialize_systems(world);
-            self.rebuild_orders_and_dependencies();
-            self.systems_modified = false;
-            self.executor.rebuild_cached_data(&self.parallel);
-            self.executor_modified = false;
-            if world.contains_resource::<ReportExecutionOrderAmbiguities>() {
-                self.report_ambiguities(world);
-            }
-            if let Some(resource_id) = self.must_read_resource {
-                self.check_uses_resource(resource_id, world);
-            }
-        } else if self.executor_modified {
+        if self.executor_modified {
             self.executor.rebuild_cached_data(&self.parallel);
             self.executor_modified = false;
         }
@@ -0,0 +1,238 @@
+use std::borrow::Cow;
+
+use crate::{
+    archetype::ArchetypeComponentId,
+    component::ComponentId,
+    query::Access,
+    system::{BoxedSystem, IntoSystem, System},
+    world::World,
+};
+
+/// A type-erased run condition stored in a [`Schedule`](super::Schedule).
+pub type BoxedCondition = BoxedSystem<(), bool>;
+
+/// A system that determines if one or more other systems or system sets should run.
+///
+/// Run conditions are regular systems returning a `bool`, which must not access the [`World`]
+/// mutably. They can be combined with [`Condition::and_then`], [`Condition::or_else`] and
+/// [`common_conditions::not`].
+pub trait Condition<Params>: IntoSystem<(), bool, Params> + Sized {
+    /// Returns a condition that is `true` if both this condition and `and` are `true`.
+    ///
+    /// `and` is only evaluated if this condition is `true`.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::{Res, Resource};
+    /// # use bevy_ecs::schedule_v3::{
+    /// #     common_conditions::resource_exists, Condition, IntoSystemConfig, Schedule,
+    /// # };
+    /// #[derive(Resource, PartialEq)]
+    /// struct Paused(bool);
+    ///
+    /// fn is_paused(paused: Res<Paused>) -> bool {
+    ///     paused.0
+    /// }
+    ///
+    /// # fn pause_menu() {}
+    /// let mut schedule = Schedule::new();
+    /// schedule.add_system(pause_menu.run_if(resource_exists::<Paused>().and_then(is_paused)));
+    /// ```
+    fn and_then<M, C: Condition<M>>(self, and: C) -> CombinedCondition {
+        CombinedCondition::new(
+            CombineOp::And,
+            vec![
+                Box::new(IntoSystem::into_system(self)),
+                Box::new(IntoSystem::into_system(and)),
+            ],
+        )
+    }
+
+    /// Returns a condition that is `true` if either this condition or `or` is `true`.
+    ///
+    /// `or` is only evaluated if this condition is `false`.
+    fn or_else<M, C: Condition<M>>(self, or: C) -> CombinedCondition {
+        CombinedCondition::new(
+            CombineOp::Or,
+            vec![
+                Box::new(IntoSystem::into_system(self)),
+                Box::new(IntoSystem::into_system(or)),
+            ],
+        )
+    }
+}
+
+impl<Params, F: IntoSystem<(), bool, Params>> Condition<Params> for F {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CombineOp {
+    And,
+    Or,
+    Not,
+}
+
+/// A run condition made of other run conditions.
+///
+/// This struct is created by [`Condition::and_then`], [`Condition::or_else`] and
+/// [`common_conditions::not`].
+pub struct CombinedCondition {
+    op: CombineOp,
+    conditions: Vec<BoxedCondition>,
+    name: Cow<'static, str>,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl CombinedCondition {
+    fn new(op: CombineOp, conditions: Vec<BoxedCondition>) -> Self {
+        let names: Vec<_> = conditions
+            .iter()
+            .map(|condition| condition.name())
+            .collect();
+        Self {
+            op,
+            name: Cow::Owned(format!("{:?}({})", op, names.join(", "))),
+            conditions,
+            component_access: Default::default(),
+            archetype_component_access: Default::default(),
+        }
+    }
+}
+
+impl System for CombinedCondition {
+    type In = ();
+    type Out = bool;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.conditions.iter().all(|condition| condition.is_send())
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.conditions
+            .iter()
+            .any(|condition| condition.is_exclusive())
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: (), world: &World) -> bool {
+        let mut conditions = self.conditions.iter_mut();
+        match self.op {
+            CombineOp::And => conditions.all(|condition| condition.run_unsafe((), world)),
+            CombineOp::Or => conditions.any(|condition| condition.run_unsafe((), world)),
+            CombineOp::Not => !conditions.next().unwrap().run_unsafe((), world),
+        }
+    }
+
+    // needed to make exclusive systems work
+    fn run(&mut self, _input: (), world: &mut World) -> bool {
+        let mut conditions = self.conditions.iter_mut();
+        match self.op {
+            CombineOp::And => conditions.all(|condition| condition.run((), world)),
+            CombineOp::Or => conditions.any(|condition| condition.run((), world)),
+            CombineOp::Not => !conditions.next().unwrap().run((), world),
+        }
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        for condition in &mut self.conditions {
+            condition.apply_buffers(world);
+        }
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        for condition in &mut self.conditions {
+            condition.initialize(world);
+            self.component_access.extend(condition.component_access());
+        }
+    }
+
+    fn update_archetype_component_access(&mut self, world: &World) {
+        for condition in &mut self.conditions {
+            condition.update_archetype_component_access(world);
+            self.archetype_component_access
+                .extend(condition.archetype_component_access());
+        }
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        for condition in &mut self.conditions {
+            condition.check_change_tick(change_tick);
+        }
+    }
+
+    fn get_last_change_tick(&self) -> u32 {
+        self.conditions[0].get_last_change_tick()
+    }
+
+    fn set_last_change_tick(&mut self, last_change_tick: u32) {
+        for condition in &mut self.conditions {
+            condition.set_last_change_tick(last_change_tick);
+        }
+    }
+}
+
+/// Common run conditions.
+pub mod common_conditions {
+    use super::{CombineOp, CombinedCondition, Condition};
+    use crate::{
+        change_detection::DetectChanges,
+        schedule::{State, StateData},
+        system::{IntoSystem, Local, Res, Resource},
+    };
+
+    /// Returns a condition that is `true` the first time it is evaluated, and `false` afterwards.
+    pub fn run_once() -> impl FnMut(Local<bool>) -> bool {
+        move |mut has_run: Local<bool>| {
+            if *has_run {
+                false
+            } else {
+                *has_run = true;
+                true
+            }
+        }
+    }
+
+    /// Returns a condition that is `true` if the resource `T` exists.
+    pub fn resource_exists<T: Resource>() -> impl FnMut(Option<Res<T>>) -> bool {
+        move |resource: Option<Res<T>>| resource.is_some()
+    }
+
+    /// Returns a condition that is `true` if the resource `T` exists and is equal to `value`.
+    pub fn resource_equals<T: Resource + PartialEq>(
+        value: T,
+    ) -> impl FnMut(Option<Res<T>>) -> bool {
+        move |resource: Option<Res<T>>| matches!(resource, Some(resource) if *resource == value)
+    }
+
+    /// Returns a condition that is `true` if the resource `T` exists and was added or changed
+    /// since the condition was last evaluated.
+    pub fn resource_changed<T: Resource>() -> impl FnMut(Option<Res<T>>) -> bool {
+        move |resource: Option<Res<T>>| matches!(resource, Some(resource) if resource.is_changed())
+    }
+
+    /// Returns a condition that is `true` if the current [`State<S>`] is `state`.
+    ///
+    /// Unlike [`State::on_update`], this condition can be combined with other conditions, such
+    /// as fixed timesteps.
+    pub fn state_equals<S: StateData>(state: S) -> impl FnMut(Option<Res<State<S>>>) -> bool {
+        move |current: Option<Res<State<S>>>| matches!(current, Some(current) if *current.current() == state)
+    }
+
+    /// Returns a condition that inverts the output of `condition`.
+    pub fn not<Params>(condition: impl Condition<Params>) -> CombinedCondition {
+        CombinedCondition::new(
+            CombineOp::Not,
+            vec![Box::new(IntoSystem::into_system(condition))],
+        )
+    }
+}
@@ -0,0 +1,263 @@
+use bevy_ecs_macros::all_tuples;
+
+use crate::{
+    schedule_v3::{
+        BoxedCondition, Condition, IntoSystemSet, SystemSet, SystemSetId, SystemTypeSet,
+    },
+    system::{BoxedSystem, IntoSystem},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DependencyKind {
+    Before,
+    After,
+}
+
+/// The sets a node of a [`Schedule`](super::Schedule) is in, and the nodes it is ordered against.
+#[derive(Debug, Default)]
+pub(crate) struct GraphInfo {
+    pub(crate) sets: Vec<SystemSetId>,
+    pub(crate) dependencies: Vec<(DependencyKind, SystemSetId)>,
+}
+
+/// A [`SystemSet`] with its scheduling metadata, ready to be configured in a
+/// [`Schedule`](super::Schedule).
+pub struct SystemSetConfig {
+    pub(crate) set: SystemSetId,
+    pub(crate) graph_info: GraphInfo,
+    pub(crate) conditions: Vec<BoxedCondition>,
+}
+
+impl SystemSetConfig {
+    fn new(set: SystemSetId) -> Self {
+        Self {
+            set,
+            graph_info: GraphInfo::default(),
+            conditions: Vec::new(),
+        }
+    }
+}
+
+/// A system with its scheduling metadata, ready to be added to a [`Schedule`](super::Schedule).
+pub struct SystemConfig {
+    pub(crate) system: BoxedSystem,
+    pub(crate) type_set: SystemSetId,
+    pub(crate) graph_info: GraphInfo,
+    pub(crate) conditions: Vec<BoxedCondition>,
+}
+
+impl SystemConfig {
+    fn new(system: BoxedSystem, type_set: SystemSetId) -> Self {
+        Self {
+            system,
+            type_set,
+            graph_info: GraphInfo::default(),
+            conditions: Vec::new(),
+        }
+    }
+}
+
+fn new_condition<P>(condition: impl Condition<P>) -> BoxedCondition {
+    Box::new(IntoSystem::into_system(condition))
+}
+
+/// Types that can be converted into a [`SystemSetConfig`].
+///
+/// This has been implemented for all types that implement [`SystemSet`].
+pub trait IntoSystemSetConfig: Sized {
+    /// Converts into a [`SystemSetConfig`].
+    fn into_config(self) -> SystemSetConfig;
+
+    /// Adds the set to another set, nesting it.
+    fn in_set(self, set: impl SystemSet) -> SystemSetConfig {
+        let mut config = self.into_config();
+        config.graph_info.sets.push(set.as_label());
+        config
+    }
+
+    /// Runs the systems of this set before the systems of `set`.
+    fn before<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfig {
+        let mut config = self.into_config();
+        config
+            .graph_info
+            .dependencies
+            .push((DependencyKind::Before, set.into_system_set()));
+        config
+    }
+
+    /// Runs the systems of this set after the systems of `set`.
+    fn after<M>(self, set: impl IntoSystemSet<M>) -> SystemSetConfig {
+        let mut config = self.into_config();
+        config
+            .graph_info
+            .dependencies
+            .push((DependencyKind::After, set.into_system_set()));
+        config
+    }
+
+    /// Only runs the systems of this set if `condition` is `true`.
+    ///
+    /// The condition is evaluated at most once per run of the schedule, right before the first
+    /// system of the set would run.
+    fn run_if<P>(self, condition: impl Condition<P>) -> SystemSetConfig {
+        let mut config = self.into_config();
+        config.conditions.push(new_condition(condition));
+        config
+    }
+}
+
+impl<S: SystemSet> IntoSystemSetConfig for S {
+    fn into_config(self) -> SystemSetConfig {
+        SystemSetConfig::new(self.as_label())
+    }
+}
+
+impl IntoSystemSetConfig for SystemSetConfig {
+    fn into_config(self) -> Self {
+        self
+    }
+}
+
+/// Types that can be converted into a [`SystemConfig`].
+///
+/// This has been implemented for all types that implement [`IntoSystem`] with no input and
+/// output, such as system functions.
+pub trait IntoSystemConfig<Params>: Sized {
+    /// Converts into a [`SystemConfig`].
+    fn into_config(self) -> SystemConfig;
+
+    /// Adds the system to `set`.
+    fn in_set(self, set: impl SystemSet) -> SystemConfig {
+        let mut config = self.into_config();
+        config.graph_info.sets.push(set.as_label());
+        config
+    }
+
+    /// Runs the system before the systems of `set`.
+    fn before<M>(self, set: impl IntoSystemSet<M>) -> SystemConfig {
+        let mut config = self.into_config();
+        config
+            .graph_info
+            .dependencies
+            .push((DependencyKind::Before, set.into_system_set()));
+        config
+    }
+
+    /// Runs the system after the systems of `set`.
+    fn after<M>(self, set: impl IntoSystemSet<M>) -> SystemConfig {
+        let mut config = self.into_config();
+        config
+            .graph_info
+            .dependencies
+            .push((DependencyKind::After, set.into_system_set()));
+        config
+    }
+
+    /// Only runs the system if `condition` is `true`.
+    ///
+    /// Calling this method several times requires all of the conditions to be `true`.
+    fn run_if<P>(self, condition: impl Condition<P>) -> SystemConfig {
+        let mut config = self.into_config();
+        config.conditions.push(new_condition(condition));
+        config
+    }
+}
+
+impl<Params, F: IntoSystem<(), (), Params> + 'static> IntoSystemConfig<Params> for F {
+    fn into_config(self) -> SystemConfig {
+        SystemConfig::new(
+            Box::new(IntoSystem::into_system(self)),
+            SystemTypeSet::<F>::new().as_label(),
+        )
+    }
+}
+
+impl IntoSystemConfig<()> for SystemConfig {
+    fn into_config(self) -> Self {
+        self
+    }
+}
+
+/// A collection of [`SystemConfig`]s.
+pub struct SystemConfigs {
+    pub(crate) systems: Vec<SystemConfig>,
+    /// If `true`, the systems run in the order they were added.
+    pub(crate) chained: bool,
+}
+
+/// Types that can be converted into [`SystemConfigs`].
+///
+/// This has been implemented for tuples of up to 15 [`IntoSystemConfig`]s.
+pub trait IntoSystemConfigs<Params>: Sized {
+    /// Converts into [`SystemConfigs`].
+    fn into_configs(self) -> SystemConfigs;
+
+    /// Adds the systems to `set`.
+    fn in_set(self, set: impl SystemSet) -> SystemConfigs {
+        let mut configs = self.into_configs();
+        let set = set.as_label();
+        for config in &mut configs.systems {
+            config.graph_info.sets.push(set);
+        }
+        configs
+    }
+
+    /// Runs the systems before the systems of `set`.
+    fn before<M>(self, set: impl IntoSystemSet<M>) -> SystemConfigs {
+        let mut configs = self.into_configs();
+        let set = set.into_system_set();
+        for config in &mut configs.systems {
+            config
+                .graph_info
+                .dependencies
+                .push((DependencyKind::Before, set));
+        }
+        configs
+    }
+
+    /// Runs the systems after the systems of `set`.
+    fn after<M>(self, set: impl IntoSystemSet<M>) -> SystemConfigs {
+        let mut configs = self.into_configs();
+        let set = set.into_system_set();
+        for config in &mut configs.systems {
+            config
+                .graph_info
+                .dependencies
+                .push((DependencyKind::After, set));
+        }
+        configs
+    }
+
+    /// Runs the systems one after the other, in the order they are listed.
+    fn chain(self) -> SystemConfigs {
+        let mut configs = self.into_configs();
+        configs.chained = true;
+        configs
+    }
+}
+
+impl IntoSystemConfigs<()> for SystemConfigs {
+    fn into_configs(self) -> Self {
+        self
+    }
+}
+
+macro_rules! impl_system_collection {
+    ($(($param: ident, $sys: ident)),*) => {
+        impl<$($param, $sys),*> IntoSystemConfigs<($($param,)*)> for ($($sys,)*)
+        where
+            $($sys: IntoSystemConfig<$param>),*
+        {
+            #[allow(non_snake_case)]
+            fn into_configs(self) -> SystemConfigs {
+                let ($($sys,)*) = self;
+                SystemConfigs {
+                    systems: vec![$($sys.into_config(),)*],
+                    chained: false,
+                }
+            }
+        }
+    }
+}
+
+all_tuples!(impl_system_collection, 1, 15, P, S);
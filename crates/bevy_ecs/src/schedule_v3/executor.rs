@@ -0,0 +1,367 @@
+use async_channel::{Receiver, Sender};
+use bevy_tasks::{ComputeTaskPool, TaskPool};
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::Instrument;
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    archetype::ArchetypeComponentId,
+    query::Access,
+    schedule_v3::{evaluate_conditions, BoxedCondition, SetNode, SystemNode, SystemSchedule},
+    world::World,
+};
+
+/// Runs the systems of a schedule with [`ExecutorKind::MultiThreaded`].
+///
+/// The schedule runs in phases: during a phase, the systems that are not exclusive are started on
+/// the [`ComputeTaskPool`] as soon as the systems they depend on have finished and their access
+/// is compatible with the systems that are running. When no such system is left, the next
+/// exclusive system whose dependencies have finished runs alone, and a new phase starts.
+///
+/// [`ExecutorKind::MultiThreaded`]: crate::schedule_v3::ExecutorKind::MultiThreaded
+pub(super) fn run_multi_threaded(
+    systems: &mut [SystemNode],
+    sets: &mut [SetNode],
+    schedule: &SystemSchedule,
+    world: &mut World,
+) {
+    let mut executor = MultiThreadedExecutor::new(systems, sets.len(), schedule);
+    loop {
+        if executor
+            .ready
+            .ones()
+            .any(|index| !executor.exclusive[index])
+        {
+            executor.run_parallel(systems, sets, schedule, world);
+        }
+
+        // Only exclusive systems can be ready at this point.
+        let index = match executor.ready.ones().next() {
+            Some(index) => index,
+            None => break,
+        };
+        executor.ready.set(index, false);
+        let sets_pass = schedule.system_sets[index].iter().all(|&set| {
+            *executor.set_results[set]
+                .get_or_insert_with(|| evaluate_conditions(&mut sets[set].conditions, world))
+        });
+        let node = &mut systems[index];
+        if sets_pass && evaluate_conditions(&mut node.conditions, world) {
+            if schedule.apply_buffers.contains(index) {
+                executor.apply_buffers(systems, schedule, world);
+            } else {
+                #[cfg(feature = "trace")]
+                let _system_span =
+                    bevy_utils::tracing::info_span!("system", name = &*node.system.name())
+                        .entered();
+                node.system.run((), world);
+                executor.unapplied.insert(index);
+            }
+        }
+        executor.finish(index, schedule);
+    }
+    executor.apply_buffers(systems, schedule, world);
+}
+
+struct MultiThreadedExecutor {
+    /// The number of dependencies of each system that haven't finished yet.
+    remaining_dependencies: Vec<usize>,
+    /// Systems whose dependencies have finished, but that haven't run yet.
+    ready: FixedBitSet,
+    /// Systems that have finished or were skipped.
+    finished: FixedBitSet,
+    /// Systems that are currently running.
+    running: FixedBitSet,
+    /// Systems that ran, but whose commands haven't been applied yet.
+    unapplied: FixedBitSet,
+    exclusive: FixedBitSet,
+    is_send: FixedBitSet,
+    /// Whether a non-send system is currently running.
+    non_send_running: bool,
+    /// The result of the run conditions of each set, once evaluated.
+    set_results: Vec<Option<bool>>,
+    system_access: Vec<Access<ArchetypeComponentId>>,
+    /// The access of the run conditions of each system.
+    condition_access: Vec<Access<ArchetypeComponentId>>,
+    /// The access of the run conditions of each set.
+    set_access: Vec<Access<ArchetypeComponentId>>,
+    /// Compound access of the running systems.
+    active_access: Access<ArchetypeComponentId>,
+}
+
+impl MultiThreadedExecutor {
+    fn new(systems: &[SystemNode], set_count: usize, schedule: &SystemSchedule) -> Self {
+        let mut remaining_dependencies = vec![0; systems.len()];
+        for &dependant in schedule.dependants.iter().flatten() {
+            remaining_dependencies[dependant] += 1;
+        }
+        let mut ready = FixedBitSet::with_capacity(systems.len());
+        let mut exclusive = FixedBitSet::with_capacity(systems.len());
+        for (index, node) in systems.iter().enumerate() {
+            ready.set(index, remaining_dependencies[index] == 0);
+            exclusive.set(index, node.system.is_exclusive());
+        }
+        Self {
+            remaining_dependencies,
+            ready,
+            finished: FixedBitSet::with_capacity(systems.len()),
+            running: FixedBitSet::with_capacity(systems.len()),
+            unapplied: FixedBitSet::with_capacity(systems.len()),
+            exclusive,
+            is_send: FixedBitSet::with_capacity(systems.len()),
+            non_send_running: false,
+            set_results: vec![None; set_count],
+            system_access: vec![Access::default(); systems.len()],
+            condition_access: vec![Access::default(); systems.len()],
+            set_access: vec![Access::default(); set_count],
+            active_access: Access::default(),
+        }
+    }
+
+    /// Runs the systems that are not exclusive until none of them can run anymore.
+    fn run_parallel(
+        &mut self,
+        systems: &mut [SystemNode],
+        sets: &mut [SetNode],
+        schedule: &SystemSchedule,
+        world: &mut World,
+    ) {
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!("update_archetypes").entered();
+        for (index, node) in systems.iter_mut().enumerate() {
+            if self.exclusive[index] || self.finished[index] {
+                continue;
+            }
+            node.system.update_archetype_component_access(world);
+            self.system_access[index].extend(node.system.archetype_component_access());
+            self.is_send.set(index, node.system.is_send());
+            update_condition_access(
+                &mut node.conditions,
+                &mut self.condition_access[index],
+                world,
+            );
+        }
+        for (set, node) in sets.iter_mut().enumerate() {
+            update_condition_access(&mut node.conditions, &mut self.set_access[set], world);
+        }
+        #[cfg(feature = "trace")]
+        drop(_span);
+
+        let world: &World = world;
+        let (finish_sender, finish_receiver) = async_channel::unbounded();
+        ComputeTaskPool::init(TaskPool::default).scope(|scope| {
+            let mut starters = vec![None; systems.len()];
+            let mut conditions = Vec::with_capacity(systems.len());
+            for (index, node) in systems.iter_mut().enumerate() {
+                let SystemNode {
+                    system,
+                    conditions: system_conditions,
+                    ..
+                } = node;
+                conditions.push(system_conditions);
+                if self.exclusive[index] || self.finished[index] {
+                    continue;
+                }
+
+                // Each task waits until the executor starts it. The tasks of the systems that
+                // don't run during this phase stop when the executor drops their sender.
+                let (start_sender, start_receiver) = async_channel::bounded(1);
+                starters[index] = Some(start_sender);
+                let finish_sender = finish_sender.clone();
+                #[cfg(feature = "trace")]
+                let system_span = bevy_utils::tracing::info_span!("system", name = &*system.name());
+                let task = async move {
+                    if start_receiver.recv().await.is_err() {
+                        return;
+                    }
+                    {
+                        #[cfg(feature = "trace")]
+                        let _system_guard = system_span.enter();
+                        // SAFETY: the executor only starts systems whose access doesn't conflict
+                        // with the access of the running systems.
+                        unsafe { system.run_unsafe((), world) };
+                    }
+                    finish_sender
+                        .send(index)
+                        .await
+                        .unwrap_or_else(|error| unreachable!("{}", error));
+                };
+                if self.is_send[index] {
+                    scope.spawn(task);
+                } else {
+                    scope.spawn_on_scope(task);
+                }
+            }
+
+            let executor =
+                self.execute(starters, conditions, sets, schedule, world, finish_receiver);
+            #[cfg(feature = "trace")]
+            let executor =
+                executor.instrument(bevy_utils::tracing::info_span!("multi-threaded executor"));
+            // Run conditions are evaluated by the executor, which runs on this thread so that
+            // they can access non-send resources.
+            scope.spawn_on_scope(executor);
+        });
+    }
+
+    async fn execute(
+        &mut self,
+        starters: Vec<Option<Sender<()>>>,
+        mut conditions: Vec<&mut Vec<BoxedCondition>>,
+        sets: &mut [SetNode],
+        schedule: &SystemSchedule,
+        world: &World,
+        finish_receiver: Receiver<usize>,
+    ) {
+        let mut candidates = Vec::new();
+        loop {
+            // Skipping a system can make other systems ready, so keep going until none is skipped.
+            let mut skipped = true;
+            while skipped {
+                skipped = false;
+                candidates.extend(
+                    self.ready
+                        .ones()
+                        .filter(|&index| !self.exclusive[index] && self.can_start(index, schedule)),
+                );
+                for index in candidates.drain(..) {
+                    // Starting the previous candidates may have made this one conflict.
+                    if !self.can_start(index, schedule) {
+                        continue;
+                    }
+                    self.ready.set(index, false);
+                    // SAFETY: the access of the conditions doesn't conflict with the access of
+                    // the running systems, and the conditions don't access the world mutably.
+                    let should_run = unsafe {
+                        self.evaluate_conditions(
+                            index,
+                            conditions[index],
+                            sets,
+                            schedule,
+                            world,
+                        )
+                    };
+                    if should_run {
+                        starters[index]
+                            .as_ref()
+                            .unwrap()
+                            .try_send(())
+                            .unwrap_or_else(|error| unreachable!("{}", error));
+                        self.running.insert(index);
+                        self.unapplied.insert(index);
+                        if !self.is_send[index] {
+                            self.non_send_running = true;
+                        }
+                        self.active_access.extend(&self.system_access[index]);
+                    } else {
+                        self.finish(index, schedule);
+                        skipped = true;
+                    }
+                }
+            }
+
+            if self.running.count_ones(..) == 0 {
+                break;
+            }
+            let index = finish_receiver
+                .recv()
+                .await
+                .unwrap_or_else(|error| unreachable!("{}", error));
+            self.finish_running(index, schedule);
+            while let Ok(index) = finish_receiver.try_recv() {
+                self.finish_running(index, schedule);
+            }
+            self.active_access.clear();
+            for index in self.running.ones() {
+                self.active_access.extend(&self.system_access[index]);
+            }
+        }
+    }
+
+    /// Returns `true` if the system, its run conditions and the run conditions of its sets that
+    /// haven't been evaluated yet don't conflict with the running systems.
+    fn can_start(&self, index: usize, schedule: &SystemSchedule) -> bool {
+        (!self.non_send_running || self.is_send[index])
+            && self.system_access[index].is_compatible(&self.active_access)
+            && self.condition_access[index].is_compatible(&self.active_access)
+            && schedule.system_sets[index].iter().all(|&set| {
+                self.set_results[set].is_some()
+                    || self.set_access[set].is_compatible(&self.active_access)
+            })
+    }
+
+    /// # Safety
+    ///
+    /// The access of the run conditions of the system and of its sets must not conflict with the
+    /// access of the running systems.
+    unsafe fn evaluate_conditions(
+        &mut self,
+        index: usize,
+        conditions: &mut [BoxedCondition],
+        sets: &mut [SetNode],
+        schedule: &SystemSchedule,
+        world: &World,
+    ) -> bool {
+        let sets_pass = schedule.system_sets[index].iter().all(|&set| {
+            *self.set_results[set]
+                .get_or_insert_with(|| evaluate_conditions_unsafe(&mut sets[set].conditions, world))
+        });
+        sets_pass && evaluate_conditions_unsafe(conditions, world)
+    }
+
+    fn finish_running(&mut self, index: usize, schedule: &SystemSchedule) {
+        self.running.set(index, false);
+        if !self.is_send[index] {
+            self.non_send_running = false;
+        }
+        self.finish(index, schedule);
+    }
+
+    fn finish(&mut self, index: usize, schedule: &SystemSchedule) {
+        self.finished.insert(index);
+        for &dependant in &schedule.dependants[index] {
+            self.remaining_dependencies[dependant] -= 1;
+            if self.remaining_dependencies[dependant] == 0 {
+                self.ready.insert(dependant);
+            }
+        }
+    }
+
+    /// Applies the commands of the systems that ran since the last call, in the order of the
+    /// schedule.
+    fn apply_buffers(
+        &mut self,
+        systems: &mut [SystemNode],
+        schedule: &SystemSchedule,
+        world: &mut World,
+    ) {
+        for &index in &schedule.order {
+            if self.unapplied.contains(index) {
+                systems[index].system.apply_buffers(world);
+            }
+        }
+        self.unapplied.clear();
+    }
+}
+
+fn update_condition_access(
+    conditions: &mut [BoxedCondition],
+    access: &mut Access<ArchetypeComponentId>,
+    world: &World,
+) {
+    for condition in conditions {
+        condition.update_archetype_component_access(world);
+        access.extend(condition.archetype_component_access());
+    }
+}
+
+/// # Safety
+///
+/// The access of the conditions must not conflict with the access of the running systems.
+unsafe fn evaluate_conditions_unsafe(conditions: &mut [BoxedCondition], world: &World) -> bool {
+    // All conditions are evaluated, so that their change detection state stays up to date.
+    #[allow(clippy::unnecessary_fold)]
+    conditions.iter_mut().fold(true, |result, condition| {
+        condition.run_unsafe((), world) && result
+    })
+}
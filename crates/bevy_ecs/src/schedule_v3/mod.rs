@@ -0,0 +1,397 @@
+//! Schedules made of systems and system sets ordered by a single dependency graph.
+//!
+//! Unlike the stage-based [`schedule`](crate::schedule) module, a [`Schedule`] has no hard sync
+//! points: systems from any plugin can be ordered relative to each other or to whole
+//! [`SystemSet`]s, run conditions are plain systems returning a `bool` that can be combined with
+//! [`Condition::and_then`], [`Condition::or_else`] and [`common_conditions::not`], and
+//! [`Commands`](crate::system::Commands) are applied at explicit [`apply_system_buffers`] systems.
+//!
+//! Schedules can be stored in the [`Schedules`] resource under a [`ScheduleLabel`] with
+//! [`World::add_schedule`](crate::world::World::add_schedule), and run with
+//! [`World::run_schedule`](crate::world::World::run_schedule), including from exclusive systems.
+//!
+//! This API is opt-in: it lives alongside [`SystemStage`](crate::schedule::SystemStage) and
+//! [`RunCriteria`](crate::schedule::RunCriteria), which the core stages of `App` still use. A
+//! [`Schedule`] is also a [`Stage`](crate::schedule::Stage), so plugins can already share one
+//! dependency graph by adding their systems to a schedule added as a single stage of the app.
+//! Systems run one at a time by default, and in parallel with [`ExecutorKind::MultiThreaded`].
+//!
+//! The core stages of `App` will move to system sets of a [`Schedule`] in a follow-up, after
+//! which `SystemStage` and `RunCriteria` will be deprecated.
+
+mod condition;
+mod config;
+mod executor;
+mod schedule;
+mod set;
+
+pub use self::condition::*;
+pub use self::config::*;
+pub use self::schedule::*;
+pub use self::set::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::{
+        prelude::{Commands, Component, Local, Query, Res, ResMut, Resource, World},
+        schedule::State,
+    };
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    #[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    enum TestSet {
+        A,
+        B,
+    }
+
+    #[derive(ScheduleLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    struct TestSchedule;
+
+    macro_rules! log_system {
+        ($name:ident) => {
+            fn $name(mut log: ResMut<Log>) {
+                log.0.push(stringify!($name));
+            }
+        };
+    }
+
+    log_system!(a);
+    log_system!(b);
+    log_system!(c);
+
+    fn run(schedule: &mut Schedule) -> Vec<&'static str> {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        schedule.run(&mut world);
+        world.remove_resource::<Log>().unwrap().0
+    }
+
+    #[test]
+    fn insertion_order() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(c).add_system(a).add_system(b);
+        assert_eq!(run(&mut schedule), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn system_ordering() {
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(c.after(b))
+            .add_system(b)
+            .add_system(a.before(c).before(b));
+        assert_eq!(run(&mut schedule), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn chain() {
+        let mut schedule = Schedule::new();
+        schedule.add_systems((c, b, a).chain());
+        assert_eq!(run(&mut schedule), vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn set_ordering_and_nesting() {
+        #[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        struct Outer;
+
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(TestSet::B.before(TestSet::A))
+            .configure_set(TestSet::A.in_set(Outer))
+            .add_system(a.in_set(TestSet::A))
+            .add_system(c.after(Outer))
+            .add_system(b.in_set(TestSet::B));
+        assert_eq!(run(&mut schedule), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn run_conditions() {
+        #[derive(Resource)]
+        struct Flag(bool);
+
+        fn flag(flag: Res<Flag>) -> bool {
+            flag.0
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.insert_resource(Flag(false));
+
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(a.run_if(flag))
+            .add_system(b.run_if(common_conditions::not(flag)))
+            .add_system(c.run_if(flag.or_else(common_conditions::resource_exists::<Log>())))
+            .add_system(
+                (|mut log: ResMut<Log>| log.0.push("d"))
+                    .run_if(common_conditions::resource_exists::<Log>().and_then(flag)),
+            );
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["b", "c"]);
+
+        world.resource_mut::<Log>().0.clear();
+        world.resource_mut::<Flag>().0 = true;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["a", "c", "d"]);
+    }
+
+    #[test]
+    fn set_conditions_are_evaluated_once() {
+        fn count_evaluations(mut evaluations: Local<u32>, log: Option<Res<Log>>) -> bool {
+            *evaluations += 1;
+            assert_eq!(*evaluations, 1, "set condition evaluated more than once");
+            log.is_some()
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(TestSet::A.run_if(count_evaluations))
+            .configure_set(TestSet::B.run_if(common_conditions::run_once()))
+            .add_systems((a, b).in_set(TestSet::A))
+            .add_system(c.in_set(TestSet::B));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn state_condition() {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        enum AppState {
+            Menu,
+            InGame,
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.insert_resource(State::new(AppState::Menu));
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(a.run_if(common_conditions::state_equals(AppState::Menu)))
+            .add_system(b.run_if(common_conditions::state_equals(AppState::InGame)));
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["a"]);
+    }
+
+    #[test]
+    fn apply_system_buffers_sync_point() {
+        #[derive(Component)]
+        struct Marker;
+
+        #[derive(Resource, Default)]
+        struct Counts(Vec<usize>);
+
+        fn spawn(mut commands: Commands) {
+            commands.spawn(Marker);
+        }
+
+        fn count(query: Query<&Marker>, mut counts: ResMut<Counts>) {
+            counts.0.push(query.iter().count());
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((spawn, count, apply_system_buffers, count).chain());
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counts>().0, vec![0, 1]);
+        // Commands of the last systems are applied at the end of the schedule.
+        assert_eq!(world.query::<&Marker>().iter(&world).count(), 1);
+
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(ExecutorKind::Simple)
+            .add_systems((spawn, count).chain());
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counts>().0, vec![1]);
+    }
+
+    #[test]
+    fn multi_threaded_executor() {
+        #[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        struct Outer;
+
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(ExecutorKind::MultiThreaded)
+            .configure_set(TestSet::B.before(TestSet::A))
+            .configure_set(TestSet::A.in_set(Outer))
+            .add_system(a.in_set(TestSet::A))
+            .add_system(c.after(Outer))
+            .add_system(b.in_set(TestSet::B))
+            .add_system(
+                (|mut log: ResMut<Log>| log.0.push("skipped"))
+                    .run_if(common_conditions::not(
+                        common_conditions::resource_exists::<Log>(),
+                    ))
+                    .before(TestSet::B),
+            );
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        for _ in 0..10 {
+            schedule.run(&mut world);
+            assert_eq!(
+                std::mem::take(&mut world.resource_mut::<Log>().0),
+                vec!["b", "a", "c"]
+            );
+        }
+    }
+
+    #[test]
+    fn multi_threaded_executor_sync_points() {
+        #[derive(Component)]
+        struct Marker;
+
+        #[derive(Resource, Default)]
+        struct Counts(Vec<usize>);
+
+        fn spawn(mut commands: Commands) {
+            commands.spawn(Marker);
+        }
+
+        fn count(query: Query<&Marker>, mut counts: ResMut<Counts>) {
+            counts.0.push(query.iter().count());
+        }
+
+        fn exclusive(world: &mut World) {
+            world.spawn(Marker);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counts>();
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(ExecutorKind::MultiThreaded)
+            .add_systems((spawn, count, apply_system_buffers, count, exclusive, count).chain());
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counts>().0, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn multi_threaded_executor_respects_access() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        static RUNNING: AtomicBool = AtomicBool::new(false);
+
+        fn write_log(mut log: ResMut<Log>) {
+            assert!(!RUNNING.swap(true, Ordering::SeqCst));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            log.0.push("write");
+            RUNNING.store(false, Ordering::SeqCst);
+        }
+
+        fn read_log(_log: Res<Log>) {
+            assert!(!RUNNING.load(Ordering::SeqCst));
+        }
+
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(ExecutorKind::MultiThreaded)
+            .add_system(write_log)
+            .add_system(read_log)
+            .add_system(write_log)
+            .add_system(read_log)
+            .add_system(write_log);
+        assert_eq!(run(&mut schedule), vec!["write"; 3]);
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(a.after(c))
+            .add_system(b.after(a))
+            .add_system(c.after(b));
+        let err = schedule.initialize(&mut world).unwrap_err();
+        match err {
+            ScheduleBuildError::DependencyCycle(systems) => assert_eq!(systems.len(), 3),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn hierarchy_cycle() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(TestSet::A.in_set(TestSet::B))
+            .configure_set(TestSet::B.in_set(TestSet::A));
+        assert!(matches!(
+            schedule.initialize(&mut world),
+            Err(ScheduleBuildError::HierarchyCycle(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mutable_run_condition_panics() {
+        fn mutable(mut log: ResMut<Log>) -> bool {
+            log.0.clear();
+            true
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut schedule = Schedule::new();
+        schedule.add_system(a.run_if(mutable));
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn labeled_schedules() {
+        fn run_nested(world: &mut World) {
+            world.run_schedule(TestSchedule);
+        }
+
+        #[derive(ScheduleLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        struct Main;
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut nested = Schedule::new();
+        nested.add_system(b);
+        world.add_schedule(TestSchedule, nested);
+        let mut main = Schedule::new();
+        main.add_systems((a, run_nested, c).chain());
+        world.add_schedule(Main, main);
+
+        world.run_schedule(Main);
+        world.run_schedule(Main);
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec!["a", "b", "c", "a", "b", "c"]
+        );
+        assert!(world.resource::<Schedules>().contains(&TestSchedule));
+    }
+
+    #[test]
+    fn schedule_as_stage() {
+        use crate::schedule::{Schedule as StageSchedule, Stage, StageLabel, SystemStage};
+
+        #[derive(StageLabel)]
+        enum TestStage {
+            Before,
+            Graph,
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut graph = Schedule::new();
+        graph.add_system(c.after(b)).add_system(b);
+        let mut stages = StageSchedule::default();
+        stages
+            .add_stage(TestStage::Before, SystemStage::single_threaded().with_system(a))
+            .add_stage(TestStage::Graph, graph);
+        stages.run(&mut world);
+        assert_eq!(world.resource::<Log>().0, vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,674 @@
+use std::{
+    any::TypeId,
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{self, Debug},
+};
+
+use bevy_utils::{tracing::warn, HashMap};
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    self as bevy_ecs,
+    change_detection::CHECK_TICK_THRESHOLD,
+    schedule::Stage,
+    schedule_v3::{
+        executor::run_multi_threaded, BoxedCondition, DependencyKind, GraphInfo, IntoSystemConfig,
+        IntoSystemConfigs, IntoSystemSetConfig, ScheduleLabel, ScheduleLabelId, SystemSetId,
+    },
+    system::{BoxedSystem, IntoSystem, Resource, System},
+    world::World,
+};
+
+/// Resource that stores [`Schedule`]s mapped to [`ScheduleLabel`]s.
+#[derive(Default, Resource)]
+pub struct Schedules {
+    inner: HashMap<ScheduleLabelId, Schedule>,
+}
+
+impl Schedules {
+    /// Constructs an empty `Schedules` with zero initial capacity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a labeled schedule into the map.
+    ///
+    /// If the map already had an entry for `label`, `schedule` is inserted,
+    /// and the old schedule is returned. Otherwise, `None` is returned.
+    pub fn insert(&mut self, label: impl ScheduleLabel, schedule: Schedule) -> Option<Schedule> {
+        self.inner.insert(label.as_label(), schedule)
+    }
+
+    /// Removes the schedule corresponding to the `label` from the map, returning it if it existed.
+    pub fn remove(&mut self, label: &dyn ScheduleLabel) -> Option<Schedule> {
+        self.inner.remove(&label.as_label())
+    }
+
+    /// Returns `true` if a schedule is stored for `label`.
+    pub fn contains(&self, label: &dyn ScheduleLabel) -> bool {
+        self.inner.contains_key(&label.as_label())
+    }
+
+    /// Returns a reference to the schedule associated with `label`, if it exists.
+    pub fn get(&self, label: &dyn ScheduleLabel) -> Option<&Schedule> {
+        self.inner.get(&label.as_label())
+    }
+
+    /// Returns a mutable reference to the schedule associated with `label`, if it exists.
+    pub fn get_mut(&mut self, label: &dyn ScheduleLabel) -> Option<&mut Schedule> {
+        self.inner.get_mut(&label.as_label())
+    }
+
+    /// Returns an iterator over the labels and schedules stored in this map.
+    pub fn iter(&self) -> impl Iterator<Item = (&ScheduleLabelId, &Schedule)> {
+        self.inner.iter()
+    }
+
+    /// Iterates the change ticks of all systems in all stored schedules and clamps any older than
+    /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
+    /// This prevents overflow and thus prevents false positives.
+    pub fn check_change_ticks(&mut self, change_tick: u32) {
+        for schedule in self.inner.values_mut() {
+            schedule.check_change_ticks(change_tick);
+        }
+    }
+}
+
+impl World {
+    /// Stores `schedule` under `label` in the [`Schedules`] resource, creating it if needed.
+    ///
+    /// Returns the schedule previously stored under `label`, if any.
+    pub fn add_schedule(
+        &mut self,
+        label: impl ScheduleLabel,
+        schedule: Schedule,
+    ) -> Option<Schedule> {
+        self.get_resource_or_insert_with(Schedules::default)
+            .insert(label, schedule)
+    }
+
+    /// Runs the [`Schedule`] stored under `label` in the [`Schedules`] resource.
+    ///
+    /// The schedule is removed from the resource while it runs, so it can be run from an
+    /// exclusive system of another schedule, but not from one of its own systems.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no schedule is stored under `label`.
+    pub fn run_schedule(&mut self, label: impl ScheduleLabel) {
+        let label = label.as_label();
+        let mut schedule = self
+            .get_resource_mut::<Schedules>()
+            .and_then(|mut schedules| schedules.inner.remove(&label))
+            .unwrap_or_else(|| panic!("The schedule with the label {:?} was not found.", label));
+        schedule.run(self);
+        self.get_resource_or_insert_with(Schedules::default)
+            .inner
+            .insert(label, schedule);
+    }
+}
+
+/// Specifies how a [`Schedule`] runs its systems and applies their
+/// [`Commands`](crate::system::Commands).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutorKind {
+    /// Runs the systems one at a time, and only applies commands at [`apply_system_buffers`]
+    /// systems and at the end of the schedule.
+    #[default]
+    SingleThreaded,
+    /// Runs the systems one at a time, and applies the commands of each system right after it
+    /// runs. [`apply_system_buffers`] systems are no-ops.
+    Simple,
+    /// Runs the systems in parallel on the [`ComputeTaskPool`](bevy_tasks::ComputeTaskPool),
+    /// following the dependency graph of the schedule. Systems whose data access conflicts never
+    /// run at the same time, and exclusive systems run alone. Commands are applied like with
+    /// [`ExecutorKind::SingleThreaded`].
+    ///
+    /// Systems that aren't ordered relative to each other may run in any order.
+    MultiThreaded,
+}
+
+/// An exclusive system that applies the [`Commands`](crate::system::Commands) of every system
+/// that ran before it in the schedule, making them visible to the systems that run after it.
+///
+/// ```
+/// # use bevy_ecs::prelude::{Commands, Component, Query, World};
+/// # use bevy_ecs::schedule_v3::{apply_system_buffers, IntoSystemConfigs, Schedule};
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// fn spawn(mut commands: Commands) {
+///     commands.spawn(Enemy);
+/// }
+///
+/// fn count(query: Query<&Enemy>) {
+///     assert_eq!(query.iter().count(), 1);
+/// }
+///
+/// let mut world = World::new();
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems((spawn, apply_system_buffers, count).chain());
+/// schedule.run(&mut world);
+/// ```
+pub fn apply_system_buffers(_world: &mut World) {}
+
+/// Returns `true` if `system` is [`apply_system_buffers`].
+fn is_apply_system_buffers(system: &dyn System<In = (), Out = ()>) -> bool {
+    fn system_type_id<P, S: IntoSystem<(), (), P>>(_: &S) -> TypeId {
+        TypeId::of::<S::System>()
+    }
+    System::type_id(system) == system_type_id(&apply_system_buffers)
+}
+
+pub(super) struct SystemNode {
+    pub(super) system: BoxedSystem,
+    type_set: SystemSetId,
+    graph_info: GraphInfo,
+    pub(super) conditions: Vec<BoxedCondition>,
+}
+
+pub(super) struct SetNode {
+    set: SystemSetId,
+    graph_info: GraphInfo,
+    pub(super) conditions: Vec<BoxedCondition>,
+    /// The number of conditions that were already initialized.
+    initialized_conditions: usize,
+}
+
+/// The order in which the systems of a [`Schedule`] run, computed from its dependency graph.
+#[derive(Default)]
+pub(super) struct SystemSchedule {
+    pub(super) order: Vec<usize>,
+    /// The indices of the systems that must run after each system.
+    pub(super) dependants: Vec<Vec<usize>>,
+    /// The indices of the sets each system is in, directly or through other sets.
+    pub(super) system_sets: Vec<Vec<usize>>,
+    pub(super) apply_buffers: FixedBitSet,
+}
+
+/// An error that occurred when building a [`Schedule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleBuildError {
+    /// A system set contains itself, directly or through other sets.
+    HierarchyCycle(String),
+    /// The systems form a dependency cycle, listed in order.
+    DependencyCycle(Vec<String>),
+}
+
+impl std::error::Error for ScheduleBuildError {}
+
+impl fmt::Display for ScheduleBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleBuildError::HierarchyCycle(set) => {
+                write!(f, "System set {} contains itself.", set)
+            }
+            ScheduleBuildError::DependencyCycle(systems) => {
+                write!(f, "Found a dependency cycle between systems: ")?;
+                for system in systems {
+                    write!(f, "{} -> ", system)?;
+                }
+                write!(f, "{}", systems[0])
+            }
+        }
+    }
+}
+
+/// A collection of systems and system sets, ordered by a single dependency graph.
+///
+/// Systems and sets can be ordered relative to each other with
+/// [`before`](IntoSystemConfig::before) and [`after`](IntoSystemConfig::after), can be nested in
+/// sets with [`in_set`](IntoSystemConfig::in_set), and only run if all of their run conditions
+/// and the run conditions of their sets are `true`. Systems without an ordering relation run in
+/// the order they were added.
+///
+/// Unlike [`SystemStage`](crate::schedule::SystemStage), a `Schedule` has no implicit sync
+/// points: [`Commands`](crate::system::Commands) are applied at explicit
+/// [`apply_system_buffers`] systems, and at the end of the schedule.
+///
+/// ```
+/// # use bevy_ecs::prelude::{Res, ResMut, Resource, World};
+/// # use bevy_ecs::schedule_v3::{
+/// #     common_conditions::resource_exists, IntoSystemConfig, IntoSystemSetConfig, Schedule,
+/// #     SystemSet,
+/// # };
+/// #[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// struct Physics;
+///
+/// #[derive(Resource, Default)]
+/// struct Steps(Vec<&'static str>);
+///
+/// fn integrate(mut steps: ResMut<Steps>) {
+///     steps.0.push("integrate");
+/// }
+///
+/// fn input(mut steps: ResMut<Steps>) {
+///     steps.0.push("input");
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Steps>();
+///
+/// let mut schedule = Schedule::new();
+/// schedule
+///     .configure_set(Physics.run_if(resource_exists::<Steps>()))
+///     .add_system(integrate.in_set(Physics))
+///     .add_system(input.before(Physics));
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Steps>().0, vec!["input", "integrate"]);
+/// ```
+#[derive(Default)]
+pub struct Schedule {
+    systems: Vec<SystemNode>,
+    sets: Vec<SetNode>,
+    set_indices: HashMap<SystemSetId, usize>,
+    chains: Vec<(usize, usize)>,
+    uninitialized_systems: usize,
+    executable: Option<SystemSchedule>,
+    executor_kind: ExecutorKind,
+    last_tick_check: u32,
+}
+
+impl Debug for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let systems: Vec<_> = self.systems.iter().map(|node| node.system.name()).collect();
+        let sets: Vec<_> = self.sets.iter().map(|node| node.set).collect();
+        f.debug_struct("Schedule")
+            .field("systems", &systems)
+            .field("sets", &sets)
+            .field("executor_kind", &self.executor_kind)
+            .finish()
+    }
+}
+
+/// Runs the schedule as a stage, so that it can be added to an `App` next to the
+/// [`SystemStage`](crate::schedule::SystemStage)s of the core stages.
+impl Stage for Schedule {
+    fn run(&mut self, world: &mut World) {
+        Schedule::run(self, world);
+    }
+}
+
+impl Schedule {
+    /// Constructs an empty `Schedule`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a system to the schedule.
+    pub fn add_system<P>(&mut self, system: impl IntoSystemConfig<P>) -> &mut Self {
+        self.add_system_inner(system.into_config());
+        self
+    }
+
+    /// Adds several systems to the schedule, which run in order if they were
+    /// [`chain`](IntoSystemConfigs::chain)ed.
+    pub fn add_systems<P>(&mut self, systems: impl IntoSystemConfigs<P>) -> &mut Self {
+        let configs = systems.into_configs();
+        let mut previous = None;
+        for config in configs.systems {
+            let index = self.add_system_inner(config);
+            if let (true, Some(previous)) = (configs.chained, previous) {
+                self.chains.push((previous, index));
+            }
+            previous = Some(index);
+        }
+        self
+    }
+
+    /// Configures a system set in the schedule, adding it if it doesn't exist yet.
+    ///
+    /// The ordering, hierarchy and run conditions of the configuration are added to those of
+    /// previous configurations of the same set.
+    pub fn configure_set(&mut self, set: impl IntoSystemSetConfig) -> &mut Self {
+        let config = set.into_config();
+        for &set in &config.graph_info.sets {
+            self.set_index(set);
+        }
+        let index = self.set_index(config.set);
+        let node = &mut self.sets[index];
+        node.graph_info.sets.extend(config.graph_info.sets);
+        node.graph_info
+            .dependencies
+            .extend(config.graph_info.dependencies);
+        node.conditions.extend(config.conditions);
+        self.executable = None;
+        self
+    }
+
+    /// Changes how the systems of the schedule are run and their commands applied.
+    pub fn set_executor_kind(&mut self, executor_kind: ExecutorKind) -> &mut Self {
+        self.executor_kind = executor_kind;
+        self
+    }
+
+    /// Returns how the systems of the schedule are run and their commands applied.
+    pub fn executor_kind(&self) -> ExecutorKind {
+        self.executor_kind
+    }
+
+    fn add_system_inner(&mut self, config: crate::schedule_v3::SystemConfig) -> usize {
+        for &set in &config.graph_info.sets {
+            self.set_index(set);
+        }
+        self.systems.push(SystemNode {
+            system: config.system,
+            type_set: config.type_set,
+            graph_info: config.graph_info,
+            conditions: config.conditions,
+        });
+        self.executable = None;
+        self.systems.len() - 1
+    }
+
+    fn set_index(&mut self, set: SystemSetId) -> usize {
+        let sets = &mut self.sets;
+        *self.set_indices.entry(set).or_insert_with(|| {
+            sets.push(SetNode {
+                set,
+                graph_info: GraphInfo::default(),
+                conditions: Vec::new(),
+                initialized_conditions: 0,
+            });
+            sets.len() - 1
+        })
+    }
+
+    /// Initializes the systems and run conditions added since the last call, and computes the
+    /// order in which the systems run if the schedule changed.
+    pub fn initialize(&mut self, world: &mut World) -> Result<(), ScheduleBuildError> {
+        for node in &mut self.systems[self.uninitialized_systems..] {
+            node.system.initialize(world);
+            initialize_conditions(&mut node.conditions, world);
+        }
+        self.uninitialized_systems = self.systems.len();
+        for node in &mut self.sets {
+            initialize_conditions(&mut node.conditions[node.initialized_conditions..], world);
+            node.initialized_conditions = node.conditions.len();
+        }
+
+        if self.executable.is_none() {
+            self.executable = Some(self.build()?);
+        }
+        Ok(())
+    }
+
+    /// Runs all systems of the schedule on the world, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schedule can't be built, see [`ScheduleBuildError`].
+    pub fn run(&mut self, world: &mut World) {
+        if let Err(err) = self.initialize(world) {
+            panic!("{}", err);
+        }
+        match self.executor_kind {
+            ExecutorKind::SingleThreaded | ExecutorKind::Simple => self.run_single_threaded(world),
+            ExecutorKind::MultiThreaded => run_multi_threaded(
+                &mut self.systems,
+                &mut self.sets,
+                self.executable.as_ref().unwrap(),
+                world,
+            ),
+        }
+
+        let change_tick = world.change_tick();
+        if change_tick.wrapping_sub(self.last_tick_check) >= CHECK_TICK_THRESHOLD {
+            self.check_change_ticks(change_tick);
+            world.check_change_ticks();
+            self.last_tick_check = change_tick;
+        }
+    }
+
+    fn run_single_threaded(&mut self, world: &mut World) {
+        let executable = self.executable.as_ref().unwrap();
+        let mut set_results = vec![None; self.sets.len()];
+        let mut unapplied_systems = Vec::new();
+
+        for &index in &executable.order {
+            let sets_pass = executable.system_sets[index].iter().all(|&set| {
+                *set_results[set].get_or_insert_with(|| {
+                    evaluate_conditions(&mut self.sets[set].conditions, world)
+                })
+            });
+            let node = &mut self.systems[index];
+            if !sets_pass || !evaluate_conditions(&mut node.conditions, world) {
+                continue;
+            }
+
+            if executable.apply_buffers.contains(index) {
+                for unapplied in unapplied_systems.drain(..) {
+                    let node: &mut SystemNode = &mut self.systems[unapplied];
+                    node.system.apply_buffers(world);
+                }
+                continue;
+            }
+
+            #[cfg(feature = "trace")]
+            let _system_span =
+                bevy_utils::tracing::info_span!("system", name = &*node.system.name()).entered();
+            node.system.run((), world);
+            if self.executor_kind == ExecutorKind::Simple {
+                node.system.apply_buffers(world);
+            } else {
+                unapplied_systems.push(index);
+            }
+        }
+
+        for unapplied in unapplied_systems {
+            self.systems[unapplied].system.apply_buffers(world);
+        }
+    }
+
+    /// Iterates the change ticks of all systems in the schedule and clamps any older than
+    /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
+    /// This prevents overflow and thus prevents false positives.
+    pub fn check_change_ticks(&mut self, change_tick: u32) {
+        for node in &mut self.systems {
+            node.system.check_change_tick(change_tick);
+            for condition in &mut node.conditions {
+                condition.check_change_tick(change_tick);
+            }
+        }
+        for node in &mut self.sets {
+            for condition in &mut node.conditions {
+                condition.check_change_tick(change_tick);
+            }
+        }
+    }
+
+    fn build(&self) -> Result<SystemSchedule, ScheduleBuildError> {
+        // The sets each set is in, directly or not.
+        let mut set_ancestors = Vec::with_capacity(self.sets.len());
+        for index in 0..self.sets.len() {
+            let ancestors = self.collect_ancestors(&self.sets[index].graph_info.sets);
+            if ancestors.contains(index) {
+                return Err(ScheduleBuildError::HierarchyCycle(format!(
+                    "{:?}",
+                    self.sets[index].set
+                )));
+            }
+            set_ancestors.push(ancestors);
+        }
+
+        let mut system_sets = Vec::with_capacity(self.systems.len());
+        let mut set_members = vec![FixedBitSet::with_capacity(self.systems.len()); self.sets.len()];
+        let mut type_set_members: HashMap<SystemSetId, FixedBitSet> = HashMap::default();
+        for (index, node) in self.systems.iter().enumerate() {
+            let sets = self.collect_ancestors(&node.graph_info.sets);
+            for set in sets.ones() {
+                set_members[set].insert(index);
+            }
+            system_sets.push(sets.ones().collect::<Vec<_>>());
+            type_set_members
+                .entry(node.type_set)
+                .or_insert_with(|| FixedBitSet::with_capacity(self.systems.len()))
+                .insert(index);
+        }
+
+        let members_of = |set: SystemSetId| -> Option<FixedBitSet> {
+            let mut members = FixedBitSet::with_capacity(self.systems.len());
+            let mut known = false;
+            if let Some(&index) = self.set_indices.get(&set) {
+                members.union_with(&set_members[index]);
+                known = true;
+            }
+            if let Some(systems) = type_set_members.get(&set) {
+                members.union_with(systems);
+                known = true;
+            }
+            known.then_some(members)
+        };
+
+        // Edges from each system to the systems that must run after it.
+        let mut dependants = vec![Vec::new(); self.systems.len()];
+        let mut add_dependencies =
+            |name: &dyn Debug, nodes: &FixedBitSet, graph_info: &GraphInfo| {
+                for &(kind, target) in &graph_info.dependencies {
+                    let targets = match members_of(target) {
+                        Some(targets) => targets,
+                        None => {
+                            warn!(
+                                "{:?} wants to run {:?} unknown set: {:?}",
+                                name, kind, target
+                            );
+                            continue;
+                        }
+                    };
+                    for node in nodes.ones() {
+                        for target in targets.ones() {
+                            match kind {
+                                DependencyKind::Before => dependants[node].push(target),
+                                DependencyKind::After => dependants[target].push(node),
+                            }
+                        }
+                    }
+                }
+            };
+        for (index, node) in self.systems.iter().enumerate() {
+            let mut nodes = FixedBitSet::with_capacity(self.systems.len());
+            nodes.insert(index);
+            add_dependencies(&node.system.name(), &nodes, &node.graph_info);
+        }
+        for (index, node) in self.sets.iter().enumerate() {
+            add_dependencies(&node.set, &set_members[index], &node.graph_info);
+        }
+        for &(before, after) in &self.chains {
+            dependants[before].push(after);
+        }
+
+        let order = self.topological_order(&dependants)?;
+        let mut apply_buffers = FixedBitSet::with_capacity(self.systems.len());
+        for (index, node) in self.systems.iter().enumerate() {
+            apply_buffers.set(index, is_apply_system_buffers(&*node.system));
+        }
+
+        Ok(SystemSchedule {
+            order,
+            dependants,
+            system_sets,
+            apply_buffers,
+        })
+    }
+
+    /// Returns the indices of the given sets and of all the sets they are in.
+    fn collect_ancestors(&self, sets: &[SystemSetId]) -> FixedBitSet {
+        let mut ancestors = FixedBitSet::with_capacity(self.sets.len());
+        let mut stack: Vec<usize> = sets.iter().map(|set| self.set_indices[set]).collect();
+        while let Some(index) = stack.pop() {
+            if ancestors.put(index) {
+                continue;
+            }
+            stack.extend(
+                self.sets[index]
+                    .graph_info
+                    .sets
+                    .iter()
+                    .map(|set| self.set_indices[set]),
+            );
+        }
+        ancestors
+    }
+
+    /// Sorts the systems so that each system runs after its dependencies, keeping the order
+    /// in which they were added otherwise.
+    fn topological_order(
+        &self,
+        dependants: &[Vec<usize>],
+    ) -> Result<Vec<usize>, ScheduleBuildError> {
+        let mut dependency_counts = vec![0usize; dependants.len()];
+        for &dependant in dependants.iter().flatten() {
+            dependency_counts[dependant] += 1;
+        }
+        let mut ready: BinaryHeap<_> = dependency_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count == 0)
+            .map(|(index, _)| Reverse(index))
+            .collect();
+
+        let mut order = Vec::with_capacity(dependants.len());
+        while let Some(Reverse(index)) = ready.pop() {
+            order.push(index);
+            for &dependant in &dependants[index] {
+                dependency_counts[dependant] -= 1;
+                if dependency_counts[dependant] == 0 {
+                    ready.push(Reverse(dependant));
+                }
+            }
+        }
+
+        if order.len() == dependants.len() {
+            return Ok(order);
+        }
+
+        // Every remaining system depends on another remaining system, so walking their
+        // dependencies until one repeats finds a cycle.
+        let mut dependencies = vec![Vec::new(); dependants.len()];
+        for (index, dependants) in dependants.iter().enumerate() {
+            for &dependant in dependants {
+                dependencies[dependant].push(index);
+            }
+        }
+        let mut path = Vec::new();
+        let mut visited = FixedBitSet::with_capacity(dependants.len());
+        let mut current = (0..dependants.len())
+            .find(|&index| dependency_counts[index] > 0)
+            .unwrap();
+        while !visited.put(current) {
+            path.push(current);
+            current = *dependencies[current]
+                .iter()
+                .find(|&&dependency| dependency_counts[dependency] > 0)
+                .unwrap();
+        }
+        let start = path.iter().position(|&index| index == current).unwrap();
+        Err(ScheduleBuildError::DependencyCycle(
+            path[start..]
+                .iter()
+                .rev()
+                .map(|&index| self.systems[index].system.name().into_owned())
+                .collect(),
+        ))
+    }
+}
+
+fn initialize_conditions(conditions: &mut [BoxedCondition], world: &mut World) {
+    for condition in conditions {
+        condition.initialize(world);
+        assert!(
+            !condition.is_exclusive() && condition.component_access().writes().next().is_none(),
+            "Run condition {} must not access the World mutably.",
+            condition.name()
+        );
+    }
+}
+
+pub(super) fn evaluate_conditions(conditions: &mut [BoxedCondition], world: &mut World) -> bool {
+    // All conditions are evaluated, so that their change detection state stays up to date.
+    #[allow(clippy::unnecessary_fold)]
+    conditions
+        .iter_mut()
+        .fold(true, |result, condition| condition.run((), world) && result)
+}
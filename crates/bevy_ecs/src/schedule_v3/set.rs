@@ -0,0 +1,92 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+pub use bevy_ecs_macros::{ScheduleLabel, SystemSet};
+use bevy_utils::define_label;
+
+use crate::system::{
+    ExclusiveSystemParam, ExclusiveSystemParamFunction, IsExclusiveFunctionSystem,
+    IsFunctionSystem, SystemParam, SystemParamFunction,
+};
+
+define_label!(
+    /// A strongly-typed class of labels used to identify [`Schedule`](super::Schedule)s.
+    ScheduleLabel,
+    /// Strongly-typed identifier for a [`ScheduleLabel`].
+    ScheduleLabelId,
+);
+define_label!(
+    /// A strongly-typed class of labels used to identify groups of systems in a
+    /// [`Schedule`](super::Schedule).
+    ///
+    /// Sets can be ordered relative to each other and to systems, can be nested inside of other
+    /// sets, and can have run conditions, which apply to every system they contain.
+    SystemSet,
+    /// Strongly-typed identifier for a [`SystemSet`].
+    SystemSetId,
+);
+
+/// A [`SystemSet`] grouping all instances of the system type `T`.
+///
+/// Every system added to a [`Schedule`](super::Schedule) is in the set of its own type, which
+/// is how systems can be ordered relative to system functions, as in `a.before(b)`.
+pub struct SystemTypeSet<T: 'static>(PhantomData<fn() -> T>);
+
+impl<T: 'static> SystemTypeSet<T> {
+    pub(crate) fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: 'static> SystemSet for SystemTypeSet<T> {
+    #[inline]
+    fn as_str(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+}
+
+impl<T> Debug for SystemTypeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SystemTypeSet")
+            .field(&std::any::type_name::<T>())
+            .finish()
+    }
+}
+
+impl<T> Clone for SystemTypeSet<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SystemTypeSet<T> {}
+
+/// Types that can be converted into a [`SystemSetId`]: [`SystemSet`]s, and system functions,
+/// which are converted to their [`SystemTypeSet`].
+pub trait IntoSystemSet<Marker> {
+    fn into_system_set(self) -> SystemSetId;
+}
+
+impl<S: SystemSet> IntoSystemSet<()> for S {
+    #[inline]
+    fn into_system_set(self) -> SystemSetId {
+        self.as_label()
+    }
+}
+
+impl<In, Out, Param: SystemParam, Marker, F: SystemParamFunction<In, Out, Param, Marker>>
+    IntoSystemSet<(IsFunctionSystem, In, Out, Param, Marker)> for F
+{
+    #[inline]
+    fn into_system_set(self) -> SystemSetId {
+        SystemTypeSet::<F>::new().as_label()
+    }
+}
+
+impl<Param: ExclusiveSystemParam, Marker, F: ExclusiveSystemParamFunction<Param, Marker>>
+    IntoSystemSet<(IsExclusiveFunctionSystem, Param, Marker)> for F
+{
+    #[inline]
+    fn into_system_set(self) -> SystemSetId {
+        SystemTypeSet::<F>::new().as_label()
+    }
+}
@@ -1,14 +1,44 @@
-use std::mem::{ManuallyDrop, MaybeUninit};
-
-use super::Command;
-use crate::world::World;
+use std::{
+    alloc::Layout,
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr::NonNull,
+};
+
+use super::{Command, Insert, Remove};
+use crate::{
+    bundle::{Bundle, BundleId, ComponentPtrs},
+    component::ComponentId,
+    entity::Entity,
+    world::World,
+};
+use bevy_ptr::OwningPtr;
 
 struct CommandMeta {
     offset: usize,
     func: unsafe fn(value: *mut MaybeUninit<u8>, world: &mut World),
+    /// Set for the [`Insert`] and [`Remove`] commands of [`EntityCommands`](super::EntityCommands),
+    /// so that consecutive ones targeting the same entity can be merged.
+    bundle_meta: Option<BundleCommandMeta>,
+}
+
+#[derive(Clone, Copy)]
+struct BundleCommandMeta {
+    entity: Entity,
+    insert: bool,
+    /// Returns the id of the bundle inserted or removed by the command.
+    bundle_id: fn(world: &mut World) -> BundleId,
+    take_components: TakeComponentsFn,
 }
 
+/// Consumes a command, passing ownership of each of its component values to `func`.
+type TakeComponentsFn = unsafe fn(value: *mut MaybeUninit<u8>, func: &mut dyn FnMut(OwningPtr<'_>));
+
 /// A queue of [`Command`]s
+///
+/// Consecutive commands inserting components into the same entity are applied with a single
+/// archetype move, as if their bundles were inserted together, unless they insert the same
+/// component several times or their components have hooks. The same goes for consecutive commands
+/// removing components from the same entity.
 //
 // NOTE: [`CommandQueue`] is implemented via a `Vec<MaybeUninit<u8>>` over a `Vec<Box<dyn Command>>`
 // as an optimization. Since commands are used frequently in systems as a way to spawn
@@ -31,6 +61,62 @@ impl CommandQueue {
     /// Push a [`Command`] onto the queue.
     #[inline]
     pub fn push<C>(&mut self, command: C)
+    where
+        C: Command,
+    {
+        self.push_with_meta(command, None);
+    }
+
+    /// Push an [`Insert`] command onto the queue, which can be merged with its neighbours.
+    #[inline]
+    pub(crate) fn push_insert<B: Bundle>(&mut self, entity: Entity, bundle: B) {
+        /// SAFETY: This function is only called when the `value` bytes are an `Insert<B>`, and
+        /// only reads them via `read_unaligned`.
+        unsafe fn take_components<B: Bundle>(
+            value: *mut MaybeUninit<u8>,
+            mut func: &mut dyn FnMut(OwningPtr<'_>),
+        ) {
+            let command = value.cast::<Insert<B>>().read_unaligned();
+            command.bundle.get_components(&mut func);
+        }
+
+        self.push_with_meta(
+            Insert { entity, bundle },
+            Some(BundleCommandMeta {
+                entity,
+                insert: true,
+                bundle_id: bundle_id::<B>,
+                take_components: take_components::<B>,
+            }),
+        );
+    }
+
+    /// Push a [`Remove`] command onto the queue, which can be merged with its neighbours.
+    #[inline]
+    pub(crate) fn push_remove<B: Bundle>(&mut self, entity: Entity) {
+        /// SAFETY: `Remove` commands hold no values and don't need to be dropped.
+        unsafe fn take_components(
+            _value: *mut MaybeUninit<u8>,
+            _func: &mut dyn FnMut(OwningPtr<'_>),
+        ) {
+        }
+
+        self.push_with_meta(
+            Remove::<B> {
+                entity,
+                phantom: Default::default(),
+            },
+            Some(BundleCommandMeta {
+                entity,
+                insert: false,
+                bundle_id: bundle_id::<B>,
+                take_components,
+            }),
+        );
+    }
+
+    #[inline]
+    fn push_with_meta<C>(&mut self, command: C, bundle_meta: Option<BundleCommandMeta>)
     where
         C: Command,
     {
@@ -48,6 +134,7 @@ impl CommandQueue {
         self.metas.push(CommandMeta {
             offset: old_len,
             func: write_command::<C>,
+            bundle_meta,
         });
 
         // Use `ManuallyDrop` to forget `command` right away, avoiding
@@ -87,15 +174,238 @@ impl CommandQueue {
         // This operation is so that we can reuse the bytes `Vec<u8>`'s internal storage and prevent
         // unnecessary allocations.
         unsafe { self.bytes.set_len(0) };
+        let bytes = self.bytes.as_mut_ptr();
+
+        let mut metas = self.metas.drain(..).peekable();
+        let mut run = Vec::new();
+        let mut scratch = BundleScratch::default();
+        while let Some(meta) = metas.next() {
+            let Some(bundle_meta) = meta.bundle_meta else {
+                // SAFETY: The implementation of `write_command` is safe for the according Command type.
+                // It's ok to read from `bytes` because we just wrote to it in `push`.
+                // The bytes are safely cast to their original type, safely read, and then dropped.
+                unsafe { (meta.func)(bytes.add(meta.offset), world) };
+                continue;
+            };
+
+            run.clear();
+            run.push(meta);
+            while let Some(next) = metas.next_if(|next| {
+                matches!(next.bundle_meta, Some(next) if next.entity == bundle_meta.entity && next.insert == bundle_meta.insert)
+            }) {
+                run.push(next);
+            }
+            // SAFETY: The commands of `run` were written to `bytes` in `push_insert` or
+            // `push_remove`, and haven't been applied yet.
+            unsafe { apply_bundle_commands(world, bytes, &run, bundle_meta, &mut scratch) };
+        }
+    }
+}
 
-        for meta in self.metas.drain(..) {
-            // SAFETY: The implementation of `write_command` is safe for the according Command type.
-            // It's ok to read from `bytes.as_mut_ptr()` because we just wrote to it in `push`.
-            // The bytes are safely cast to their original type, safely read, and then dropped.
-            unsafe {
-                (meta.func)(self.bytes.as_mut_ptr().add(meta.offset), world);
+/// State reused across the runs of bundle commands applied by [`CommandQueue::apply`].
+///
+/// Systems usually queue the same commands for many entities, so the merged bundle of a run is
+/// kept, and reused as long as the following runs are made of the same bundles.
+#[derive(Default)]
+struct BundleScratch {
+    /// The bundles of the commands of the last run.
+    bundle_ids: Vec<BundleId>,
+    insert: bool,
+    /// The merged bundle of the last run, or `None` if its commands can't be merged.
+    merged: Option<MergedBundle>,
+    component_ids: Vec<ComponentId>,
+    /// The offset and size of each component of an inserted merged bundle, in its buffer.
+    fields: Vec<(usize, usize)>,
+}
+
+#[derive(Clone, Copy)]
+struct MergedBundle {
+    id: BundleId,
+    /// The layout of the buffer holding the components of an inserted merged bundle.
+    layout: Layout,
+}
+
+impl BundleScratch {
+    /// Updates the merged bundle for a `run` of bundle commands.
+    fn update(&mut self, world: &mut World, insert: bool, run: &[CommandMeta]) {
+        let mut changed = self.insert != insert || self.bundle_ids.len() != run.len();
+        self.insert = insert;
+        self.bundle_ids.truncate(run.len());
+        for (i, meta) in run.iter().enumerate() {
+            let bundle_id = (meta.bundle_meta.unwrap().bundle_id)(world);
+            match self.bundle_ids.get_mut(i) {
+                Some(previous) if *previous == bundle_id => {}
+                Some(previous) => {
+                    *previous = bundle_id;
+                    changed = true;
+                }
+                None => {
+                    self.bundle_ids.push(bundle_id);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return;
+        }
+
+        self.component_ids.clear();
+        for &bundle_id in &self.bundle_ids {
+            let components = world.bundles.get(bundle_id).unwrap().components();
+            self.component_ids.extend_from_slice(components);
+        }
+        if insert {
+            // Runs are short, so a quadratic scan is cheaper than sorting a copy of the ids.
+            let component_ids = &self.component_ids;
+            let has_duplicates = component_ids
+                .iter()
+                .enumerate()
+                .any(|(i, id)| component_ids[..i].contains(id));
+            if has_duplicates {
+                // The later values of a component replace the earlier ones, which a single
+                // bundle can't express.
+                self.merged = None;
+                return;
+            }
+        } else {
+            self.component_ids.sort_unstable();
+            self.component_ids.dedup();
+        }
+
+        // SAFETY: The ids come from bundles of this world, and were deduplicated above.
+        let id = unsafe {
+            world
+                .bundles
+                .init_dynamic_info(&mut world.components, &self.component_ids)
+                .id()
+        };
+        // Lay the values out like a struct with a field for each component.
+        let mut layout = Layout::new::<()>();
+        self.fields.clear();
+        if insert {
+            for &component_id in &self.component_ids {
+                // SAFETY: The ids come from bundles of this world.
+                let component_layout =
+                    unsafe { world.components.get_info_unchecked(component_id) }.layout();
+                let (extended, offset) = layout.extend(component_layout).unwrap();
+                layout = extended;
+                self.fields.push((offset, component_layout.size()));
+            }
+        }
+        self.merged = Some(MergedBundle { id, layout });
+    }
+}
+
+fn bundle_id<B: Bundle>(world: &mut World) -> BundleId {
+    world
+        .bundles
+        .init_info::<B>(&mut world.components, &mut world.storages)
+        .id()
+}
+
+/// Returns `true` if inserting or removing the bundle runs component hooks.
+///
+/// A hook can observe the components inserted or removed by the previous commands, so the
+/// commands of a run with hooks are applied one by one, running the hooks of each command in order.
+fn has_hooks(world: &World, bundle_id: BundleId, insert: bool) -> bool {
+    let bundle_info = world.bundles.get(bundle_id).unwrap();
+    let required_components = bundle_info
+        .required_components()
+        .iter()
+        .map(|required| required.component_id)
+        .filter(|_| insert);
+    bundle_info
+        .components()
+        .iter()
+        .copied()
+        .chain(required_components)
+        .any(|component_id| {
+            // SAFETY: The ids come from a bundle of this world.
+            let hooks = unsafe { world.components.get_info_unchecked(component_id) }.hooks();
+            if insert {
+                hooks.on_add.is_some() || hooks.on_insert.is_some()
+            } else {
+                hooks.on_remove.is_some()
             }
+        })
+}
+
+/// Applies consecutive [`Insert`] or [`Remove`] commands targeting the same entity, moving the
+/// entity to its new archetype once.
+///
+/// # Safety
+///
+/// `run` must describe commands of the kind and entity of `bundle_meta`, written in `bytes`, that
+/// haven't been applied yet.
+unsafe fn apply_bundle_commands(
+    world: &mut World,
+    bytes: *mut MaybeUninit<u8>,
+    run: &[CommandMeta],
+    bundle_meta: BundleCommandMeta,
+    scratch: &mut BundleScratch,
+) {
+    let apply_one_by_one = |world: &mut World| {
+        for meta in run {
+            (meta.func)(bytes.add(meta.offset), world);
         }
+    };
+    if run.len() == 1 || world.get_entity(bundle_meta.entity).is_none() {
+        // Let the commands handle missing entities themselves.
+        apply_one_by_one(world);
+        return;
+    }
+
+    scratch.update(world, bundle_meta.insert, run);
+    let Some(merged) = scratch
+        .merged
+        .filter(|merged| !has_hooks(world, merged.id, bundle_meta.insert))
+    else {
+        apply_one_by_one(world);
+        return;
+    };
+
+    if !bundle_meta.insert {
+        for meta in run {
+            (meta.bundle_meta.unwrap().take_components)(bytes.add(meta.offset), &mut |_| {});
+        }
+        world
+            .entity_mut(bundle_meta.entity)
+            .remove_intersection_by_bundle(merged.id);
+        return;
+    }
+
+    // Move the values out of the unaligned command bytes, into a buffer holding the merged bundle.
+    let layout = merged.layout;
+    let buffer = if layout.size() == 0 {
+        // A dangling pointer with the right alignment, as in `NonNull::dangling`.
+        layout.align() as *mut u8
+    } else {
+        let buffer = std::alloc::alloc(layout);
+        if buffer.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
+        buffer
+    };
+
+    let fields = &scratch.fields;
+    let mut field = 0;
+    for meta in run {
+        (meta.bundle_meta.unwrap().take_components)(bytes.add(meta.offset), &mut |component| {
+            let (offset, size) = fields[field];
+            std::ptr::copy_nonoverlapping(component.as_ptr(), buffer.add(offset), size);
+            field += 1;
+        });
+    }
+
+    let components = fields
+        .iter()
+        .map(|&(offset, _)| OwningPtr::new(NonNull::new_unchecked(buffer.add(offset))));
+    world
+        .entity_mut(bundle_meta.entity)
+        .insert_with_bundle_id(merged.id, ComponentPtrs(components));
+
+    if layout.size() != 0 {
+        std::alloc::dealloc(buffer, layout);
     }
 }
 
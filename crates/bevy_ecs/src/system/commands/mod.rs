@@ -3,15 +3,15 @@ mod parallel_scope;
 
 use crate::{
     bundle::Bundle,
-    entity::{Entities, Entity},
+    entity::{Entities, Entity, EntityMap, EntityMapper},
     world::{FromWorld, World},
 };
-use bevy_utils::tracing::{error, info};
+use bevy_utils::tracing::{error, info, warn};
 pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
-use super::Resource;
+use super::{Resource, RunSystem, SystemId};
 
 /// A [`World`] mutation.
 ///
@@ -187,6 +187,42 @@ impl<'w, 's> Commands<'w, 's> {
         }
     }
 
+    /// Returns an [`EntityMapper`] that maps the entities of another world to entities reserved
+    /// by these commands.
+    ///
+    /// The reserved entities can be used with [`Commands::entity`] right away, so that the
+    /// components received from another world, like a network message, are inserted with their
+    /// entity references mapped consistently, even when they reference entities that haven't been
+    /// received yet.
+    ///
+    /// ```
+    /// # use bevy_ecs::{entity::{EntityMap, MapEntities}, prelude::*};
+    /// #[derive(Resource, Default)]
+    /// struct RemoteEntities(EntityMap);
+    ///
+    /// # #[derive(Component)]
+    /// # struct Target(Entity);
+    /// # impl MapEntities for Target {
+    /// #     fn map_entities(&mut self, entity_mapper: &mut bevy_ecs::entity::EntityMapper) {
+    /// #         self.0 = entity_mapper.get_or_reserve(self.0);
+    /// #     }
+    /// # }
+    /// fn receive_target(mut commands: Commands, mut remote_entities: ResMut<RemoteEntities>) {
+    ///     # let (remote_entity, mut target) = (Entity::from_raw(1), Target(Entity::from_raw(2)));
+    ///     let mut entity_mapper = commands.entity_mapper(&mut remote_entities.0);
+    ///     let entity = entity_mapper.get_or_reserve(remote_entity);
+    ///     target.map_entities(&mut entity_mapper);
+    ///     commands.entity(entity).insert(target);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(receive_target);
+    /// ```
+    pub fn entity_mapper<'m>(&self, entity_map: &'m mut EntityMap) -> EntityMapper<'m>
+    where
+        'w: 'm,
+    {
+        EntityMapper::new(entity_map, self.entities)
+    }
+
     /// Pushes a [`Command`] to the queue for creating a new entity with the given [`Bundle`]'s components,
     /// and returns its corresponding [`EntityCommands`].
     ///
@@ -414,6 +450,33 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue.push(InsertOrSpawnBatch { bundles_iter });
     }
 
+    /// Pushes a [`Command`] to the queue for removing the components of a [`Bundle`] from each
+    /// of the `entities`.
+    ///
+    /// This is equivalent to calling [`EntityCommands::remove`] for each entity, but it is faster
+    /// as it queues a single command. Entities that don't exist are skipped.
+    pub fn remove_batch<T, I>(&mut self, entities: I)
+    where
+        T: Bundle,
+        I: IntoIterator<Item = Entity> + Send + Sync + 'static,
+    {
+        self.queue.push(RemoveBatch::<I, T> {
+            entities,
+            phantom: PhantomData,
+        });
+    }
+
+    /// Pushes a [`Command`] to the queue for despawning each of the `entities`.
+    ///
+    /// This is equivalent to calling [`EntityCommands::despawn`] for each entity, but it is
+    /// faster as it queues a single command.
+    pub fn despawn_batch<I>(&mut self, entities: I)
+    where
+        I: IntoIterator<Item = Entity> + Send + Sync + 'static,
+    {
+        self.queue.push(DespawnBatch { entities });
+    }
+
     /// Pushes a [`Command`] to the queue for inserting a [`Resource`] in the [`World`] with an inferred value.
     ///
     /// The inferred value is determined by the [`FromWorld`] trait of the resource.
@@ -536,6 +599,27 @@ impl<'w, 's> Commands<'w, 's> {
     pub fn add<C: Command>(&mut self, command: C) {
         self.queue.push(command);
     }
+
+    /// Pushes a [`Command`] to the queue for running a system registered with
+    /// [`World::register_system`].
+    ///
+    /// See [`World::run_system`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, system::SystemId};
+    /// #[derive(Resource)]
+    /// struct OnClick(SystemId);
+    ///
+    /// fn click_button(mut commands: Commands, on_click: Res<OnClick>) {
+    ///     commands.run_system(on_click.0);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(click_button);
+    /// ```
+    pub fn run_system(&mut self, system_id: SystemId) {
+        self.queue.push(RunSystem { system_id });
+    }
 }
 
 /// A list of commands that will be run to modify an [entity](crate::entity).
@@ -613,10 +697,7 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     /// # bevy_ecs::system::assert_is_system(add_combat_stats_system);
     /// ```
     pub fn insert(&mut self, bundle: impl Bundle) -> &mut Self {
-        self.commands.add(Insert {
-            entity: self.entity,
-            bundle,
-        });
+        self.commands.queue.push_insert(self.entity, bundle);
         self
     }
 
@@ -670,10 +751,7 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
     where
         T: Bundle,
     {
-        self.commands.add(Remove::<T> {
-            entity: self.entity,
-            phantom: PhantomData,
-        });
+        self.commands.queue.push_remove::<T>(self.entity);
         self
     }
 
@@ -824,6 +902,30 @@ impl Command for Despawn {
     }
 }
 
+/// A [`Command`] that despawns each of the `entities`, with [`World::despawn_batch`].
+///
+/// Logs a warning with the entities that don't exist.
+pub struct DespawnBatch<I>
+where
+    I: IntoIterator<Item = Entity> + Send + Sync + 'static,
+{
+    pub entities: I,
+}
+
+impl<I> Command for DespawnBatch<I>
+where
+    I: IntoIterator<Item = Entity> + Send + Sync + 'static,
+{
+    fn write(self, world: &mut World) {
+        if let Err(invalid_entities) = world.despawn_batch(self.entities) {
+            warn!(
+                "error[B0003]: Could not despawn the following entities because they don't exist in this World: {:?}",
+                invalid_entities
+            );
+        }
+    }
+}
+
 pub struct Insert<T> {
     pub entity: Entity,
     pub bundle: T,
@@ -861,6 +963,29 @@ where
     }
 }
 
+/// A [`Command`] that removes the components of the [`Bundle`] `T` from each of the `entities`,
+/// with [`World::remove_batch`].
+///
+/// Entities that don't exist, and components they don't have, are skipped.
+pub struct RemoveBatch<I, T>
+where
+    I: IntoIterator<Item = Entity> + Send + Sync + 'static,
+    T: Bundle,
+{
+    pub entities: I,
+    pub phantom: PhantomData<T>,
+}
+
+impl<I, T> Command for RemoveBatch<I, T>
+where
+    I: IntoIterator<Item = Entity> + Send + Sync + 'static,
+    T: Bundle,
+{
+    fn write(self, world: &mut World) {
+        world.remove_batch::<T>(self.entities);
+    }
+}
+
 pub struct InitResource<R: Resource + FromWorld> {
     _phantom: PhantomData<R>,
 }
@@ -912,7 +1037,9 @@ impl Command for LogComponents {
 mod tests {
     use crate::{
         self as bevy_ecs,
-        component::Component,
+        component::{Component, ComponentId},
+        entity::{Entity, EntityMap, EntityMapper, MapEntities},
+        query::Added,
         system::{CommandQueue, Commands, Resource},
         world::World,
     };
@@ -1067,4 +1194,255 @@ mod tests {
         assert!(!world.contains_resource::<W<i32>>());
         assert!(world.contains_resource::<W<f64>>());
     }
+
+    #[test]
+    fn consecutive_inserts_move_entity_once() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        world.clear_trackers();
+        let archetypes = world.archetypes().len();
+
+        let (dropck, drops) = DropCk::new_pair();
+        let (sparse_dropck, sparse_drops) = DropCk::new_pair();
+        {
+            let mut commands = Commands::new(&mut command_queue, &world);
+            commands
+                .entity(entity)
+                .insert(W(1u8))
+                .insert((W(2u16), W("a")))
+                .insert(dropck)
+                .insert(SparseDropCk(sparse_dropck));
+            commands.entity(other).insert(W(3u8));
+        }
+        command_queue.apply(&mut world);
+
+        // Only the archetypes of the final component sets were created.
+        assert_eq!(world.archetypes().len(), archetypes + 2);
+        let entity_ref = world.entity(entity);
+        assert_eq!(entity_ref.get::<W<u8>>().unwrap().0, 1);
+        assert_eq!(entity_ref.get::<W<u16>>().unwrap().0, 2);
+        assert_eq!(entity_ref.get::<W<&str>>().unwrap().0, "a");
+        assert_eq!(world.get::<W<u8>>(other).unwrap().0, 3);
+        let mut added = world.query_filtered::<Entity, Added<W<u16>>>();
+        assert_eq!(added.iter(&world).collect::<Vec<_>>(), vec![entity]);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+        assert_eq!(sparse_drops.load(Ordering::Relaxed), 0);
+        world.despawn(entity);
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+        assert_eq!(sparse_drops.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn entity_mapper_reserves_entities() {
+        #[derive(Component)]
+        struct Link(Entity);
+
+        impl MapEntities for Link {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                self.0 = entity_mapper.get_or_reserve(self.0);
+            }
+        }
+
+        let mut world = World::default();
+        let mut entity_map = EntityMap::default();
+        let (remote_a, remote_b) = (Entity::from_raw(7), Entity::from_raw(9));
+
+        // `a` arrives first, linking to `b` which hasn't arrived yet.
+        let mut queue = CommandQueue::default();
+        let (a, b) = {
+            let mut commands = Commands::new(&mut queue, &world);
+            let mut entity_mapper = commands.entity_mapper(&mut entity_map);
+            let a = entity_mapper.get_or_reserve(remote_a);
+            let mut link = Link(remote_b);
+            link.map_entities(&mut entity_mapper);
+            let b = link.0;
+            commands.entity(a).insert(link);
+            (a, b)
+        };
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Link>(a).unwrap().0, b);
+
+        // `b` arrives later, linking back to `a`.
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            let mut entity_mapper = commands.entity_mapper(&mut entity_map);
+            assert_eq!(entity_mapper.get_or_reserve(remote_b), b);
+            let mut link = Link(remote_a);
+            link.map_entities(&mut entity_mapper);
+            commands.entity(b).insert(link);
+        }
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Link>(b).unwrap().0, a);
+        assert_eq!(entity_map.len(), 2);
+    }
+
+    #[test]
+    fn consecutive_inserts_of_same_component() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn_empty().id();
+        let (first, first_drops) = DropCk::new_pair();
+        let (second, second_drops) = DropCk::new_pair();
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert((W(1u32), first))
+            .insert((W(2u32), second));
+        command_queue.apply(&mut world);
+
+        assert_eq!(world.get::<W<u32>>(entity).unwrap().0, 2);
+        assert_eq!(first_drops.load(Ordering::Relaxed), 1);
+        assert_eq!(second_drops.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn consecutive_removes_move_entity_once() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let (dropck, drops) = DropCk::new_pair();
+        let entity = world.spawn((W(1u8), W(2u16), W(3u32), dropck)).id();
+        let archetypes = world.archetypes().len();
+
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .remove::<W<u8>>()
+            .remove::<(W<u16>, DropCk)>()
+            .remove::<(W<u8>, W<u64>)>();
+        command_queue.apply(&mut world);
+
+        assert_eq!(world.archetypes().len(), archetypes + 1);
+        let entity_ref = world.entity(entity);
+        assert!(!entity_ref.contains::<W<u8>>());
+        assert!(!entity_ref.contains::<W<u16>>());
+        assert!(entity_ref.contains::<W<u32>>());
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+        assert_eq!(world.removed::<W<u16>>().collect::<Vec<_>>(), vec![entity]);
+    }
+
+    #[test]
+    fn coalesced_inserts_run_hooks() {
+        #[derive(Resource, Default)]
+        struct Inserted(Vec<ComponentId>);
+
+        fn on_insert(world: &mut World, _entity: Entity, component_id: ComponentId) {
+            world.resource_mut::<Inserted>().0.push(component_id);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Inserted>();
+        world
+            .register_component_hooks::<W<u8>>()
+            .on_insert(on_insert);
+        world
+            .register_component_hooks::<W<u16>>()
+            .on_insert(on_insert);
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn_empty().id();
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert(W(1u8))
+            .insert(W(2u16));
+        command_queue.apply(&mut world);
+
+        let ids = [
+            world.component_id::<W<u8>>().unwrap(),
+            world.component_id::<W<u16>>().unwrap(),
+        ];
+        assert_eq!(world.resource::<Inserted>().0, ids);
+    }
+
+    #[test]
+    fn coalesced_commands_run_hooks_in_command_order() {
+        /// The hooks that ran, with whether the entity had `W<u8>` and `W<u16>` at the time.
+        #[derive(Resource, Default)]
+        struct Log(Vec<(&'static str, bool, bool)>);
+
+        fn log(world: &mut World, entity: Entity, hook: &'static str) {
+            let entity_ref = world.entity(entity);
+            let state = (
+                hook,
+                entity_ref.contains::<W<u8>>(),
+                entity_ref.contains::<W<u16>>(),
+            );
+            world.resource_mut::<Log>().0.push(state);
+        }
+
+        let mut world = World::default();
+        world.init_resource::<Log>();
+        world
+            .register_component_hooks::<W<u8>>()
+            .on_add(|world, entity, _| log(world, entity, "add u8"))
+            .on_insert(|world, entity, _| log(world, entity, "insert u8"))
+            .on_remove(|world, entity, _| log(world, entity, "remove u8"));
+        world
+            .register_component_hooks::<W<u16>>()
+            .on_add(|world, entity, _| log(world, entity, "add u16"))
+            .on_insert(|world, entity, _| log(world, entity, "insert u16"))
+            .on_remove(|world, entity, _| log(world, entity, "remove u16"));
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn_empty().id();
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert(W(1u8))
+            .insert(W(2u16));
+        command_queue.apply(&mut world);
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .remove::<W<u8>>()
+            .remove::<W<u16>>();
+        command_queue.apply(&mut world);
+
+        assert_eq!(
+            world.resource::<Log>().0,
+            [
+                ("add u8", true, false),
+                ("insert u8", true, false),
+                ("add u16", true, true),
+                ("insert u16", true, true),
+                ("remove u8", true, true),
+                ("remove u16", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not insert a bundle")]
+    fn coalesced_inserts_on_missing_entity_panic() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let entity = world.spawn_empty().id();
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert(W(1u8))
+            .insert(W(2u16));
+        world.despawn(entity);
+        command_queue.apply(&mut world);
+    }
+
+    #[test]
+    fn batched_remove_and_despawn() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let entities: Vec<_> = (0..10)
+            .map(|i| world.spawn((W(i as u8), W(i as u16))).id())
+            .collect();
+        let (removed, despawned) = entities.split_at(5);
+        {
+            let mut commands = Commands::new(&mut command_queue, &world);
+            commands.remove_batch::<W<u8>, _>(removed.to_vec());
+            commands.despawn_batch(despawned.to_vec());
+        }
+        command_queue.apply(&mut world);
+
+        for &entity in removed {
+            assert!(!world.entity(entity).contains::<W<u8>>());
+            assert!(world.entity(entity).contains::<W<u16>>());
+        }
+        assert!(despawned
+            .iter()
+            .all(|&entity| world.get_entity(entity).is_none()));
+        assert_eq!(world.entities().len(), 5);
+    }
 }
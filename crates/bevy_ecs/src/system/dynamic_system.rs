@@ -0,0 +1,347 @@
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::track_changes_by;
+use crate::{
+    archetype::ArchetypeComponentId,
+    change_detection::MAX_CHANGE_AGE,
+    component::ComponentId,
+    entity::Entity,
+    query::{
+        Access, DynamicQueryItem, DynamicQueryIter, DynamicQueryState, FilteredAccessSet,
+        QueryEntityError,
+    },
+    system::{check_system_change_tick, System},
+    world::World,
+};
+use std::borrow::Cow;
+
+/// A [`System`] running a function on queries built at runtime with a
+/// [`QueryBuilder`](crate::query::QueryBuilder).
+///
+/// The component access of the queries is reported to the executor like the access of a
+/// [`Query`](crate::system::Query) parameter, so a dynamic system never runs in parallel with
+/// systems that conflict with it. This is how scripts and editors can run queries on components
+/// that are only known at runtime.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, query::QueryBuilder, system::DynamicSystem};
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// let mut world = World::new();
+/// world.spawn(Health(10.0));
+///
+/// let health_id = world.init_component::<Health>();
+/// let query = QueryBuilder::new(&world).write(health_id).build();
+/// let mut stage = SystemStage::parallel();
+/// stage.add_system(DynamicSystem::new("regenerate", vec![query], |queries| {
+///     for mut item in queries[0].iter_mut() {
+///         // SAFETY: the pointer comes from the component id of `Health`.
+///         unsafe { item.get_mut(0).unwrap().deref_mut::<Health>().0 += 1.0 };
+///     }
+/// }));
+/// stage.run(&mut world);
+/// assert_eq!(world.query::<&Health>().single(&world).0, 11.0);
+/// ```
+pub struct DynamicSystem<F> {
+    name: Cow<'static, str>,
+    queries: Vec<DynamicQueryState>,
+    func: F,
+    component_access_set: FilteredAccessSet<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+    last_change_tick: u32,
+}
+
+impl<F> DynamicSystem<F>
+where
+    F: FnMut(&mut [DynamicQuery<'_, '_>]) + Send + Sync + 'static,
+{
+    /// Creates a system with the given name that runs `func` on the given queries.
+    ///
+    /// `func` receives a [`DynamicQuery`] for each query, in the same order.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        queries: Vec<DynamicQueryState>,
+        func: F,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            queries,
+            func,
+            component_access_set: FilteredAccessSet::default(),
+            archetype_component_access: Access::default(),
+            last_change_tick: 0,
+        }
+    }
+}
+
+impl<F> System for DynamicSystem<F>
+where
+    F: FnMut(&mut [DynamicQuery<'_, '_>]) + Send + Sync + 'static,
+{
+    type In = ();
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.component_access_set.combined_access()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        true
+    }
+
+    fn is_exclusive(&self) -> bool {
+        false
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: Self::In, world: &World) -> Self::Out {
+        let change_tick = world.increment_change_tick();
+        #[cfg(feature = "track_change_detection")]
+        let _change_author = track_changes_by(&self.name);
+
+        let last_change_tick = self.last_change_tick;
+        let mut queries: Vec<_> = self
+            .queries
+            .iter()
+            .map(|state| DynamicQuery {
+                world,
+                state,
+                last_change_tick,
+                change_tick,
+            })
+            .collect();
+        (self.func)(&mut queries);
+        self.last_change_tick = change_tick;
+    }
+
+    fn apply_buffers(&mut self, _world: &mut World) {}
+
+    /// # Panics
+    ///
+    /// Panics if a query was built for another [`World`], or if the queries access components in
+    /// conflicting ways.
+    fn initialize(&mut self, world: &mut World) {
+        self.last_change_tick = world.change_tick().wrapping_sub(MAX_CHANGE_AGE);
+        self.component_access_set.clear();
+        for query in &self.queries {
+            query.validate_world(world);
+            let conflicts = self
+                .component_access_set
+                .get_conflicts_single(query.component_access());
+            if !conflicts.is_empty() {
+                let conflicting_components = conflicts
+                    .into_iter()
+                    .map(|component_id| world.components.get_info(component_id).unwrap().name())
+                    .collect::<Vec<&str>>();
+                panic!("error[B0001]: a dynamic query in system {} accesses component(s) {} in a way that conflicts with a previous query.",
+                    self.name, conflicting_components.join(", "));
+            }
+            self.component_access_set
+                .add(query.component_access().clone());
+        }
+    }
+
+    fn update_archetype_component_access(&mut self, world: &World) {
+        for query in &mut self.queries {
+            query.update_archetypes(world);
+            self.archetype_component_access
+                .extend(query.archetype_component_access());
+        }
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        check_system_change_tick(&mut self.last_change_tick, change_tick, self.name.as_ref());
+    }
+
+    fn get_last_change_tick(&self) -> u32 {
+        self.last_change_tick
+    }
+
+    fn set_last_change_tick(&mut self, last_change_tick: u32) {
+        self.last_change_tick = last_change_tick;
+    }
+}
+
+/// A query built at runtime, as given to the function of a [`DynamicSystem`].
+///
+/// This is the [`DynamicQueryState`] equivalent of [`Query`](crate::system::Query): it can only
+/// access the archetypes matched before the system ran, with the change ticks of the system.
+pub struct DynamicQuery<'w, 's> {
+    world: &'w World,
+    state: &'s DynamicQueryState,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<'w, 's> DynamicQuery<'w, 's> {
+    /// Returns the state of this query.
+    #[inline]
+    pub fn state(&self) -> &DynamicQueryState {
+        self.state
+    }
+
+    /// Returns an [`Iterator`] over the query items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query accesses a component mutably. Use [`DynamicQuery::iter_mut`] instead.
+    pub fn iter(&self) -> DynamicQueryIter<'_, 's> {
+        self.state.assert_read_only();
+        // SAFETY: the query is read-only, and the system's access was checked by the executor
+        unsafe {
+            DynamicQueryIter::new(
+                self.world,
+                self.state,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the query items, with mutable access to the components
+    /// accessed with [`DynamicAccess::Write`](crate::query::DynamicAccess::Write).
+    pub fn iter_mut(&mut self) -> DynamicQueryIter<'_, 's> {
+        // SAFETY: the query is borrowed mutably, and the system's access was checked by the
+        // executor
+        unsafe {
+            DynamicQueryIter::new(
+                self.world,
+                self.state,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Gets the query item for the given [`Entity`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query accesses a component mutably. Use [`DynamicQuery::get_mut`] instead.
+    pub fn get(&self, entity: Entity) -> Result<DynamicQueryItem<'_>, QueryEntityError> {
+        self.state.assert_read_only();
+        // SAFETY: the query is read-only, and the system's access was checked by the executor
+        unsafe {
+            self.state.get_unchecked_manual(
+                self.world,
+                entity,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Gets the query item for the given [`Entity`], with mutable access to the components
+    /// accessed with [`DynamicAccess::Write`](crate::query::DynamicAccess::Write).
+    pub fn get_mut(&mut self, entity: Entity) -> Result<DynamicQueryItem<'_>, QueryEntityError> {
+        // SAFETY: the query is borrowed mutably, and the system's access was checked by the
+        // executor
+        unsafe {
+            self.state.get_unchecked_manual(
+                self.world,
+                entity,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicSystem;
+    use crate::{
+        self as bevy_ecs,
+        component::ComponentId,
+        prelude::*,
+        query::QueryBuilder,
+        system::{BoxedSystem, System},
+    };
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    fn increment_a(world: &World, with: Option<ComponentId>) -> BoxedSystem {
+        let a = world.component_id::<A>().unwrap();
+        let mut builder = QueryBuilder::new(world);
+        builder.write(a);
+        if let Some(with) = with {
+            builder.with(with);
+        }
+        let query = builder.build();
+        Box::new(DynamicSystem::new("increment_a", vec![query], |queries| {
+            for mut item in queries[0].iter_mut() {
+                // SAFETY: `a` is the id of `A`
+                unsafe { item.get_mut(0).unwrap().deref_mut::<A>().0 += 1 };
+            }
+        }))
+    }
+
+    #[test]
+    fn runs_in_stage() {
+        let mut world = World::new();
+        world.init_component::<A>();
+        let mut stage = SystemStage::parallel();
+        stage.add_system(increment_a(&world, None));
+        // Entities spawned after the query was built are matched too.
+        let entity = world.spawn(A(0)).id();
+        stage.run(&mut world);
+        stage.run(&mut world);
+        assert_eq!(world.get::<A>(entity).unwrap().0, 2);
+        assert!(world
+            .entity(entity)
+            .get_change_ticks::<A>()
+            .unwrap()
+            .is_changed(world.last_change_tick(), world.read_change_tick()));
+    }
+
+    #[test]
+    fn conflicting_dynamic_systems() {
+        let mut world = World::new();
+        world.spawn((A(0), B));
+        world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        let mut stage = SystemStage::parallel();
+        stage
+            .add_system(increment_a(&world, None))
+            .add_system(increment_a(&world, Some(b)));
+        stage.run(&mut world);
+        let ambiguities = stage.ambiguities(&world);
+        assert_eq!(ambiguities.len(), 1);
+        assert_eq!(ambiguities[0].conflicts, vec![std::any::type_name::<A>()]);
+
+        let mut first = increment_a(&world, None);
+        let mut second = increment_a(&world, None);
+        first.initialize(&mut world);
+        second.initialize(&mut world);
+        first.update_archetype_component_access(&world);
+        second.update_archetype_component_access(&world);
+        assert!(!first
+            .archetype_component_access()
+            .is_compatible(second.archetype_component_access()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_queries_in_one_system_panic() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let queries = vec![
+            QueryBuilder::new(&world).read(a).build(),
+            QueryBuilder::new(&world).write(a).build(),
+        ];
+        let mut system = DynamicSystem::new("conflicting", queries, |_| {});
+        system.initialize(&mut world);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::track_changes_by;
 use crate::{
     archetype::ArchetypeComponentId,
     change_detection::MAX_CHANGE_AGE,
@@ -92,6 +94,8 @@ where
     }
 
     fn run(&mut self, _input: Self::In, world: &mut World) -> Self::Out {
+        #[cfg(feature = "track_change_detection")]
+        let _change_author = track_changes_by(&self.system_meta.name);
         let saved_last_tick = world.last_change_tick;
         world.last_change_tick = self.system_meta.last_change_tick;
 
@@ -122,6 +126,8 @@ where
 
     #[inline]
     fn apply_buffers(&mut self, world: &mut World) {
+        #[cfg(feature = "track_change_detection")]
+        let _change_author = track_changes_by(&self.system_meta.name);
         let param_state = self.param_state.as_mut().expect(PARAM_MESSAGE);
         param_state.apply(world);
     }
@@ -1,3 +1,5 @@
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::track_changes_by;
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeGeneration, ArchetypeId},
     change_detection::MAX_CHANGE_AGE,
@@ -395,6 +397,8 @@ where
     #[inline]
     unsafe fn run_unsafe(&mut self, input: Self::In, world: &World) -> Self::Out {
         let change_tick = world.increment_change_tick();
+        #[cfg(feature = "track_change_detection")]
+        let _change_author = track_changes_by(&self.system_meta.name);
 
         // Safety:
         // We update the archetype component access correctly based on `Param`'s requirements
@@ -421,6 +425,8 @@ where
 
     #[inline]
     fn apply_buffers(&mut self, world: &mut World) {
+        #[cfg(feature = "track_change_detection")]
+        let _change_author = track_changes_by(&self.system_meta.name);
         let param_state = self.param_state.as_mut().expect(Self::PARAM_MESSAGE);
         param_state.apply(world);
     }
@@ -59,6 +59,7 @@
 //! - [`NonSendMut`] and `Option<NonSendMut>`
 //! - [`&World`](crate::world::World)
 //! - [`RemovedComponents`]
+//! - [`RemovedResources`]
 //! - [`SystemName`]
 //! - [`SystemChangeTick`]
 //! - [`Archetypes`](crate::archetype::Archetypes) (Provides Archetype metadata)
@@ -69,6 +70,7 @@
 //! - [`()` (unit primitive type)](https://doc.rust-lang.org/stable/std/primitive.unit.html)
 
 mod commands;
+mod dynamic_system;
 mod exclusive_function_system;
 mod exclusive_system_param;
 mod function_system;
@@ -77,8 +79,10 @@ mod query;
 mod system;
 mod system_param;
 mod system_piping;
+mod system_registry;
 
 pub use commands::*;
+pub use dynamic_system::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
 pub use function_system::*;
@@ -86,6 +90,7 @@ pub use query::*;
 pub use system::*;
 pub use system_param::*;
 pub use system_piping::*;
+pub use system_registry::*;
 
 /// Ensure that a given function is a system
 ///
@@ -110,6 +115,7 @@ mod tests {
         self as bevy_ecs,
         archetype::{ArchetypeComponentId, Archetypes},
         bundle::Bundles,
+        change_detection::DetectChanges,
         component::{Component, Components},
         entity::{Entities, Entity},
         prelude::AnyOf,
@@ -117,7 +123,7 @@ mod tests {
         schedule::{Schedule, Stage, SystemStage},
         system::{
             Commands, IntoSystem, Local, NonSend, NonSendMut, ParamSet, Query, QueryComponentError,
-            RemovedComponents, Res, ResMut, Resource, System, SystemState,
+            RemovedComponents, RemovedResources, Res, ResMut, Resource, System, SystemState,
         },
         world::{FromWorld, World},
     };
@@ -621,6 +627,50 @@ mod tests {
         assert_eq!(world.resource::<NSystems>().0, 2);
     }
 
+    #[test]
+    fn removed_resources_system() {
+        #[derive(Resource)]
+        struct Kept;
+        #[derive(Resource)]
+        struct Removed;
+        struct RemovedNonSend;
+
+        let mut world = World::default();
+        world.insert_resource(Kept);
+        world.insert_resource(Removed);
+        world.insert_non_send_resource(RemovedNonSend);
+        world.insert_resource(SystemRan::No);
+
+        world.remove_resource::<Removed>();
+        world.remove_non_send_resource::<RemovedNonSend>();
+        // Removing a missing resource isn't recorded twice.
+        world.remove_resource::<Removed>();
+
+        fn validate_removed(removed: RemovedResources, mut system_ran: ResMut<SystemRan>) {
+            assert!(removed.contains::<Removed>());
+            assert!(removed.contains::<RemovedNonSend>());
+            assert!(!removed.contains::<Kept>());
+            assert_eq!(removed.iter().count(), 2);
+            *system_ran = SystemRan::Yes;
+        }
+
+        run_system(&mut world, validate_removed);
+        assert_eq!(*world.resource::<SystemRan>(), SystemRan::Yes);
+
+        world.clear_trackers();
+        fn validate_cleared(removed: RemovedResources) {
+            assert!(removed.is_empty());
+        }
+        run_system(&mut world, validate_cleared);
+
+        let kept = world
+            .components()
+            .get_resource_id(TypeId::of::<Kept>())
+            .unwrap();
+        world.remove_resource_by_id(kept);
+        assert_eq!(world.removed_resources(), &[kept]);
+    }
+
     #[test]
     fn world_collections_system() {
         let mut world = World::default();
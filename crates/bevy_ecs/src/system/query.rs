@@ -2,8 +2,9 @@ use crate::{
     component::Component,
     entity::Entity,
     query::{
-        QueryCombinationIter, QueryEntityError, QueryIter, QueryManyIter, QuerySingleError,
-        QueryState, ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
+        GroupBy, QueryCombinationIter, QueryEntityError, QueryIter, QueryManyIter,
+        QueryManyUniqueIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyWorldQuery,
+        WorldQuery,
     },
     world::{Mut, World},
 };
@@ -189,7 +190,9 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// |:---:|---|
 /// |[`iter`]\([`_mut`][`iter_mut`])|Returns an iterator over all query items.|
 /// |[`for_each`]\([`_mut`][`for_each_mut`]),<br>[`par_for_each`]\([`_mut`][`par_for_each_mut`])|Runs a specified function for each query item.|
-/// |[`iter_many`]\([`_mut`][`iter_many_mut`])|Iterates or runs a specified function over query items generated by a list of entities.|
+/// |[`iter_many`]\([`_mut`][`iter_many_mut`]),<br>[`iter_many_unique_mut`]|Iterates or runs a specified function over query items generated by a list of entities.|
+/// |[`iter_sorted_by_key`]\([`_mut`][`iter_sorted_by_key_mut`])|Returns an iterator over all query items, sorted by a key.|
+/// |[`iter_grouped_by_key`]\([`_mut`][`iter_grouped_by_key_mut`])|Returns an iterator over groups of query items sharing the same key.|
 /// |[`iter_combinations`]\([`_mut`][`iter_combinations_mut`])|Returns an iterator over all combinations of a specified number of query items.|
 /// |[`get`]\([`_mut`][`get_mut`])|Returns the query item for the specified entity.|
 /// |[`many`]\([`_mut`][`many_mut`]),<br>[`get_many`]\([`_mut`][`get_many_mut`])|Returns the query items for the specified entities.|
@@ -226,6 +229,8 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// |[`iter`]\([`_mut`][`iter_mut`])|O(n)|
 /// |[`for_each`]\([`_mut`][`for_each_mut`]),<br>[`par_for_each`]\([`_mut`][`par_for_each_mut`])|O(n)|
 /// |[`iter_many`]\([`_mut`][`iter_many_mut`])|O(k)|
+/// |[`iter_many_unique_mut`]|O(k)|
+/// |[`iter_sorted_by_key`]\([`_mut`][`iter_sorted_by_key_mut`]),<br>[`iter_grouped_by_key`]\([`_mut`][`iter_grouped_by_key_mut`])|O(n log n)|
 /// |[`iter_combinations`]\([`_mut`][`iter_combinations_mut`])|O(<sub>n</sub>C<sub>r</sub>)|
 /// |[`get`]\([`_mut`][`get_mut`])|O(1)|
 /// |([`get_`][`get_many`])[`many`]|O(k)|
@@ -257,9 +262,14 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// [`iter`]: Self::iter
 /// [`iter_combinations`]: Self::iter_combinations
 /// [`iter_combinations_mut`]: Self::iter_combinations_mut
+/// [`iter_grouped_by_key`]: Self::iter_grouped_by_key
+/// [`iter_grouped_by_key_mut`]: Self::iter_grouped_by_key_mut
 /// [`iter_many`]: Self::iter_many
 /// [`iter_many_mut`]: Self::iter_many_mut
+/// [`iter_many_unique_mut`]: Self::iter_many_unique_mut
 /// [`iter_mut`]: Self::iter_mut
+/// [`iter_sorted_by_key`]: Self::iter_sorted_by_key
+/// [`iter_sorted_by_key_mut`]: Self::iter_sorted_by_key_mut
 /// [`many`]: Self::many
 /// [`many_mut`]: Self::many_mut
 /// [`Or`]: crate::query::Or
@@ -582,6 +592,248 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the query items generated from a list of distinct [`Entity`]s.
+    ///
+    /// Unlike [`iter_many_mut`](Self::iter_many_mut), the list is checked for duplicates up
+    /// front, so that the returned value is a regular [`Iterator`]: its items can be held at the
+    /// same time. Returns a [`QueryEntityError::AliasedMutability`] error if the list contains an
+    /// entity more than once.
+    ///
+    /// Items are returned in the order of the list of entities.
+    /// Entities that don't match the query are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Resource)]
+    /// struct Targets(Vec<Entity>);
+    ///
+    /// fn heal_targets(targets: Res<Targets>, mut query: Query<&mut Health>) {
+    ///     let Ok(healths) = query.iter_many_unique_mut(&targets.0) else {
+    ///         return;
+    ///     };
+    ///     let mut healths: Vec<_> = healths.collect();
+    ///     let total: u32 = healths.iter().map(|health| health.0).sum();
+    ///     // Share the health of the targets evenly.
+    ///     let count = healths.len() as u32;
+    ///     for health in &mut healths {
+    ///         health.0 = total / count;
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(heal_targets);
+    /// ```
+    #[inline]
+    pub fn iter_many_unique_mut<EntityList: IntoIterator>(
+        &mut self,
+        entities: EntityList,
+    ) -> Result<QueryManyUniqueIter<'_, 's, Q, F>, QueryEntityError>
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.iter_many_unique_unchecked_manual(
+                entities,
+                self.world,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the read-only query items, sorted by the key that `key`
+    /// computes for each of them.
+    ///
+    /// Items with equal keys are returned in storage order, so that the order is deterministic.
+    /// The key can't borrow from the query item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Layer(i32);
+    ///
+    /// #[derive(Component)]
+    /// struct Sprite;
+    ///
+    /// fn draw_back_to_front(query: Query<(Entity, &Layer), With<Sprite>>) {
+    ///     for (entity, layer) in query.iter_sorted_by_key(|(_, layer)| layer.0) {
+    ///         println!("Drawing {entity:?} on layer {}", layer.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(draw_back_to_front);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`iter_sorted_by_key_mut`](Self::iter_sorted_by_key_mut) to get mutable query items.
+    #[inline]
+    pub fn iter_sorted_by_key<K: Ord>(
+        &self,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> QueryManyUniqueIter<'_, 's, Q::ReadOnly, F::ReadOnly> {
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state
+                .as_readonly()
+                .iter_sorted_by_key_unchecked_manual(
+                    self.world,
+                    key,
+                    self.last_change_tick,
+                    self.change_tick,
+                )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the query items, sorted by the key that `key` computes for
+    /// the read-only version of each of them.
+    ///
+    /// Items with equal keys are returned in storage order, so that the order is deterministic.
+    /// The key can't borrow from the query item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Priority(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Budget(u32);
+    ///
+    /// #[derive(Resource)]
+    /// struct Available(u32);
+    ///
+    /// // Hands out the available budget to the entities with the highest priority first.
+    /// fn allocate(mut available: ResMut<Available>, mut query: Query<(&Priority, &mut Budget)>) {
+    ///     for (_, mut budget) in query.iter_sorted_by_key_mut(|(priority, _)| std::cmp::Reverse(priority.0)) {
+    ///         budget.0 = available.0.min(10);
+    ///         available.0 -= budget.0;
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(allocate);
+    /// ```
+    #[inline]
+    pub fn iter_sorted_by_key_mut<K: Ord>(
+        &mut self,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> QueryManyUniqueIter<'_, 's, Q, F> {
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.iter_sorted_by_key_unchecked_manual(
+                self.world,
+                key,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the groups of read-only query items that share the same key,
+    /// computed by `key` for each of them.
+    ///
+    /// Groups are returned in increasing order of their key, and the items of each group in
+    /// storage order. See [`GroupBy`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+    /// enum Team {
+    ///     Red,
+    ///     Blue,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// fn team_scores(query: Query<(&Team, &Score)>) {
+    ///     for (team, members) in query.iter_grouped_by_key(|(team, _)| **team) {
+    ///         let score: u32 = members.map(|(_, score)| score.0).sum();
+    ///         println!("{team:?}: {score}");
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(team_scores);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`iter_grouped_by_key_mut`](Self::iter_grouped_by_key_mut) to get mutable query items.
+    #[inline]
+    pub fn iter_grouped_by_key<K: Ord>(
+        &self,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> GroupBy<'_, 's, Q::ReadOnly, F::ReadOnly, K> {
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state
+                .as_readonly()
+                .iter_grouped_by_key_unchecked_manual(
+                    self.world,
+                    key,
+                    self.last_change_tick,
+                    self.change_tick,
+                )
+        }
+    }
+
+    /// Returns an [`Iterator`] over the groups of query items that share the same key, computed
+    /// by `key` for the read-only version of each of them.
+    ///
+    /// Groups are returned in increasing order of their key, and the items of each group in
+    /// storage order. Since the groups are disjoint, the items of several groups can be held at
+    /// the same time. See [`GroupBy`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    /// struct Team(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// // Evens out the health of the members of each team.
+    /// fn share_health(mut query: Query<(&Team, &mut Health)>) {
+    ///     for (_, members) in query.iter_grouped_by_key_mut(|(team, _)| **team) {
+    ///         let mut healths: Vec<_> = members.map(|(_, health)| health).collect();
+    ///         let total: u32 = healths.iter().map(|health| health.0).sum();
+    ///         let count = healths.len() as u32;
+    ///         for health in &mut healths {
+    ///             health.0 = total / count;
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(share_health);
+    /// ```
+    #[inline]
+    pub fn iter_grouped_by_key_mut<K: Ord>(
+        &mut self,
+        key: impl FnMut(&ROQueryItem<'_, Q>) -> K,
+    ) -> GroupBy<'_, 's, Q, F, K> {
+        // SAFETY: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.iter_grouped_by_key_unchecked_manual(
+                self.world,
+                key,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the query items.
     ///
     /// # Safety
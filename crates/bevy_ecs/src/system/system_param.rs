@@ -1,9 +1,11 @@
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::ChangedBy;
 pub use crate::change_detection::{NonSendMut, ResMut};
 use crate::{
     archetype::{Archetype, Archetypes},
     bundle::Bundles,
-    change_detection::Ticks,
-    component::{Component, ComponentId, ComponentTicks, Components, Tick},
+    change_detection::{DetectChanges, RefTicks, Ticks},
+    component::{Component, ComponentId, ComponentTicks, Components},
     entity::{Entities, Entity},
     query::{
         Access, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyWorldQuery, WorldQuery,
@@ -14,9 +16,9 @@ use crate::{
 pub use bevy_ecs_macros::Resource;
 pub use bevy_ecs_macros::SystemParam;
 use bevy_ecs_macros::{all_tuples, impl_param_set};
-use bevy_ptr::UnsafeCellDeref;
 use bevy_utils::synccell::SyncCell;
 use std::{
+    any::TypeId,
     borrow::Cow,
     fmt::Debug,
     marker::PhantomData,
@@ -272,11 +274,8 @@ pub trait Resource: Send + Sync + 'static {}
 ///
 /// Use `Option<Res<T>>` instead if the resource might not always exist.
 pub struct Res<'w, T: Resource> {
-    value: &'w T,
-    added: &'w Tick,
-    changed: &'w Tick,
-    last_change_tick: u32,
-    change_tick: u32,
+    pub(crate) value: &'w T,
+    pub(crate) ticks: RefTicks<'w>,
 }
 
 // SAFETY: Res only reads a single World resource
@@ -297,27 +296,39 @@ impl<'w, T: Resource> Res<'w, T> {
     pub fn clone(this: &Self) -> Self {
         Self {
             value: this.value,
-            added: this.added,
-            changed: this.changed,
-            last_change_tick: this.last_change_tick,
-            change_tick: this.change_tick,
+            ticks: this.ticks,
         }
     }
 
-    /// Returns `true` if the resource was added after the system last ran.
-    pub fn is_added(&self) -> bool {
-        self.added
-            .is_older_than(self.last_change_tick, self.change_tick)
+    pub fn into_inner(self) -> &'w T {
+        self.value
     }
+}
 
-    /// Returns `true` if the resource was added or mutably dereferenced after the system last ran.
-    pub fn is_changed(&self) -> bool {
-        self.changed
-            .is_older_than(self.last_change_tick, self.change_tick)
+impl<'w, T: Resource> DetectChanges for Res<'w, T> {
+    #[inline]
+    fn is_added(&self) -> bool {
+        self.ticks
+            .added
+            .is_older_than(self.ticks.last_change_tick, self.ticks.change_tick)
     }
 
-    pub fn into_inner(self) -> &'w T {
-        self.value
+    #[inline]
+    fn is_changed(&self) -> bool {
+        self.ticks
+            .changed
+            .is_older_than(self.ticks.last_change_tick, self.ticks.change_tick)
+    }
+
+    #[inline]
+    fn last_changed(&self) -> u32 {
+        self.ticks.changed.tick
+    }
+
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    fn changed_by(&self) -> ChangedBy {
+        self.ticks.changed.changed_by
     }
 }
 
@@ -340,10 +351,7 @@ impl<'w, T: Resource> From<ResMut<'w, T>> for Res<'w, T> {
     fn from(res: ResMut<'w, T>) -> Self {
         Self {
             value: res.value,
-            added: res.ticks.added,
-            changed: res.ticks.changed,
-            change_tick: res.ticks.change_tick,
-            last_change_tick: res.ticks.last_change_tick,
+            ticks: res.ticks.into(),
         }
     }
 }
@@ -421,10 +429,7 @@ impl<'w, 's, T: Resource> SystemParamFetch<'w, 's> for ResState<T> {
             });
         Res {
             value: ptr.deref(),
-            added: ticks.added.deref(),
-            changed: ticks.changed.deref(),
-            last_change_tick: system_meta.last_change_tick,
-            change_tick,
+            ticks: RefTicks::from_tick_cells(ticks, system_meta.last_change_tick, change_tick),
         }
     }
 }
@@ -463,10 +468,7 @@ impl<'w, 's, T: Resource> SystemParamFetch<'w, 's> for OptionResState<T> {
             .get_resource_with_ticks(state.0.component_id)
             .map(|(ptr, ticks)| Res {
                 value: ptr.deref(),
-                added: ticks.added.deref(),
-                changed: ticks.changed.deref(),
-                last_change_tick: system_meta.last_change_tick,
-                change_tick,
+                ticks: RefTicks::from_tick_cells(ticks, system_meta.last_change_tick, change_tick),
             })
     }
 }
@@ -895,6 +897,102 @@ impl<'w, 's, T: Component> SystemParamFetch<'w, 's> for RemovedComponentsState<T
     }
 }
 
+/// A [`SystemParam`] that grants access to the resources that were removed since the last time
+/// [`World::clear_trackers`] was called.
+///
+/// Unlike [`RemovedComponents`], this lists the removals of every resource type, and it isn't
+/// possible to read the removed values.
+///
+/// If you are using `bevy_ecs` as a standalone crate, note that the list will not be cleared
+/// automatically, see [`RemovedComponents`] for details.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #
+/// # #[derive(Resource)]
+/// # struct Connection;
+///
+/// fn on_disconnect(removed: RemovedResources) {
+///     if removed.contains::<Connection>() {
+///         println!("connection lost");
+///     }
+/// }
+///
+/// # bevy_ecs::system::assert_is_system(on_disconnect);
+/// ```
+pub struct RemovedResources<'a> {
+    world: &'a World,
+}
+
+impl<'a> RemovedResources<'a> {
+    /// Returns an iterator over the [`ComponentId`]s of the removed resources.
+    pub fn iter(&self) -> std::iter::Copied<std::slice::Iter<'a, ComponentId>> {
+        self.world.removed_resources().iter().copied()
+    }
+
+    /// Returns `true` if the resource of type `R` was removed.
+    ///
+    /// This works for both [`Resource`]s and non-[`Send`] resources.
+    pub fn contains<R: 'static>(&self) -> bool {
+        let component_id = self.world.components().get_resource_id(TypeId::of::<R>());
+        matches!(component_id, Some(component_id) if self.contains_id(component_id))
+    }
+
+    /// Returns `true` if the resource with the given [`ComponentId`] was removed.
+    pub fn contains_id(&self, component_id: ComponentId) -> bool {
+        self.world.removed_resources().contains(&component_id)
+    }
+
+    /// Returns `true` if no resource was removed.
+    pub fn is_empty(&self) -> bool {
+        self.world.removed_resources().is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a RemovedResources<'a> {
+    type Item = ComponentId;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, ComponentId>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// SAFETY: Only reads World resource removals
+unsafe impl ReadOnlySystemParamFetch for RemovedResourcesState {}
+
+/// The [`SystemParamState`] of [`RemovedResources`].
+#[doc(hidden)]
+pub struct RemovedResourcesState;
+
+impl<'a> SystemParam for RemovedResources<'a> {
+    type Fetch = RemovedResourcesState;
+}
+
+// SAFETY: no component access. The removed resource list can be read in parallel and is
+// never mutably borrowed during system execution
+unsafe impl SystemParamState for RemovedResourcesState {
+    fn init(_world: &mut World, _system_meta: &mut SystemMeta) -> Self {
+        Self
+    }
+}
+
+impl<'w, 's> SystemParamFetch<'w, 's> for RemovedResourcesState {
+    type Item = RemovedResources<'w>;
+
+    #[inline]
+    unsafe fn get_param(
+        _state: &'s mut Self,
+        _system_meta: &SystemMeta,
+        world: &'w World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        RemovedResources { world }
+    }
+}
+
 /// Shared borrow of a non-[`Send`] resource.
 ///
 /// Only `Send` resources may be accessed with the [`Res`] [`SystemParam`]. In case that the
@@ -926,17 +1024,28 @@ where
     }
 }
 
-impl<'w, T: 'static> NonSend<'w, T> {
-    /// Returns `true` if the resource was added after the system last ran.
-    pub fn is_added(&self) -> bool {
+impl<'w, T: 'static> DetectChanges for NonSend<'w, T> {
+    #[inline]
+    fn is_added(&self) -> bool {
         self.ticks.is_added(self.last_change_tick, self.change_tick)
     }
 
-    /// Returns `true` if the resource was added or mutably dereferenced after the system last ran.
-    pub fn is_changed(&self) -> bool {
+    #[inline]
+    fn is_changed(&self) -> bool {
         self.ticks
             .is_changed(self.last_change_tick, self.change_tick)
     }
+
+    #[inline]
+    fn last_changed(&self) -> u32 {
+        self.ticks.changed.tick
+    }
+
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    fn changed_by(&self) -> ChangedBy {
+        self.ticks.changed.changed_by
+    }
 }
 
 impl<'w, T> Deref for NonSend<'w, T> {
@@ -0,0 +1,418 @@
+use crate::{
+    self as bevy_ecs,
+    change_detection::DetectChangesMut,
+    system::{BoxedSystem, Command, IntoSystem, Resource},
+    world::World,
+};
+use bevy_utils::{tracing::error, HashMap};
+use std::fmt;
+
+/// A system that has been registered in a [`World`] with [`World::register_system`].
+struct RegisteredSystem {
+    initialized: bool,
+    /// The system, or `None` while it is running.
+    system: Option<BoxedSystem>,
+}
+
+/// The systems registered in a [`World`], stored in a resource rather than on entities so that
+/// they are neither visible to queries nor removed by [`World::clear_entities`].
+#[derive(Resource, Default)]
+struct RegisteredSystems {
+    systems: HashMap<SystemId, RegisteredSystem>,
+    next_id: u64,
+}
+
+/// An identifier for a system registered with [`World::register_system`].
+///
+/// It can be used to run the system on demand with [`World::run_system`] or
+/// [`Commands::run_system`](crate::system::Commands::run_system).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SystemId(u64);
+
+impl World {
+    /// Registers a system and returns a [`SystemId`] that can be used to run it on demand with
+    /// [`World::run_system`] or [`Commands::run_system`](crate::system::Commands::run_system).
+    ///
+    /// The system's state (such as [`Local`](crate::system::Local)s and change detection ticks)
+    /// is kept between runs. Calling this method several times with the same system registers
+    /// independent copies of it.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Counter(u8);
+    ///
+    /// fn increment(mut counter: ResMut<Counter>, mut runs: Local<u8>) {
+    ///     *runs += 1;
+    ///     counter.0 = *runs;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Counter>();
+    /// let id = world.register_system(increment);
+    /// world.run_system(id).unwrap();
+    /// world.run_system(id).unwrap();
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// ```
+    pub fn register_system<Params, S: IntoSystem<(), (), Params> + 'static>(
+        &mut self,
+        system: S,
+    ) -> SystemId {
+        self.register_boxed_system(Box::new(IntoSystem::into_system(system)))
+    }
+
+    /// Same as [`World::register_system`], but for an already boxed system.
+    pub fn register_boxed_system(&mut self, system: BoxedSystem) -> SystemId {
+        let mut registered_systems = self.get_resource_or_insert_with(RegisteredSystems::default);
+        let id = SystemId(registered_systems.next_id);
+        registered_systems.next_id += 1;
+        registered_systems.systems.insert(
+            id,
+            RegisteredSystem {
+                initialized: false,
+                system: Some(system),
+            },
+        );
+        id
+    }
+
+    /// Removes a system registered with [`World::register_system`] and returns it.
+    ///
+    /// Returns an error if `id` does not refer to a registered system, or if the system is
+    /// currently running.
+    pub fn remove_system(&mut self, id: SystemId) -> Result<BoxedSystem, RegisteredSystemError> {
+        let mut registered_systems = self
+            .get_resource_mut::<RegisteredSystems>()
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        let registered_system = registered_systems
+            .systems
+            .get(&id)
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        if registered_system.system.is_none() {
+            return Err(RegisteredSystemError::SystemRunning(id));
+        }
+        Ok(registered_systems
+            .systems
+            .remove(&id)
+            .and_then(|registered_system| registered_system.system)
+            .unwrap())
+    }
+
+    /// Runs a system registered with [`World::register_system`], then applies its
+    /// [`Commands`](crate::system::Commands).
+    ///
+    /// The system is initialized the first time it runs, and its state is kept for later runs.
+    ///
+    /// Returns an error if `id` does not refer to a registered system, or if the system is
+    /// already running, which happens when it (indirectly) runs itself.
+    pub fn run_system(&mut self, id: SystemId) -> Result<(), RegisteredSystemError> {
+        let mut registered_systems = self
+            .get_resource_mut::<RegisteredSystems>()
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        let registered_system = registered_systems
+            .systems
+            .get_mut(&id)
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        // The system is taken out of the world while it runs, so that it can access the world
+        // mutably.
+        let mut system = registered_system
+            .system
+            .take()
+            .ok_or(RegisteredSystemError::Recursive(id))?;
+        let initialized = std::mem::replace(&mut registered_system.initialized, true);
+
+        if !initialized {
+            system.initialize(self);
+        }
+        system.run((), self);
+        system.apply_buffers(self);
+
+        if let Some(registered_system) = self
+            .get_resource_mut::<RegisteredSystems>()
+            .and_then(|registered_systems| registered_systems.into_inner().systems.get_mut(&id))
+        {
+            registered_system.system = Some(system);
+        }
+        Ok(())
+    }
+
+    /// Clamps the change ticks of the registered systems, which are not stored in any stage.
+    ///
+    /// See [`World::check_change_ticks`].
+    pub(crate) fn check_registered_system_change_ticks(&mut self, change_tick: u32) {
+        if let Some(mut registered_systems) = self.get_resource_mut::<RegisteredSystems>() {
+            for registered_system in registered_systems
+                .bypass_change_detection()
+                .systems
+                .values_mut()
+            {
+                // A running system is out of the registry, but its ticks are recent.
+                if let Some(system) = &mut registered_system.system {
+                    system.check_change_tick(change_tick);
+                }
+            }
+        }
+    }
+}
+
+/// A [`Command`] that runs the system registered with the given [`SystemId`].
+///
+/// See [`World::run_system`] for more details.
+#[derive(Debug, Clone, Copy)]
+pub struct RunSystem {
+    /// The id of the registered system to run.
+    pub system_id: SystemId,
+}
+
+impl Command for RunSystem {
+    fn write(self, world: &mut World) {
+        if let Err(err) = world.run_system(self.system_id) {
+            error!("Failed to run registered system: {}", err);
+        }
+    }
+}
+
+/// An error returned when a system registered with [`World::register_system`] can not be run or
+/// removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisteredSystemError {
+    /// No system is registered with the given [`SystemId`].
+    SystemIdNotRegistered(SystemId),
+    /// The system is already running.
+    Recursive(SystemId),
+    /// The system can not be removed while it is running.
+    SystemRunning(SystemId),
+}
+
+impl std::error::Error for RegisteredSystemError {}
+
+impl fmt::Display for RegisteredSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegisteredSystemError::SystemIdNotRegistered(id) => {
+                write!(f, "System {:?} was not registered.", id)
+            }
+            RegisteredSystemError::Recursive(id) => {
+                write!(f, "System {:?} tried to run itself recursively.", id)
+            }
+            RegisteredSystemError::SystemRunning(id) => {
+                write!(f, "System {:?} can not be removed while it is running.", id)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        change_detection::{CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
+        prelude::*,
+        system::{CommandQueue, RegisteredSystemError, SystemId},
+    };
+
+    #[derive(Resource, Default, PartialEq, Debug)]
+    struct Counter(u8);
+
+    #[test]
+    fn run_system_keeps_local_state() {
+        fn count_up(mut counter: ResMut<Counter>, mut local: Local<u8>) {
+            *local += 1;
+            counter.0 += *local;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count_up);
+        world.run_system(id).unwrap();
+        world.run_system(id).unwrap();
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(1 + 2 + 3));
+    }
+
+    #[test]
+    fn run_system_keeps_change_detection_state() {
+        fn count_changes(mut counter: ResMut<Counter>, query: Query<(), Changed<Transform>>) {
+            counter.0 += query.iter().count() as u8;
+        }
+
+        #[derive(Component)]
+        struct Transform;
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count_changes);
+        let entity = world.spawn(Transform).id();
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(1));
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(1));
+        world.get_mut::<Transform>(entity).unwrap().set_changed();
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(2));
+    }
+
+    #[test]
+    fn run_system_after_max_change_age() {
+        fn count_changes(mut counter: ResMut<Counter>, query: Query<(), Changed<Transform>>) {
+            counter.0 += query.iter().count() as u8;
+        }
+
+        #[derive(Component)]
+        struct Transform;
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count_changes);
+        let entity = world.spawn(Transform).id();
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(1));
+
+        // The system doesn't run while the change tick wraps around, with the change ticks
+        // checked as often as in an app, and the component changes right before the wraparound.
+        let advance = |world: &mut World, ticks: u32| {
+            let change_tick = world.change_tick.get_mut();
+            *change_tick = change_tick.wrapping_add(ticks);
+            world.check_change_ticks();
+        };
+        advance(&mut world, MAX_CHANGE_AGE + CHECK_TICK_THRESHOLD);
+        advance(&mut world, MAX_CHANGE_AGE + CHECK_TICK_THRESHOLD);
+        advance(&mut world, 2 * CHECK_TICK_THRESHOLD - 10);
+        world.get_mut::<Transform>(entity).unwrap().set_changed();
+        advance(&mut world, 20);
+        world.run_system(id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(2));
+    }
+
+    #[test]
+    fn run_system_applies_commands() {
+        #[derive(Component)]
+        struct Spawned;
+
+        fn spawn(mut commands: Commands) {
+            commands.spawn(Spawned);
+        }
+
+        let mut world = World::new();
+        let id = world.register_system(spawn);
+        world.run_system(id).unwrap();
+        assert_eq!(world.query::<&Spawned>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn run_system_from_commands() {
+        fn increment(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(increment);
+
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.run_system(id);
+            commands.run_system(id);
+        }
+        queue.apply(&mut world);
+        assert_eq!(*world.resource::<Counter>(), Counter(2));
+    }
+
+    #[test]
+    fn nested_systems() {
+        #[derive(Resource)]
+        struct Inner(SystemId);
+
+        fn outer(mut commands: Commands, inner: Res<Inner>) {
+            commands.run_system(inner.0);
+        }
+
+        fn inner(mut counter: ResMut<Counter>) {
+            counter.0 += 1;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let inner_id = world.register_system(inner);
+        world.insert_resource(Inner(inner_id));
+        let outer_id = world.register_system(outer);
+        world.run_system(outer_id).unwrap();
+        assert_eq!(*world.resource::<Counter>(), Counter(1));
+    }
+
+    #[test]
+    fn recursive_run_system_fails() {
+        #[derive(Resource)]
+        struct Me(SystemId);
+
+        #[derive(Resource, Default)]
+        struct Result(Option<std::result::Result<(), RegisteredSystemError>>);
+
+        fn run_self(world: &mut World) {
+            let id = world.resource::<Me>().0;
+            let result = world.run_system(id);
+            world.resource_mut::<Result>().0 = Some(result);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Result>();
+        let id = world.register_system(run_self);
+        world.insert_resource(Me(id));
+        world.run_system(id).unwrap();
+        assert_eq!(
+            world.resource::<Result>().0,
+            Some(Err(RegisteredSystemError::Recursive(id)))
+        );
+    }
+
+    #[test]
+    fn remove_system() {
+        fn noop() {}
+
+        let mut world = World::new();
+        let id = world.register_system(noop);
+        assert!(world.remove_system(id).is_ok());
+        assert_eq!(
+            world.run_system(id),
+            Err(RegisteredSystemError::SystemIdNotRegistered(id))
+        );
+    }
+
+    #[test]
+    fn remove_running_system_fails() {
+        #[derive(Resource)]
+        struct Me(SystemId);
+
+        #[derive(Resource, Default)]
+        struct Removed(Option<RegisteredSystemError>);
+
+        fn remove_self(world: &mut World) {
+            let id = world.resource::<Me>().0;
+            world.resource_mut::<Removed>().0 = world.remove_system(id).err();
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Removed>();
+        let id = world.register_system(remove_self);
+        world.insert_resource(Me(id));
+        world.run_system(id).unwrap();
+        assert_eq!(
+            world.resource::<Removed>().0,
+            Some(RegisteredSystemError::SystemRunning(id))
+        );
+        // The system is still registered.
+        assert!(world.run_system(id).is_ok());
+    }
+
+    #[test]
+    fn registered_systems_are_not_entities() {
+        fn noop() {}
+
+        let mut world = World::new();
+        let id = world.register_system(noop);
+        assert_eq!(world.query::<Entity>().iter(&world).count(), 0);
+        world.clear_entities();
+        assert!(world.run_system(id).is_ok());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInfo},
-    change_detection::{MutUntyped, Ticks},
+    bundle::{Bundle, BundleId, BundleInfo, ComponentPtrs, DynamicBundle, WithRequiredComponents},
+    change_detection::{MutUntyped, Ref, RefTicks, Ticks},
     component::{Component, ComponentId, ComponentTicks, Components, StorageType, TickCells},
     entity::{Entities, Entity, EntityLocation},
     storage::{SparseSet, Storages},
@@ -9,6 +9,7 @@ use crate::{
 };
 use bevy_ptr::{OwningPtr, Ptr};
 use bevy_utils::tracing::debug;
+use fixedbitset::FixedBitSet;
 use std::any::TypeId;
 
 /// A read-only reference to a particular [`Entity`] and all of its components
@@ -74,6 +75,29 @@ impl<'w> EntityRef<'w> {
         }
     }
 
+    /// Gets access to the component of type `T` for the current entity,
+    /// including its change detection information as a [`Ref`].
+    ///
+    /// Returns `None` if the entity does not have a component of type `T`.
+    #[inline]
+    pub fn get_ref<T: Component>(&self) -> Option<Ref<'w, T>> {
+        let last_change_tick = self.world.last_change_tick();
+        let change_tick = self.world.read_change_tick();
+        // SAFETY: entity location is valid and returned component is of type T
+        unsafe {
+            get_component_and_ticks_with_type(
+                self.world,
+                TypeId::of::<T>(),
+                self.entity,
+                self.location,
+            )
+            .map(|(value, ticks)| Ref {
+                value: value.deref::<T>(),
+                ticks: RefTicks::from_tick_cells(ticks, last_change_tick, change_tick),
+            })
+        }
+    }
+
     /// Retrieves the change ticks for the given component. This can be useful for implementing change
     /// detection in custom runtimes.
     #[inline]
@@ -241,11 +265,102 @@ impl<'w> EntityMut<'w> {
     ///
     /// This will overwrite any previous value(s) of the same component type.
     pub fn insert<T: Bundle>(&mut self, bundle: T) -> &mut Self {
-        let change_tick = self.world.change_tick();
-        let bundle_info = self
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        // SAFETY: `bundle_id` is the id of the bundle info of `T`
+        unsafe { self.insert_with_bundle_id(bundle_id, bundle) }
+    }
+
+    /// Inserts a component with the given [`ComponentId`] and value into the entity.
+    ///
+    /// **You should prefer to use the typed API [`EntityMut::insert`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// This will overwrite any previous value of the same component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` doesn't exist in this entity's [`World`].
+    ///
+    /// # Safety
+    ///
+    /// `component` must point to a valid value of the component of `component_id`.
+    pub unsafe fn insert_by_id(
+        &mut self,
+        component_id: ComponentId,
+        component: OwningPtr<'_>,
+    ) -> &mut Self {
+        self.insert_by_ids(&[component_id], std::iter::once(component))
+    }
+
+    /// Inserts components with the given [`ComponentId`]s and values into the entity, moving it to
+    /// its new archetype once.
+    ///
+    /// **You should prefer to use the typed API [`EntityMut::insert`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// This will overwrite any previous values of the same components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `component_ids` doesn't exist in this entity's [`World`], or if
+    /// `component_ids` contains duplicates.
+    ///
+    /// # Safety
+    ///
+    /// `components` must yield exactly one valid value for each component of `component_ids`, in
+    /// the same order.
+    pub unsafe fn insert_by_ids<'a>(
+        &mut self,
+        component_ids: &[ComponentId],
+        components: impl Iterator<Item = OwningPtr<'a>>,
+    ) -> &mut Self {
+        for &component_id in component_ids {
+            assert!(
+                self.world.components.get_info(component_id).is_some(),
+                "{component_id:?} doesn't exist in this world"
+            );
+        }
+        let bundle_id = self
             .world
             .bundles
-            .init_info::<T>(&mut self.world.components, &mut self.world.storages);
+            .init_dynamic_info(&mut self.world.components, component_ids)
+            .id();
+        self.insert_with_bundle_id(bundle_id, ComponentPtrs(components))
+    }
+
+    /// Inserts the values of `bundle`, whose components are those of the bundle of `bundle_id`,
+    /// along with the components they require that the entity doesn't have.
+    ///
+    /// # Safety
+    ///
+    /// `bundle` must match the [`BundleInfo`] of `bundle_id`.
+    pub(crate) unsafe fn insert_with_bundle_id<T: DynamicBundle>(
+        &mut self,
+        bundle_id: BundleId,
+        bundle: T,
+    ) -> &mut Self {
+        let change_tick = self.world.change_tick();
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let (bundle_id, required) = self.world.bundles.with_required_components(
+            &mut self.world.components,
+            bundle_id,
+            Some(archetype),
+        );
+        let bundle = WithRequiredComponents {
+            bundle,
+            required: &required,
+        };
+        let bundle_info = self.world.bundles.get(bundle_id).unwrap();
+        let hooks = self
+            .world
+            .components
+            .insert_hooks(bundle_info.components(), Some(archetype));
         let mut bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
@@ -255,13 +370,39 @@ impl<'w> EntityMut<'w> {
             change_tick,
         );
         // SAFETY: location matches current entity. `T` matches `bundle_info`
-        unsafe {
-            self.location = bundle_inserter.insert(self.entity, self.location.index, bundle);
+        self.location = bundle_inserter.insert(self.entity, self.location.index, bundle);
+
+        if !hooks.is_empty() {
+            let entity = self.entity;
+            self.world_scope(|world| world.run_component_hooks(entity, hooks));
         }
 
         self
     }
 
+    /// Runs the `on_remove` hooks of the components of the bundle that the entity has, before
+    /// they are removed. If `intersection` is `false`, no hooks run unless the entity has all of
+    /// them.
+    fn trigger_remove_hooks(&mut self, bundle_id: BundleId, intersection: bool) {
+        let bundle_info = self.world.bundles.get(bundle_id).unwrap();
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let mut component_ids = bundle_info.components().iter().copied();
+        if !intersection && !component_ids.all(|id| archetype.contains(id)) {
+            return;
+        }
+        let hooks = self.world.components.remove_hooks(
+            bundle_info
+                .components()
+                .iter()
+                .copied()
+                .filter(|&id| archetype.contains(id)),
+        );
+        if !hooks.is_empty() {
+            let entity = self.entity;
+            self.world_scope(|world| world.run_component_hooks(entity, hooks));
+        }
+    }
+
     #[deprecated(
         since = "0.9.0",
         note = "Use `remove` instead, which now accepts bundles, components, and tuples of bundles and components."
@@ -275,13 +416,20 @@ impl<'w> EntityMut<'w> {
     ///
     /// Returns `None` if the entity does not contain the bundle.
     pub fn remove<T: Bundle>(&mut self) -> Option<T> {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        self.trigger_remove_hooks(bundle_id, false);
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
         let entities = &mut self.world.entities;
         let removed_components = &mut self.world.removed_components;
 
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        let bundle_info = self.world.bundles.get(bundle_id).unwrap();
         let old_location = self.location;
         // SAFETY: `archetype_id` exists because it is referenced in the old `EntityLocation` which is valid,
         // components exist in `bundle_info` because `Bundles::init_info` initializes a `BundleInfo` containing all components of the bundle type `T`
@@ -409,17 +557,29 @@ impl<'w> EntityMut<'w> {
     // TODO: move to BundleInfo
     /// Remove any components in the bundle that the entity has.
     pub fn remove_intersection<T: Bundle>(&mut self) {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        self.remove_intersection_by_bundle(bundle_id);
+    }
+
+    /// Removes any components of the bundle of `bundle_id` that the entity has.
+    pub(crate) fn remove_intersection_by_bundle(&mut self, bundle_id: BundleId) {
+        self.trigger_remove_hooks(bundle_id, true);
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
         let entities = &mut self.world.entities;
         let removed_components = &mut self.world.removed_components;
 
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        let bundle_info = self.world.bundles.get(bundle_id).unwrap();
         let old_location = self.location;
 
         // SAFETY: `archetype_id` exists because it is referenced in the old `EntityLocation` which is valid,
-        // components exist in `bundle_info` because `Bundles::init_info` initializes a `BundleInfo` containing all components of the bundle type `T`
+        // components exist in `bundle_info` because bundle infos are only initialized with valid components
         let new_archetype_id = unsafe {
             remove_bundle_from_archetype(
                 archetypes,
@@ -474,46 +634,37 @@ impl<'w> EntityMut<'w> {
     pub fn despawn(self) {
         debug!("Despawning entity {:?}", self.entity);
         let world = self.world;
+        // `on_remove` hooks may insert components on the entity, which then need their own
+        // `on_remove` hooks to run, so keep going until every component was visited.
+        let mut visited = FixedBitSet::new();
+        loop {
+            visited.grow(world.components.len());
+            let location = world
+                .entities
+                .get(self.entity)
+                .expect("entity should exist at this point.");
+            let hooks = world.components.remove_hooks(
+                world.archetypes[location.archetype_id]
+                    .components()
+                    .filter(|id| !visited.put(id.index())),
+            );
+            if hooks.is_empty() {
+                break;
+            }
+            world.run_component_hooks(self.entity, hooks);
+        }
         world.flush();
         let location = world
             .entities
-            .free(self.entity)
+            .get(self.entity)
             .expect("entity should exist at this point.");
-        let table_row;
-        let moved_entity;
-        {
-            let archetype = &mut world.archetypes[location.archetype_id];
-            for component_id in archetype.components() {
-                let removed_components = world
-                    .removed_components
-                    .get_or_insert_with(component_id, Vec::new);
-                removed_components.push(self.entity);
-            }
-            let remove_result = archetype.swap_remove(location.index);
-            if let Some(swapped_entity) = remove_result.swapped_entity {
-                // SAFETY: swapped_entity is valid and the swapped entity's components are
-                // moved to the new location immediately after.
-                unsafe {
-                    world.entities.set(swapped_entity.index(), location);
-                }
-            }
-            table_row = remove_result.table_row;
-
-            for component_id in archetype.sparse_set_components() {
-                let sparse_set = world.storages.sparse_sets.get_mut(component_id).unwrap();
-                sparse_set.remove(self.entity);
-            }
-            // SAFETY: table rows stored in archetypes always exist
-            moved_entity = unsafe {
-                world.storages.tables[archetype.table_id()].swap_remove_unchecked(table_row)
-            };
-        };
-
-        if let Some(moved_entity) = moved_entity {
-            let moved_location = world.entities.get(moved_entity).unwrap();
-            world.archetypes[moved_location.archetype_id]
-                .set_entity_table_row(moved_location.index, table_row);
+        for component_id in world.archetypes[location.archetype_id].components() {
+            world
+                .removed_components
+                .get_or_insert_with(component_id, Vec::new)
+                .push(self.entity);
         }
+        free_entity(world, self.entity);
     }
 
     #[inline]
@@ -625,7 +776,7 @@ pub(crate) unsafe fn get_component(
 /// # Safety
 /// Caller must ensure that `component_id` is valid
 #[inline]
-unsafe fn get_component_and_ticks(
+pub(crate) unsafe fn get_component_and_ticks(
     world: &World,
     component_id: ComponentId,
     entity: Entity,
@@ -778,6 +929,182 @@ fn contains_component_with_id(
     world.archetypes[location.archetype_id].contains(component_id)
 }
 
+/// Frees `entity` and drops its components, without running their `on_remove` hooks or
+/// recording them as removed.
+fn free_entity(world: &mut World, entity: Entity) {
+    let location = world
+        .entities
+        .free(entity)
+        .expect("entity should exist at this point.");
+    let table_row;
+    let moved_entity;
+    {
+        let archetype = &mut world.archetypes[location.archetype_id];
+        let remove_result = archetype.swap_remove(location.index);
+        if let Some(swapped_entity) = remove_result.swapped_entity {
+            // SAFETY: swapped_entity is valid and the swapped entity's components are
+            // moved to the new location immediately after.
+            unsafe {
+                world.entities.set(swapped_entity.index(), location);
+            }
+        }
+        table_row = remove_result.table_row;
+
+        for component_id in archetype.sparse_set_components() {
+            let sparse_set = world.storages.sparse_sets.get_mut(component_id).unwrap();
+            sparse_set.remove(entity);
+        }
+        // SAFETY: table rows stored in archetypes always exist
+        moved_entity =
+            unsafe { world.storages.tables[archetype.table_id()].swap_remove_unchecked(table_row) };
+    };
+
+    if let Some(moved_entity) = moved_entity {
+        let moved_location = world.entities.get(moved_entity).unwrap();
+        world.archetypes[moved_location.archetype_id]
+            .set_entity_table_row(moved_location.index, table_row);
+    }
+}
+
+/// Despawns the `entities`, which were all in the archetype `archetype_id` when the batch was
+/// built. Entities that don't exist anymore are added to `invalid_entities`.
+///
+/// If the archetype has no `on_remove` hooks, the removed components are recorded once for the
+/// whole batch. Otherwise, or if an entity was moved to another archetype by a hook of a previous
+/// batch, the entities are despawned one-by-one with [`EntityMut::despawn`].
+pub(crate) fn despawn_archetype_batch(
+    world: &mut World,
+    archetype_id: ArchetypeId,
+    entities: &[Entity],
+    invalid_entities: &mut Vec<Entity>,
+) {
+    let has_hooks = !world
+        .components
+        .remove_hooks(world.archetypes[archetype_id].components())
+        .is_empty();
+    let mut despawned = Vec::with_capacity(entities.len());
+    for &entity in entities {
+        match world.entities.get(entity) {
+            Some(location) if !has_hooks && location.archetype_id == archetype_id => {
+                debug!("Despawning entity {:?}", entity);
+                free_entity(world, entity);
+                despawned.push(entity);
+            }
+            Some(_) => world.entity_mut(entity).despawn(),
+            None => invalid_entities.push(entity),
+        }
+    }
+    if despawned.is_empty() {
+        return;
+    }
+    for component_id in world.archetypes[archetype_id].components() {
+        world
+            .removed_components
+            .get_or_insert_with(component_id, Vec::new)
+            .extend_from_slice(&despawned);
+    }
+}
+
+/// Removes the components of the bundle of `bundle_id` from the `entities`, which were all in the
+/// archetype `archetype_id` when the batch was built. Entities that don't exist anymore are
+/// skipped.
+///
+/// If the removed components have no `on_remove` hooks, the target archetype is only looked up
+/// once and the removed components are recorded once for the whole batch. Otherwise, or if an
+/// entity was moved to another archetype by a hook of a previous batch, the components are removed
+/// one entity at a time.
+pub(crate) fn remove_archetype_batch(
+    world: &mut World,
+    bundle_id: BundleId,
+    archetype_id: ArchetypeId,
+    entities: &[Entity],
+) {
+    let bundle_info = world.bundles.get(bundle_id).unwrap();
+    let removed: Vec<ComponentId> = bundle_info
+        .components()
+        .iter()
+        .copied()
+        .filter(|&id| world.archetypes[archetype_id].contains(id))
+        .collect();
+    if removed.is_empty() {
+        return;
+    }
+    if !world
+        .components
+        .remove_hooks(removed.iter().copied())
+        .is_empty()
+    {
+        for &entity in entities {
+            if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.remove_intersection_by_bundle(bundle_id);
+            }
+        }
+        return;
+    }
+
+    // SAFETY: `archetype_id` exists because it is referenced by the location of an entity,
+    // components exist in `bundle_info` because bundle infos are only initialized with valid components
+    let new_archetype_id = unsafe {
+        remove_bundle_from_archetype(
+            &mut world.archetypes,
+            &mut world.storages,
+            &mut world.components,
+            archetype_id,
+            bundle_info,
+            true,
+        )
+        .expect("intersections should always return a result")
+    };
+
+    let mut moved = Vec::with_capacity(entities.len());
+    for &entity in entities {
+        match world.entities.get(entity) {
+            Some(location) if location.archetype_id == archetype_id => {
+                for &component_id in &removed {
+                    // Make sure to drop components stored in sparse sets.
+                    // Dense components are dropped later in `move_to_and_drop_missing_unchecked`.
+                    if let Some(StorageType::SparseSet) =
+                        world.archetypes[archetype_id].get_storage_type(component_id)
+                    {
+                        world
+                            .storages
+                            .sparse_sets
+                            .get_mut(component_id)
+                            .unwrap()
+                            .remove(entity);
+                    }
+                }
+                let mut new_location = location;
+                // SAFETY: `location` is the current location of `entity`, and `new_archetype_id`
+                // is the archetype of `archetype_id` without the removed components
+                unsafe {
+                    EntityMut::move_entity_from_remove::<true>(
+                        entity,
+                        &mut new_location,
+                        archetype_id,
+                        location,
+                        &mut world.entities,
+                        &mut world.archetypes,
+                        &mut world.storages,
+                        new_archetype_id,
+                    );
+                }
+                moved.push(entity);
+            }
+            Some(_) => world
+                .entity_mut(entity)
+                .remove_intersection_by_bundle(bundle_id),
+            None => {}
+        }
+    }
+    for component_id in removed {
+        world
+            .removed_components
+            .get_or_insert_with(component_id, Vec::new)
+            .extend_from_slice(&moved);
+    }
+}
+
 /// Removes a bundle from the given archetype and returns the resulting archetype (or None if the
 /// removal was invalid). in the event that adding the given bundle does not result in an Archetype
 /// change. Results are cached in the Archetype Graph to avoid redundant work.
@@ -938,8 +1265,13 @@ pub(crate) unsafe fn get_mut_by_id(
 #[cfg(test)]
 mod tests {
     use crate as bevy_ecs;
-    use crate::component::ComponentId;
+    use crate::component::{ComponentDescriptor, ComponentId, StorageType};
     use crate::prelude::*; // for the `#[derive(Component)]`
+    use bevy_ptr::OwningPtr;
+    use std::{
+        alloc::Layout,
+        sync::atomic::{AtomicU32, Ordering},
+    };
 
     #[test]
     fn sorted_remove() {
@@ -1028,4 +1360,102 @@ mod tests {
         assert!(entity.get_by_id(invalid_component_id).is_none());
         assert!(entity.get_mut_by_id(invalid_component_id).is_none());
     }
+
+    #[test]
+    fn entity_mut_insert_by_id() {
+        static DROP_COUNT: AtomicU32 = AtomicU32::new(0);
+
+        let mut world = World::new();
+        let ids = [StorageType::Table, StorageType::SparseSet].map(|storage_type| {
+            // SAFETY: the drop function is valid for the layout and the data will be safe to
+            // access from any thread
+            let descriptor = unsafe {
+                ComponentDescriptor::new_with_layout(
+                    "Custom Test Component",
+                    storage_type,
+                    Layout::new::<u64>(),
+                    Some(|_| {
+                        DROP_COUNT.fetch_add(1, Ordering::SeqCst);
+                    }),
+                )
+            };
+            world.init_component_with_descriptor(descriptor)
+        });
+
+        let mut entity = world.spawn(TestComponent(0));
+        for (value, &component_id) in [1u64, 2].into_iter().zip(&ids) {
+            OwningPtr::make(value, |ptr| {
+                // SAFETY: `u64` matches the layout of the component
+                unsafe { entity.insert_by_id(component_id, ptr) };
+            });
+        }
+        let value = entity.get_mut_by_id(ids[1]).unwrap();
+        // SAFETY: `u64` matches the layout of the component
+        unsafe { *value.into_inner().deref_mut::<u64>() += 10 };
+
+        let entity = entity.id();
+        // SAFETY: `u64` matches the layout of the components
+        let values = ids.map(|id| unsafe { *world.get_by_id(entity, id).unwrap().deref::<u64>() });
+        assert_eq!(values, [1, 12]);
+        assert_eq!(world.get::<TestComponent>(entity).unwrap().0, 0);
+
+        // Inserting a component again drops its previous value.
+        OwningPtr::make(3u64, |ptr| {
+            // SAFETY: `u64` matches the layout of the component
+            unsafe { world.entity_mut(entity).insert_by_id(ids[0], ptr) };
+        });
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 1);
+        world.despawn(entity);
+        assert_eq!(DROP_COUNT.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn entity_mut_insert_by_ids() {
+        #[derive(Component)]
+        struct A(u32);
+
+        let mut world = World::new();
+        // SAFETY: `u8` has no drop glue
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                "Custom Test Component",
+                StorageType::Table,
+                Layout::new::<u8>(),
+                None,
+            )
+        };
+        let dynamic_id = world.init_component_with_descriptor(descriptor);
+        let a_id = world.init_component::<A>();
+
+        let entity = world.spawn_empty().id();
+        OwningPtr::make(A(7), |a| {
+            OwningPtr::make(5u8, |dynamic| {
+                // SAFETY: the values match the components, in order
+                unsafe {
+                    world
+                        .entity_mut(entity)
+                        .insert_by_ids(&[a_id, dynamic_id], [a, dynamic].into_iter());
+                }
+            });
+        });
+
+        assert_eq!(world.get::<A>(entity).unwrap().0, 7);
+        // SAFETY: `u8` matches the layout of the component
+        let value = unsafe { *world.get_by_id(entity, dynamic_id).unwrap().deref::<u8>() };
+        assert_eq!(value, 5);
+        let entity = world.entity(entity);
+        let archetype = entity.archetype();
+        assert!(archetype.contains(a_id) && archetype.contains(dynamic_id));
+    }
+
+    #[test]
+    #[should_panic]
+    fn entity_mut_insert_by_id_invalid_component_id() {
+        let mut world = World::new();
+        let mut entity = world.spawn_empty();
+        OwningPtr::make(0u32, |ptr| {
+            // SAFETY: the component doesn't exist, which panics before the value is used
+            unsafe { entity.insert_by_id(ComponentId::new(usize::MAX), ptr) };
+        });
+    }
 }
@@ -1,29 +1,33 @@
 mod entity_ref;
+mod snapshot;
 mod spawn_batch;
 mod world_cell;
 
 pub use crate::change_detection::Mut;
 pub use entity_ref::*;
+pub use snapshot::*;
 pub use spawn_batch::*;
 pub use world_cell::*;
 
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInserter, BundleSpawner, Bundles},
-    change_detection::{MutUntyped, Ticks},
+    bundle::{Bundle, BundleInserter, BundleSpawner, Bundles, WithRequiredComponents},
+    change_detection::{MutUntyped, RefTicks, Ticks},
     component::{
-        Component, ComponentDescriptor, ComponentId, ComponentInfo, Components, TickCells,
+        Component, ComponentDescriptor, ComponentHook, ComponentHooks, ComponentId, ComponentInfo,
+        Components, TickCells,
     },
-    entity::{AllocAtWithoutReplacement, Entities, Entity},
+    entity::{AllocAtWithoutReplacement, Entities, Entity, EntityNamespace, ReserveNamespaceError},
     query::{QueryState, ReadOnlyWorldQuery, WorldQuery},
     storage::{ResourceData, SparseSet, Storages},
-    system::Resource,
+    system::{Res, Resource},
 };
 use bevy_ptr::{OwningPtr, Ptr};
-use bevy_utils::tracing::warn;
+use bevy_utils::{tracing::warn, HashMap};
 use std::{
     any::TypeId,
     fmt,
+    ops::Range,
     sync::atomic::{AtomicU32, Ordering},
 };
 mod identifier;
@@ -57,6 +61,7 @@ pub struct World {
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
     pub(crate) removed_components: SparseSet<ComponentId, Vec<Entity>>,
+    pub(crate) removed_resources: Vec<ComponentId>,
     /// Access cache used by [WorldCell].
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
     main_thread_validator: MainThreadValidator,
@@ -74,6 +79,7 @@ impl Default for World {
             storages: Default::default(),
             bundles: Default::default(),
             removed_components: Default::default(),
+            removed_resources: Vec::new(),
             archetype_component_access: Default::default(),
             main_thread_validator: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
@@ -171,6 +177,55 @@ impl World {
             .init_component_with_descriptor(&mut self.storages, descriptor)
     }
 
+    /// Returns the [`ComponentHooks`] of the [`Component`] type `T`, initializing the component if
+    /// needed, so that lifecycle hooks can be registered for it.
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct Dead(Vec<Entity>);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Dead>();
+    /// world
+    ///     .register_component_hooks::<Health>()
+    ///     .on_remove(|world, entity, _| world.resource_mut::<Dead>().0.push(entity));
+    ///
+    /// let entity = world.spawn(Health(10)).id();
+    /// world.despawn(entity);
+    /// assert_eq!(world.resource::<Dead>().0, vec![entity]);
+    /// ```
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let component_id = self.init_component::<T>();
+        self.components.get_hooks_mut(component_id).unwrap()
+    }
+
+    /// Returns the [`ComponentHooks`] of the component with the given [`ComponentId`], so that
+    /// lifecycle hooks can be registered for it.
+    ///
+    /// Returns [`None`] if `component_id` does not belong to a component of this world.
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        component_id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        self.components.get_hooks_mut(component_id)
+    }
+
+    /// Runs the given component lifecycle hooks for `entity`, in order.
+    pub(crate) fn run_component_hooks(
+        &mut self,
+        entity: Entity,
+        hooks: Vec<(ComponentHook, ComponentId)>,
+    ) {
+        for (hook, component_id) in hooks {
+            hook(self, entity, component_id);
+        }
+    }
+
     /// Returns the [`ComponentId`] of the given [`Component`] type `T`.
     ///
     /// The returned `ComponentId` is specific to the `World` instance
@@ -277,7 +332,8 @@ impl World {
     /// # Note
     /// Spawning a specific `entity` value is rarely the right choice. Most apps should favor [`World::spawn`].
     /// This method should generally only be used for sharing entities across apps, and only when they have a
-    /// scheme worked out to share an ID space (which doesn't happen by default).
+    /// scheme worked out to share an ID space (which doesn't happen by default), like
+    /// [`EntityNamespace`]s.
     #[inline]
     pub fn get_or_spawn(&mut self, entity: Entity) -> Option<EntityMut> {
         self.flush();
@@ -450,10 +506,67 @@ impl World {
     pub fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityMut {
         self.flush();
         let entity = self.entities.alloc();
-        let entity_location = {
-            let bundle_info = self
+        // SAFETY: entity was just allocated
+        unsafe { self.spawn_at_internal(entity, bundle) }
+    }
+
+    /// Spawns a new [`Entity`] with a given [`Bundle`] of [components](`Component`), using an
+    /// index of `namespace`. Returns [`None`] if all of the indices of `namespace` are in use.
+    ///
+    /// See [`EntityNamespace`] for details.
+    ///
+    /// ```
+    /// use bevy_ecs::{component::Component, world::World};
+    ///
+    /// #[derive(Component)]
+    /// struct Projectile;
+    ///
+    /// let mut world = World::new();
+    /// let namespace = world.reserve_entity_namespace(1000..1010).unwrap();
+    /// let entity = world.spawn_in(namespace, Projectile).unwrap().id();
+    /// assert_eq!(entity.index(), 1000);
+    /// ```
+    pub fn spawn_in<B: Bundle>(
+        &mut self,
+        namespace: EntityNamespace,
+        bundle: B,
+    ) -> Option<EntityMut> {
+        self.flush();
+        let entity = self.entities.alloc_in(namespace)?;
+        // SAFETY: entity was just allocated
+        Some(unsafe { self.spawn_at_internal(entity, bundle) })
+    }
+
+    /// Sets aside the entity indices of `range`, so that entities are only spawned with them
+    /// through [`World::spawn_in`] and [`World::get_or_spawn`].
+    ///
+    /// See [`EntityNamespace`] for details.
+    pub fn reserve_entity_namespace(
+        &mut self,
+        range: Range<u32>,
+    ) -> Result<EntityNamespace, ReserveNamespaceError> {
+        self.flush();
+        self.entities.reserve_namespace(range)
+    }
+
+    /// # Safety
+    /// must be called on an entity that was just allocated
+    unsafe fn spawn_at_internal<B: Bundle>(&mut self, entity: Entity, bundle: B) -> EntityMut {
+        let (entity_location, hooks) = {
+            let bundle_id = self
                 .bundles
-                .init_info::<B>(&mut self.components, &mut self.storages);
+                .init_info::<B>(&mut self.components, &mut self.storages)
+                .id();
+            // SAFETY: `bundle_id` was just initialized in this world
+            let (bundle_id, required) =
+                self.bundles
+                    .with_required_components(&mut self.components, bundle_id, None);
+            let bundle = WithRequiredComponents {
+                bundle,
+                required: &required,
+            };
+            let bundle_info = self.bundles.get(bundle_id).unwrap();
+            let hooks = self.components.insert_hooks(bundle_info.components(), None);
             let mut spawner = bundle_info.get_bundle_spawner(
                 &mut self.entities,
                 &mut self.archetypes,
@@ -463,11 +576,17 @@ impl World {
             );
 
             // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
-            unsafe { spawner.spawn_non_existent(entity, bundle) }
+            let entity_location = unsafe { spawner.spawn_non_existent(entity, bundle) };
+            (entity_location, hooks)
         };
 
-        // SAFETY: entity and location are valid, as they were just created above
-        unsafe { EntityMut::new(self, entity, entity_location) }
+        if hooks.is_empty() {
+            // SAFETY: entity and location are valid, as they were just created above
+            unsafe { EntityMut::new(self, entity, entity_location) }
+        } else {
+            self.run_component_hooks(entity, hooks);
+            self.entity_mut(entity)
+        }
     }
 
     /// # Safety
@@ -591,6 +710,7 @@ impl World {
         for entities in self.removed_components.values_mut() {
             entities.clear();
         }
+        self.removed_resources.clear();
 
         self.last_change_tick = self.increment_change_tick();
     }
@@ -709,6 +829,19 @@ impl World {
         }
     }
 
+    /// Returns the [`ComponentId`]s of the resources that were removed
+    /// since the last call to [`World::clear_trackers`].
+    #[inline]
+    pub fn removed_resources(&self) -> &[ComponentId] {
+        &self.removed_resources
+    }
+
+    fn record_removed_resource(&mut self, component_id: ComponentId) {
+        if !self.removed_resources.contains(&component_id) {
+            self.removed_resources.push(component_id);
+        }
+    }
+
     /// Inserts a new resource with standard starting values.
     ///
     /// If the resource already exists, nothing happens.
@@ -802,10 +935,12 @@ impl World {
     pub unsafe fn remove_resource_unchecked<R: 'static>(&mut self) -> Option<R> {
         let component_id = self.components.get_resource_id(TypeId::of::<R>())?;
         // SAFETY: the resource is of type R and the value is returned back to the caller.
-        unsafe {
+        let resource = unsafe {
             let (ptr, _) = self.storages.resources.get_mut(component_id)?.remove()?;
-            Some(ptr.read::<R>())
-        }
+            ptr.read::<R>()
+        };
+        self.record_removed_resource(component_id);
+        Some(resource)
     }
 
     /// Returns `true` if a resource of type `R` exists. Otherwise returns `false`.
@@ -858,6 +993,27 @@ impl World {
         }
     }
 
+    /// Gets a reference to the resource of the given type, with its change detection information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist.
+    /// Use [`get_resource_ref`](World::get_resource_ref) instead if you want to handle this case.
+    #[inline]
+    #[track_caller]
+    pub fn resource_ref<R: Resource>(&self) -> Res<'_, R> {
+        match self.get_resource_ref() {
+            Some(x) => x,
+            None => panic!(
+                "Requested resource {} does not exist in the `World`. 
+                Did you forget to add it using `app.insert_resource` / `app.init_resource`? 
+                Resources are also implicitly added via `app.add_event`,
+                and can be added by plugins.",
+                std::any::type_name::<R>()
+            ),
+        }
+    }
+
     /// Gets a mutable reference to the resource of the given type
     ///
     /// # Panics
@@ -890,6 +1046,26 @@ impl World {
         unsafe { self.get_resource_with_id(component_id) }
     }
 
+    /// Gets a reference to the resource of the given type if it exists,
+    /// with its change detection information.
+    #[inline]
+    pub fn get_resource_ref<R: Resource>(&self) -> Option<Res<'_, R>> {
+        let component_id = self.components.get_resource_id(TypeId::of::<R>())?;
+        let (ptr, ticks) = self.get_resource_with_ticks(component_id)?;
+        // SAFETY: `component_id` was obtained from the type ID of `R`, and the world is borrowed
+        // immutably for the lifetime of the returned value.
+        unsafe {
+            Some(Res {
+                value: ptr.deref(),
+                ticks: RefTicks::from_tick_cells(
+                    ticks,
+                    self.last_change_tick(),
+                    self.read_change_tick(),
+                ),
+            })
+        }
+    }
+
     /// Gets a mutable reference to the resource of the given type if it exists
     #[inline]
     pub fn get_resource_mut<R: Resource>(&mut self) -> Option<Mut<'_, R>> {
@@ -1054,6 +1230,32 @@ impl World {
         let bundle_info = self
             .bundles
             .init_info::<B>(&mut self.components, &mut self.storages);
+
+        if !bundle_info.required_components().is_empty()
+            || !self
+                .components
+                .insert_hooks(bundle_info.components(), None)
+                .is_empty()
+        {
+            // Component hooks need exclusive access to the world between each insertion, and the
+            // required components to insert depend on each entity, so fall back to inserting
+            // bundles one at a time.
+            let mut invalid_entities = Vec::new();
+            for (entity, bundle) in iter {
+                match self.get_or_spawn(entity) {
+                    Some(mut entity_mut) => {
+                        entity_mut.insert(bundle);
+                    }
+                    None => invalid_entities.push(entity),
+                }
+            }
+            return if invalid_entities.is_empty() {
+                Ok(())
+            } else {
+                Err(invalid_entities)
+            };
+        }
+
         enum SpawnOrInsert<'a, 'b> {
             Spawn(BundleSpawner<'a, 'b>),
             Insert(BundleInserter<'a, 'b>, ArchetypeId),
@@ -1135,6 +1337,96 @@ impl World {
         }
     }
 
+    /// Removes the components of the [`Bundle`] `B` from each of the `entities`, like
+    /// [`EntityMut::remove_intersection`]. Entities that don't exist are skipped.
+    ///
+    /// The entities are grouped by archetype, so the target archetype is looked up once per
+    /// archetype rather than once per entity. Entities of archetypes whose removed components have
+    /// `on_remove` hooks are handled one-by-one.
+    ///
+    /// ```
+    /// use bevy_ecs::{component::Component, world::World};
+    /// #[derive(Component)]
+    /// struct A;
+    /// #[derive(Component)]
+    /// struct B;
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = (0..10).map(|_| world.spawn((A, B)).id()).collect();
+    /// world.remove_batch::<A>(entities.iter().copied());
+    ///
+    /// assert!(entities.iter().all(|&entity| world.get::<A>(entity).is_none()));
+    /// assert!(entities.iter().all(|&entity| world.get::<B>(entity).is_some()));
+    /// ```
+    pub fn remove_batch<B: Bundle>(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.flush();
+        let bundle_id = self
+            .bundles
+            .init_info::<B>(&mut self.components, &mut self.storages)
+            .id();
+        for (archetype_id, batch) in self.group_by_archetype(entities, &mut Vec::new()) {
+            remove_archetype_batch(self, bundle_id, archetype_id, &batch);
+        }
+    }
+
+    /// Despawns each of the `entities`.
+    ///
+    /// The entities are grouped by archetype, so the removed components are recorded once per
+    /// archetype rather than once per entity. Entities of archetypes with `on_remove` hooks are
+    /// despawned one-by-one. Returns [`Ok`] if all of the entities existed, and otherwise an
+    /// [`Err`] with the entities that didn't exist.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<_> = (0..10).map(|_| world.spawn_empty().id()).collect();
+    /// assert!(world.despawn_batch(entities.iter().copied()).is_ok());
+    /// assert!(world.entities().is_empty());
+    /// assert_eq!(world.despawn_batch([entities[0]]), Err(vec![entities[0]]));
+    /// ```
+    pub fn despawn_batch(
+        &mut self,
+        entities: impl IntoIterator<Item = Entity>,
+    ) -> Result<(), Vec<Entity>> {
+        self.flush();
+        let mut invalid_entities = Vec::new();
+        for (archetype_id, batch) in self.group_by_archetype(entities, &mut invalid_entities) {
+            despawn_archetype_batch(self, archetype_id, &batch, &mut invalid_entities);
+        }
+        if invalid_entities.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_entities)
+        }
+    }
+
+    /// Groups the `entities` by archetype, in the order in which the archetypes are first seen.
+    /// Entities that don't exist are added to `invalid_entities`.
+    fn group_by_archetype(
+        &self,
+        entities: impl IntoIterator<Item = Entity>,
+        invalid_entities: &mut Vec<Entity>,
+    ) -> Vec<(ArchetypeId, Vec<Entity>)> {
+        let mut batches: Vec<(ArchetypeId, Vec<Entity>)> = Vec::new();
+        let mut batch_indices = HashMap::default();
+        for entity in entities {
+            match self.entities.get(entity) {
+                Some(location) => {
+                    let index = *batch_indices
+                        .entry(location.archetype_id)
+                        .or_insert_with(|| {
+                            batches.push((location.archetype_id, Vec::new()));
+                            batches.len() - 1
+                        });
+                    batches[index].1.push(entity);
+                }
+                None => invalid_entities.push(entity),
+            }
+        }
+        batches
+    }
+
     /// Temporarily removes the requested resource from this [`World`], then re-adds it before returning.
     ///
     /// This enables safe simultaneous mutable access to both a resource and the rest of the [`World`].
@@ -1318,6 +1610,42 @@ impl World {
             .insert(value, change_tick);
     }
 
+    /// Inserts `value` as the resource of the given [`ComponentId`], unless it already exists, in
+    /// which case `value` is dropped. Returns `true` if `value` was inserted.
+    ///
+    /// This is the untyped counterpart of [`World::init_resource`], for resources whose type is
+    /// only known at runtime, like those initialized with
+    /// [`World::init_component_with_descriptor`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `component_id` doesn't exist in this [`World`].
+    ///
+    /// # Safety
+    /// The value referenced by `value` must be valid for the given [`ComponentId`] of this world
+    pub unsafe fn init_resource_by_id(
+        &mut self,
+        component_id: ComponentId,
+        value: OwningPtr<'_>,
+    ) -> bool {
+        let info = self.components.get_info(component_id).unwrap_or_else(|| {
+            panic!("{component_id:?} doesn't exist in this world");
+        });
+        let drop = info.drop();
+        let change_tick = self.change_tick();
+        // SAFETY: component_id was checked above
+        let resource = self.initialize_resource_internal(component_id);
+        if resource.is_present() {
+            if let Some(drop) = drop {
+                drop(value);
+            }
+            false
+        } else {
+            resource.insert(value, change_tick);
+            true
+        }
+    }
+
     /// # Safety
     /// `component_id` must be valid for this world
     #[inline]
@@ -1409,6 +1737,7 @@ impl World {
         self.storages.tables.check_change_ticks(change_tick);
         self.storages.sparse_sets.check_change_ticks(change_tick);
         self.storages.resources.check_change_ticks(change_tick);
+        self.check_registered_system_change_ticks(change_tick);
     }
 
     pub fn clear_entities(&mut self) {
@@ -1473,12 +1802,13 @@ impl World {
         if !info.is_send_and_sync() {
             self.validate_non_send_access_untyped(info.name());
         }
-        // SAFETY: The underlying type is Send and Sync or we've already validated we're on the main thread
-        unsafe {
-            self.storages
-                .resources
-                .get_mut(component_id)?
-                .remove_and_drop();
+        let resource = self.storages.resources.get_mut(component_id)?;
+        if resource.is_present() {
+            // SAFETY: The underlying type is Send and Sync or we've already validated we're on the main thread
+            unsafe {
+                resource.remove_and_drop();
+            }
+            self.record_removed_resource(component_id);
         }
         Some(())
     }
@@ -1582,10 +1912,11 @@ impl Default for MainThreadValidator {
 mod tests {
     use super::World;
     use crate::{
-        change_detection::DetectChanges,
-        component::{ComponentDescriptor, ComponentInfo, StorageType},
+        change_detection::DetectChangesMut,
+        component::{ComponentDescriptor, ComponentId, ComponentInfo, StorageType},
+        entity::Entity,
         ptr::OwningPtr,
-        system::Resource,
+        system::{CommandQueue, Commands, Resource},
     };
     use bevy_ecs_macros::Component;
     use bevy_utils::{HashMap, HashSet};
@@ -1802,6 +2133,45 @@ mod tests {
         assert_eq!(DROP_COUNT.load(std::sync::atomic::Ordering::SeqCst), 1);
     }
 
+    #[test]
+    fn init_resource_by_id() {
+        static DROP_COUNT: AtomicU32 = AtomicU32::new(0);
+
+        let mut world = World::new();
+        // SAFETY: the drop function is valid for the layout and the data will be safe to access from any thread
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                "Custom Test Resource".to_string(),
+                StorageType::Table,
+                std::alloc::Layout::new::<u32>(),
+                Some(|_| {
+                    DROP_COUNT.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                }),
+            )
+        };
+        let component_id = world.init_component_with_descriptor(descriptor);
+
+        for value in [1u32, 2] {
+            OwningPtr::make(value, |ptr| {
+                // SAFETY: value is valid for the component layout
+                let inserted = unsafe { world.init_resource_by_id(component_id, ptr) };
+                assert_eq!(inserted, value == 1);
+            });
+        }
+        assert_eq!(DROP_COUNT.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // SAFETY: u32 is the correct type for the resource
+        let data = unsafe {
+            world
+                .get_resource_by_id(component_id)
+                .unwrap()
+                .deref::<u32>()
+        };
+        assert_eq!(*data, 1);
+        drop(world);
+        assert_eq!(DROP_COUNT.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
     #[derive(Component)]
     struct Foo;
 
@@ -1936,4 +2306,335 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[derive(Component)]
+    struct Hooked(u32);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct SparseHooked;
+
+    #[derive(Resource, Default)]
+    struct HookLog(Vec<(&'static str, Entity)>);
+
+    fn init_hook_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<HookLog>();
+        world
+            .register_component_hooks::<Hooked>()
+            .on_add(|world, entity, _| world.resource_mut::<HookLog>().0.push(("add", entity)))
+            .on_insert(|world, entity, _| {
+                world.resource_mut::<HookLog>().0.push(("insert", entity));
+            })
+            .on_remove(|world, entity, _| {
+                // The component can still be read while its `on_remove` hook runs.
+                assert!(world.get::<Hooked>(entity).is_some());
+                world.resource_mut::<HookLog>().0.push(("remove", entity));
+            });
+        world
+    }
+
+    fn take_hook_log(world: &mut World) -> Vec<(&'static str, Entity)> {
+        std::mem::take(&mut world.resource_mut::<HookLog>().0)
+    }
+
+    #[test]
+    fn component_hooks_on_insert_and_remove() {
+        let mut world = init_hook_world();
+
+        let entity = world.spawn(Hooked(0)).id();
+        assert_eq!(
+            take_hook_log(&mut world),
+            vec![("add", entity), ("insert", entity)]
+        );
+
+        // Replacing the value doesn't trigger `on_add`.
+        world.entity_mut(entity).insert(Hooked(1));
+        assert_eq!(take_hook_log(&mut world), vec![("insert", entity)]);
+
+        // Removing a bundle the entity only partially has does nothing.
+        world.entity_mut(entity).remove::<(Hooked, SparseHooked)>();
+        assert!(take_hook_log(&mut world).is_empty());
+
+        world
+            .entity_mut(entity)
+            .remove_intersection::<(Hooked, SparseHooked)>();
+        assert_eq!(take_hook_log(&mut world), vec![("remove", entity)]);
+        assert!(!world.entity(entity).contains::<Hooked>());
+
+        world.entity_mut(entity).insert(Hooked(2));
+        take_hook_log(&mut world);
+        world.despawn(entity);
+        assert_eq!(take_hook_log(&mut world), vec![("remove", entity)]);
+    }
+
+    #[test]
+    fn component_hooks_on_spawn_batch() {
+        let mut world = init_hook_world();
+
+        let entities: Vec<_> = world.spawn_batch((0..3).map(Hooked)).collect();
+        assert_eq!(entities.len(), 3);
+        let log = take_hook_log(&mut world);
+        let expected: Vec<_> = entities
+            .iter()
+            .flat_map(|&entity| [("add", entity), ("insert", entity)])
+            .collect();
+        assert_eq!(log, expected);
+
+        let new_entity = Entity::from_raw(100);
+        world
+            .insert_or_spawn_batch([(entities[0], Hooked(10)), (new_entity, Hooked(11))])
+            .unwrap();
+        assert_eq!(
+            take_hook_log(&mut world),
+            vec![
+                ("insert", entities[0]),
+                ("add", new_entity),
+                ("insert", new_entity)
+            ]
+        );
+        assert_eq!(world.get::<Hooked>(new_entity).unwrap().0, 11);
+    }
+
+    #[test]
+    fn remove_and_despawn_batch_across_archetypes() {
+        #[derive(Component)]
+        struct A;
+
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct B;
+
+        let mut world = init_hook_world();
+        let plain = world.spawn(A).id();
+        let sparse = world.spawn((A, B)).id();
+        let hooked = world.spawn((A, Hooked(0))).id();
+        let untouched = world.spawn(B).id();
+        take_hook_log(&mut world);
+
+        world.remove_batch::<(A, Hooked)>([plain, hooked, sparse, plain, untouched]);
+        assert_eq!(take_hook_log(&mut world), vec![("remove", hooked)]);
+        for entity in [plain, sparse, hooked] {
+            assert!(!world.entity(entity).contains::<A>());
+        }
+        assert!(world.entity(sparse).contains::<B>());
+        let mut removed: Vec<_> = world.removed::<A>().collect();
+        removed.sort();
+        assert_eq!(removed, {
+            let mut expected = vec![plain, sparse, hooked];
+            expected.sort();
+            expected
+        });
+
+        world.clear_trackers();
+        world.entity_mut(hooked).insert(Hooked(1));
+        take_hook_log(&mut world);
+        assert_eq!(
+            world.despawn_batch([sparse, hooked, untouched, sparse]),
+            Err(vec![sparse])
+        );
+        assert_eq!(take_hook_log(&mut world), vec![("remove", hooked)]);
+        assert_eq!(world.entities().len(), 1);
+        assert!(world.get_entity(plain).is_some());
+        let mut removed: Vec<_> = world.removed::<B>().collect();
+        removed.sort();
+        assert_eq!(removed, {
+            let mut expected = vec![sparse, untouched];
+            expected.sort();
+            expected
+        });
+    }
+
+    #[test]
+    fn component_hooks_from_commands() {
+        let mut world = init_hook_world();
+        let mut queue = CommandQueue::default();
+
+        let entity = {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.spawn(Hooked(0)).id()
+        };
+        assert!(take_hook_log(&mut world).is_empty());
+        queue.apply(&mut world);
+        assert_eq!(
+            take_hook_log(&mut world),
+            vec![("add", entity), ("insert", entity)]
+        );
+
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(entity).remove::<Hooked>();
+        }
+        queue.apply(&mut world);
+        assert_eq!(take_hook_log(&mut world), vec![("remove", entity)]);
+    }
+
+    #[test]
+    fn component_hooks_can_change_the_entity() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<Hooked>()
+            .on_add(|world, entity, _| {
+                world.entity_mut(entity).insert(SparseHooked);
+            })
+            .on_remove(|world, entity, _| {
+                world.entity_mut(entity).remove::<SparseHooked>();
+            });
+
+        let mut entity = world.spawn(Hooked(0));
+        assert!(entity.contains::<SparseHooked>());
+        entity.remove::<Hooked>();
+        assert!(!entity.contains::<SparseHooked>());
+        assert!(entity.insert(Hooked(1)).contains::<SparseHooked>());
+    }
+
+    #[test]
+    fn component_hooks_on_remove_inserting_during_despawn() {
+        let mut world = init_hook_world();
+        world
+            .register_component_hooks::<SparseHooked>()
+            .on_remove(|world, entity, _| {
+                world.entity_mut(entity).insert(Hooked(1));
+            });
+
+        let entity = world.spawn(SparseHooked).id();
+        take_hook_log(&mut world);
+        world.despawn(entity);
+        // `Hooked` was inserted by the `on_remove` hook of `SparseHooked` during the despawn, and
+        // its own `on_remove` hook still runs before it is dropped.
+        assert_eq!(
+            take_hook_log(&mut world),
+            vec![("add", entity), ("insert", entity), ("remove", entity)]
+        );
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    fn component_hooks_from_derive() {
+        #[derive(Component)]
+        #[component(on_insert = "on_insert_counter", on_remove = "on_remove_counter")]
+        struct Counted;
+
+        #[derive(Resource, Default)]
+        struct Counter(i32);
+
+        fn on_insert_counter(world: &mut World, _: Entity, _: ComponentId) {
+            world.resource_mut::<Counter>().0 += 1;
+        }
+
+        fn on_remove_counter(world: &mut World, _: Entity, _: ComponentId) {
+            world.resource_mut::<Counter>().0 -= 1;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let a = world.spawn(Counted).id();
+        world.spawn(Counted);
+        assert_eq!(world.resource::<Counter>().0, 2);
+        world.despawn(a);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        let id = world.init_component::<Counted>();
+        let hooks = world.register_component_hooks_by_id(id).unwrap();
+        assert!(hooks.try_on_insert(|_, _, _| {}).is_none());
+        assert!(hooks.try_on_add(|_, _, _| {}).is_some());
+    }
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    #[component(require(RequiredLeaf = "required_leaf"))]
+    struct RequiredMiddle(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct RequiredLeaf(u32);
+
+    #[derive(Component)]
+    #[component(require(RequiredMiddle))]
+    struct Requiring;
+
+    fn required_leaf() -> RequiredLeaf {
+        RequiredLeaf(7)
+    }
+
+    #[test]
+    fn required_components_on_insert_and_spawn() {
+        let mut world = World::new();
+
+        let spawned = world.spawn(Requiring).id();
+        assert_eq!(
+            world.get::<RequiredMiddle>(spawned),
+            Some(&RequiredMiddle(0))
+        );
+        assert_eq!(world.get::<RequiredLeaf>(spawned), Some(&RequiredLeaf(7)));
+
+        let inserted = world.spawn(RequiredLeaf(1)).id();
+        world.entity_mut(inserted).insert(Requiring);
+        assert_eq!(
+            world.get::<RequiredMiddle>(inserted),
+            Some(&RequiredMiddle(0))
+        );
+        // Existing components are not replaced.
+        assert_eq!(world.get::<RequiredLeaf>(inserted), Some(&RequiredLeaf(1)));
+
+        // Components of the bundle win over required ones.
+        let explicit = world.spawn((Requiring, RequiredMiddle(3))).id();
+        assert_eq!(
+            world.get::<RequiredMiddle>(explicit),
+            Some(&RequiredMiddle(3))
+        );
+        assert_eq!(world.get::<RequiredLeaf>(explicit), Some(&RequiredLeaf(7)));
+
+        let batch: Vec<_> = world.spawn_batch([Requiring, Requiring]).collect();
+        for entity in batch {
+            assert_eq!(world.get::<RequiredLeaf>(entity), Some(&RequiredLeaf(7)));
+        }
+
+        let new_entity = Entity::from_raw(100);
+        world
+            .insert_or_spawn_batch([(inserted, Requiring), (new_entity, Requiring)])
+            .unwrap();
+        assert_eq!(world.get::<RequiredLeaf>(inserted), Some(&RequiredLeaf(1)));
+        assert_eq!(
+            world.get::<RequiredLeaf>(new_entity),
+            Some(&RequiredLeaf(7))
+        );
+    }
+
+    #[test]
+    fn required_components_from_commands() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let entity = world.spawn(RequiredMiddle(2)).id();
+        world.entity_mut(entity).remove::<RequiredLeaf>();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(entity).insert(Requiring);
+        }
+        queue.apply(&mut world);
+        assert_eq!(
+            world.get::<RequiredMiddle>(entity),
+            Some(&RequiredMiddle(2))
+        );
+        // Transitive requirements are inserted even if the entity has the component requiring them.
+        assert_eq!(world.get::<RequiredLeaf>(entity), Some(&RequiredLeaf(7)));
+    }
+
+    #[test]
+    fn required_components_run_hooks() {
+        #[derive(Component)]
+        #[component(require(Hooked = "hooked"))]
+        struct RequiresHooked;
+
+        fn hooked() -> Hooked {
+            Hooked(5)
+        }
+
+        let mut world = init_hook_world();
+        let entity = world.spawn(RequiresHooked).id();
+        assert_eq!(world.get::<Hooked>(entity).unwrap().0, 5);
+        assert_eq!(
+            take_hook_log(&mut world),
+            vec![("add", entity), ("insert", entity)]
+        );
+    }
 }
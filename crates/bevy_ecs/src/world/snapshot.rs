@@ -0,0 +1,927 @@
+//! Snapshots of a registered subset of the components and resources of a [`World`].
+
+use std::{any::Any, fmt, sync::Arc};
+
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::{ReflectComponent, ReflectResource};
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId},
+    entity::Entity,
+    system::Resource,
+    world::World,
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{Reflect, TypeRegistration};
+
+type SnapshotData = Box<dyn Any + Send + Sync>;
+
+/// The components and resources captured by [`WorldSnapshot`]s.
+///
+/// Components and resources are registered either with a [`Clone`] and [`PartialEq`]
+/// implementation, or through reflection. Snapshots only store the registered types, in one
+/// column of values per type, which keeps them small and cheap to take every frame.
+///
+/// An entity is part of a snapshot if it has at least one registered component. Rolling a
+/// [`World`] back to a snapshot with [`WorldSnapshot::rollback`]:
+/// - despawns the entities that have registered components but are not part of the snapshot,
+/// - respawns the entities of the snapshot that were despawned since, with the same [`Entity`]
+///   ids, so that references to them stay valid,
+/// - restores the registered components and resources of the snapshot, inserting and removing
+///   them as needed.
+///
+/// Components and resources that are not registered are left untouched, and respawned entities
+/// only get the registered components back.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::SnapshotRegistry};
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Position(i32);
+///
+/// let mut world = World::new();
+/// let player = world.spawn(Position(0)).id();
+///
+/// let mut registry = SnapshotRegistry::default();
+/// registry.register_component::<Position>();
+/// let snapshot = registry.snapshot(&world);
+///
+/// world.entity_mut(player).insert(Position(5));
+/// let projectile = world.spawn(Position(10)).id();
+/// assert_eq!(snapshot.diff(&registry.snapshot(&world)).spawned, vec![projectile]);
+///
+/// snapshot.rollback(&mut world).unwrap();
+/// assert_eq!(world.get::<Position>(player), Some(&Position(0)));
+/// assert!(world.get_entity(projectile).is_none());
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct SnapshotRegistry {
+    entries: Vec<Arc<dyn SnapshotEntry>>,
+}
+
+impl fmt::Debug for SnapshotRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|entry| entry.name()))
+            .finish()
+    }
+}
+
+impl SnapshotRegistry {
+    /// Captures the component `T` in snapshots.
+    ///
+    /// Values are copied with [`Clone`], and compared with [`PartialEq`] to find changes and to
+    /// only write the values that differ on rollback.
+    pub fn register_component<T: Component + Clone + PartialEq>(&mut self) -> &mut Self {
+        self.entries
+            .push(Arc::new(TypedComponent::<T>(Default::default())));
+        self
+    }
+
+    /// Captures the resource `R` in snapshots.
+    ///
+    /// Values are copied with [`Clone`], and compared with [`PartialEq`] to find changes and to
+    /// only write the values that differ on rollback.
+    pub fn register_resource<R: Resource + Clone + PartialEq>(&mut self) -> &mut Self {
+        self.entries
+            .push(Arc::new(TypedResource::<R>(Default::default())));
+        self
+    }
+
+    /// Captures the component or resource of `registration` in snapshots, through its
+    /// [`ReflectComponent`] or [`ReflectResource`] type data.
+    ///
+    /// Values are copied with [`Reflect::clone_value`], and compared with
+    /// [`Reflect::reflect_partial_eq`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type has neither [`ReflectComponent`] nor [`ReflectResource`] type data.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_reflect(&mut self, registration: &TypeRegistration) -> &mut Self {
+        let entry: Arc<dyn SnapshotEntry> = if let Some(reflect_component) =
+            registration.data::<ReflectComponent>()
+        {
+            Arc::new(ReflectedComponent {
+                name: registration.type_name(),
+                type_id: registration.type_id(),
+                reflect_component: reflect_component.clone(),
+            })
+        } else if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+            Arc::new(ReflectedResource {
+                name: registration.type_name(),
+                reflect_resource: reflect_resource.clone(),
+            })
+        } else {
+            panic!(
+                    "{} can't be registered for snapshots, as it reflects neither `Component` nor `Resource`.",
+                    registration.type_name()
+                );
+        };
+        self.entries.push(entry);
+        self
+    }
+
+    /// Returns the names of the registered components and resources.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|entry| entry.name())
+    }
+
+    /// Captures the registered components and resources of `world`.
+    pub fn snapshot(&self, world: &World) -> WorldSnapshot {
+        let data: Vec<_> = self
+            .entries
+            .iter()
+            .map(|entry| entry.snapshot(world))
+            .collect();
+        let mut entities = Vec::new();
+        for (entry, data) in self.entries.iter().zip(&data) {
+            entities.extend_from_slice(entry.entities(&**data));
+        }
+        entities.sort_unstable();
+        entities.dedup();
+        WorldSnapshot {
+            entries: self.entries.clone(),
+            entities,
+            data,
+        }
+    }
+}
+
+/// The registered components and resources of a [`World`] at some point in time, captured by a
+/// [`SnapshotRegistry`].
+pub struct WorldSnapshot {
+    entries: Vec<Arc<dyn SnapshotEntry>>,
+    /// The entities with at least one registered component, sorted.
+    entities: Vec<Entity>,
+    data: Vec<SnapshotData>,
+}
+
+impl fmt::Debug for WorldSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorldSnapshot")
+            .field("entities", &self.entities)
+            .finish()
+    }
+}
+
+impl WorldSnapshot {
+    /// Returns the entities that are part of this snapshot, sorted.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns `true` if `entity` is part of this snapshot.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.binary_search(&entity).is_ok()
+    }
+
+    /// Returns the changes between this snapshot and the more recent snapshot `newer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshots were not taken with the same [`SnapshotRegistry`].
+    pub fn diff(&self, newer: &WorldSnapshot) -> SnapshotDiff {
+        self.assert_same_registry(newer);
+        let mut diff = SnapshotDiff {
+            spawned: newer
+                .entities
+                .iter()
+                .copied()
+                .filter(|&entity| !self.contains(entity))
+                .collect(),
+            despawned: self
+                .entities
+                .iter()
+                .copied()
+                .filter(|&entity| !newer.contains(entity))
+                .collect(),
+            ..Default::default()
+        };
+        let in_both = |entity| self.contains(entity) && newer.contains(entity);
+        for ((entry, old), new) in self.entries.iter().zip(&self.data).zip(&newer.data) {
+            entry.diff(&**old, &**new, &in_both, &mut diff);
+        }
+        diff.components.sort();
+        diff
+    }
+
+    /// Rolls `world` back to this snapshot.
+    ///
+    /// See [`SnapshotRegistry`] for what is restored.
+    ///
+    /// If an entity of the snapshot can't be respawned because its index is used by an entity
+    /// without registered components, an error is returned and `world` is left untouched.
+    pub fn rollback(&self, world: &mut World) -> Result<(), RollbackError> {
+        world.flush();
+        let mut current = Vec::new();
+        for entry in &self.entries {
+            entry.current_entities(world, &mut current);
+        }
+        current.sort_unstable();
+        current.dedup();
+
+        // Entities outside of the snapshot scope are never despawned, so their ids can't be
+        // given back to the entities of the snapshot.
+        let entities = world.entities();
+        for &entity in &self.entities {
+            if !entities.contains(entity)
+                && matches!(
+                    entities.resolve_from_id(entity.index()),
+                    Some(other) if entities.contains(other) && current.binary_search(&other).is_err()
+                )
+            {
+                return Err(RollbackError::EntityIdTaken(entity));
+            }
+        }
+
+        for entity in current {
+            if !self.contains(entity) {
+                world.despawn(entity);
+            }
+        }
+
+        for &entity in &self.entities {
+            if !world.entities().contains(entity) {
+                world.get_or_spawn(entity);
+            }
+        }
+
+        for (entry, data) in self.entries.iter().zip(&self.data) {
+            entry.restore(world, &**data);
+        }
+        Ok(())
+    }
+
+    fn assert_same_registry(&self, other: &WorldSnapshot) {
+        assert!(
+            self.entries.len() == other.entries.len()
+                && self
+                    .entries
+                    .iter()
+                    .zip(&other.entries)
+                    .all(|(a, b)| Arc::ptr_eq(a, b)),
+            "Attempted to compare snapshots taken with different SnapshotRegistries."
+        );
+    }
+}
+
+/// The changes between two [`WorldSnapshot`]s, returned by [`WorldSnapshot::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    /// The entities that are only part of the newer snapshot.
+    pub spawned: Vec<Entity>,
+    /// The entities that are only part of the older snapshot.
+    pub despawned: Vec<Entity>,
+    /// The registered components that changed on entities that are part of both snapshots,
+    /// sorted by entity.
+    pub components: Vec<ComponentChange>,
+    /// The registered resources that changed.
+    pub resources: Vec<ResourceChange>,
+}
+
+impl SnapshotDiff {
+    /// Returns `true` if the snapshots hold the same data.
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty()
+            && self.despawned.is_empty()
+            && self.components.is_empty()
+            && self.resources.is_empty()
+    }
+}
+
+/// How a component or resource changed between two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SnapshotChange {
+    /// The value only exists in the newer snapshot.
+    Added,
+    /// The value only exists in the older snapshot.
+    Removed,
+    /// The value exists in both snapshots, but is different.
+    Changed,
+}
+
+/// A component that changed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ComponentChange {
+    pub entity: Entity,
+    /// The type name of the component.
+    pub component: &'static str,
+    pub change: SnapshotChange,
+}
+
+/// A resource that changed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResourceChange {
+    /// The type name of the resource.
+    pub resource: &'static str,
+    pub change: SnapshotChange,
+}
+
+/// An error that occurs when rolling back a [`World`] with [`WorldSnapshot::rollback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollbackError {
+    /// The entity was despawned since the snapshot, and its index was reused by an entity
+    /// without registered components.
+    EntityIdTaken(Entity),
+}
+
+impl std::error::Error for RollbackError {}
+
+impl fmt::Display for RollbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RollbackError::EntityIdTaken(entity) => write!(
+                f,
+                "The entity {:?} can't be respawned, as its index is used by another entity.",
+                entity
+            ),
+        }
+    }
+}
+
+/// A registered component or resource, which knows how to capture, compare and restore it.
+trait SnapshotEntry: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    fn snapshot(&self, world: &World) -> SnapshotData;
+
+    /// Returns the entities of a snapshot of this entry, which is empty for resources.
+    fn entities<'a>(&self, data: &'a (dyn Any + Send + Sync)) -> &'a [Entity];
+
+    /// Adds the entities of `world` that have this component to `entities`.
+    fn current_entities(&self, world: &World, entities: &mut Vec<Entity>);
+
+    fn diff(
+        &self,
+        old: &(dyn Any + Send + Sync),
+        new: &(dyn Any + Send + Sync),
+        in_both: &dyn Fn(Entity) -> bool,
+        diff: &mut SnapshotDiff,
+    );
+
+    fn restore(&self, world: &mut World, data: &(dyn Any + Send + Sync));
+}
+
+/// The values of one component, sorted by entity.
+struct Column<T> {
+    entities: Vec<Entity>,
+    values: Vec<T>,
+}
+
+impl<T> Column<T> {
+    fn get(&self, entity: Entity) -> Option<&T> {
+        let index = self.entities.binary_search(&entity).ok()?;
+        Some(&self.values[index])
+    }
+}
+
+fn entities_with(world: &World, component_id: Option<ComponentId>) -> Vec<Entity> {
+    let mut entities = Vec::new();
+    let Some(component_id) = component_id else {
+        return entities;
+    };
+    for archetype in world.archetypes().iter() {
+        if archetype.contains(component_id) {
+            entities.extend(archetype.entities().iter().map(|entity| entity.entity()));
+        }
+    }
+    entities.sort_unstable();
+    entities
+}
+
+fn collect_column<T: Send + Sync + 'static>(
+    world: &World,
+    component_id: Option<ComponentId>,
+    get: impl Fn(Entity) -> T,
+) -> SnapshotData {
+    let entities = entities_with(world, component_id);
+    let values = entities.iter().map(|&entity| get(entity)).collect();
+    Box::new(Column { entities, values })
+}
+
+fn diff_columns<T>(
+    name: &'static str,
+    old: &Column<T>,
+    new: &Column<T>,
+    in_both: &dyn Fn(Entity) -> bool,
+    eq: impl Fn(&T, &T) -> bool,
+    diff: &mut SnapshotDiff,
+) {
+    let mut push = |entity, change| {
+        if in_both(entity) {
+            diff.components.push(ComponentChange {
+                entity,
+                component: name,
+                change,
+            });
+        }
+    };
+    for (&entity, old_value) in old.entities.iter().zip(&old.values) {
+        match new.get(entity) {
+            Some(new_value) if !eq(old_value, new_value) => push(entity, SnapshotChange::Changed),
+            Some(_) => {}
+            None => push(entity, SnapshotChange::Removed),
+        }
+    }
+    for &entity in &new.entities {
+        if old.get(entity).is_none() {
+            push(entity, SnapshotChange::Added);
+        }
+    }
+}
+
+fn diff_resources<T>(
+    name: &'static str,
+    old: &Option<T>,
+    new: &Option<T>,
+    eq: impl Fn(&T, &T) -> bool,
+    diff: &mut SnapshotDiff,
+) {
+    let change = match (old, new) {
+        (Some(old), Some(new)) if !eq(old, new) => SnapshotChange::Changed,
+        (None, Some(_)) => SnapshotChange::Added,
+        (Some(_), None) => SnapshotChange::Removed,
+        _ => return,
+    };
+    diff.resources.push(ResourceChange {
+        resource: name,
+        change,
+    });
+}
+
+fn downcast<T: 'static>(data: &(dyn Any + Send + Sync)) -> &T {
+    data.downcast_ref::<T>()
+        .expect("snapshot data doesn't match its SnapshotRegistry entry")
+}
+
+struct TypedComponent<T>(std::marker::PhantomData<fn() -> T>);
+
+impl<T: Component + Clone + PartialEq> SnapshotEntry for TypedComponent<T> {
+    fn name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn snapshot(&self, world: &World) -> SnapshotData {
+        collect_column(world, world.component_id::<T>(), |entity| {
+            world.get::<T>(entity).unwrap().clone()
+        })
+    }
+
+    fn entities<'a>(&self, data: &'a (dyn Any + Send + Sync)) -> &'a [Entity] {
+        &downcast::<Column<T>>(data).entities
+    }
+
+    fn current_entities(&self, world: &World, entities: &mut Vec<Entity>) {
+        entities.extend(entities_with(world, world.component_id::<T>()));
+    }
+
+    fn diff(
+        &self,
+        old: &(dyn Any + Send + Sync),
+        new: &(dyn Any + Send + Sync),
+        in_both: &dyn Fn(Entity) -> bool,
+        diff: &mut SnapshotDiff,
+    ) {
+        diff_columns(
+            self.name(),
+            downcast::<Column<T>>(old),
+            downcast::<Column<T>>(new),
+            in_both,
+            T::eq,
+            diff,
+        );
+    }
+
+    fn restore(&self, world: &mut World, data: &(dyn Any + Send + Sync)) {
+        let column = downcast::<Column<T>>(data);
+        for entity in entities_with(world, world.component_id::<T>()) {
+            if column.get(entity).is_none() {
+                world.entity_mut(entity).remove::<T>();
+            }
+        }
+        for (&entity, value) in column.entities.iter().zip(&column.values) {
+            let mut entity_mut = world.entity_mut(entity);
+            match entity_mut.get_mut::<T>() {
+                Some(mut current) => {
+                    if *current != *value {
+                        *current = value.clone();
+                    }
+                }
+                None => {
+                    entity_mut.insert(value.clone());
+                }
+            }
+        }
+    }
+}
+
+struct TypedResource<R>(std::marker::PhantomData<fn() -> R>);
+
+impl<R: Resource + Clone + PartialEq> SnapshotEntry for TypedResource<R> {
+    fn name(&self) -> &'static str {
+        std::any::type_name::<R>()
+    }
+
+    fn snapshot(&self, world: &World) -> SnapshotData {
+        Box::new(world.get_resource::<R>().cloned())
+    }
+
+    fn entities<'a>(&self, _data: &'a (dyn Any + Send + Sync)) -> &'a [Entity] {
+        &[]
+    }
+
+    fn current_entities(&self, _world: &World, _entities: &mut Vec<Entity>) {}
+
+    fn diff(
+        &self,
+        old: &(dyn Any + Send + Sync),
+        new: &(dyn Any + Send + Sync),
+        _in_both: &dyn Fn(Entity) -> bool,
+        diff: &mut SnapshotDiff,
+    ) {
+        diff_resources(
+            self.name(),
+            downcast::<Option<R>>(old),
+            downcast::<Option<R>>(new),
+            R::eq,
+            diff,
+        );
+    }
+
+    fn restore(&self, world: &mut World, data: &(dyn Any + Send + Sync)) {
+        match downcast::<Option<R>>(data) {
+            Some(value) => match world.get_resource_mut::<R>() {
+                Some(mut current) => {
+                    if *current != *value {
+                        *current = value.clone();
+                    }
+                }
+                None => world.insert_resource(value.clone()),
+            },
+            None => {
+                world.remove_resource::<R>();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectedComponent {
+    name: &'static str,
+    type_id: std::any::TypeId,
+    reflect_component: ReflectComponent,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl ReflectedComponent {
+    fn component_id(&self, world: &World) -> Option<ComponentId> {
+        world.components().get_id(self.type_id)
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl SnapshotEntry for ReflectedComponent {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn snapshot(&self, world: &World) -> SnapshotData {
+        collect_column(world, self.component_id(world), |entity| {
+            self.reflect_component
+                .reflect(world, entity)
+                .unwrap()
+                .clone_value()
+        })
+    }
+
+    fn entities<'a>(&self, data: &'a (dyn Any + Send + Sync)) -> &'a [Entity] {
+        &downcast::<Column<Box<dyn Reflect>>>(data).entities
+    }
+
+    fn current_entities(&self, world: &World, entities: &mut Vec<Entity>) {
+        entities.extend(entities_with(world, self.component_id(world)));
+    }
+
+    fn diff(
+        &self,
+        old: &(dyn Any + Send + Sync),
+        new: &(dyn Any + Send + Sync),
+        in_both: &dyn Fn(Entity) -> bool,
+        diff: &mut SnapshotDiff,
+    ) {
+        diff_columns(
+            self.name,
+            downcast::<Column<Box<dyn Reflect>>>(old),
+            downcast::<Column<Box<dyn Reflect>>>(new),
+            in_both,
+            |a, b| a.reflect_partial_eq(&**b).unwrap_or(false),
+            diff,
+        );
+    }
+
+    fn restore(&self, world: &mut World, data: &(dyn Any + Send + Sync)) {
+        let column = downcast::<Column<Box<dyn Reflect>>>(data);
+        for entity in entities_with(world, self.component_id(world)) {
+            if column.get(entity).is_none() {
+                self.reflect_component.remove(world, entity);
+            }
+        }
+        for (&entity, value) in column.entities.iter().zip(&column.values) {
+            let unchanged = matches!(
+                self.reflect_component.reflect(world, entity),
+                Some(current) if current.reflect_partial_eq(&**value).unwrap_or(false)
+            );
+            if !unchanged {
+                // `insert` replaces the whole value, unlike `apply` which can leave elements of
+                // lists and maps behind.
+                self.reflect_component.insert(world, entity, &**value);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectedResource {
+    name: &'static str,
+    reflect_resource: ReflectResource,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl SnapshotEntry for ReflectedResource {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn snapshot(&self, world: &World) -> SnapshotData {
+        Box::new(
+            self.reflect_resource
+                .reflect(world)
+                .map(|resource| resource.clone_value()),
+        )
+    }
+
+    fn entities<'a>(&self, _data: &'a (dyn Any + Send + Sync)) -> &'a [Entity] {
+        &[]
+    }
+
+    fn current_entities(&self, _world: &World, _entities: &mut Vec<Entity>) {}
+
+    fn diff(
+        &self,
+        old: &(dyn Any + Send + Sync),
+        new: &(dyn Any + Send + Sync),
+        _in_both: &dyn Fn(Entity) -> bool,
+        diff: &mut SnapshotDiff,
+    ) {
+        diff_resources(
+            self.name,
+            downcast::<Option<Box<dyn Reflect>>>(old),
+            downcast::<Option<Box<dyn Reflect>>>(new),
+            |a, b| a.reflect_partial_eq(&**b).unwrap_or(false),
+            diff,
+        );
+    }
+
+    fn restore(&self, world: &mut World, data: &(dyn Any + Send + Sync)) {
+        match downcast::<Option<Box<dyn Reflect>>>(data) {
+            Some(value) => {
+                let unchanged = matches!(
+                    self.reflect_resource.reflect(world),
+                    Some(current) if current.reflect_partial_eq(&**value).unwrap_or(false)
+                );
+                if !unchanged {
+                    self.reflect_resource.insert(world, &**value);
+                }
+            }
+            None => self.reflect_resource.remove(world),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Health(u32);
+
+    #[derive(Component, Debug)]
+    struct Unregistered;
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Tick(u32);
+
+    fn registry() -> SnapshotRegistry {
+        let mut registry = SnapshotRegistry::default();
+        registry
+            .register_component::<Position>()
+            .register_component::<Health>()
+            .register_resource::<Tick>();
+        registry
+    }
+
+    #[test]
+    fn rollback_restores_values() {
+        let mut world = World::new();
+        let registry = registry();
+        let a = world.spawn((Position(0), Health(10), Unregistered)).id();
+        let b = world.spawn(Position(1)).id();
+        world.insert_resource(Tick(0));
+        let snapshot = registry.snapshot(&world);
+        assert_eq!(snapshot.entities(), &[a, b]);
+
+        world.entity_mut(a).insert(Position(5)).remove::<Health>();
+        world.entity_mut(b).insert(Health(3));
+        world.resource_mut::<Tick>().0 = 4;
+        snapshot.rollback(&mut world).unwrap();
+
+        assert_eq!(world.get::<Position>(a), Some(&Position(0)));
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert!(world.get::<Unregistered>(a).is_some());
+        assert_eq!(world.get::<Position>(b), Some(&Position(1)));
+        assert!(world.get::<Health>(b).is_none());
+        assert_eq!(world.resource::<Tick>(), &Tick(0));
+        assert!(registry.snapshot(&world).diff(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn rollback_only_changes_differing_values() {
+        let mut world = World::new();
+        let registry = registry();
+        let a = world.spawn(Position(0)).id();
+        let b = world.spawn(Position(1)).id();
+        let snapshot = registry.snapshot(&world);
+
+        world.entity_mut(b).insert(Position(2));
+        world.clear_trackers();
+        snapshot.rollback(&mut world).unwrap();
+
+        let mut query = world.query_filtered::<Entity, Changed<Position>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![b]);
+        assert_eq!(world.get::<Position>(a), Some(&Position(0)));
+    }
+
+    #[test]
+    fn rollback_spawned_and_despawned_entities() {
+        let mut world = World::new();
+        let registry = registry();
+        let kept = world.spawn(Position(0)).id();
+        let despawned = world.spawn((Position(1), Health(2))).id();
+        let outside = world.spawn(Unregistered).id();
+        let snapshot = registry.snapshot(&world);
+
+        world.despawn(despawned);
+        let spawned = world.spawn(Health(7)).id();
+        let diff = snapshot.diff(&registry.snapshot(&world));
+        assert_eq!(diff.spawned, vec![spawned]);
+        assert_eq!(diff.despawned, vec![despawned]);
+
+        snapshot.rollback(&mut world).unwrap();
+        assert!(world.get_entity(spawned).is_none());
+        assert!(world.get_entity(kept).is_some());
+        assert!(world.get_entity(outside).is_some());
+        assert_eq!(world.get::<Position>(despawned), Some(&Position(1)));
+        assert_eq!(world.get::<Health>(despawned), Some(&Health(2)));
+        assert!(registry.snapshot(&world).diff(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn rollback_reclaims_reused_index() {
+        let mut world = World::new();
+        let registry = registry();
+        let old = world.spawn(Position(0)).id();
+        let snapshot = registry.snapshot(&world);
+
+        world.despawn(old);
+        let new = world.spawn(Position(1)).id();
+        assert_eq!(new.index(), old.index());
+        snapshot.rollback(&mut world).unwrap();
+        assert!(world.get_entity(new).is_none());
+        assert_eq!(world.get::<Position>(old), Some(&Position(0)));
+    }
+
+    #[test]
+    fn rollback_fails_if_index_is_taken() {
+        let mut world = World::new();
+        let registry = registry();
+        let old = world.spawn(Position(0)).id();
+        let snapshot = registry.snapshot(&world);
+
+        world.despawn(old);
+        let new = world.spawn(Unregistered).id();
+        assert_eq!(new.index(), old.index());
+        assert_eq!(
+            snapshot.rollback(&mut world),
+            Err(RollbackError::EntityIdTaken(old))
+        );
+        assert!(world.get::<Unregistered>(new).is_some());
+    }
+
+    #[test]
+    fn diff_components_and_resources() {
+        let mut world = World::new();
+        let registry = registry();
+        let a = world.spawn((Position(0), Health(1))).id();
+        let b = world.spawn(Position(1)).id();
+        world.spawn(Position(2));
+        let old = registry.snapshot(&world);
+
+        world.entity_mut(a).insert(Position(3)).remove::<Health>();
+        world.entity_mut(b).insert(Health(4));
+        world.insert_resource(Tick(1));
+        let diff = old.diff(&registry.snapshot(&world));
+
+        assert!(diff.spawned.is_empty() && diff.despawned.is_empty());
+        let position = std::any::type_name::<Position>();
+        let health = std::any::type_name::<Health>();
+        let mut expected = vec![
+            ComponentChange {
+                entity: a,
+                component: position,
+                change: SnapshotChange::Changed,
+            },
+            ComponentChange {
+                entity: a,
+                component: health,
+                change: SnapshotChange::Removed,
+            },
+            ComponentChange {
+                entity: b,
+                component: health,
+                change: SnapshotChange::Added,
+            },
+        ];
+        expected.sort();
+        assert_eq!(diff.components, expected);
+        assert_eq!(
+            diff.resources,
+            vec![ResourceChange {
+                resource: std::any::type_name::<Tick>(),
+                change: SnapshotChange::Added,
+            }]
+        );
+
+        world.remove_resource::<Tick>();
+        snapshot_roundtrip(&registry, &mut world, &old);
+    }
+
+    fn snapshot_roundtrip(
+        registry: &SnapshotRegistry,
+        world: &mut World,
+        snapshot: &WorldSnapshot,
+    ) {
+        snapshot.rollback(world).unwrap();
+        assert!(snapshot.diff(&registry.snapshot(world)).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn diff_with_different_registries_panics() {
+        let world = World::new();
+        registry()
+            .snapshot(&world)
+            .diff(&registry().snapshot(&world));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn reflect_registration() {
+        use crate::reflect::{ReflectComponent, ReflectResource};
+        use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect};
+
+        #[derive(Component, Reflect, FromReflect, Default, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Inventory(Vec<u32>);
+
+        #[derive(Resource, Reflect, FromReflect, Default, Debug, PartialEq)]
+        #[reflect(Resource)]
+        struct Seed(u64);
+
+        let mut registry = SnapshotRegistry::default();
+        registry
+            .register_reflect(&Inventory::get_type_registration())
+            .register_reflect(&Seed::get_type_registration());
+
+        let mut world = World::new();
+        let a = world.spawn(Inventory(vec![1, 2])).id();
+        world.insert_resource(Seed(3));
+        let snapshot = registry.snapshot(&world);
+
+        world.get_mut::<Inventory>(a).unwrap().0.push(3);
+        let b = world.spawn(Inventory(vec![])).id();
+        world.remove_resource::<Seed>();
+        let diff = snapshot.diff(&registry.snapshot(&world));
+        assert_eq!(diff.spawned, vec![b]);
+        assert_eq!(diff.components.len(), 1);
+        assert_eq!(diff.resources[0].change, SnapshotChange::Removed);
+
+        snapshot.rollback(&mut world).unwrap();
+        assert_eq!(world.get::<Inventory>(a), Some(&Inventory(vec![1, 2])));
+        assert!(world.get_entity(b).is_none());
+        assert_eq!(world.resource::<Seed>(), &Seed(3));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    bundle::{Bundle, BundleSpawner},
+    bundle::{Bundle, BundleSpawner, WithRequiredComponents},
+    component::RequiredComponent,
     entity::Entity,
     world::World,
 };
@@ -11,7 +12,11 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    spawner: BundleSpawner<'w, 'w>,
+    /// `None` if the entities were spawned eagerly, see [`SpawnBatchIter::new`].
+    spawner: Option<BundleSpawner<'w, 'w>>,
+    /// The components required by the bundles, spawned along with each of them.
+    required: Vec<RequiredComponent>,
+    spawned: std::vec::IntoIter<Entity>,
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -20,7 +25,7 @@ where
     I::Item: Bundle,
 {
     #[inline]
-    pub(crate) fn new(world: &'w mut World, iter: I) -> Self {
+    pub(crate) fn new(world: &'w mut World, mut iter: I) -> Self {
         // Ensure all entity allocations are accounted for so `self.entities` can realloc if
         // necessary
         world.flush();
@@ -28,9 +33,36 @@ where
         let (lower, upper) = iter.size_hint();
         let length = upper.unwrap_or(lower);
 
-        let bundle_info = world
+        let bundle_id = world
             .bundles
-            .init_info::<I::Item>(&mut world.components, &mut world.storages);
+            .init_info::<I::Item>(&mut world.components, &mut world.storages)
+            .id();
+        // SAFETY: `bundle_id` was just initialized in this world
+        let (bundle_id, required) = unsafe {
+            world
+                .bundles
+                .with_required_components(&mut world.components, bundle_id, None)
+        };
+        let has_hooks = !world
+            .components
+            .insert_hooks(world.bundles.get(bundle_id).unwrap().components(), None)
+            .is_empty();
+        if has_hooks {
+            // Component hooks need exclusive access to the world after each spawn, so spawn the
+            // whole batch right away instead of lazily.
+            let spawned: Vec<_> = iter
+                .by_ref()
+                .map(|bundle| world.spawn(bundle).id())
+                .collect();
+            return Self {
+                inner: iter,
+                spawner: None,
+                required: Vec::new(),
+                spawned: spawned.into_iter(),
+            };
+        }
+
+        let bundle_info = world.bundles.get(bundle_id).unwrap();
         world.entities.reserve(length as u32);
         let mut spawner = bundle_info.get_bundle_spawner(
             &mut world.entities,
@@ -43,7 +75,9 @@ where
 
         Self {
             inner: iter,
-            spawner,
+            spawner: Some(spawner),
+            required,
+            spawned: Vec::new().into_iter(),
         }
     }
 }
@@ -66,13 +100,22 @@ where
     type Item = Entity;
 
     fn next(&mut self) -> Option<Entity> {
-        let bundle = self.inner.next()?;
+        let spawner = match &mut self.spawner {
+            Some(spawner) => spawner,
+            None => return self.spawned.next(),
+        };
+        let bundle = WithRequiredComponents {
+            bundle: self.inner.next()?,
+            required: &self.required,
+        };
         // SAFETY: bundle matches spawner type
-        unsafe { Some(self.spawner.spawn(bundle)) }
+        unsafe { Some(spawner.spawn(bundle)) }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.inner.size_hint()
+        let (lower, upper) = self.inner.size_hint();
+        let spawned = self.spawned.len();
+        (lower + spawned, upper.map(|upper| upper + spawned))
     }
 }
 
@@ -82,7 +125,7 @@ where
     T: Bundle,
 {
     fn len(&self) -> usize {
-        self.inner.len()
+        self.inner.len() + self.spawned.len()
     }
 }
 
@@ -0,0 +1,15 @@
+use bevy_ecs::prelude::*;
+
+#[derive(Component)]
+struct A(usize);
+
+fn system(mut query: Query<&mut A>) {
+    let (_, mut group) = query.iter_grouped_by_key_mut(|a| a.0).next().unwrap();
+    let first = group.next().unwrap();
+    // this should fail to compile
+    for a in query.iter_mut() {
+        println!("{} {}", first.0, a.0);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use bevy_ecs::prelude::*;
+
+#[derive(Component)]
+struct A(usize);
+
+fn system(mut query: Query<&mut A>, e: Entity) {
+    let mut iter = query.iter_many_unique_mut([e]).unwrap();
+    let first = iter.next().unwrap();
+    // this should fail to compile
+    let same = query.get_mut(e).unwrap();
+    println!("{} {}", first.0, same.0);
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use bevy_ecs::prelude::*;
+
+#[derive(Component, PartialEq, Eq, PartialOrd, Ord)]
+struct A(usize);
+
+fn system(mut query: Query<&mut A>) {
+    // The key can't borrow from the query item, which is mutably borrowed during the iteration.
+    for _ in query.iter_sorted_by_key_mut(|a| *a) {}
+}
+
+fn main() {}
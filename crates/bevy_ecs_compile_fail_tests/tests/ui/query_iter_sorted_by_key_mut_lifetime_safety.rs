@@ -0,0 +1,14 @@
+use bevy_ecs::prelude::*;
+
+#[derive(Component)]
+struct A(usize);
+
+fn system(mut query: Query<&mut A>) {
+    let mut iter = query.iter_sorted_by_key_mut(|a| a.0);
+    let first = iter.next().unwrap();
+    // this should fail to compile
+    let single = query.single_mut();
+    println!("{} {}", first.0, single.0);
+}
+
+fn main() {}
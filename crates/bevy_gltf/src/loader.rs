@@ -1,6 +1,6 @@
 use anyhow::Result;
 use bevy_asset::{
-    AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset,
+    AssetIoError, AssetLoader, AssetMetaError, BoxedFuture, Handle, LoadContext, LoadedAsset,
 };
 use bevy_core::Name;
 use bevy_core_pipeline::prelude::Camera3d;
@@ -40,6 +40,7 @@ use gltf::{
     texture::{MagFilter, MinFilter, WrappingMode},
     Material, Node, Primitive,
 };
+use serde::{Deserialize, Serialize};
 use std::{collections::VecDeque, path::Path};
 use thiserror::Error;
 
@@ -68,6 +69,42 @@ pub enum GltfError {
     MissingAnimationSampler(usize),
     #[error("failed to generate tangents: {0}")]
     GenerateTangentsError(#[from] bevy_render::mesh::GenerateTangentsError),
+    #[error("failed to read the loader settings: {0}")]
+    AssetMetaError(#[from] AssetMetaError),
+}
+
+/// The settings of the [`GltfLoader`], read from the `.meta` file of a glTF file or given to
+/// [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings).
+///
+/// They choose which parts of the file are loaded, for example to load a level without its
+/// cameras:
+///
+/// ```ron
+/// (
+///     settings: (
+///         load_cameras: false,
+///     ),
+/// )
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct GltfLoaderSettings {
+    /// Whether the cameras of the file are added to its scenes.
+    pub load_cameras: bool,
+    /// Whether the lights of the file are added to its scenes.
+    pub load_lights: bool,
+    /// Whether the animations of the file are loaded.
+    pub load_animations: bool,
+}
+
+impl Default for GltfLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_cameras: true,
+            load_lights: true,
+            load_animations: true,
+        }
+    }
 }
 
 /// Loads glTF files with all of their data as their corresponding bevy representations.
@@ -110,6 +147,7 @@ async fn load_gltf<'a, 'b>(
     load_context: &'a mut LoadContext<'b>,
     supported_compressed_formats: CompressedImageFormats,
 ) -> Result<(), GltfError> {
+    let settings = load_context.settings::<GltfLoaderSettings>()?;
     let gltf = gltf::Gltf::from_slice(bytes)?;
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
@@ -153,7 +191,10 @@ async fn load_gltf<'a, 'b>(
         let mut animations = vec![];
         let mut named_animations = HashMap::default();
         let mut animation_roots = HashSet::default();
-        for animation in gltf.animations() {
+        for animation in gltf
+            .animations()
+            .filter(|_| settings.load_animations)
+        {
             let mut animation_clip = bevy_animation::AnimationClip::default();
             for channel in animation.channels() {
                 match channel.sampler().interpolation() {
@@ -475,6 +516,7 @@ async fn load_gltf<'a, 'b>(
                         &mut node_index_to_entity_map,
                         &mut entity_to_skin_index_map,
                         &mut active_camera_found,
+                        &settings,
                     );
                     if result.is_err() {
                         err = Some(result);
@@ -629,7 +671,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
     let base_color_texture = pbr.base_color_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_asset_path(Some(&label));
         load_context.get_handle(path)
     });
 
@@ -638,14 +680,14 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
             // TODO: handle normal_texture.scale
             // TODO: handle normal_texture.tex_coord() (the *set* index for the right texcoords)
             let label = texture_label(&normal_texture.texture());
-            let path = AssetPath::new_ref(load_context.path(), Some(&label));
+            let path = load_context.get_asset_path(Some(&label));
             load_context.get_handle(path)
         });
 
     let metallic_roughness_texture = pbr.metallic_roughness_texture().map(|info| {
         // TODO: handle info.tex_coord() (the *set* index for the right texcoords)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_asset_path(Some(&label));
         load_context.get_handle(path)
     });
 
@@ -653,7 +695,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&occlusion_texture.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_asset_path(Some(&label));
         load_context.get_handle(path)
     });
 
@@ -662,7 +704,7 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
         let label = texture_label(&info.texture());
-        let path = AssetPath::new_ref(load_context.path(), Some(&label));
+        let path = load_context.get_asset_path(Some(&label));
         load_context.get_handle(path)
     });
 
@@ -699,6 +741,7 @@ fn load_node(
     node_index_to_entity_map: &mut HashMap<usize, Entity>,
     entity_to_skin_index_map: &mut HashMap<Entity, usize>,
     active_camera_found: &mut bool,
+    settings: &GltfLoaderSettings,
 ) -> Result<(), GltfError> {
     let transform = gltf_node.transform();
     let mut gltf_error = None;
@@ -715,7 +758,7 @@ fn load_node(
     }
 
     // create camera node
-    if let Some(camera) = gltf_node.camera() {
+    if let Some(camera) = gltf_node.camera().filter(|_| settings.load_cameras) {
         let projection = match camera.projection() {
             gltf::camera::Projection::Orthographic(orthographic) => {
                 let xmag = orthographic.xmag();
@@ -779,10 +822,8 @@ fn load_node(
 
                 let primitive_label = primitive_label(&mesh, &primitive);
                 let bounds = primitive.bounding_box();
-                let mesh_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&primitive_label));
-                let material_asset_path =
-                    AssetPath::new_ref(load_context.path(), Some(&material_label));
+                let mesh_asset_path = load_context.get_asset_path(Some(&primitive_label));
+                let material_asset_path = load_context.get_asset_path(Some(&material_label));
 
                 let mut mesh_entity = parent.spawn(PbrBundle {
                     mesh: load_context.get_handle(mesh_asset_path),
@@ -809,7 +850,7 @@ fn load_node(
             }
         }
 
-        if let Some(light) = gltf_node.light() {
+        if let Some(light) = gltf_node.light().filter(|_| settings.load_lights) {
             match light.kind() {
                 gltf::khr_lights_punctual::Kind::Directional => {
                     let mut entity = parent.spawn(DirectionalLightBundle {
@@ -894,6 +935,7 @@ fn load_node(
                 node_index_to_entity_map,
                 entity_to_skin_index_map,
                 active_camera_found,
+                settings,
             ) {
                 gltf_error = Some(err);
                 return;
@@ -1,6 +1,6 @@
 use bevy_ecs::{
     component::Component,
-    entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    entity::{Entity, EntityMapper, MapEntities},
     reflect::{ReflectComponent, ReflectMapEntities},
     world::{FromWorld, World},
 };
@@ -36,13 +36,12 @@ impl FromWorld for Parent {
 }
 
 impl MapEntities for Parent {
-    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
         // Parent of an entity in the new world can be in outside world, in which case it
         // should not be mapped.
-        if let Ok(mapped_entity) = entity_map.get(self.0) {
+        if let Some(mapped_entity) = entity_mapper.get(self.0) {
             self.0 = mapped_entity;
         }
-        Ok(())
     }
 }
 
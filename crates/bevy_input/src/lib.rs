@@ -125,6 +125,14 @@ impl Plugin for InputPlugin {
             .register_type::<ButtonSettings>()
             .register_type::<AxisSettings>()
             .register_type::<ButtonAxisSettings>();
+
+        #[cfg(feature = "bevy_ci_testing")]
+        app.add_ci_testing_event::<KeyboardInput>()
+            .add_ci_testing_event::<MouseButtonInput>()
+            .add_ci_testing_event::<MouseMotion>()
+            .add_ci_testing_event::<MouseWheel>()
+            .add_ci_testing_event::<GamepadEventRaw>()
+            .add_ci_testing_event::<TouchInput>();
     }
 }
 
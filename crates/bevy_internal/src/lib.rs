@@ -173,5 +173,17 @@ pub mod dynamic_plugin {
     pub use bevy_dynamic_plugin::*;
 }
 
+#[cfg(feature = "bevy_remote")]
+pub mod remote {
+    //! Remote debugging protocol to query and modify a running app
+    pub use bevy_remote::*;
+}
+
+#[cfg(feature = "bevy_test")]
+pub mod test {
+    //! Headless harness to test apps frame by frame
+    pub use bevy_test::*;
+}
+
 #[cfg(target_os = "android")]
 pub use ndk_glue;
@@ -487,6 +487,7 @@ fn extract_materials<M: Material>(
                 changed_assets.remove(handle);
                 removed.push(handle.clone_weak());
             }
+            AssetEvent::LoadedWithDependencies { .. } | AssetEvent::Failed { .. } => {}
         }
     }
 
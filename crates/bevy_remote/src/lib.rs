@@ -0,0 +1,307 @@
+//! A remote debugging protocol to query and modify a running [`App`] from another process.
+//!
+//! The [`RemotePlugin`] listens on a TCP or Unix socket for [JSON-RPC 2.0] requests, one per line,
+//! and answers them at the end of every frame. Components and resources are read and written
+//! through reflection, so only the types registered in the [`AppTypeRegistry`] with
+//! `#[reflect(Component)]` or `#[reflect(Resource)]` are visible. Their values are serialized with
+//! [`TypedReflectSerializer`](bevy_reflect::serde::TypedReflectSerializer).
+//!
+//! The methods are listed in the [`protocol`] module. For example, with the default address:
+//!
+//! ```text
+//! $ echo '{"jsonrpc":"2.0","id":1,"method":"bevy/list","params":{"name":"Player"}}' | nc localhost 15702
+//! {"jsonrpc":"2.0","id":1,"result":[{"entity":{"generation":0,"index":4},"name":"Player","components":[...]}]}
+//! ```
+//!
+//! The server has no authentication: only enable it in development builds, on a local address.
+//!
+//! [JSON-RPC 2.0]: https://www.jsonrpc.org/specification
+
+mod methods;
+pub mod protocol;
+mod server;
+
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+#[cfg(unix)]
+use std::path::PathBuf;
+
+use bevy_app::{prelude::*, AppExit, AppTypeRegistry};
+use bevy_ecs::prelude::*;
+use bevy_reflect::TypeRegistry;
+use bevy_utils::tracing::error;
+use crossbeam_channel::{Receiver, Sender};
+use serde_json::Value;
+
+use crate::{
+    methods::{get, get_resource, parse_params, process_method, to_value},
+    protocol::*,
+    server::{ConnectionId, Listener, RemoteMessage},
+};
+
+/// The port used by [`RemotePlugin::default`].
+pub const DEFAULT_PORT: u16 = 15702;
+
+/// The address the remote server listens on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteAddress {
+    Tcp(SocketAddr),
+    /// The path of a Unix socket. Binding fails if a file already exists at this path. The file is
+    /// removed when the server stops.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl Default for RemoteAddress {
+    fn default() -> Self {
+        RemoteAddress::Tcp(SocketAddr::from((Ipv4Addr::LOCALHOST, DEFAULT_PORT)))
+    }
+}
+
+/// Adds a remote debugging server to Apps.
+///
+/// The server listens on `localhost:15702` by default. If the address can't be bound, an error is
+/// logged and the app runs without the server. The server stops when the app exits, or when the
+/// [`RemoteServer`] resource is dropped.
+#[derive(Debug, Clone, Default)]
+pub struct RemotePlugin {
+    pub address: RemoteAddress,
+}
+
+impl RemotePlugin {
+    /// Listens on a TCP socket. Use port `0` to let the system choose a free port, which can then
+    /// be read from [`RemoteServer::address`].
+    pub fn tcp(address: impl Into<SocketAddr>) -> Self {
+        Self {
+            address: RemoteAddress::Tcp(address.into()),
+        }
+    }
+
+    /// Listens on a Unix socket.
+    #[cfg(unix)]
+    pub fn unix(path: impl Into<PathBuf>) -> Self {
+        Self {
+            address: RemoteAddress::Unix(path.into()),
+        }
+    }
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let Some(listener) = listen(&self.address, sender) else {
+            return;
+        };
+        app.insert_resource(RemoteServer {
+            listener,
+            messages: receiver,
+            subscriptions: Vec::new(),
+            next_subscription: 0,
+        })
+        .add_system_to_stage(CoreStage::Last, process_remote_requests)
+        .add_system_to_stage(
+            CoreStage::Last,
+            stop_remote_server_on_exit.after(process_remote_requests),
+        );
+    }
+}
+
+fn listen(address: &RemoteAddress, sender: Sender<RemoteMessage>) -> Option<Listener> {
+    let result = match address {
+        RemoteAddress::Tcp(address) => {
+            TcpListener::bind(address).and_then(|listener| server::listen_tcp(listener, sender))
+        }
+        #[cfg(unix)]
+        RemoteAddress::Unix(path) => std::os::unix::net::UnixListener::bind(path)
+            .map(|listener| server::listen_unix(listener, path.clone(), sender)),
+    };
+    match result {
+        Ok(listener) => Some(listener),
+        Err(err) => {
+            error!("Failed to start the remote server on {address:?}: {err}");
+            None
+        }
+    }
+}
+
+/// The state of the remote server added by the [`RemotePlugin`].
+#[derive(Resource)]
+pub struct RemoteServer {
+    listener: Listener,
+    messages: Receiver<RemoteMessage>,
+    subscriptions: Vec<Subscription>,
+    next_subscription: u64,
+}
+
+/// A subscription made with [`BEVY_WATCH`] or [`BEVY_WATCH_RESOURCE`].
+struct Subscription {
+    id: u64,
+    connection: ConnectionId,
+    responder: Sender<String>,
+    target: WatchTarget,
+    /// The last outcome sent to the subscriber.
+    last: Option<Value>,
+}
+
+enum WatchTarget {
+    Components(GetParams),
+    Resource(ResourceParams),
+}
+
+impl RemoteServer {
+    /// The address the server listens on.
+    pub fn address(&self) -> &RemoteAddress {
+        self.listener.address()
+    }
+
+    /// Stops accepting connections. This is done when the app exits, since some runners never
+    /// return to drop the app.
+    pub fn stop(&self) {
+        self.listener.stop();
+    }
+
+    fn process_request(
+        &mut self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        connection: ConnectionId,
+        responder: &Sender<String>,
+        request: RemoteRequest,
+    ) -> Result<Value, RemoteError> {
+        let target = match request.method.as_str() {
+            BEVY_WATCH => WatchTarget::Components(parse_params(request.params)?),
+            BEVY_WATCH_RESOURCE => WatchTarget::Resource(parse_params(request.params)?),
+            BEVY_UNWATCH => {
+                let params: UnwatchParams = parse_params(request.params)?;
+                let len = self.subscriptions.len();
+                self.subscriptions.retain(|subscription| {
+                    subscription.id != params.subscription || subscription.connection != connection
+                });
+                if self.subscriptions.len() == len {
+                    return Err(RemoteError::new(
+                        RemoteError::INVALID_PARAMS,
+                        format!("No subscription {}", params.subscription),
+                    ));
+                }
+                return Ok(Value::Null);
+            }
+            method => return process_method(world, registry, method, request.params),
+        };
+        let id = self.next_subscription;
+        self.next_subscription += 1;
+        self.subscriptions.push(Subscription {
+            id,
+            connection,
+            responder: responder.clone(),
+            target,
+            last: None,
+        });
+        to_value(WatchResponse { subscription: id })
+    }
+
+    /// Answers the requests received since the last frame with `error`.
+    fn reject_requests(&mut self, error: RemoteError) {
+        while let Ok(message) = self.messages.try_recv() {
+            match message {
+                RemoteMessage::Request {
+                    request: RemoteRequest { id: Some(id), .. },
+                    responder,
+                    ..
+                } => {
+                    let response = RemoteResponse::new(id, RemoteOutcome::Error(error.clone()));
+                    if let Ok(response) = serde_json::to_string(&response) {
+                        let _ = responder.send(response);
+                    }
+                }
+                RemoteMessage::Request { .. } => {}
+                RemoteMessage::Disconnected(connection) => {
+                    self.subscriptions
+                        .retain(|subscription| subscription.connection != connection);
+                }
+            }
+        }
+    }
+
+    /// Notifies the subscribers whose watched values changed since the last notification.
+    fn notify_subscribers(&mut self, world: &World, registry: &TypeRegistry) {
+        self.subscriptions.retain_mut(|subscription| {
+            let outcome = RemoteOutcome::from(match &subscription.target {
+                WatchTarget::Components(params) => get(world, registry, params).and_then(to_value),
+                WatchTarget::Resource(params) => {
+                    get_resource(world, registry, params).and_then(to_value)
+                }
+            });
+            let Ok(outcome_value) = serde_json::to_value(&outcome) else {
+                return true;
+            };
+            if subscription.last.as_ref() == Some(&outcome_value) {
+                return true;
+            }
+            subscription.last = Some(outcome_value);
+            let notification = to_value(ChangedNotification {
+                subscription: subscription.id,
+                outcome,
+            })
+            .map(|params| RemoteNotification {
+                jsonrpc: JSONRPC_VERSION.to_string(),
+                method: BEVY_CHANGED.to_string(),
+                params,
+            });
+            match notification.map(|notification| serde_json::to_string(&notification)) {
+                // The connection was closed if the notification can't be sent.
+                Ok(Ok(notification)) => subscription.responder.send(notification).is_ok(),
+                _ => true,
+            }
+        });
+    }
+}
+
+/// Answers the requests received by the [`RemoteServer`] since the last frame, then notifies
+/// the subscribers of the values that changed.
+pub fn process_remote_requests(world: &mut World) {
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        world
+            .resource_mut::<RemoteServer>()
+            .reject_requests(RemoteError::new(
+                RemoteError::INTERNAL_ERROR,
+                "The app has no `AppTypeRegistry`",
+            ));
+        return;
+    };
+    let registry = registry.read();
+    world.resource_scope(|world, mut server: Mut<RemoteServer>| {
+        while let Ok(message) = server.messages.try_recv() {
+            match message {
+                RemoteMessage::Request {
+                    connection,
+                    request,
+                    responder,
+                } => {
+                    let id = request.id.clone();
+                    let result =
+                        server.process_request(world, &registry, connection, &responder, request);
+                    // Requests without an id are notifications, which don't get a response.
+                    let Some(id) = id else {
+                        continue;
+                    };
+                    let response = RemoteResponse::new(id, result.into());
+                    if let Ok(response) = serde_json::to_string(&response) {
+                        let _ = responder.send(response);
+                    }
+                }
+                RemoteMessage::Disconnected(connection) => {
+                    server
+                        .subscriptions
+                        .retain(|subscription| subscription.connection != connection);
+                }
+            }
+        }
+        server.notify_subscribers(world, &registry);
+    });
+}
+
+/// Stops the [`RemoteServer`] when an [`AppExit`] event is sent.
+pub fn stop_remote_server_on_exit(mut exits: EventReader<AppExit>, server: Res<RemoteServer>) {
+    if exits.iter().last().is_some() {
+        server.stop();
+    }
+}
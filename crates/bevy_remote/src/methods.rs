@@ -0,0 +1,287 @@
+use bevy_ecs::{
+    component::ComponentId,
+    entity::Entity,
+    reflect::{InspectorFilter, ReflectComponent, ReflectResource, WorldInspector},
+    world::{EntityRef, World},
+};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry,
+};
+use serde::{de::DeserializeOwned, de::DeserializeSeed, Serialize};
+use serde_json::{Map, Value};
+
+use crate::protocol::*;
+
+/// Runs the method of a request that doesn't depend on the connection it was received from.
+pub(crate) fn process_method(
+    world: &mut World,
+    registry: &TypeRegistry,
+    method: &str,
+    params: Value,
+) -> Result<Value, RemoteError> {
+    match method {
+        BEVY_LIST => {
+            let params = if params.is_null() {
+                ListParams::default()
+            } else {
+                parse_params(params)?
+            };
+            to_value(list(world, registry, &params))
+        }
+        BEVY_GET => to_value(get(world, registry, &parse_params(params)?)?),
+        BEVY_QUERY => to_value(query(world, registry, &parse_params(params)?)?),
+        BEVY_INSERT => insert(world, registry, parse_params(params)?).map(|_| Value::Null),
+        BEVY_REMOVE => remove(world, registry, &parse_params(params)?).map(|_| Value::Null),
+        BEVY_GET_RESOURCE => to_value(get_resource(world, registry, &parse_params(params)?)?),
+        BEVY_INSERT_RESOURCE => {
+            insert_resource(world, registry, parse_params(params)?).map(|_| Value::Null)
+        }
+        _ => Err(RemoteError::new(
+            RemoteError::METHOD_NOT_FOUND,
+            format!("The method {method} does not exist"),
+        )),
+    }
+}
+
+pub(crate) fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RemoteError> {
+    serde_json::from_value(params)
+        .map_err(|error| RemoteError::new(RemoteError::INVALID_PARAMS, error.to_string()))
+}
+
+pub(crate) fn to_value(value: impl Serialize) -> Result<Value, RemoteError> {
+    serde_json::to_value(value)
+        .map_err(|error| RemoteError::new(RemoteError::INTERNAL_ERROR, error.to_string()))
+}
+
+fn list(world: &World, registry: &TypeRegistry, params: &ListParams) -> Vec<ListEntry> {
+    let filter = match &params.name {
+        Some(name) => InspectorFilter::default().with_name(name.clone()),
+        None => InspectorFilter::default(),
+    };
+    WorldInspector::new(registry)
+        .entities(world, &filter)
+        .into_iter()
+        .map(|node| ListEntry {
+            entity: node.entity,
+            name: node.name,
+            components: node
+                .components
+                .into_iter()
+                .map(|component| component.type_name)
+                .collect(),
+        })
+        .collect()
+}
+
+pub(crate) fn get(
+    world: &World,
+    registry: &TypeRegistry,
+    params: &GetParams,
+) -> Result<ComponentValues, RemoteError> {
+    let entity = get_entity(world, params.entity)?.id();
+    let mut components = Map::new();
+    for type_name in &params.components {
+        let (registration, reflect_component) = component_registration(registry, type_name)?;
+        let value = reflect_component
+            .reflect(world, entity)
+            .ok_or_else(|| RemoteError::missing_component(entity, type_name))?;
+        components.insert(
+            registration.type_name().to_string(),
+            serialize(value, registry)?,
+        );
+    }
+    Ok(ComponentValues { components })
+}
+
+fn query(
+    world: &World,
+    registry: &TypeRegistry,
+    params: &QueryParams,
+) -> Result<Vec<QueryEntry>, RemoteError> {
+    let components = params
+        .components
+        .iter()
+        .map(|type_name| component_registration(registry, type_name))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Components that were never added to the world can't be on any entity.
+    let mut required = Vec::new();
+    for registration in components
+        .iter()
+        .map(|(registration, _)| *registration)
+        .chain(
+            params
+                .with
+                .iter()
+                .map(|type_name| registration(registry, type_name))
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    {
+        match component_id(world, registration) {
+            Some(component_id) => required.push(component_id),
+            None => return Ok(Vec::new()),
+        }
+    }
+    let mut excluded = Vec::new();
+    for type_name in &params.without {
+        excluded.extend(component_id(world, registration(registry, type_name)?));
+    }
+
+    let mut entities: Vec<_> = world.iter_entities().collect();
+    entities.sort();
+    entities
+        .into_iter()
+        .map(|entity| world.entity(entity))
+        .filter(|entity| {
+            required.iter().all(|id| entity.contains_id(*id))
+                && !excluded.iter().any(|id| entity.contains_id(*id))
+        })
+        .map(|entity| {
+            let mut values = Map::new();
+            for (registration, reflect_component) in &components {
+                let value = reflect_component
+                    .reflect(world, entity.id())
+                    .ok_or_else(|| {
+                        RemoteError::missing_component(entity.id(), registration.type_name())
+                    })?;
+                values.insert(
+                    registration.type_name().to_string(),
+                    serialize(value, registry)?,
+                );
+            }
+            Ok(QueryEntry {
+                entity: entity.id(),
+                components: values,
+            })
+        })
+        .collect()
+}
+
+fn insert(
+    world: &mut World,
+    registry: &TypeRegistry,
+    params: InsertParams,
+) -> Result<(), RemoteError> {
+    let entity = get_entity(world, params.entity)?.id();
+    // Deserialize every component before inserting any of them, so that the request is either
+    // applied entirely or not at all.
+    let components = params
+        .components
+        .into_iter()
+        .map(|(type_name, value)| {
+            let (registration, reflect_component) = component_registration(registry, &type_name)?;
+            Ok((
+                reflect_component,
+                deserialize(registration, registry, value)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, RemoteError>>()?;
+    for (reflect_component, value) in components {
+        reflect_component.insert(world, entity, &*value);
+    }
+    Ok(())
+}
+
+fn remove(
+    world: &mut World,
+    registry: &TypeRegistry,
+    params: &RemoveParams,
+) -> Result<(), RemoteError> {
+    let entity = get_entity(world, params.entity)?.id();
+    let components = params
+        .components
+        .iter()
+        .map(|type_name| component_registration(registry, type_name))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (_, reflect_component) in components {
+        reflect_component.remove(world, entity);
+    }
+    Ok(())
+}
+
+pub(crate) fn get_resource(
+    world: &World,
+    registry: &TypeRegistry,
+    params: &ResourceParams,
+) -> Result<ResourceValue, RemoteError> {
+    let (registration, reflect_resource) = resource_registration(registry, &params.resource)?;
+    let value = reflect_resource
+        .reflect(world)
+        .ok_or_else(|| RemoteError::missing_resource(&params.resource))?;
+    Ok(ResourceValue {
+        resource: registration.type_name().to_string(),
+        value: serialize(value, registry)?,
+    })
+}
+
+fn insert_resource(
+    world: &mut World,
+    registry: &TypeRegistry,
+    params: InsertResourceParams,
+) -> Result<(), RemoteError> {
+    let (registration, reflect_resource) = resource_registration(registry, &params.resource)?;
+    let value = deserialize(registration, registry, params.value)?;
+    reflect_resource.insert(world, &*value);
+    Ok(())
+}
+
+fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, RemoteError> {
+    world
+        .get_entity(entity)
+        .ok_or_else(|| RemoteError::no_such_entity(entity))
+}
+
+fn registration<'r>(
+    registry: &'r TypeRegistry,
+    type_name: &str,
+) -> Result<&'r TypeRegistration, RemoteError> {
+    registry
+        .get_with_name(type_name)
+        .or_else(|| registry.get_with_short_name(type_name))
+        .ok_or_else(|| RemoteError::unregistered_type(type_name))
+}
+
+fn component_registration<'r>(
+    registry: &'r TypeRegistry,
+    type_name: &str,
+) -> Result<(&'r TypeRegistration, &'r ReflectComponent), RemoteError> {
+    let registration = registration(registry, type_name)?;
+    let reflect_component = registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| RemoteError::unregistered_type(type_name))?;
+    Ok((registration, reflect_component))
+}
+
+fn resource_registration<'r>(
+    registry: &'r TypeRegistry,
+    type_name: &str,
+) -> Result<(&'r TypeRegistration, &'r ReflectResource), RemoteError> {
+    let registration = registration(registry, type_name)?;
+    let reflect_resource = registration
+        .data::<ReflectResource>()
+        .ok_or_else(|| RemoteError::unregistered_type(type_name))?;
+    Ok((registration, reflect_resource))
+}
+
+fn component_id(world: &World, registration: &TypeRegistration) -> Option<ComponentId> {
+    world.components().get_id(registration.type_id())
+}
+
+fn serialize(value: &dyn Reflect, registry: &TypeRegistry) -> Result<Value, RemoteError> {
+    to_value(TypedReflectSerializer::new(value, registry))
+}
+
+fn deserialize(
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+    value: Value,
+) -> Result<Box<dyn Reflect>, RemoteError> {
+    TypedReflectDeserializer::new(registration, registry)
+        .deserialize(value)
+        .map_err(|error| {
+            RemoteError::new(
+                RemoteError::INVALID_PARAMS,
+                format!("Invalid value for {}: {error}", registration.short_name()),
+            )
+        })
+}
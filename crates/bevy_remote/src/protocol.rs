@@ -0,0 +1,305 @@
+use std::fmt;
+
+use bevy_ecs::entity::Entity;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The version of JSON-RPC spoken by the remote protocol.
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Lists the entities and the type names of their components.
+///
+/// Params: [`ListParams`], optional. Result: a list of [`ListEntry`].
+pub const BEVY_LIST: &str = "bevy/list";
+/// Gets the values of components of an entity.
+///
+/// Params: [`GetParams`]. Result: [`ComponentValues`].
+pub const BEVY_GET: &str = "bevy/get";
+/// Queries the entities having some components.
+///
+/// Params: [`QueryParams`]. Result: a list of [`QueryEntry`].
+pub const BEVY_QUERY: &str = "bevy/query";
+/// Inserts components into an entity, replacing the existing ones.
+///
+/// Params: [`InsertParams`]. Result: `null`.
+pub const BEVY_INSERT: &str = "bevy/insert";
+/// Removes components from an entity.
+///
+/// Params: [`RemoveParams`]. Result: `null`.
+pub const BEVY_REMOVE: &str = "bevy/remove";
+/// Gets the value of a resource.
+///
+/// Params: [`ResourceParams`]. Result: [`ResourceValue`].
+pub const BEVY_GET_RESOURCE: &str = "bevy/get_resource";
+/// Inserts a resource, replacing the existing one.
+///
+/// Params: [`InsertResourceParams`]. Result: `null`.
+pub const BEVY_INSERT_RESOURCE: &str = "bevy/insert_resource";
+/// Subscribes to the changes of components of an entity.
+///
+/// Params: [`GetParams`]. Result: [`WatchResponse`]. The result of [`BEVY_GET`] is then sent in
+/// a [`BEVY_CHANGED`] notification at the end of every frame where it changed.
+pub const BEVY_WATCH: &str = "bevy/watch";
+/// Subscribes to the changes of a resource.
+///
+/// Params: [`ResourceParams`]. Result: [`WatchResponse`]. The result of [`BEVY_GET_RESOURCE`] is
+/// then sent in a [`BEVY_CHANGED`] notification at the end of every frame where it changed.
+pub const BEVY_WATCH_RESOURCE: &str = "bevy/watch_resource";
+/// Cancels a subscription made with [`BEVY_WATCH`] or [`BEVY_WATCH_RESOURCE`].
+///
+/// Params: [`UnwatchParams`]. Result: `null`.
+pub const BEVY_UNWATCH: &str = "bevy/unwatch";
+/// The notification sent to subscribers when a watched value changed.
+///
+/// Params: [`ChangedNotification`].
+pub const BEVY_CHANGED: &str = "bevy/changed";
+
+/// A JSON-RPC request sent by a client, on a single line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteRequest {
+    pub jsonrpc: String,
+    pub method: String,
+    /// The id of the request, repeated in its response. Requests without an id don't get a
+    /// response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+}
+
+impl RemoteRequest {
+    pub fn new(id: impl Into<Value>, method: impl Into<String>, params: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            method: method.into(),
+            id: Some(id.into()),
+            params,
+        }
+    }
+}
+
+/// A JSON-RPC response sent by the server, on a single line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteResponse {
+    pub jsonrpc: String,
+    /// The id of the request, or `null` if it couldn't be read.
+    pub id: Value,
+    #[serde(flatten)]
+    pub outcome: RemoteOutcome,
+}
+
+impl RemoteResponse {
+    pub fn new(id: Value, outcome: RemoteOutcome) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            outcome,
+        }
+    }
+}
+
+/// A JSON-RPC notification sent by the server, on a single line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Value,
+}
+
+/// The result of a request, serialized as the `result` or `error` field of a message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteOutcome {
+    Result(Value),
+    Error(RemoteError),
+}
+
+impl From<Result<Value, RemoteError>> for RemoteOutcome {
+    fn from(result: Result<Value, RemoteError>) -> Self {
+        match result {
+            Ok(value) => RemoteOutcome::Result(value),
+            Err(error) => RemoteOutcome::Error(error),
+        }
+    }
+}
+
+/// A JSON-RPC error object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RemoteError {
+    /// The request isn't valid JSON.
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The request isn't a valid JSON-RPC request.
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The method doesn't exist.
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// The params of the method are invalid, including values that can't be deserialized.
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// A value couldn't be serialized.
+    pub const INTERNAL_ERROR: i64 = -32603;
+    /// The entity doesn't exist.
+    pub const NO_SUCH_ENTITY: i64 = -32001;
+    /// The type isn't registered as a reflected component or resource.
+    pub const UNREGISTERED_TYPE: i64 = -32002;
+    /// The entity doesn't have the component.
+    pub const MISSING_COMPONENT: i64 = -32003;
+    /// The resource doesn't exist.
+    pub const MISSING_RESOURCE: i64 = -32004;
+
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn no_such_entity(entity: Entity) -> Self {
+        Self::new(
+            Self::NO_SUCH_ENTITY,
+            format!("The entity {entity:?} does not exist"),
+        )
+    }
+
+    pub(crate) fn unregistered_type(type_name: &str) -> Self {
+        Self::new(
+            Self::UNREGISTERED_TYPE,
+            format!("The type {type_name} is not registered for reflection"),
+        )
+    }
+
+    pub(crate) fn missing_component(entity: Entity, type_name: &str) -> Self {
+        Self::new(
+            Self::MISSING_COMPONENT,
+            format!("The entity {entity:?} has no component {type_name}"),
+        )
+    }
+
+    pub(crate) fn missing_resource(type_name: &str) -> Self {
+        Self::new(
+            Self::MISSING_RESOURCE,
+            format!("The resource {type_name} does not exist"),
+        )
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (error {})", self.message, self.code)
+    }
+}
+
+/// The params of [`BEVY_LIST`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListParams {
+    /// Only lists the entities whose name contains this string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// An entity listed by [`BEVY_LIST`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListEntry {
+    pub entity: Entity,
+    pub name: Option<String>,
+    /// The full type names of the components of the entity.
+    pub components: Vec<String>,
+}
+
+/// The params of [`BEVY_GET`] and [`BEVY_WATCH`].
+///
+/// Components are named by their full or short type name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GetParams {
+    pub entity: Entity,
+    pub components: Vec<String>,
+}
+
+/// The serialized values of components, by full type name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentValues {
+    pub components: Map<String, Value>,
+}
+
+/// The params of [`BEVY_QUERY`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryParams {
+    /// The components to return. Entities must have all of them.
+    pub components: Vec<String>,
+    /// Components that entities must have, without returning them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub with: Vec<String>,
+    /// Components that entities must not have.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub without: Vec<String>,
+}
+
+/// An entity returned by [`BEVY_QUERY`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryEntry {
+    pub entity: Entity,
+    pub components: Map<String, Value>,
+}
+
+/// The params of [`BEVY_INSERT`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertParams {
+    pub entity: Entity,
+    /// The serialized values of the components, by full or short type name.
+    pub components: Map<String, Value>,
+}
+
+/// The params of [`BEVY_REMOVE`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoveParams {
+    pub entity: Entity,
+    pub components: Vec<String>,
+}
+
+/// The params of [`BEVY_GET_RESOURCE`] and [`BEVY_WATCH_RESOURCE`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceParams {
+    /// The full or short type name of the resource.
+    pub resource: String,
+}
+
+/// The serialized value of a resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceValue {
+    /// The full type name of the resource.
+    pub resource: String,
+    pub value: Value,
+}
+
+/// The params of [`BEVY_INSERT_RESOURCE`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsertResourceParams {
+    pub resource: String,
+    pub value: Value,
+}
+
+/// The result of [`BEVY_WATCH`] and [`BEVY_WATCH_RESOURCE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchResponse {
+    pub subscription: u64,
+}
+
+/// The params of [`BEVY_UNWATCH`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnwatchParams {
+    pub subscription: u64,
+}
+
+/// The params of [`BEVY_CHANGED`] notifications.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangedNotification {
+    pub subscription: u64,
+    /// The result of [`BEVY_GET`] or [`BEVY_GET_RESOURCE`] for the watched values.
+    #[serde(flatten)]
+    pub outcome: RemoteOutcome,
+}
@@ -0,0 +1,235 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+use bevy_utils::tracing::{error, warn};
+use crossbeam_channel::Sender;
+use serde_json::Value;
+
+use crate::{
+    protocol::{RemoteError, RemoteOutcome, RemoteRequest, RemoteResponse, JSONRPC_VERSION},
+    RemoteAddress,
+};
+
+/// Identifies a connection to the server, to clean up its subscriptions once it is closed.
+pub(crate) type ConnectionId = u64;
+
+/// A message sent by the connection threads to the [`RemoteServer`](crate::RemoteServer).
+pub(crate) enum RemoteMessage {
+    Request {
+        connection: ConnectionId,
+        request: RemoteRequest,
+        /// Sends lines to the client of the connection.
+        responder: Sender<String>,
+    },
+    Disconnected(ConnectionId),
+}
+
+static NEXT_CONNECTION: AtomicU64 = AtomicU64::new(0);
+
+/// The listener thread of the server, which is stopped when this is dropped.
+pub(crate) struct Listener {
+    address: RemoteAddress,
+    stopped: Arc<AtomicBool>,
+}
+
+impl Listener {
+    /// The address the listener is bound to.
+    pub(crate) fn address(&self) -> &RemoteAddress {
+        &self.address
+    }
+
+    /// Stops accepting connections, and removes the file of a Unix socket so that the next run
+    /// can bind it again.
+    pub(crate) fn stop(&self) {
+        if self.stopped.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        // The listener thread is blocked until the next connection, so connect to wake it up.
+        match &self.address {
+            RemoteAddress::Tcp(address) => {
+                let mut address = *address;
+                if address.ip().is_unspecified() {
+                    address.set_ip(match address {
+                        SocketAddr::V4(_) => Ipv4Addr::LOCALHOST.into(),
+                        SocketAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+                    });
+                }
+                let _ = TcpStream::connect(address);
+            }
+            #[cfg(unix)]
+            RemoteAddress::Unix(path) => {
+                let _ = UnixStream::connect(path);
+                if let Err(err) = std::fs::remove_file(path) {
+                    warn!("Failed to remove the remote server socket {path:?}: {err}");
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+pub(crate) fn listen_tcp(
+    listener: TcpListener,
+    messages: Sender<RemoteMessage>,
+) -> io::Result<Listener> {
+    let address = RemoteAddress::Tcp(listener.local_addr()?);
+    Ok(spawn_listener(address, move |stopped| {
+        accept_connections(
+            listener.incoming(),
+            TcpStream::try_clone,
+            &messages,
+            stopped,
+        );
+    }))
+}
+
+#[cfg(unix)]
+pub(crate) fn listen_unix(
+    listener: UnixListener,
+    path: std::path::PathBuf,
+    messages: Sender<RemoteMessage>,
+) -> Listener {
+    spawn_listener(RemoteAddress::Unix(path), move |stopped| {
+        accept_connections(
+            listener.incoming(),
+            UnixStream::try_clone,
+            &messages,
+            stopped,
+        );
+    })
+}
+
+fn spawn_listener(
+    address: RemoteAddress,
+    accept: impl FnOnce(&AtomicBool) + Send + 'static,
+) -> Listener {
+    let stopped = Arc::new(AtomicBool::new(false));
+    let thread_stopped = stopped.clone();
+    spawn_thread("remote listener".to_string(), move || {
+        accept(&thread_stopped);
+    });
+    Listener { address, stopped }
+}
+
+fn accept_connections<S: Read + Write + Send + 'static>(
+    incoming: impl Iterator<Item = io::Result<S>>,
+    try_clone: fn(&S) -> io::Result<S>,
+    messages: &Sender<RemoteMessage>,
+    stopped: &AtomicBool,
+) {
+    for stream in incoming {
+        if stopped.load(Ordering::Relaxed) {
+            break;
+        }
+        match stream.and_then(|stream| Ok((try_clone(&stream)?, stream))) {
+            Ok((reader, writer)) => spawn_connection(reader, writer, messages.clone()),
+            Err(err) => warn!("Failed to accept a remote connection: {err}"),
+        }
+    }
+}
+
+/// Spawns the threads reading the requests of a connection and writing the messages sent to its
+/// client.
+fn spawn_connection(
+    reader: impl Read + Send + 'static,
+    mut writer: impl Write + Send + 'static,
+    messages: Sender<RemoteMessage>,
+) {
+    let connection = NEXT_CONNECTION.fetch_add(1, Ordering::Relaxed);
+    let (responder, lines) = crossbeam_channel::unbounded::<String>();
+
+    spawn_thread(
+        format!("remote connection {connection} writer"),
+        move || {
+            for line in lines {
+                if writeln!(writer, "{line}")
+                    .and_then(|_| writer.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        },
+    );
+
+    spawn_thread(
+        format!("remote connection {connection} reader"),
+        move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match parse_request(&line) {
+                    Ok(request) => {
+                        let message = RemoteMessage::Request {
+                            connection,
+                            request,
+                            responder: responder.clone(),
+                        };
+                        if messages.send(message).is_err() {
+                            // The app was dropped.
+                            return;
+                        }
+                    }
+                    Err((id, error)) => {
+                        let response = RemoteResponse::new(id, RemoteOutcome::Error(error));
+                        if let Ok(response) = serde_json::to_string(&response) {
+                            let _ = responder.send(response);
+                        }
+                    }
+                }
+            }
+            let _ = messages.send(RemoteMessage::Disconnected(connection));
+        },
+    );
+}
+
+fn parse_request(line: &str) -> Result<RemoteRequest, (Value, RemoteError)> {
+    let value: Value = serde_json::from_str(line).map_err(|error| {
+        (
+            Value::Null,
+            RemoteError::new(RemoteError::PARSE_ERROR, error.to_string()),
+        )
+    })?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request: RemoteRequest = serde_json::from_value(value).map_err(|error| {
+        (
+            id.clone(),
+            RemoteError::new(RemoteError::INVALID_REQUEST, error.to_string()),
+        )
+    })?;
+    if request.jsonrpc != JSONRPC_VERSION {
+        return Err((
+            id,
+            RemoteError::new(
+                RemoteError::INVALID_REQUEST,
+                format!("Unsupported JSON-RPC version {}", request.jsonrpc),
+            ),
+        ));
+    }
+    Ok(request)
+}
+
+fn spawn_thread(name: String, f: impl FnOnce() + Send + 'static) {
+    if let Err(err) = thread::Builder::new().name(name).spawn(f) {
+        error!("Failed to spawn a remote server thread: {err}");
+    }
+}
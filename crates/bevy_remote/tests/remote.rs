@@ -0,0 +1,429 @@
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{Ipv4Addr, TcpStream},
+    time::Duration,
+};
+
+use bevy_app::App;
+use bevy_ecs::{prelude::*, reflect::ReflectEntityName};
+use bevy_reflect::Reflect;
+use bevy_remote::{protocol::*, RemoteAddress, RemotePlugin, RemoteServer};
+use serde_json::{json, Value};
+
+#[derive(Component, Reflect, Default, Debug, PartialEq)]
+#[reflect(Component)]
+struct Health {
+    current: u32,
+    max: u32,
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, EntityName)]
+struct Label(String);
+
+impl AsRef<str> for Label {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct Enemy;
+
+#[derive(Resource, Reflect, Default, Debug, PartialEq)]
+#[reflect(Resource)]
+struct Score(u32);
+
+struct Client {
+    reader: BufReader<Box<dyn std::io::Read>>,
+    writer: Box<dyn Write>,
+    next_id: u64,
+}
+
+impl Client {
+    fn connect(app: &App) -> Self {
+        match app.world.resource::<RemoteServer>().address() {
+            RemoteAddress::Tcp(address) => {
+                let stream = TcpStream::connect(address).unwrap();
+                stream
+                    .set_read_timeout(Some(Duration::from_millis(10)))
+                    .unwrap();
+                Self {
+                    reader: BufReader::new(Box::new(stream.try_clone().unwrap())),
+                    writer: Box::new(stream),
+                    next_id: 0,
+                }
+            }
+            #[cfg(unix)]
+            RemoteAddress::Unix(path) => {
+                let stream = std::os::unix::net::UnixStream::connect(path).unwrap();
+                stream
+                    .set_read_timeout(Some(Duration::from_millis(10)))
+                    .unwrap();
+                Self {
+                    reader: BufReader::new(Box::new(stream.try_clone().unwrap())),
+                    writer: Box::new(stream),
+                    next_id: 0,
+                }
+            }
+        }
+    }
+
+    fn send(&mut self, line: &str) {
+        writeln!(self.writer, "{line}").unwrap();
+        self.writer.flush().unwrap();
+    }
+
+    /// Updates the app until the next message from the server is received.
+    fn receive(&mut self, app: &mut App) -> Value {
+        let mut line = String::new();
+        for _ in 0..500 {
+            app.update();
+            match self.reader.read_line(&mut line) {
+                Ok(_) => return serde_json::from_str(&line).unwrap(),
+                Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+                Err(err) => panic!("{err}"),
+            }
+        }
+        panic!("no message received from the server");
+    }
+
+    fn request(&mut self, app: &mut App, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let request = RemoteRequest::new(self.next_id, method, params);
+        self.send(&serde_json::to_string(&request).unwrap());
+        let response = self.receive(app);
+        assert_eq!(response["id"], json!(self.next_id));
+        response
+    }
+
+    fn result(&mut self, app: &mut App, method: &str, params: Value) -> Value {
+        let response = self.request(app, method, params);
+        assert!(response.get("error").is_none(), "{response}");
+        response["result"].clone()
+    }
+
+    fn error_code(&mut self, app: &mut App, method: &str, params: Value) -> i64 {
+        let response = self.request(app, method, params);
+        response["error"]["code"].as_i64().unwrap()
+    }
+}
+
+fn app(plugin: RemotePlugin) -> App {
+    let mut app = App::new();
+    app.add_plugin(plugin)
+        .register_type::<Health>()
+        .register_type::<Label>()
+        .register_type::<Enemy>()
+        .register_type::<Score>()
+        .insert_resource(Score(3));
+    app
+}
+
+fn local_app() -> App {
+    app(RemotePlugin::tcp((Ipv4Addr::LOCALHOST, 0)))
+}
+
+fn health_name() -> &'static str {
+    std::any::type_name::<Health>()
+}
+
+#[test]
+fn list_and_get_components() {
+    let mut app = local_app();
+    let player = app
+        .world
+        .spawn((
+            Label("Player".to_string()),
+            Health {
+                current: 5,
+                max: 10,
+            },
+        ))
+        .id();
+    app.world.spawn((Label("Goblin".to_string()), Enemy));
+    let mut client = Client::connect(&app);
+
+    let entities: Vec<ListEntry> =
+        serde_json::from_value(client.result(&mut app, BEVY_LIST, Value::Null)).unwrap();
+    assert_eq!(entities.len(), 2);
+
+    let entities: Vec<ListEntry> = serde_json::from_value(client.result(
+        &mut app,
+        BEVY_LIST,
+        json!(ListParams {
+            name: Some("Play".to_string())
+        }),
+    ))
+    .unwrap();
+    assert_eq!(entities.len(), 1);
+    assert_eq!(entities[0].entity, player);
+    assert_eq!(entities[0].name.as_deref(), Some("Player"));
+    assert!(entities[0]
+        .components
+        .iter()
+        .any(|name| name == health_name()));
+
+    let result = client.result(
+        &mut app,
+        BEVY_GET,
+        json!(GetParams {
+            entity: player,
+            components: vec!["Health".to_string()],
+        }),
+    );
+    assert_eq!(
+        result,
+        json!({ "components": { health_name(): { "current": 5, "max": 10 } } })
+    );
+}
+
+#[test]
+fn query_components() {
+    let mut app = local_app();
+    let player = app
+        .world
+        .spawn(Health {
+            current: 5,
+            max: 10,
+        })
+        .id();
+    let enemy = app.world.spawn((Health { current: 1, max: 2 }, Enemy)).id();
+    let mut client = Client::connect(&app);
+
+    let entries: Vec<QueryEntry> = serde_json::from_value(client.result(
+        &mut app,
+        BEVY_QUERY,
+        json!(QueryParams {
+            components: vec!["Health".to_string()],
+            ..Default::default()
+        }),
+    ))
+    .unwrap();
+    assert_eq!(
+        entries.iter().map(|entry| entry.entity).collect::<Vec<_>>(),
+        vec![player, enemy]
+    );
+
+    let entries: Vec<QueryEntry> = serde_json::from_value(client.result(
+        &mut app,
+        BEVY_QUERY,
+        json!(QueryParams {
+            components: vec!["Health".to_string()],
+            with: Vec::new(),
+            without: vec!["Enemy".to_string()],
+        }),
+    ))
+    .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].entity, player);
+    assert_eq!(entries[0].components[health_name()]["current"], json!(5));
+}
+
+#[test]
+fn insert_and_remove_components() {
+    let mut app = local_app();
+    let entity = app.world.spawn_empty().id();
+    let mut client = Client::connect(&app);
+
+    client.result(
+        &mut app,
+        BEVY_INSERT,
+        json!({
+            "entity": entity,
+            "components": { "Health": { "current": 7, "max": 8 }, "Enemy": {} },
+        }),
+    );
+    assert_eq!(
+        app.world.get::<Health>(entity),
+        Some(&Health { current: 7, max: 8 })
+    );
+    assert!(app.world.entity(entity).contains::<Enemy>());
+
+    client.result(
+        &mut app,
+        BEVY_REMOVE,
+        json!(RemoveParams {
+            entity,
+            components: vec!["Health".to_string()],
+        }),
+    );
+    assert!(!app.world.entity(entity).contains::<Health>());
+
+    // Invalid values don't insert any component.
+    assert_eq!(
+        client.error_code(
+            &mut app,
+            BEVY_INSERT,
+            json!({
+                "entity": entity,
+                "components": { "Health": { "current": 7, "max": 8 }, "Score": "nope" },
+            }),
+        ),
+        RemoteError::UNREGISTERED_TYPE
+    );
+    assert_eq!(
+        client.error_code(
+            &mut app,
+            BEVY_INSERT,
+            json!({ "entity": entity, "components": { "Health": { "current": "7" } } }),
+        ),
+        RemoteError::INVALID_PARAMS
+    );
+    assert!(!app.world.entity(entity).contains::<Health>());
+}
+
+#[test]
+fn read_and_write_resources() {
+    let mut app = local_app();
+    let mut client = Client::connect(&app);
+
+    let value: ResourceValue = serde_json::from_value(client.result(
+        &mut app,
+        BEVY_GET_RESOURCE,
+        json!(ResourceParams {
+            resource: "Score".to_string()
+        }),
+    ))
+    .unwrap();
+    assert_eq!(value.resource, std::any::type_name::<Score>());
+    assert_eq!(value.value, json!([3]));
+
+    client.result(
+        &mut app,
+        BEVY_INSERT_RESOURCE,
+        json!({ "resource": "Score", "value": [12] }),
+    );
+    assert_eq!(app.world.resource::<Score>(), &Score(12));
+}
+
+#[test]
+fn watch_changes() {
+    let mut app = local_app();
+    let entity = app
+        .world
+        .spawn(Health {
+            current: 5,
+            max: 10,
+        })
+        .id();
+    let mut client = Client::connect(&app);
+
+    let response: WatchResponse = serde_json::from_value(client.result(
+        &mut app,
+        BEVY_WATCH,
+        json!(GetParams {
+            entity,
+            components: vec!["Health".to_string()],
+        }),
+    ))
+    .unwrap();
+
+    // The current value is sent right away.
+    let notification = client.receive(&mut app);
+    assert_eq!(notification["method"], json!(BEVY_CHANGED));
+    assert_eq!(
+        notification["params"]["subscription"],
+        json!(response.subscription)
+    );
+    assert_eq!(
+        notification["params"]["result"]["components"][health_name()]["current"],
+        json!(5)
+    );
+
+    app.world.get_mut::<Health>(entity).unwrap().current = 4;
+    let notification = client.receive(&mut app);
+    assert_eq!(
+        notification["params"]["result"]["components"][health_name()]["current"],
+        json!(4)
+    );
+
+    app.world.entity_mut(entity).remove::<Health>();
+    let notification = client.receive(&mut app);
+    assert_eq!(
+        notification["params"]["error"]["code"],
+        json!(RemoteError::MISSING_COMPONENT)
+    );
+
+    // Values that didn't change aren't sent again, so the next message is the response.
+    client.result(
+        &mut app,
+        BEVY_UNWATCH,
+        json!(UnwatchParams {
+            subscription: response.subscription
+        }),
+    );
+    app.world.entity_mut(entity).insert(Health::default());
+    assert_eq!(
+        client.error_code(
+            &mut app,
+            BEVY_UNWATCH,
+            json!(UnwatchParams {
+                subscription: response.subscription
+            }),
+        ),
+        RemoteError::INVALID_PARAMS
+    );
+}
+
+#[test]
+fn invalid_requests() {
+    let mut app = local_app();
+    let mut client = Client::connect(&app);
+
+    client.send("not json");
+    assert_eq!(
+        client.receive(&mut app)["error"]["code"],
+        json!(RemoteError::PARSE_ERROR)
+    );
+    client.send(r#"{"jsonrpc":"1.0","id":3,"method":"bevy/list"}"#);
+    let response = client.receive(&mut app);
+    assert_eq!(response["id"], json!(3));
+    assert_eq!(
+        response["error"]["code"],
+        json!(RemoteError::INVALID_REQUEST)
+    );
+
+    assert_eq!(
+        client.error_code(&mut app, "bevy/explode", Value::Null),
+        RemoteError::METHOD_NOT_FOUND
+    );
+    assert_eq!(
+        client.error_code(&mut app, BEVY_GET, json!({ "entity": 3 })),
+        RemoteError::INVALID_PARAMS
+    );
+    let missing = app.world.spawn_empty().id();
+    app.world.despawn(missing);
+    assert_eq!(
+        client.error_code(
+            &mut app,
+            BEVY_GET,
+            json!(GetParams {
+                entity: missing,
+                components: Vec::new(),
+            }),
+        ),
+        RemoteError::NO_SUCH_ENTITY
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_socket() {
+    let path = std::env::temp_dir().join(format!("bevy_remote_test_{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut app = app(RemotePlugin::unix(&path));
+    let mut client = Client::connect(&app);
+
+    let value: ResourceValue = serde_json::from_value(client.result(
+        &mut app,
+        BEVY_GET_RESOURCE,
+        json!(ResourceParams {
+            resource: "Score".to_string()
+        }),
+    ))
+    .unwrap();
+    assert_eq!(value.value, json!([3]));
+    let _ = std::fs::remove_file(&path);
+}
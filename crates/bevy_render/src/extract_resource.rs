@@ -3,7 +3,10 @@ use std::marker::PhantomData;
 use bevy_app::{App, Plugin};
 #[cfg(debug_assertions)]
 use bevy_ecs::system::Local;
-use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    system::{Commands, Res, ResMut, Resource},
+};
 pub use bevy_render_macros::ExtractResource;
 
 use crate::{Extract, RenderApp, RenderStage};
@@ -683,6 +683,7 @@ impl PipelineCache {
                     }
                 }
                 AssetEvent::Removed { handle } => cache.remove_shader(handle),
+                AssetEvent::LoadedWithDependencies { .. } | AssetEvent::Failed { .. } => {}
             }
         }
     }
@@ -6,7 +6,7 @@ use naga::valid::Capabilities;
 use naga::{valid::ModuleInfo, Module};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::{borrow::Cow, marker::Copy, ops::Deref, path::PathBuf, str::FromStr};
+use std::{borrow::Cow, marker::Copy, ops::Deref};
 use thiserror::Error;
 use wgpu::Features;
 use wgpu::{util::make_spirv, ShaderModuleDescriptor, ShaderSource};
@@ -266,14 +266,13 @@ impl AssetLoader for ShaderLoader {
                 shader.import_path = shader_imports.import_path;
             } else {
                 shader.import_path = Some(ShaderImport::AssetPath(
-                    load_context.path().to_string_lossy().to_string(),
+                    load_context.get_asset_path(None).to_string(),
                 ));
             }
             let mut asset = LoadedAsset::new(shader);
             for import in shader_imports.imports {
                 if let ShaderImport::AssetPath(asset_path) = import {
-                    let path = PathBuf::from_str(&asset_path)?;
-                    asset.add_dependency(path.into());
+                    asset.add_dependency(asset_path.as_str().into());
                 }
             }
 
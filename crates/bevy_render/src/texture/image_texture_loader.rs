@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,60 @@ pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+/// The settings of the [`ImageTextureLoader`], read from the `.meta` file of an image or given to
+/// [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings).
+///
+/// For example, a normal map can be loaded as linear data with this meta file:
+///
+/// ```ron
+/// (
+///     settings: (
+///         is_srgb: false,
+///         sampler: Linear,
+///     ),
+/// )
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ImageTextureLoaderSettings {
+    /// Whether the image is in the sRGB color space. Images holding linear data, such as normal
+    /// maps, should set this to `false`.
+    pub is_srgb: bool,
+    /// The sampler of the image.
+    pub sampler: ImageSamplerSettings,
+}
+
+impl Default for ImageTextureLoaderSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImageSamplerSettings::Default,
+        }
+    }
+}
+
+/// The sampler of an image loaded by the [`ImageTextureLoader`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSamplerSettings {
+    /// The default sampler of the [`ImagePlugin`](super::ImagePlugin).
+    #[default]
+    Default,
+    /// A sampler with linear filtering. See [`ImageSampler::linear`].
+    Linear,
+    /// A sampler with nearest filtering. See [`ImageSampler::nearest`].
+    Nearest,
+}
+
+impl From<ImageSamplerSettings> for ImageSampler {
+    fn from(settings: ImageSamplerSettings) -> Self {
+        match settings {
+            ImageSamplerSettings::Default => ImageSampler::Default,
+            ImageSamplerSettings::Linear => ImageSampler::linear(),
+            ImageSamplerSettings::Nearest => ImageSampler::nearest(),
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "basis-universal")]
     "basis",
@@ -45,17 +100,19 @@ impl AssetLoader for ImageTextureLoader {
         Box::pin(async move {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings = load_context.settings::<ImageTextureLoaderSettings>()?;
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = settings.sampler.into();
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
@@ -100,6 +157,20 @@ impl std::fmt::Display for FileTextureError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_settings_sampler() {
+        let settings = ImageTextureLoaderSettings::default();
+        assert!(settings.is_srgb);
+        assert!(matches!(
+            ImageSampler::from(settings.sampler),
+            ImageSampler::Default
+        ));
+        assert!(matches!(
+            ImageSampler::from(ImageSamplerSettings::Nearest),
+            ImageSampler::Descriptor(_)
+        ));
+    }
+
     #[test]
     fn test_supported_file_extensions() {
         for ext in FILE_EXTENSIONS {
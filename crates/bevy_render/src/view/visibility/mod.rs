@@ -4,7 +4,7 @@ pub use render_layers::*;
 
 use bevy_app::{CoreStage, Plugin};
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{entity::Disabled, prelude::*};
 use bevy_hierarchy::{Children, Parent};
 use bevy_reflect::Reflect;
 use bevy_reflect::{std_traits::ReflectDefault, FromReflect};
@@ -26,7 +26,13 @@ use crate::{
 
 /// If an entity is hidden in this way,  all [`Children`] (and all of their children and so on) will also be hidden.
 /// This is done by setting the values of their [`ComputedVisibility`] component.
+///
+/// Entities with the [`Disabled`] component, and their descendants, are hidden as well.
+///
+/// Inserting a [`Visibility`] also inserts a default [`ComputedVisibility`] if the entity doesn't
+/// have one.
 #[derive(Component, Clone, Reflect, FromReflect, Debug)]
+#[component(require(ComputedVisibility))]
 #[reflect(Component, Default)]
 pub struct Visibility {
     /// Indicates whether this entity is visible. Hidden values will propagate down the entity hierarchy.
@@ -283,6 +289,7 @@ pub fn update_frusta<T: Component + CameraProjection + Send + Sync + 'static>(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn visibility_propagate_system(
     mut root_query: Query<
         (
@@ -290,15 +297,29 @@ fn visibility_propagate_system(
             &Visibility,
             &mut ComputedVisibility,
             Entity,
+            Option<&Disabled>,
         ),
         Without<Parent>,
     >,
-    mut visibility_query: Query<(&Visibility, &mut ComputedVisibility, &Parent)>,
-    children_query: Query<&Children, (With<Parent>, With<Visibility>, With<ComputedVisibility>)>,
+    mut visibility_query: Query<(
+        &Visibility,
+        &mut ComputedVisibility,
+        &Parent,
+        Option<&Disabled>,
+    )>,
+    children_query: Query<
+        &Children,
+        (
+            With<Parent>,
+            With<Visibility>,
+            With<ComputedVisibility>,
+            Option<&Disabled>,
+        ),
+    >,
 ) {
-    for (children, visibility, mut computed_visibility, entity) in root_query.iter_mut() {
+    for (children, visibility, mut computed_visibility, entity, disabled) in root_query.iter_mut() {
         // reset "view" visibility here ... if this entity should be drawn a future system should set this to true
-        computed_visibility.reset(visibility.is_visible);
+        computed_visibility.reset(visibility.is_visible && disabled.is_none());
         if let Some(children) = children {
             for child in children.iter() {
                 let _ = propagate_recursive(
@@ -313,23 +334,37 @@ fn visibility_propagate_system(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn propagate_recursive(
     parent_visible: bool,
-    visibility_query: &mut Query<(&Visibility, &mut ComputedVisibility, &Parent)>,
-    children_query: &Query<&Children, (With<Parent>, With<Visibility>, With<ComputedVisibility>)>,
+    visibility_query: &mut Query<(
+        &Visibility,
+        &mut ComputedVisibility,
+        &Parent,
+        Option<&Disabled>,
+    )>,
+    children_query: &Query<
+        &Children,
+        (
+            With<Parent>,
+            With<Visibility>,
+            With<ComputedVisibility>,
+            Option<&Disabled>,
+        ),
+    >,
     entity: Entity,
     expected_parent: Entity,
     // BLOCKED: https://github.com/rust-lang/rust/issues/31436
     // We use a result here to use the `?` operator. Ideally we'd use a try block instead
 ) -> Result<(), ()> {
     let is_visible = {
-        let (visibility, mut computed_visibility, child_parent) =
+        let (visibility, mut computed_visibility, child_parent, disabled) =
             visibility_query.get_mut(entity).map_err(drop)?;
         assert_eq!(
             child_parent.get(), expected_parent,
             "Malformed hierarchy. This probably means that your hierarchy has been improperly maintained, or contains a cycle"
         );
-        let visible_in_hierarchy = visibility.is_visible && parent_visible;
+        let visible_in_hierarchy = visibility.is_visible && disabled.is_none() && parent_visible;
         // reset "view" visibility here ... if this entity should be drawn a future system should set this to true
         computed_visibility.reset(visible_in_hierarchy);
         visible_in_hierarchy
@@ -578,4 +613,56 @@ mod test {
             "child's invisibility propagates down to grandchild"
         );
     }
+
+    #[test]
+    fn disabled_entities_are_hidden() {
+        let mut app = App::new();
+        app.add_system(visibility_propagate_system);
+
+        let root = app
+            .world
+            .spawn((Visibility::default(), ComputedVisibility::default()))
+            .id();
+        let child = app
+            .world
+            .spawn((Visibility::default(), ComputedVisibility::default()))
+            .id();
+        let grandchild = app
+            .world
+            .spawn((Visibility::default(), ComputedVisibility::default()))
+            .id();
+        app.world.entity_mut(root).push_children(&[child]);
+        app.world.entity_mut(child).push_children(&[grandchild]);
+
+        let is_visible = |app: &App, e: Entity| {
+            app.world
+                .entity(e)
+                .get::<ComputedVisibility>()
+                .unwrap()
+                .is_visible_in_hierarchy()
+        };
+
+        app.update();
+        assert!(is_visible(&app, child));
+        assert!(is_visible(&app, grandchild));
+
+        app.world.entity_mut(child).insert(Disabled);
+        app.update();
+        assert!(is_visible(&app, root));
+        assert!(!is_visible(&app, child), "disabled entities are hidden");
+        assert!(
+            !is_visible(&app, grandchild),
+            "disabling propagates down to children"
+        );
+
+        app.world.entity_mut(root).insert(Disabled);
+        app.world.entity_mut(child).remove::<Disabled>();
+        app.update();
+        assert!(!is_visible(&app, root), "disabled roots are hidden");
+        assert!(!is_visible(&app, grandchild));
+
+        app.world.entity_mut(root).remove::<Disabled>();
+        app.update();
+        assert!(is_visible(&app, grandchild));
+    }
 }
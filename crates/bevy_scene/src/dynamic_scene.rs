@@ -2,7 +2,7 @@ use crate::{DynamicSceneBuilder, Scene, SceneSpawnError};
 use anyhow::Result;
 use bevy_app::AppTypeRegistry;
 use bevy_ecs::{
-    entity::EntityMap,
+    entity::{Entity, EntityMap, EntityMapper},
     reflect::{ReflectComponent, ReflectMapEntities},
     world::World,
 };
@@ -42,6 +42,10 @@ impl DynamicScene {
     }
 
     /// Create a new dynamic scene from a given world.
+    ///
+    /// [`Disabled`](bevy_ecs::entity::Disabled) entities are included, and stay disabled when the
+    /// scene is spawned as long as `Disabled` is registered in the type registry, which
+    /// `CorePlugin` does.
     pub fn from_world(world: &World, type_registry: &AppTypeRegistry) -> Self {
         let mut builder =
             DynamicSceneBuilder::from_world_with_type_registry(world, type_registry.clone());
@@ -66,11 +70,11 @@ impl DynamicScene {
 
         for scene_entity in &self.entities {
             // Fetch the entity with the given entity id from the `entity_map`
-            // or spawn a new entity with a transiently unique id if there is
-            // no corresponding entry.
-            let entity = *entity_map
-                .entry(bevy_ecs::entity::Entity::from_raw(scene_entity.entity))
-                .or_insert_with(|| world.spawn_empty().id());
+            // or reserve a new entity if there is no corresponding entry, which
+            // is the case unless a component referenced it before.
+            let entity = EntityMapper::new(entity_map, world.entities())
+                .get_or_reserve(Entity::from_raw(scene_entity.entity));
+            world.get_or_spawn(entity);
 
             // Apply/ add each component to the given entity.
             for component in &scene_entity.components {
@@ -95,9 +99,7 @@ impl DynamicScene {
 
         for registration in type_registry.iter() {
             if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect
-                    .map_entities(world, entity_map)
-                    .unwrap();
+                map_entities_reflect.map_entities(world, entity_map);
             }
         }
 
@@ -1,6 +1,6 @@
 use bevy_app::AppTypeRegistry;
 use bevy_ecs::{
-    entity::EntityMap,
+    entity::{EntityMap, EntityMapper},
     reflect::{ReflectComponent, ReflectMapEntities},
     world::World,
 };
@@ -63,10 +63,9 @@ impl Scene {
         let type_registry = type_registry.read();
         for archetype in self.world.archetypes().iter() {
             for scene_entity in archetype.entities() {
-                let entity = *instance_info
-                    .entity_map
-                    .entry(scene_entity.entity())
-                    .or_insert_with(|| world.spawn_empty().id());
+                let entity = EntityMapper::new(&mut instance_info.entity_map, world.entities())
+                    .get_or_reserve(scene_entity.entity());
+                world.get_or_spawn(entity);
                 for component_id in archetype.components() {
                     let component_info = self
                         .world
@@ -92,9 +91,7 @@ impl Scene {
         }
         for registration in type_registry.iter() {
             if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                map_entities_reflect
-                    .map_entities(world, &instance_info.entity_map)
-                    .unwrap();
+                map_entities_reflect.map_entities(world, &mut instance_info.entity_map);
             }
         }
 
@@ -387,10 +387,12 @@ impl<'a, 'de> Visitor<'de> for ComponentVisitor<'a> {
 #[cfg(test)]
 mod tests {
     use crate::serde::{SceneDeserializer, SceneSerializer};
-    use crate::{DynamicScene, DynamicSceneBuilder};
+    use crate::{DynamicEntity, DynamicScene, DynamicSceneBuilder};
     use bevy_app::AppTypeRegistry;
-    use bevy_ecs::entity::EntityMap;
+    use bevy_ecs::entity::{Disabled, Entity, EntityMap, EntityMapper, MapEntities};
     use bevy_ecs::prelude::{Component, ReflectComponent, World};
+    use bevy_ecs::reflect::ReflectMapEntities;
+    use bevy_ecs::world::FromWorld;
     use bevy_reflect::{FromReflect, Reflect, ReflectSerialize};
     use bincode::Options;
     use serde::de::DeserializeSeed;
@@ -529,6 +531,105 @@ mod tests {
         assert_eq!(1, dst_world.query::<&Baz>().iter(&dst_world).count());
     }
 
+    #[test]
+    fn should_roundtrip_disabled_entities() {
+        let mut world = create_world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Disabled>();
+
+        world.spawn(Foo(1));
+        world.spawn((Foo(2), Disabled));
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let scene = DynamicScene::from_world(&world, registry);
+        assert_eq!(2, scene.entities.len(), "disabled entities are saved");
+
+        let serialized_scene = scene.serialize_ron(registry).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized_scene).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+
+        let mut dst_world = create_world();
+        dst_world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Disabled>();
+        scene
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+
+        let enabled: Vec<_> = dst_world
+            .query::<&Foo>()
+            .iter(&dst_world)
+            .map(|foo| foo.0)
+            .collect();
+        assert_eq!(vec![1], enabled, "disabled entities stay disabled");
+        assert_eq!(
+            2,
+            dst_world
+                .query::<(&Foo, Option<&Disabled>)>()
+                .iter(&dst_world)
+                .count()
+        );
+    }
+
+    #[test]
+    fn should_map_entity_references() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component, MapEntities)]
+        struct Link(Entity);
+
+        impl FromWorld for Link {
+            fn from_world(_world: &mut World) -> Self {
+                Link(Entity::PLACEHOLDER)
+            }
+        }
+
+        impl MapEntities for Link {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                self.0 = entity_mapper.get_or_reserve(self.0);
+            }
+        }
+
+        let mut world = create_world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Link>();
+        let link = |index| -> Box<dyn Reflect> { Box::new(Link(Entity::from_raw(index))) };
+        // The second entity links to an entity that isn't part of the scene.
+        let scene = DynamicScene {
+            entities: vec![
+                DynamicEntity {
+                    entity: 0,
+                    components: vec![link(1)],
+                },
+                DynamicEntity {
+                    entity: 1,
+                    components: vec![link(5)],
+                },
+            ],
+        };
+
+        let mut map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut map).unwrap();
+        let [first, second, outside] =
+            [0, 1, 5].map(|index| map.get(Entity::from_raw(index)).unwrap());
+        assert_eq!(world.get::<Link>(first).unwrap().0, second);
+        assert_eq!(world.get::<Link>(second).unwrap().0, outside);
+        assert!(world.get_entity(outside).is_some());
+
+        // Writing the scene again maps the references to the same entities.
+        scene.write_to_world(&mut world, &mut map).unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(world.get::<Link>(second).unwrap().0, outside);
+        assert_eq!(world.iter_entities().count(), 3);
+    }
+
     #[test]
     fn should_roundtrip_postcard() {
         let mut world = create_world();
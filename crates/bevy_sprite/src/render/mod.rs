@@ -309,6 +309,13 @@ pub fn extract_sprite_events(
             AssetEvent::Removed { handle } => AssetEvent::Removed {
                 handle: handle.clone_weak(),
             },
+            AssetEvent::LoadedWithDependencies { handle } => AssetEvent::LoadedWithDependencies {
+                handle: handle.clone_weak(),
+            },
+            AssetEvent::Failed { handle, error } => AssetEvent::Failed {
+                handle: handle.clone_weak(),
+                error: error.clone(),
+            },
         });
     }
 }
@@ -463,7 +470,9 @@ pub fn queue_sprites(
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
         match event {
-            AssetEvent::Created { .. } => None,
+            AssetEvent::Created { .. }
+            | AssetEvent::LoadedWithDependencies { .. }
+            | AssetEvent::Failed { .. } => None,
             AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
                 image_bind_groups.values.remove(handle)
             }
@@ -0,0 +1,524 @@
+//! A headless harness to test [`App`]s frame by frame.
+//!
+//! [`TestApp`] wraps an [`App`] without a runner or a window backend. Each [`TestApp::update`]
+//! advances [`Time`] by a fixed frame time, so that tests relying on timers are deterministic.
+//! Input and window events can be injected before a frame, and the state of the world and the
+//! events sent by the app can be asserted after it.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{keyboard::KeyCode, Input};
+//! # use bevy_test::TestApp;
+//! #[derive(Component)]
+//! struct Bullet;
+//!
+//! fn shoot(mut commands: Commands, keys: Res<Input<KeyCode>>) {
+//!     if keys.just_pressed(KeyCode::Space) {
+//!         commands.spawn(Bullet);
+//!     }
+//! }
+//!
+//! let mut test = TestApp::new();
+//! test.app.add_system(shoot);
+//!
+//! test.press_key(KeyCode::Space).update();
+//! test.assert_entity_count::<With<Bullet>>(1);
+//!
+//! // The key is still held, but was only just pressed on the first frame.
+//! test.update_frames(10);
+//! test.assert_entity_count::<With<Bullet>>(1);
+//! ```
+
+use std::fmt::Debug;
+
+use bevy_app::App;
+use bevy_ecs::{
+    event::{Event, EventReader},
+    query::{ROQueryItem, ReadOnlyWorldQuery},
+    system::{ResMut, Resource},
+    world::World,
+};
+use bevy_input::{
+    gamepad::{Gamepad, GamepadEventRaw, GamepadEventType},
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion},
+    ButtonState, InputPlugin,
+};
+use bevy_math::Vec2;
+use bevy_time::{Time, TimePlugin, TimeUpdateStrategy};
+use bevy_utils::Duration;
+use bevy_window::{CursorMoved, WindowId, WindowPlugin, WindowResized};
+
+/// The frame time used by [`TestApp::new`], for 60 frames per second.
+pub const DEFAULT_FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// An [`App`] updated manually, one frame at a time, with a controlled [`Time`].
+pub struct TestApp {
+    pub app: App,
+    frame_time: Duration,
+    frame: u32,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    /// Creates a test app with the [`TimePlugin`], the [`InputPlugin`] and a [`WindowPlugin`]
+    /// without windows.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugin(TimePlugin)
+            .add_plugin(InputPlugin)
+            .add_plugin(WindowPlugin {
+                add_primary_window: false,
+                exit_on_all_closed: false,
+                ..Default::default()
+            });
+        Self::from_app(app)
+    }
+
+    /// Wraps an existing app. It shouldn't have a runner that updates it, such as the
+    /// `ScheduleRunnerPlugin` or the `WinitPlugin`.
+    ///
+    /// [`Time`] is only controlled if the app has the [`TimePlugin`].
+    pub fn from_app(app: App) -> Self {
+        Self {
+            app,
+            frame_time: DEFAULT_FRAME_TIME,
+            frame: 0,
+        }
+    }
+
+    /// The duration [`Time`] advances by on every frame.
+    pub fn frame_time(&self) -> Duration {
+        self.frame_time
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `frame_time` is zero.
+    pub fn set_frame_time(&mut self, frame_time: Duration) -> &mut Self {
+        assert!(!frame_time.is_zero(), "the frame time can't be zero");
+        self.frame_time = frame_time;
+        self
+    }
+
+    /// The number of frames run so far.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    /// Runs a single frame, [`Time`] advancing by the [frame time](Self::frame_time).
+    ///
+    /// Like with a real clock, [`Time`] doesn't advance on the first frame: its
+    /// [`delta`](Time::delta) and [`elapsed`](Time::elapsed) time stay zero.
+    ///
+    /// The plugins of the app are [finished](App::finish) and [cleaned up](App::cleanup) before
+    /// the first frame, so plugins can't be added afterwards.
+    pub fn update(&mut self) -> &mut Self {
+        self.app.cleanup();
+        if let Some(time) = self.app.world.get_resource::<Time>() {
+            let next_update = match time.last_update() {
+                Some(last_update) => last_update + self.frame_time,
+                None => time.startup(),
+            };
+            self.app
+                .insert_resource(TimeUpdateStrategy::ManualInstant(next_update));
+        }
+        self.app.update();
+        self.frame += 1;
+        self
+    }
+
+    /// Runs `frames` frames.
+    pub fn update_frames(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Runs as many frames as needed for [`Time::elapsed`] to advance by at least `duration`.
+    ///
+    /// If the app has no [`Time`], runs as many frames as needed for their
+    /// [frame time](Self::frame_time) to add up to `duration`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Time`] doesn't advance, for example because it is paused.
+    pub fn update_for(&mut self, duration: Duration) -> &mut Self {
+        let Some(time) = self.app.world.get_resource::<Time>() else {
+            let mut elapsed = Duration::ZERO;
+            while elapsed < duration {
+                self.update();
+                elapsed += self.frame_time;
+            }
+            return self;
+        };
+        let end = time.elapsed() + duration;
+        loop {
+            let time = self.app.world.resource::<Time>();
+            let elapsed = time.elapsed();
+            if elapsed >= end {
+                return self;
+            }
+            let first_frame = time.last_update().is_none();
+            self.update();
+            assert!(
+                first_frame || self.app.world.resource::<Time>().elapsed() > elapsed,
+                "`Time` didn't advance while updating for {duration:?}"
+            );
+        }
+    }
+
+    /// Sends an event, read by the systems of the next frame.
+    pub fn send_event<E: Event>(&mut self, event: E) -> &mut Self {
+        self.app.world.send_event(event);
+        self
+    }
+
+    /// Presses a key on the next frame. Its scan code is `0`.
+    pub fn press_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_key(key_code, ButtonState::Pressed)
+    }
+
+    /// Releases a key on the next frame. Its scan code is `0`.
+    pub fn release_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_key(key_code, ButtonState::Released)
+    }
+
+    fn send_key(&mut self, key_code: KeyCode, state: ButtonState) -> &mut Self {
+        self.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state,
+        })
+    }
+
+    /// Presses a mouse button on the next frame.
+    pub fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_event(MouseButtonInput {
+            button,
+            state: ButtonState::Pressed,
+        })
+    }
+
+    /// Releases a mouse button on the next frame.
+    pub fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_event(MouseButtonInput {
+            button,
+            state: ButtonState::Released,
+        })
+    }
+
+    /// Moves the mouse by `delta` on the next frame.
+    pub fn move_mouse(&mut self, delta: Vec2) -> &mut Self {
+        self.send_event(MouseMotion { delta })
+    }
+
+    /// Sends a raw gamepad event on the next frame.
+    ///
+    /// The gamepad must first be connected with a [`GamepadEventType::Connected`] event for its
+    /// other events to be handled.
+    pub fn send_gamepad_event(
+        &mut self,
+        gamepad: Gamepad,
+        event_type: GamepadEventType,
+    ) -> &mut Self {
+        self.send_event(GamepadEventRaw::new(gamepad, event_type))
+    }
+
+    /// Moves the cursor to `position` on a window on the next frame.
+    pub fn move_cursor(&mut self, id: WindowId, position: Vec2) -> &mut Self {
+        self.send_event(CursorMoved { id, position })
+    }
+
+    /// Notifies that a window was resized on the next frame.
+    pub fn resize_window(&mut self, id: WindowId, width: f32, height: f32) -> &mut Self {
+        self.send_event(WindowResized { id, width, height })
+    }
+
+    /// Records the events of type `E` sent by the app from now on, to be checked with
+    /// [`recorded_events`](Self::recorded_events).
+    pub fn record_events<E: Event + Clone>(&mut self) -> &mut Self {
+        if !self.app.world.contains_resource::<RecordedEvents<E>>() {
+            self.app
+                .insert_resource(RecordedEvents::<E>(Vec::new()))
+                .add_system_to_stage(bevy_app::CoreStage::Last, record_events::<E>);
+        }
+        self
+    }
+
+    /// The events of type `E` recorded since [`record_events`](Self::record_events) was called,
+    /// or since they were last cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the events of type `E` aren't recorded.
+    pub fn recorded_events<E: Event>(&self) -> &[E] {
+        &self
+            .app
+            .world
+            .get_resource::<RecordedEvents<E>>()
+            .unwrap_or_else(|| panic!("{} events aren't recorded", std::any::type_name::<E>()))
+            .0
+    }
+
+    /// Clears the events of type `E` recorded so far.
+    pub fn clear_recorded_events<E: Event>(&mut self) -> &mut Self {
+        if let Some(mut recorded) = self.app.world.get_resource_mut::<RecordedEvents<E>>() {
+            recorded.0.clear();
+        }
+        self
+    }
+
+    /// The items of a query over all the entities of the world.
+    pub fn query<Q: ReadOnlyWorldQuery>(&mut self) -> Vec<ROQueryItem<'_, Q>> {
+        self.query_filtered::<Q, ()>()
+    }
+
+    /// The items of a query over the entities matching the filter `F`.
+    pub fn query_filtered<Q: ReadOnlyWorldQuery, F: ReadOnlyWorldQuery>(
+        &mut self,
+    ) -> Vec<ROQueryItem<'_, Q>> {
+        let mut state = self.app.world.query_filtered::<Q, F>();
+        state.iter(&self.app.world).collect()
+    }
+
+    /// Asserts that `expected` entities match the filter `F`.
+    #[track_caller]
+    pub fn assert_entity_count<F: ReadOnlyWorldQuery>(&mut self, expected: usize) -> &mut Self {
+        let count = self
+            .app
+            .world
+            .query_filtered::<(), F>()
+            .iter(&self.app.world)
+            .count();
+        assert_eq!(
+            count,
+            expected,
+            "expected {expected} entities matching {} on frame {}, found {count}",
+            std::any::type_name::<F>(),
+            self.frame
+        );
+        self
+    }
+
+    /// Asserts that the resource `R` exists and satisfies `predicate`.
+    #[track_caller]
+    pub fn assert_resource<R: Resource + Debug>(
+        &mut self,
+        predicate: impl FnOnce(&R) -> bool,
+    ) -> &mut Self {
+        let Some(resource) = self.app.world.get_resource::<R>() else {
+            panic!("the resource {} doesn't exist", std::any::type_name::<R>());
+        };
+        assert!(
+            predicate(resource),
+            "unexpected value of {} on frame {}: {resource:?}",
+            std::any::type_name::<R>(),
+            self.frame
+        );
+        self
+    }
+
+    /// Asserts that a recorded event of type `E` satisfies `predicate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the events of type `E` aren't [recorded](Self::record_events).
+    #[track_caller]
+    pub fn assert_event_recorded<E: Event + Debug>(
+        &mut self,
+        predicate: impl Fn(&E) -> bool,
+    ) -> &mut Self {
+        let recorded = self.recorded_events::<E>();
+        assert!(
+            recorded.iter().any(predicate),
+            "no recorded {} event matches on frame {}, found {recorded:?}",
+            std::any::type_name::<E>(),
+            self.frame
+        );
+        self
+    }
+}
+
+#[derive(Resource)]
+struct RecordedEvents<E: Event>(Vec<E>);
+
+fn record_events<E: Event + Clone>(
+    mut events: EventReader<E>,
+    mut recorded: ResMut<RecordedEvents<E>>,
+) {
+    recorded.0.extend(events.iter().cloned());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::AppExit;
+    use bevy_ecs::prelude::*;
+    use bevy_input::{
+        gamepad::{GamepadButton, GamepadButtonType, GamepadInfo},
+        Input,
+    };
+    use bevy_time::{Timer, TimerMode};
+    use bevy_window::WindowId;
+
+    use super::*;
+
+    #[derive(Resource)]
+    struct Countdown(Timer);
+
+    fn countdown(
+        time: Res<Time>,
+        mut countdown: ResMut<Countdown>,
+        mut exit: EventWriter<AppExit>,
+    ) {
+        if countdown.0.tick(time.delta()).just_finished() {
+            exit.send(AppExit);
+        }
+    }
+
+    #[test]
+    fn frame_stepping() {
+        let mut test = TestApp::new();
+        test.set_frame_time(Duration::from_millis(100))
+            .record_events::<AppExit>()
+            .app
+            .insert_resource(Countdown(Timer::from_seconds(0.95, TimerMode::Once)))
+            .add_system(countdown);
+
+        test.update_frames(3);
+        assert_eq!(test.frame(), 3);
+        let time = test.world().resource::<Time>();
+        assert_eq!(time.delta(), Duration::from_millis(100));
+        // Time doesn't advance on the first frame.
+        assert_eq!(time.elapsed(), Duration::from_millis(200));
+
+        test.update_for(Duration::from_millis(650));
+        assert_eq!(test.frame(), 10);
+        assert_eq!(
+            test.world().resource::<Time>().elapsed(),
+            Duration::from_millis(900)
+        );
+        assert!(test.recorded_events::<AppExit>().is_empty());
+        test.update();
+        test.assert_event_recorded::<AppExit>(|_| true);
+    }
+
+    #[test]
+    fn update_for_from_first_frame() {
+        let mut test = TestApp::new();
+        test.set_frame_time(Duration::from_millis(100))
+            .update_for(Duration::from_secs(1));
+        // The first frame doesn't advance `Time`.
+        assert_eq!(test.frame(), 11);
+        assert_eq!(
+            test.world().resource::<Time>().elapsed(),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`Time` didn't advance")]
+    fn update_for_paused_time() {
+        let mut test = TestApp::new();
+        test.update();
+        test.world_mut().resource_mut::<Time>().pause();
+        test.update_for(Duration::from_secs(1));
+    }
+
+    #[test]
+    fn keyboard_and_mouse_input() {
+        let mut test = TestApp::new();
+        test.press_key(KeyCode::Space)
+            .press_mouse_button(MouseButton::Left)
+            .update()
+            .assert_resource::<Input<KeyCode>>(|keys| keys.just_pressed(KeyCode::Space))
+            .assert_resource::<Input<MouseButton>>(|buttons| buttons.pressed(MouseButton::Left));
+
+        test.update()
+            .assert_resource::<Input<KeyCode>>(|keys| {
+                keys.pressed(KeyCode::Space) && !keys.just_pressed(KeyCode::Space)
+            })
+            .release_key(KeyCode::Space)
+            .update()
+            .assert_resource::<Input<KeyCode>>(|keys| keys.just_released(KeyCode::Space));
+    }
+
+    #[test]
+    fn gamepad_input() {
+        let gamepad = Gamepad::new(0);
+        let button = GamepadButton::new(gamepad, GamepadButtonType::South);
+        let mut test = TestApp::new();
+        test.send_gamepad_event(
+            gamepad,
+            GamepadEventType::Connected(GamepadInfo {
+                name: "Test pad".to_string(),
+            }),
+        )
+        .update()
+        .send_gamepad_event(
+            gamepad,
+            GamepadEventType::ButtonChanged(GamepadButtonType::South, 1.0),
+        )
+        .update()
+        .assert_resource::<Input<GamepadButton>>(|buttons| buttons.just_pressed(button));
+    }
+
+    #[test]
+    fn window_events() {
+        let mut test = TestApp::new();
+        test.record_events::<CursorMoved>()
+            .record_events::<WindowResized>()
+            .move_cursor(WindowId::primary(), Vec2::new(4.0, 2.0))
+            .resize_window(WindowId::primary(), 640.0, 480.0)
+            .update()
+            .assert_event_recorded::<CursorMoved>(|event| event.position == Vec2::new(4.0, 2.0))
+            .assert_event_recorded::<WindowResized>(|event| event.width == 640.0);
+
+        test.clear_recorded_events::<CursorMoved>().update();
+        assert!(test.recorded_events::<CursorMoved>().is_empty());
+        assert_eq!(test.recorded_events::<WindowResized>().len(), 1);
+    }
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[test]
+    fn queries() {
+        let mut test = TestApp::new();
+        test.world_mut().spawn((Health(3), Enemy));
+        test.world_mut().spawn(Health(5));
+
+        test.assert_entity_count::<With<Health>>(2)
+            .assert_entity_count::<(With<Health>, Without<Enemy>)>(1);
+        let mut health = test.query::<&Health>();
+        health.sort_by_key(|health| health.0);
+        assert_eq!(health, vec![&Health(3), &Health(5)]);
+        assert_eq!(
+            test.query_filtered::<&Health, With<Enemy>>(),
+            vec![&Health(3)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 1 entities matching")]
+    fn failed_assertion() {
+        TestApp::new().assert_entity_count::<With<Enemy>>(1);
+    }
+}
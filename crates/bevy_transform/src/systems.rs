@@ -74,7 +74,9 @@ fn propagate_recursive(
     // We use a result here to use the `?` operator. Ideally we'd use a try block instead
 ) {
     let Ok(actual_parent) = parent_query.get(entity) else {
-        panic!("Propagated child for {:?} has no Parent component!", entity);
+        // The child was despawned or removed from the hierarchy without updating the `Children`
+        // of its parent, which happens with `World::despawn`.
+        return;
     };
     assert_eq!(
         actual_parent.get(), expected_parent,
@@ -81,6 +81,16 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowMoved>()
             .init_resource::<Windows>();
 
+        #[cfg(feature = "bevy_ci_testing")]
+        app.add_ci_testing_event::<WindowResized>()
+            .add_ci_testing_event::<WindowCloseRequested>()
+            .add_ci_testing_event::<CursorMoved>()
+            .add_ci_testing_event::<CursorEntered>()
+            .add_ci_testing_event::<CursorLeft>()
+            .add_ci_testing_event::<ReceivedCharacter>()
+            .add_ci_testing_event::<WindowFocused>()
+            .add_ci_testing_event::<FileDragAndDrop>();
+
         if self.add_primary_window {
             app.world.send_event(CreateWindow {
                 id: WindowId::primary(),
@@ -58,6 +58,7 @@ Controls:
                 asset_folder: std::env::var("CARGO_MANIFEST_DIR")
                     .unwrap_or_else(|_| ".".to_string()),
                 watch_for_changes: true,
+                ..default()
             }),
     )
     .add_startup_system(setup)
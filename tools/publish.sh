@@ -14,6 +14,7 @@ crates=(
     bevy_time
     bevy_log
     bevy_dynamic_plugin
+    bevy_remote
     bevy_asset
     bevy_audio
     bevy_core
@@ -36,6 +37,7 @@ crates=(
     bevy_text
     bevy_ui
     bevy_winit
+    bevy_test
     bevy_internal
     bevy_dylib
 )